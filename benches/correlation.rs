@@ -0,0 +1,43 @@
+use criterion::{
+    black_box, criterion_group, criterion_main, AxisScale, Criterion, ParameterizedBenchmark,
+    PlotConfiguration,
+};
+use ndarray::prelude::*;
+use ndarray_rand::RandomExt;
+use ndarray_stats::CorrelationExt;
+use rand::distributions::Uniform;
+
+fn cov_tall_skinny(c: &mut Criterion) {
+    let n_observations = vec![1_000, 100_000, 1_000_000];
+    let benchmark = ParameterizedBenchmark::new(
+        "cov_tall_skinny",
+        |bencher, &n_observations| {
+            let data = Array2::<f64>::random((50, n_observations), Uniform::new(-1., 1.));
+            bencher.iter(|| black_box(data.cov(1.)))
+        },
+        n_observations,
+    )
+    .plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+    c.bench("cov_tall_skinny", benchmark);
+}
+
+fn pearson_correlation_tall_skinny(c: &mut Criterion) {
+    let n_observations = vec![1_000, 100_000, 1_000_000];
+    let benchmark = ParameterizedBenchmark::new(
+        "pearson_correlation_tall_skinny",
+        |bencher, &n_observations| {
+            let data = Array2::<f64>::random((50, n_observations), Uniform::new(-1., 1.));
+            bencher.iter(|| black_box(data.pearson_correlation()))
+        },
+        n_observations,
+    )
+    .plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+    c.bench("pearson_correlation_tall_skinny", benchmark);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = cov_tall_skinny, pearson_correlation_tall_skinny
+}
+criterion_main!(benches);
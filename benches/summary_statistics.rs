@@ -0,0 +1,57 @@
+use criterion::{
+    black_box, criterion_group, criterion_main, AxisScale, Criterion, ParameterizedBenchmark,
+    PlotConfiguration,
+};
+use ndarray::prelude::*;
+use ndarray_rand::RandomExt;
+use ndarray_stats::SummaryStatisticsExt;
+use rand::distributions::Uniform;
+
+fn central_moments_f64(c: &mut Criterion) {
+    let lens = vec![100, 10_000, 1_000_000];
+    let benchmark = ParameterizedBenchmark::new(
+        "central_moments_f64",
+        |bencher, &len| {
+            let data = Array1::<f64>::random(len, Uniform::new(-1., 1.));
+            bencher.iter(|| black_box(data.central_moments(4)))
+        },
+        lens,
+    )
+    .plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+    c.bench("central_moments_f64", benchmark);
+}
+
+fn central_moments_f32(c: &mut Criterion) {
+    let lens = vec![100, 10_000, 1_000_000];
+    let benchmark = ParameterizedBenchmark::new(
+        "central_moments_f32",
+        |bencher, &len| {
+            let data = Array1::<f32>::random(len, Uniform::new(-1., 1.));
+            bencher.iter(|| black_box(data.central_moments(4)))
+        },
+        lens,
+    )
+    .plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+    c.bench("central_moments_f32", benchmark);
+}
+
+fn kurtosis_f64(c: &mut Criterion) {
+    let lens = vec![100, 10_000, 1_000_000];
+    let benchmark = ParameterizedBenchmark::new(
+        "kurtosis_f64",
+        |bencher, &len| {
+            let data = Array2::<f64>::random((len / 100, 100), Uniform::new(-1., 1.));
+            bencher.iter(|| black_box(data.kurtosis()))
+        },
+        lens,
+    )
+    .plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+    c.bench("kurtosis_f64", benchmark);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = central_moments_f64, central_moments_f32, kurtosis_f64
+}
+criterion_main!(benches);
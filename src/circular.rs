@@ -0,0 +1,533 @@
+//! Summary statistics of angles (directional data).
+use crate::errors::EmptyInput;
+use ndarray::{Array, ArrayBase, Axis, Data, Dimension, RemoveAxis};
+use num_traits::{Float, FromPrimitive};
+
+/// The unit of the angles passed to and returned by the methods of
+/// [`CircularExt`](trait.CircularExt.html).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AngleUnit {
+    /// Radians: a full turn is `2π`.
+    Radians,
+    /// Degrees: a full turn is `360`.
+    Degrees,
+}
+
+impl AngleUnit {
+    /// Converts the angle `x`, in this unit, to radians.
+    pub fn to_radians<A: Float>(self, x: A) -> A {
+        match self {
+            AngleUnit::Radians => x,
+            AngleUnit::Degrees => x.to_radians(),
+        }
+    }
+
+    /// Converts the angle `x`, in radians, to this unit.
+    pub fn from_radians<A: Float>(self, x: A) -> A {
+        match self {
+            AngleUnit::Radians => x,
+            AngleUnit::Degrees => x.to_degrees(),
+        }
+    }
+}
+
+/// Extension trait for `ArrayBase` providing [circular statistics]: summary
+/// statistics of angles, such as wind directions, compass bearings or phases,
+/// which take into account that `359°` and `1°` are only `2°` apart.
+///
+/// The angles can be given in radians or in degrees (see [`AngleUnit`]), and
+/// don't need to be reduced to a single turn. The means and medians are
+/// returned in `(-π, π]` (or `(-180, 180]` degrees).
+///
+/// All the statistics are based on the *mean resultant vector* of the
+/// angles, the mean of the unit vectors `(cos θᵢ, sin θᵢ)`: its direction is
+/// the circular mean, and its length `R ∈ [0, 1]` measures the
+/// concentration of the angles around it. NaN elements propagate to the
+/// results.
+///
+/// [circular statistics]: https://en.wikipedia.org/wiki/Directional_statistics
+/// [`AngleUnit`]: enum.AngleUnit.html
+pub trait CircularExt<A, S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    /// Returns the circular mean of the angles, the direction of their mean
+    /// resultant vector:
+    ///
+    /// ```text
+    /// atan2(∑ sin θᵢ, ∑ cos θᵢ)
+    /// ```
+    ///
+    /// The mean is ill-defined when the mean resultant length `R` is (close
+    /// to) zero, e.g. for two opposite angles.
+    ///
+    /// Returns `Err(EmptyInput)` if the array is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{AngleUnit, CircularExt};
+    ///
+    /// let bearings = array![350., 10., 30_f64];
+    /// let mean = bearings.circular_mean(AngleUnit::Degrees).unwrap();
+    /// assert!((mean - 10.).abs() < 1e-12);
+    /// ```
+    fn circular_mean(&self, unit: AngleUnit) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the [circular mean](#tymethod.circular_mean) of each lane
+    /// along `axis`.
+    ///
+    /// Returns `Err(EmptyInput)` if the length of `axis` is zero.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn circular_mean_axis(
+        &self,
+        axis: Axis,
+        unit: AngleUnit,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the circular variance of the angles, `1 - R`, where `R` is the
+    /// length of their mean resultant vector (as `scipy.stats.circvar`).
+    ///
+    /// The circular variance lies in `[0, 1]` whatever the unit of the
+    /// angles: it is zero if all the angles are equal, and one if their
+    /// resultant vector is zero, e.g. if they are evenly spread around the
+    /// circle.
+    ///
+    /// Returns `Err(EmptyInput)` if the array is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{AngleUnit, CircularExt};
+    ///
+    /// let a = array![0., 90., 180., 270_f64];
+    /// assert!((a.circular_var(AngleUnit::Degrees).unwrap() - 1.).abs() < 1e-12);
+    /// let b = array![20., 380., -340_f64];
+    /// assert!(b.circular_var(AngleUnit::Degrees).unwrap().abs() < 1e-12);
+    /// ```
+    fn circular_var(&self, unit: AngleUnit) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the [circular variance](#tymethod.circular_var) of each lane
+    /// along `axis`.
+    ///
+    /// Returns `Err(EmptyInput)` if the length of `axis` is zero.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn circular_var_axis(
+        &self,
+        axis: Axis,
+        unit: AngleUnit,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the circular standard deviation of the angles, `√(-2 ln R)`
+    /// in the given unit, where `R` is the length of their mean resultant
+    /// vector (as `scipy.stats.circstd`).
+    ///
+    /// For concentrated angles, it is close to the standard deviation of the
+    /// angles unwrapped around their mean. It is infinite if the resultant
+    /// vector is zero.
+    ///
+    /// Returns `Err(EmptyInput)` if the array is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{AngleUnit, CircularExt};
+    ///
+    /// let a = array![359., 1., 0., 358., 2_f64];
+    /// let std = a.circular_std(AngleUnit::Degrees).unwrap();
+    /// assert!((std - 1.4142).abs() < 1e-3);
+    /// ```
+    fn circular_std(&self, unit: AngleUnit) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the [circular standard deviation](#tymethod.circular_std) of
+    /// each lane along `axis`.
+    ///
+    /// Returns `Err(EmptyInput)` if the length of `axis` is zero.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn circular_std_axis(
+        &self,
+        axis: Axis,
+        unit: AngleUnit,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the circular median of the angles: the angle minimizing the
+    /// sum of the arc lengths to all the angles.
+    ///
+    /// The minimum is searched among the angles themselves, as in R's
+    /// `circular::median.circular`; if several of them reach it (e.g. the
+    /// two middle angles of an even number of concentrated angles), the
+    /// median is the circular mean of these distinct angles. This takes
+    /// `O(n²)` time for `n` elements.
+    ///
+    /// Returns `Err(EmptyInput)` if the array is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{AngleUnit, CircularExt};
+    ///
+    /// // Unlike the mean, the median is not pulled towards the outlier.
+    /// let a = array![350., 355., 0., 10., 120.];
+    /// assert_eq!(a.circular_median(AngleUnit::Degrees), Ok(0.));
+    /// let b = array![350., 355., 5., 10_f64];
+    /// assert!(b.circular_median(AngleUnit::Degrees).unwrap().abs() < 1e-12);
+    /// ```
+    fn circular_median(&self, unit: AngleUnit) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the [circular median](#tymethod.circular_median) of each lane
+    /// along `axis`.
+    ///
+    /// Returns `Err(EmptyInput)` if the length of `axis` is zero.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn circular_median_axis(
+        &self,
+        axis: Axis,
+        unit: AngleUnit,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    private_decl! {}
+}
+
+impl<A, S, D> CircularExt<A, S, D> for ArrayBase<S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn circular_mean(&self, unit: AngleUnit) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        statistic(self.iter().cloned(), unit, mean)
+    }
+
+    fn circular_mean_axis(
+        &self,
+        axis: Axis,
+        unit: AngleUnit,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        statistic_axis(self, axis, unit, mean)
+    }
+
+    fn circular_var(&self, unit: AngleUnit) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        statistic(self.iter().cloned(), unit, var)
+    }
+
+    fn circular_var_axis(
+        &self,
+        axis: Axis,
+        unit: AngleUnit,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        statistic_axis(self, axis, unit, var)
+    }
+
+    fn circular_std(&self, unit: AngleUnit) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        statistic(self.iter().cloned(), unit, std)
+    }
+
+    fn circular_std_axis(
+        &self,
+        axis: Axis,
+        unit: AngleUnit,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        statistic_axis(self, axis, unit, std)
+    }
+
+    fn circular_median(&self, unit: AngleUnit) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        statistic(self.iter().cloned(), unit, median)
+    }
+
+    fn circular_median_axis(
+        &self,
+        axis: Axis,
+        unit: AngleUnit,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        statistic_axis(self, axis, unit, median)
+    }
+
+    private_impl! {}
+}
+
+/// Applies `f` to the angles in radians, and converts its result, an angle in
+/// radians or a dimensionless quantity, back to `unit` when needed.
+fn statistic<A, I, F>(angles: I, unit: AngleUnit, f: F) -> Result<A, EmptyInput>
+where
+    A: Float,
+    I: Iterator<Item = A>,
+    F: Fn(&[A]) -> Statistic<A>,
+{
+    let radians: Vec<A> = angles.map(|x| unit.to_radians(x)).collect();
+    if radians.is_empty() {
+        return Err(EmptyInput);
+    }
+    Ok(match f(&radians) {
+        Statistic::Angle(x) => unit.from_radians(x),
+        Statistic::Scalar(x) => x,
+    })
+}
+
+fn statistic_axis<A, S, D, F>(
+    a: &ArrayBase<S, D>,
+    axis: Axis,
+    unit: AngleUnit,
+    f: F,
+) -> Result<Array<A, D::Smaller>, EmptyInput>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: RemoveAxis,
+    F: Fn(&[A]) -> Statistic<A>,
+{
+    if a.len_of(axis) == 0 {
+        return Err(EmptyInput);
+    }
+    Ok(a.map_axis(axis, |lane| {
+        statistic(lane.iter().cloned(), unit, &f).expect("The lane is not empty.")
+    }))
+}
+
+enum Statistic<A> {
+    /// An angle in radians.
+    Angle(A),
+    Scalar(A),
+}
+
+/// Returns the components of the mean resultant vector of `radians`, which
+/// must not be empty.
+fn mean_resultant<A: Float + FromPrimitive>(radians: &[A]) -> (A, A) {
+    let n = A::from_usize(radians.len())
+        .expect("Converting the number of elements to `A` must not fail.");
+    let (cos_sum, sin_sum) = radians.iter().fold((A::zero(), A::zero()), |(c, s), &x| {
+        (c + x.cos(), s + x.sin())
+    });
+    (cos_sum / n, sin_sum / n)
+}
+
+/// Returns the length of the mean resultant vector of `radians`, clamped to
+/// 1 to absorb rounding errors.
+fn mean_resultant_length<A: Float + FromPrimitive>(radians: &[A]) -> A {
+    let (c, s) = mean_resultant(radians);
+    let length = c.hypot(s);
+    // `Float::min` would also replace NaN.
+    if length > A::one() {
+        A::one()
+    } else {
+        length
+    }
+}
+
+fn mean<A: Float + FromPrimitive>(radians: &[A]) -> Statistic<A> {
+    let (c, s) = mean_resultant(radians);
+    Statistic::Angle(s.atan2(c))
+}
+
+fn var<A: Float + FromPrimitive>(radians: &[A]) -> Statistic<A> {
+    Statistic::Scalar(A::one() - mean_resultant_length(radians))
+}
+
+fn std<A: Float + FromPrimitive>(radians: &[A]) -> Statistic<A> {
+    let minus_two = -(A::one() + A::one());
+    Statistic::Angle((minus_two * mean_resultant_length(radians).ln()).sqrt())
+}
+
+/// Returns the length of the shorter arc between the angles `x` and `y`, in
+/// radians.
+fn arc_length<A: Float>(x: A, y: A) -> A {
+    let pi = A::from(core::f64::consts::PI).expect("Converting π to `A` must not fail.");
+    // `x - y` reduced to `[0, 2π)`, then folded to `[0, π]`.
+    let d = (x - y).abs() % (pi + pi);
+    d.min(pi + pi - d)
+}
+
+fn median<A: Float + FromPrimitive>(radians: &[A]) -> Statistic<A> {
+    if radians.iter().any(|x| x.is_nan()) {
+        return Statistic::Angle(A::nan());
+    }
+    let total_arc_lengths: Vec<A> = radians
+        .iter()
+        .map(|&x| {
+            radians
+                .iter()
+                .fold(A::zero(), |acc, &y| acc + arc_length(x, y))
+        })
+        .collect();
+    let minimum = total_arc_lengths
+        .iter()
+        .cloned()
+        .fold(A::infinity(), A::min);
+    // Several angles reach the minimum in exact arithmetic when the median
+    // is not unique; allow for rounding errors in the sums.
+    let tolerance = A::from(1e-10).expect("Converting the tolerance to `A` must not fail.")
+        * minimum.max(A::one());
+    // The distinct angles reaching the minimum.
+    let mut medians: Vec<A> = Vec::new();
+    for (&x, &total) in radians.iter().zip(&total_arc_lengths) {
+        if total - minimum <= tolerance && medians.iter().all(|&m| arc_length(x, m) > tolerance) {
+            medians.push(x);
+        }
+    }
+    mean(&medians)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, Array1, Array2};
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_empty() {
+        let a = Array1::<f64>::zeros(0);
+        assert_eq!(a.circular_mean(AngleUnit::Radians), Err(EmptyInput));
+        assert_eq!(a.circular_var(AngleUnit::Radians), Err(EmptyInput));
+        assert_eq!(a.circular_std(AngleUnit::Radians), Err(EmptyInput));
+        assert_eq!(a.circular_median(AngleUnit::Radians), Err(EmptyInput));
+        let b = Array2::<f64>::zeros((3, 0));
+        assert!(b.circular_mean_axis(Axis(1), AngleUnit::Radians).is_err());
+        assert_eq!(
+            b.circular_mean_axis(Axis(0), AngleUnit::Radians)
+                .unwrap()
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_scipy_values() {
+        // scipy.stats.circmean(a), circvar(a) and circstd(a).
+        let a = array![0.1, 6.2, 0.5, 2., 5_f64];
+        let mean = a.circular_mean(AngleUnit::Radians).unwrap();
+        // scipy returns the mean in [0, 2π).
+        let mean = if mean < 0. { mean + 2. * PI } else { mean };
+        assert!((mean - 0.16174619094885206).abs() < 1e-12);
+        let var = a.circular_var(AngleUnit::Radians).unwrap();
+        assert!((var - 0.44543271942054474).abs() < 1e-12);
+        let std = a.circular_std(AngleUnit::Radians).unwrap();
+        assert!((std - 1.085879499734348).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_axis() {
+        let a = array![[350., 10.], [10., 20.], [0., 30.]];
+        let means = a.circular_mean_axis(Axis(0), AngleUnit::Degrees).unwrap();
+        assert!(means.all_close(&array![0., 20.], 1e-12));
+        let medians = a.circular_median_axis(Axis(0), AngleUnit::Degrees).unwrap();
+        assert!(medians.all_close(&array![0., 20.], 1e-12));
+        let vars = a.circular_var_axis(Axis(1), AngleUnit::Degrees).unwrap();
+        let stds = a.circular_std_axis(Axis(1), AngleUnit::Degrees).unwrap();
+        for (i, row) in a.outer_iter().enumerate() {
+            assert_eq!(vars[i], row.circular_var(AngleUnit::Degrees).unwrap());
+            assert_eq!(stds[i], row.circular_std(AngleUnit::Degrees).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_nan() {
+        let a = array![1., f64::NAN];
+        assert!(a.circular_mean(AngleUnit::Radians).unwrap().is_nan());
+        assert!(a.circular_var(AngleUnit::Radians).unwrap().is_nan());
+        assert!(a.circular_median(AngleUnit::Radians).unwrap().is_nan());
+    }
+
+    #[quickcheck]
+    fn statistics_are_invariant_under_full_turns(xs: Vec<(i16, i8)>) -> bool {
+        if xs.is_empty() {
+            return true;
+        }
+        let a = Array1::from_vec(xs.iter().map(|&(x, _)| f64::from(x) / 10.).collect());
+        let turned = Array1::from_vec(
+            xs.iter()
+                .map(|&(x, turns)| f64::from(x) / 10. + 360. * f64::from(turns))
+                .collect(),
+        );
+        let unit = AngleUnit::Degrees;
+        let close = |x: f64, y: f64| arc_length(x.to_radians(), y.to_radians()) < 1e-6;
+        let radians: Vec<f64> = a.iter().map(|x| x.to_radians()).collect();
+        let r = mean_resultant_length(&radians);
+        // The mean and the median are ill-defined for a null resultant.
+        (r < 1e-6
+            || close(
+                a.circular_mean(unit).unwrap(),
+                turned.circular_mean(unit).unwrap(),
+            ))
+            && (a.circular_var(unit).unwrap() - turned.circular_var(unit).unwrap()).abs() < 1e-9
+            && (r < 1e-6
+                || close(
+                    a.circular_median(unit).unwrap(),
+                    turned.circular_median(unit).unwrap(),
+                ))
+    }
+
+    #[quickcheck]
+    fn median_matches_linear_median_of_concentrated_angles(xs: Vec<u8>) -> bool {
+        // Angles within a quarter turn behave as on a line.
+        let mut xs: Vec<f64> = xs.into_iter().map(|x| f64::from(x) / 4. - 30.).collect();
+        if xs.is_empty() {
+            return true;
+        }
+        xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = xs.len();
+        let median = if n % 2 == 1 {
+            xs[n / 2]
+        } else {
+            (xs[n / 2 - 1] + xs[n / 2]) / 2.
+        };
+        let a = Array1::from_vec(xs);
+        (a.circular_median(AngleUnit::Degrees).unwrap() - median).abs() < 1e-6
+    }
+}
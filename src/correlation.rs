@@ -1,6 +1,11 @@
+use crate::errors::{ContainsNan, ShapeMismatch, StatsError};
+use crate::{MaybeNan, MaybeNanExt, NanPolicy, RankExt, VarianceAlgorithm};
+use ndarray::linalg::general_mat_mul;
 use ndarray::prelude::*;
-use ndarray::Data;
+use ndarray::{s, Data, Zip};
 use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 /// Extension trait for `ArrayBase` providing functions
 /// to compute different correlation measures.
@@ -14,7 +19,7 @@ where
     /// Let `(r, o)` be the shape of `M`:
     /// - `r` is the number of random variables;
     /// - `o` is the number of observations we have collected
-    /// for each random variable.
+    ///   for each random variable.
     ///
     /// Every column in `M` is an experiment: a single observation for each
     /// random variable.
@@ -64,13 +69,38 @@ where
     where
         A: Float + FromPrimitive;
 
+    /// Return the [covariance matrix](#tymethod.cov), computed with the given
+    /// [`VarianceAlgorithm`] (`cov` uses `VarianceAlgorithm::TwoPass`).
+    ///
+    /// **Panics** under the same conditions as [`cov`](#tymethod.cov).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{aview2, arr2};
+    /// use ndarray_stats::{CorrelationExt, VarianceAlgorithm};
+    ///
+    /// let a = arr2(&[[1., 3., 5.],
+    ///                [2., 4., 6.]]);
+    /// let covariance = a.cov_with(1., VarianceAlgorithm::Welford);
+    /// assert_eq!(
+    ///    covariance,
+    ///    aview2(&[[4., 4.], [4., 4.]])
+    /// );
+    /// ```
+    ///
+    /// [`VarianceAlgorithm`]: enum.VarianceAlgorithm.html
+    fn cov_with(&self, ddof: A, algorithm: VarianceAlgorithm) -> Array2<A>
+    where
+        A: Float + FromPrimitive;
+
     /// Return the [Pearson correlation coefficients](https://en.wikipedia.org/wiki/Pearson_correlation_coefficient)
     /// for a 2-dimensional array of observations `M`.
     ///
     /// Let `(r, o)` be the shape of `M`:
     /// - `r` is the number of random variables;
     /// - `o` is the number of observations we have collected
-    /// for each random variable.
+    ///   for each random variable.
     ///
     /// Every column in `M` is an experiment: a single observation for each
     /// random variable.
@@ -116,6 +146,395 @@ where
     where
         A: Float + FromPrimitive;
 
+    /// Like [`cov`](#tymethod.cov), but returns an error instead of
+    /// panicking:
+    ///
+    /// - `Err(StatsError::EmptyInput)` if there are no observations;
+    /// - `Err(StatsError::InvalidDdof)` if `ddof` is not smaller than the
+    ///   number of observations.
+    ///
+    /// **Panics** if the type cast of `n_observations` from `usize` to `A`
+    /// fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{arr2, Array2};
+    /// use ndarray_stats::{errors::StatsError, CorrelationExt};
+    ///
+    /// let a = arr2(&[[1., 3., 5.], [2., 4., 6.]]);
+    /// assert_eq!(a.try_cov(1.), Ok(a.cov(1.)));
+    /// assert_eq!(a.try_cov(3.), Err(StatsError::InvalidDdof));
+    /// assert_eq!(Array2::<f64>::zeros((2, 0)).try_cov(0.), Err(StatsError::EmptyInput));
+    /// ```
+    fn try_cov(&self, ddof: A) -> Result<Array2<A>, StatsError>
+    where
+        A: Float + FromPrimitive;
+
+    /// Like [`cov_with`](#tymethod.cov_with), but returns an error instead
+    /// of panicking, as [`try_cov`](#tymethod.try_cov) does.
+    ///
+    /// **Panics** if the type cast of `n_observations` from `usize` to `A`
+    /// fails.
+    fn try_cov_with(&self, ddof: A, algorithm: VarianceAlgorithm) -> Result<Array2<A>, StatsError>
+    where
+        A: Float + FromPrimitive;
+
+    /// Like [`pearson_correlation`](#tymethod.pearson_correlation), but
+    /// returns `Err(StatsError::EmptyInput)` instead of panicking if there
+    /// are no observations.
+    ///
+    /// **Panics** if the type cast of `n_observations` from `usize` to `A`
+    /// fails.
+    fn try_pearson_correlation(&self) -> Result<Array2<A>, StatsError>
+    where
+        A: Float + FromPrimitive;
+
+    /// Return the [covariance matrix](#tymethod.cov) of `M`, using the
+    /// pairwise-complete observations of each pair of random variables.
+    ///
+    /// Entry `(i, j)` is the covariance of the random variables `i` and `j`
+    /// computed over the observations (columns of `M`) where neither of them
+    /// is NaN, with the means also taken over those observations. This
+    /// mirrors `cov(t(M), use = "pairwise.complete.obs")` in R. As each entry
+    /// is computed over its own observations, the result is not necessarily
+    /// positive semi-definite.
+    ///
+    /// An entry is NaN if its number of pairwise-complete observations is not
+    /// greater than `ddof`.
+    ///
+    /// **Panics** if the type cast of a number of observations from `usize`
+    /// to `A` fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use ndarray_stats::CorrelationExt;
+    ///
+    /// let a = arr2(&[[1., 2., ::std::f64::NAN, 4.],
+    ///                [2., 4., 6., 8.]]);
+    /// let covariance = a.cov_skipnan(1.);
+    /// assert!(covariance.all_close(&arr2(&[[7. / 3., 14. / 3.],
+    ///                                      [14. / 3., 20. / 3.]]), 1e-12));
+    /// ```
+    fn cov_skipnan(&self, ddof: A) -> Array2<A>
+    where
+        A: Float + FromPrimitive;
+
+    /// Return the [Pearson correlation coefficients](#tymethod.pearson_correlation)
+    /// of `M`, using the pairwise-complete observations of each pair of
+    /// random variables.
+    ///
+    /// Entry `(i, j)` is the correlation of the random variables `i` and `j`
+    /// computed over the observations where neither of them is NaN, with the
+    /// means and the standard deviations also taken over those observations.
+    /// This mirrors `cor(t(M), use = "pairwise.complete.obs")` in R.
+    ///
+    /// An entry is NaN if there are no pairwise-complete observations, or if
+    /// one of the random variables is constant over them.
+    ///
+    /// **Panics** if the type cast of a number of observations from `usize`
+    /// to `A` fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use ndarray_stats::CorrelationExt;
+    ///
+    /// let a = arr2(&[[1., 2., ::std::f64::NAN, 4.],
+    ///                [2., 4., 6., 8.],
+    ///                [4., ::std::f64::NAN, 2., 1.]]);
+    /// let corr = a.pearson_correlation_skipnan();
+    /// assert!((corr[[0, 1]] - 1.).abs() < 1e-12);
+    /// assert!((corr[[0, 2]] + 1.).abs() < 1e-12);
+    /// assert!((corr[[1, 2]] + 1.).abs() < 1e-12);
+    /// ```
+    fn pearson_correlation_skipnan(&self) -> Array2<A>
+    where
+        A: Float + FromPrimitive;
+
+    /// Return the [Spearman rank correlation coefficients] for a
+    /// 2-dimensional array of observations `M` (see [`cov`](#tymethod.cov)
+    /// for the layout of `M`).
+    ///
+    /// The Spearman correlation of two random variables is the
+    /// [Pearson correlation](#tymethod.pearson_correlation) of their ranks,
+    /// with tied observations getting the average of the ranks they span (see
+    /// [`RankExt::rank_axis`]). It measures how well the relationship between
+    /// the variables can be described by a monotonic function.
+    ///
+    /// NaN observations propagate to the coefficients of their random
+    /// variable, and the coefficients of a random variable whose observations
+    /// are all equal are NaN.
+    ///
+    /// **Panics** if the type cast of `n_observations` from `usize` to `A`
+    /// fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use ndarray_stats::CorrelationExt;
+    ///
+    /// let a = arr2(&[[1f64, 2., 3., 4.],
+    ///                [1., 10., 100., 1000.],
+    ///                [4., 1., 3., 2.]]);
+    /// let corr = a.spearman_correlation();
+    /// assert!((corr[[0, 1]] - 1.).abs() < 1e-12);
+    /// assert!((corr[[0, 2]] + 0.4).abs() < 1e-12);
+    /// ```
+    ///
+    /// [Spearman rank correlation coefficients]: https://en.wikipedia.org/wiki/Spearman%27s_rank_correlation_coefficient
+    /// [`RankExt::rank_axis`]: trait.RankExt.html#tymethod.rank_axis
+    fn spearman_correlation(&self) -> Array2<A>
+    where
+        A: Float + FromPrimitive;
+
+    /// Return the [Kendall rank correlation coefficients] τ_b for a
+    /// 2-dimensional array of observations `M` (see [`cov`](#tymethod.cov)
+    /// for the layout of `M`).
+    ///
+    /// For two random variables with `n` observations, let `n_c` and `n_d` be
+    /// the numbers of concordant and discordant pairs of observations, and
+    /// `t_x` and `t_y` the numbers of pairs tied in `X` and in `Y`. Then
+    ///
+    /// ```text
+    ///                     n_c - n_d
+    /// τ_b = ――――――――――――――――――――――――――――――――――――――
+    ///       sqrt((n₀ - t_x)(n₀ - t_y)),  n₀ = n(n - 1)/2
+    /// ```
+    ///
+    /// which is the same as SciPy's `kendalltau`. Each coefficient is
+    /// computed in `O(n log n)` time with Knight's algorithm.
+    ///
+    /// NaN observations propagate to the coefficients of their random
+    /// variable, and the coefficients of a random variable whose observations
+    /// are all equal are NaN.
+    ///
+    /// **Panics** if the type cast of a number of pairs from `usize` to `A`
+    /// fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use ndarray_stats::CorrelationExt;
+    ///
+    /// let a = arr2(&[[1f64, 2., 3., 4.],
+    ///                [1., 3., 2., 4.]]);
+    /// let tau = a.kendall_tau();
+    /// // 5 concordant pairs and 1 discordant pair.
+    /// assert!((tau[[0, 1]] - 4. / 6.).abs() < 1e-12);
+    /// ```
+    ///
+    /// [Kendall rank correlation coefficients]: https://en.wikipedia.org/wiki/Kendall_rank_correlation_coefficient
+    fn kendall_tau(&self) -> Array2<A>
+    where
+        A: Float + FromPrimitive;
+
+    /// Return the [covariance matrix](#tymethod.cov), handling NaN values
+    /// according to `policy`:
+    ///
+    /// - `NanPolicy::Propagate`: entries involving a random variable with a
+    ///   NaN observation are NaN (this is the behaviour of [`cov`](#tymethod.cov));
+    /// - `NanPolicy::Omit`: observations (columns) containing a NaN value are
+    ///   discarded before computing the covariance matrix;
+    /// - `NanPolicy::Error`: `Err(ContainsNan)` is returned if `M` contains any NaN value.
+    ///
+    /// **Panics** if `ddof` is greater than or equal to the number of
+    /// retained observations, or if the type cast of `n_observations` from
+    /// `usize` to `A` fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{aview2, arr2};
+    /// use ndarray_stats::{CorrelationExt, NanPolicy};
+    ///
+    /// let a = arr2(&[[1., 3., ::std::f64::NAN, 5.],
+    ///                [2., 4., 0., 6.]]);
+    /// let covariance = a.cov_with_policy(1., NanPolicy::Omit).unwrap();
+    /// assert_eq!(
+    ///    covariance,
+    ///    aview2(&[[4., 4.], [4., 4.]])
+    /// );
+    /// ```
+    fn cov_with_policy(&self, ddof: A, policy: NanPolicy) -> Result<Array2<A>, ContainsNan>
+    where
+        A: Float + FromPrimitive + MaybeNan;
+
+    /// Return the [Pearson correlation coefficients](#tymethod.pearson_correlation),
+    /// handling NaN values according to `policy`.
+    ///
+    /// See [`cov_with_policy`](#tymethod.cov_with_policy) for details on the
+    /// policies.
+    ///
+    /// **Panics** if the type cast of `n_observations` from `usize` to `A` fails.
+    fn pearson_correlation_with_policy(&self, policy: NanPolicy) -> Result<Array2<A>, ContainsNan>
+    where
+        A: Float + FromPrimitive + MaybeNan;
+
+    /// Return the [autocorrelation] of each random variable (row of `M`, see
+    /// [`cov`](#tymethod.cov)) at the lags `0` to `max_lag`, as an array of
+    /// shape `(r, max_lag + 1)`.
+    ///
+    /// The autocorrelation of a series `x` of `n` observations at lag `k` is
+    ///
+    /// ```text
+    ///       cₖ              1  n-k-1
+    /// rₖ = ――    where cₖ = ―    ∑   (xₜ₊ₖ - x̅)(xₜ - x̅)
+    ///       c₀              n   t=0
+    /// ```
+    ///
+    /// as in `statsmodels.tsa.stattools.acf`. This estimator is biased
+    /// towards zero for large lags, but it is the one whose autocorrelation
+    /// matrices are positive semi-definite. If `bias_correction` is `true`,
+    /// `cₖ` is divided by the number of terms `n - k` instead of `n`.
+    ///
+    /// The sums are computed directly in `O(n max_lag)` time, which is
+    /// faster than going through an FFT for the small lags usually needed
+    /// to diagnose the residuals of a model. NaN observations propagate to
+    /// the coefficients of their random variable; see
+    /// [`autocorrelation_skipnan`](#tymethod.autocorrelation_skipnan) to
+    /// skip them instead.
+    ///
+    /// **Panics** if `max_lag` is not smaller than the number of
+    /// observations, or if the type cast of a number of observations from
+    /// `usize` to `A` fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::CorrelationExt;
+    ///
+    /// let a = array![[1., -1., 1., -1.]];
+    /// let acf = a.autocorrelation(2, false);
+    /// assert_eq!(acf, array![[1., -0.75, 0.5]]);
+    /// ```
+    ///
+    /// [autocorrelation]: https://en.wikipedia.org/wiki/Autocorrelation#Estimation
+    fn autocorrelation(&self, max_lag: usize, bias_correction: bool) -> Array2<A>
+    where
+        A: Float + FromPrimitive;
+
+    /// Like [`autocorrelation`](#tymethod.autocorrelation), but skips NaN
+    /// observations.
+    ///
+    /// The mean and the sums only involve the non-NaN observations (the pairs
+    /// of non-NaN observations for the lagged products), and `n` and `n - k`
+    /// are replaced by the numbers of such observations and pairs. A
+    /// coefficient without any such pair is NaN.
+    ///
+    /// **Panics** if `max_lag` is not smaller than the number of
+    /// observations, or if the type cast of a number of observations from
+    /// `usize` to `A` fails.
+    fn autocorrelation_skipnan(&self, max_lag: usize, bias_correction: bool) -> Array2<A>
+    where
+        A: Float + FromPrimitive;
+
+    /// Return the cross-correlation of each random variable (row of `M`)
+    /// with the same row of `other` at the lags `-max_lag` to `max_lag`, as
+    /// an array of shape `(r, 2 max_lag + 1)` whose column `max_lag + k`
+    /// holds the lag `k`.
+    ///
+    /// The cross-correlation of the series `x` and `y` of `n` observations
+    /// at lag `k` is the correlation of `xₜ₊ₖ` and `yₜ`:
+    ///
+    /// ```text
+    ///             cₖ                      1
+    /// rₖ = ――――――――――――――    where cₖ = ―  ∑ (xₜ₊ₖ - x̅)(yₜ - y̅)
+    ///      √(cₓₓ(0) cᵧᵧ(0))              n  t
+    /// ```
+    ///
+    /// with `t` ranging over the `n - |k|` times where both observations
+    /// exist, and `cₓₓ(0)` and `cᵧᵧ(0)` the (biased) variances of `x` and
+    /// `y`. If `bias_correction` is `true`, `cₖ` is divided by `n - |k|`
+    /// instead of `n`. NaN observations propagate to the coefficients of
+    /// their random variable.
+    ///
+    /// Returns `Err(ShapeMismatch)` if `other` does not have the shape of
+    /// `M`.
+    ///
+    /// **Panics** if `max_lag` is not smaller than the number of
+    /// observations, or if the type cast of a number of observations from
+    /// `usize` to `A` fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::CorrelationExt;
+    ///
+    /// let x = array![[0., 0., 1., 0., 0., 0.]];
+    /// let y = array![[1., 0., 0., 0., 0., 0.]];
+    /// let ccf = x.cross_correlation(&y, 2, false).unwrap();
+    /// // `x` lags `y` by two observations.
+    /// let peak = ccf.row(0).iter().cloned().fold(::std::f64::MIN, f64::max);
+    /// assert_eq!(ccf[[0, 2 + 2]], peak);
+    /// ```
+    fn cross_correlation<S2>(
+        &self,
+        other: &ArrayBase<S2, Ix2>,
+        max_lag: usize,
+        bias_correction: bool,
+    ) -> Result<Array2<A>, ShapeMismatch>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = A>;
+
+    /// Like [`cross_correlation`](#tymethod.cross_correlation), but skips NaN
+    /// observations, as
+    /// [`autocorrelation_skipnan`](#tymethod.autocorrelation_skipnan) does.
+    ///
+    /// Returns `Err(ShapeMismatch)` if `other` does not have the shape of
+    /// `M`.
+    ///
+    /// **Panics** if `max_lag` is not smaller than the number of
+    /// observations, or if the type cast of a number of observations from
+    /// `usize` to `A` fails.
+    fn cross_correlation_skipnan<S2>(
+        &self,
+        other: &ArrayBase<S2, Ix2>,
+        max_lag: usize,
+        bias_correction: bool,
+    ) -> Result<Array2<A>, ShapeMismatch>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = A>;
+
+    /// Return the [covariance matrix](#tymethod.cov), computing its entries
+    /// in parallel.
+    ///
+    /// **Panics** under the same conditions as [`cov`](#tymethod.cov).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{aview2, arr2};
+    /// use ndarray_stats::CorrelationExt;
+    ///
+    /// let a = arr2(&[[1., 3., 5.],
+    ///                [2., 4., 6.]]);
+    /// assert_eq!(a.par_cov(1.), aview2(&[[4., 4.], [4., 4.]]));
+    /// ```
+    #[cfg(feature = "rayon")]
+    fn par_cov(&self, ddof: A) -> Array2<A>
+    where
+        A: Float + FromPrimitive + Send + Sync;
+
+    /// Return the [Pearson correlation coefficients](#tymethod.pearson_correlation),
+    /// computing the covariance matrix in parallel.
+    ///
+    /// **Panics** under the same conditions as
+    /// [`pearson_correlation`](#tymethod.pearson_correlation).
+    #[cfg(feature = "rayon")]
+    fn par_pearson_correlation(&self) -> Array2<A>
+    where
+        A: Float + FromPrimitive + Send + Sync;
+
     private_decl! {}
 }
 
@@ -124,6 +543,13 @@ where
     S: Data<Elem = A>,
 {
     fn cov(&self, ddof: A) -> Array2<A>
+    where
+        A: Float + FromPrimitive,
+    {
+        self.cov_with(ddof, VarianceAlgorithm::TwoPass)
+    }
+
+    fn cov_with(&self, ddof: A, algorithm: VarianceAlgorithm) -> Array2<A>
     where
         A: Float + FromPrimitive,
     {
@@ -138,10 +564,79 @@ where
         } else {
             n_observations - ddof
         };
-        let mean = self.mean_axis(observation_axis);
-        let denoised = self - &mean.insert_axis(observation_axis);
-        let covariance = denoised.dot(&denoised.t());
-        covariance.mapv_into(|x| x / dof)
+        let scatter = match algorithm {
+            VarianceAlgorithm::OnePass => {
+                let origin = Array1::zeros(self.rows());
+                let mut scatter = blocked_scatter(self, &origin);
+                if self.cols() > 0 {
+                    let mean = self.mean_axis(observation_axis).insert_axis(Axis(1));
+                    general_mat_mul(-n_observations, &mean, &mean.t(), A::one(), &mut scatter);
+                }
+                scatter
+            }
+            VarianceAlgorithm::TwoPass => {
+                let mean = self.mean_axis(observation_axis);
+                blocked_scatter(self, &mean)
+            }
+            VarianceAlgorithm::Welford => welford_scatter(self),
+        };
+        scatter.mapv_into(|x| x / dof)
+    }
+
+    fn try_cov(&self, ddof: A) -> Result<Array2<A>, StatsError>
+    where
+        A: Float + FromPrimitive,
+    {
+        self.try_cov_with(ddof, VarianceAlgorithm::TwoPass)
+    }
+
+    fn try_cov_with(&self, ddof: A, algorithm: VarianceAlgorithm) -> Result<Array2<A>, StatsError>
+    where
+        A: Float + FromPrimitive,
+    {
+        let n_observations = self.len_of(Axis(1));
+        if n_observations == 0 {
+            return Err(StatsError::EmptyInput);
+        }
+        if ddof.is_nan() || ddof >= A::from_usize(n_observations).unwrap() {
+            return Err(StatsError::InvalidDdof);
+        }
+        Ok(self.cov_with(ddof, algorithm))
+    }
+
+    fn try_pearson_correlation(&self) -> Result<Array2<A>, StatsError>
+    where
+        A: Float + FromPrimitive,
+    {
+        if self.len_of(Axis(1)) == 0 {
+            return Err(StatsError::EmptyInput);
+        }
+        Ok(self.pearson_correlation())
+    }
+
+    fn cov_skipnan(&self, ddof: A) -> Array2<A>
+    where
+        A: Float + FromPrimitive,
+    {
+        pairwise_complete(self, |scatter| {
+            let dof = A::from_usize(scatter.count)
+                .expect("Converting number of observations to `A` must not fail.")
+                - ddof;
+            if dof > A::zero() {
+                scatter.xy / dof
+            } else {
+                A::nan()
+            }
+        })
+    }
+
+    fn pearson_correlation_skipnan(&self) -> Array2<A>
+    where
+        A: Float + FromPrimitive,
+    {
+        pairwise_complete(self, |scatter| {
+            scatter.xy / (scatter.xx * scatter.yy).sqrt()
+        })
     }
 
     fn pearson_correlation(&self) -> Array2<A>
@@ -163,17 +658,518 @@ where
         cov / std_matrix
     }
 
+    fn spearman_correlation(&self) -> Array2<A>
+    where
+        A: Float + FromPrimitive,
+    {
+        self.rank_axis(Axis(1)).pearson_correlation()
+    }
+
+    fn kendall_tau(&self) -> Array2<A>
+    where
+        A: Float + FromPrimitive,
+    {
+        let n_variables = self.rows();
+        let mut tau = Array2::zeros((n_variables, n_variables));
+        for i in 0..n_variables {
+            for j in i..n_variables {
+                let tau_ij = kendall_tau(self.row(i), self.row(j));
+                tau[[i, j]] = tau_ij;
+                tau[[j, i]] = tau_ij;
+            }
+        }
+        tau
+    }
+
+    fn cov_with_policy(&self, ddof: A, policy: NanPolicy) -> Result<Array2<A>, ContainsNan>
+    where
+        A: Float + FromPrimitive + MaybeNan,
+    {
+        Ok(apply_policy_to_observations(self, policy)?.cov(ddof))
+    }
+
+    fn pearson_correlation_with_policy(&self, policy: NanPolicy) -> Result<Array2<A>, ContainsNan>
+    where
+        A: Float + FromPrimitive + MaybeNan,
+    {
+        Ok(apply_policy_to_observations(self, policy)?.pearson_correlation())
+    }
+
+    fn autocorrelation(&self, max_lag: usize, bias_correction: bool) -> Array2<A>
+    where
+        A: Float + FromPrimitive,
+    {
+        lagged_correlations(self, self, 0, max_lag, bias_correction, false)
+    }
+
+    fn autocorrelation_skipnan(&self, max_lag: usize, bias_correction: bool) -> Array2<A>
+    where
+        A: Float + FromPrimitive,
+    {
+        lagged_correlations(self, self, 0, max_lag, bias_correction, true)
+    }
+
+    fn cross_correlation<S2>(
+        &self,
+        other: &ArrayBase<S2, Ix2>,
+        max_lag: usize,
+        bias_correction: bool,
+    ) -> Result<Array2<A>, ShapeMismatch>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = A>,
+    {
+        check_same_shape(self, other)?;
+        Ok(lagged_correlations(
+            self,
+            other,
+            max_lag,
+            max_lag,
+            bias_correction,
+            false,
+        ))
+    }
+
+    fn cross_correlation_skipnan<S2>(
+        &self,
+        other: &ArrayBase<S2, Ix2>,
+        max_lag: usize,
+        bias_correction: bool,
+    ) -> Result<Array2<A>, ShapeMismatch>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = A>,
+    {
+        check_same_shape(self, other)?;
+        Ok(lagged_correlations(
+            self,
+            other,
+            max_lag,
+            max_lag,
+            bias_correction,
+            true,
+        ))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_cov(&self, ddof: A) -> Array2<A>
+    where
+        A: Float + FromPrimitive + Send + Sync,
+    {
+        let observation_axis = Axis(1);
+        let n_observations = A::from_usize(self.len_of(observation_axis)).unwrap();
+        let dof = if ddof >= n_observations {
+            panic!(
+                "`ddof` needs to be strictly smaller than the \
+                 number of observations provided for each \
+                 random variable!"
+            )
+        } else {
+            n_observations - ddof
+        };
+        let mean = self.mean_axis(observation_axis);
+        let n_variables = self.rows();
+        let block_len = cov_block_len::<A>(n_variables);
+        let n_blocks = (self.cols() + block_len - 1) / block_len;
+        let zeros = || Array2::zeros((n_variables, n_variables));
+        let observations = self.view();
+        let covariance = (0..n_blocks)
+            .into_par_iter()
+            .fold(
+                || (zeros(), Array2::zeros((n_variables, block_len))),
+                |(mut covariance, mut centered), i| {
+                    let end = observations.cols().min((i + 1) * block_len);
+                    let block = observations.slice(s![.., i * block_len..end]);
+                    add_block_scatter(&mut covariance, &mut centered, &block, &mean);
+                    (covariance, centered)
+                },
+            )
+            .map(|(covariance, _)| covariance)
+            .reduce(zeros, |a, b| a + b);
+        covariance.mapv_into(|x| x / dof)
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_pearson_correlation(&self) -> Array2<A>
+    where
+        A: Float + FromPrimitive + Send + Sync,
+    {
+        let observation_axis = Axis(1);
+        // See `pearson_correlation` for the choice of `ddof`.
+        let ddof = -A::one();
+        let cov = self.par_cov(ddof);
+        let std = self
+            .std_axis(observation_axis, ddof)
+            .insert_axis(observation_axis);
+        let std_matrix = std.dot(&std.t());
+        // element-wise division
+        cov / std_matrix
+    }
+
     private_impl! {}
 }
 
+/// The centered sums of squares and products of two random variables over
+/// their pairwise-complete observations.
+struct PairScatter<A> {
+    count: usize,
+    xx: A,
+    yy: A,
+    xy: A,
+}
+
+impl<A: Float + FromPrimitive> PairScatter<A> {
+    fn new(x: ArrayView1<'_, A>, y: ArrayView1<'_, A>) -> Self {
+        let complete = || {
+            x.iter()
+                .zip(y)
+                .filter(|(x, y)| !x.is_nan() && !y.is_nan())
+                .map(|(&x, &y)| (x, y))
+        };
+        let (count, x_sum, y_sum) = complete().fold((0, A::zero(), A::zero()), |acc, (x, y)| {
+            (acc.0 + 1, acc.1 + x, acc.2 + y)
+        });
+        let n =
+            A::from_usize(count).expect("Converting number of observations to `A` must not fail.");
+        let (x_mean, y_mean) = (x_sum / n, y_sum / n);
+        let (xx, yy, xy) = complete().fold((A::zero(), A::zero(), A::zero()), |acc, (x, y)| {
+            let (dx, dy) = (x - x_mean, y - y_mean);
+            (acc.0 + dx * dx, acc.1 + dy * dy, acc.2 + dx * dy)
+        });
+        PairScatter { count, xx, yy, xy }
+    }
+}
+
+/// Returns the symmetric matrix of `entry` applied to the pairwise-complete
+/// scatter of each pair of rows of `a`.
+fn pairwise_complete<A, S, F>(a: &ArrayBase<S, Ix2>, entry: F) -> Array2<A>
+where
+    A: Float + FromPrimitive,
+    S: Data<Elem = A>,
+    F: Fn(PairScatter<A>) -> A,
+{
+    let n = a.rows();
+    let mut matrix = Array2::zeros((n, n));
+    for i in 0..n {
+        for j in 0..=i {
+            let value = entry(PairScatter::new(a.row(i), a.row(j)));
+            matrix[[i, j]] = value;
+            matrix[[j, i]] = value;
+        }
+    }
+    matrix
+}
+
+fn check_same_shape<A, S, S2>(
+    a: &ArrayBase<S, Ix2>,
+    b: &ArrayBase<S2, Ix2>,
+) -> Result<(), ShapeMismatch>
+where
+    S: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    if a.shape() == b.shape() {
+        Ok(())
+    } else {
+        Err(ShapeMismatch {
+            first_shape: a.shape().to_vec(),
+            second_shape: b.shape().to_vec(),
+        })
+    }
+}
+
+/// Returns the correlations of each row `x` of `a` with the same row `y` of
+/// `b` at the lags `-min_lag` to `max_lag` (see
+/// `CorrelationExt::cross_correlation`), skipping NaN observations if
+/// `skipnan` is `true`.
+fn lagged_correlations<A, S, S2>(
+    a: &ArrayBase<S, Ix2>,
+    b: &ArrayBase<S2, Ix2>,
+    min_lag: usize,
+    max_lag: usize,
+    bias_correction: bool,
+    skipnan: bool,
+) -> Array2<A>
+where
+    A: Float + FromPrimitive,
+    S: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    let n = a.cols();
+    assert!(
+        max_lag < n,
+        "`max_lag` must be smaller than the number of observations."
+    );
+    let to_a = |count: usize| {
+        A::from_usize(count).expect("Converting the number of observations to `A` must not fail.")
+    };
+    let mut correlations = Array2::zeros((a.rows(), min_lag + max_lag + 1));
+    for ((x, y), mut row) in a
+        .outer_iter()
+        .zip(b.outer_iter())
+        .zip(correlations.outer_iter_mut())
+    {
+        // The deviations from the mean, with `None` for skipped observations.
+        let deviations = |series: ArrayView1<'_, A>| -> Vec<Option<A>> {
+            let (sum, count) = series
+                .iter()
+                .filter(|x| !(skipnan && x.is_nan()))
+                .fold((A::zero(), 0), |(sum, count), &x| (sum + x, count + 1));
+            let mean = sum / to_a(count);
+            series
+                .iter()
+                .map(|&x| {
+                    if skipnan && x.is_nan() {
+                        None
+                    } else {
+                        Some(x - mean)
+                    }
+                })
+                .collect()
+        };
+        let dx = deviations(x);
+        let dy = deviations(y);
+        // Returns the sum of the products of `dx[t + shift]` and `dy[t]`
+        // over the pairs of observations, and their number.
+        let products = |dx: &[Option<A>], dy: &[Option<A>], shift: usize| {
+            dx[shift..]
+                .iter()
+                .zip(dy)
+                .filter_map(|pair| match pair {
+                    (Some(x), Some(y)) => Some(*x * *y),
+                    _ => None,
+                })
+                .fold((A::zero(), 0), |(sum, count), p| (sum + p, count + 1))
+        };
+        let variance = |d: &[Option<A>]| {
+            let (sum, count) = products(d, d, 0);
+            sum / to_a(count)
+        };
+        let scale = (variance(&dx) * variance(&dy)).sqrt();
+        let n_terms = if skipnan { products(&dx, &dy, 0).1 } else { n };
+        for (column, c) in row.iter_mut().enumerate() {
+            let (sum, count) = if column >= min_lag {
+                products(&dx, &dy, column - min_lag)
+            } else {
+                products(&dy, &dx, min_lag - column)
+            };
+            *c = if count == 0 {
+                A::nan()
+            } else if bias_correction {
+                sum / to_a(count) / scale
+            } else {
+                sum / to_a(n_terms) / scale
+            };
+        }
+    }
+    correlations
+}
+
+/// Returns Kendall's τ_b of `x` and `y`, computed with Knight's algorithm.
+fn kendall_tau<A>(x: ArrayView1<'_, A>, y: ArrayView1<'_, A>) -> A
+where
+    A: Float + FromPrimitive,
+{
+    if x.iter().chain(&y).any(|v| v.is_nan()) {
+        return A::nan();
+    }
+    let cmp = |a: &A, b: &A| a.partial_cmp(b).expect("NaN values have been excluded.");
+    let mut pairs: Vec<(A, A)> = x.iter().cloned().zip(y.iter().cloned()).collect();
+    pairs.sort_unstable_by(|a, b| cmp(&a.0, &b.0).then_with(|| cmp(&a.1, &b.1)));
+    let n = pairs.len();
+    let n_pairs = n * n.saturating_sub(1) / 2;
+    let tied_x = tied_pairs(&pairs, |a, b| a.0 == b.0);
+    let tied_xy = tied_pairs(&pairs, |a, b| a == b);
+    // Sorting `y` by merge sort counts the discordant pairs, which are the
+    // inversions of `y` in the order of `x`.
+    let mut ys: Vec<A> = pairs.iter().map(|&(_, y)| y).collect();
+    let discordant = sort_counting_inversions(&mut ys, &mut Vec::with_capacity(n));
+    let tied_y = tied_pairs(&ys, |a, b| a == b);
+    let to_a =
+        |n: usize| A::from_usize(n).expect("Converting a number of pairs to `A` must not fail.");
+    let concordant_minus_discordant =
+        to_a(n_pairs + tied_xy) - to_a(tied_x + tied_y) - to_a(discordant) * (A::one() + A::one());
+    concordant_minus_discordant
+        / ((to_a(n_pairs) - to_a(tied_x)) * (to_a(n_pairs) - to_a(tied_y))).sqrt()
+}
+
+/// Returns the number of pairs of equal elements of `sorted`, where equal
+/// elements are adjacent.
+fn tied_pairs<T, F>(sorted: &[T], equal: F) -> usize
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let mut n_tied = 0;
+    let mut run = 1;
+    for i in 1..=sorted.len() {
+        if i < sorted.len() && equal(&sorted[i - 1], &sorted[i]) {
+            run += 1;
+        } else {
+            n_tied += run * (run - 1) / 2;
+            run = 1;
+        }
+    }
+    n_tied
+}
+
+/// Sorts `xs` with a merge sort, using `buffer` as scratch space, and returns
+/// the number of pairs `i < j` such that `xs[i] > xs[j]` before sorting.
+fn sort_counting_inversions<A: Float>(xs: &mut [A], buffer: &mut Vec<A>) -> usize {
+    if xs.len() < 2 {
+        return 0;
+    }
+    let mid = xs.len() / 2;
+    let mut inversions = sort_counting_inversions(&mut xs[..mid], buffer)
+        + sort_counting_inversions(&mut xs[mid..], buffer);
+    buffer.clear();
+    let (mut i, mut j) = (0, mid);
+    while i < mid && j < xs.len() {
+        if xs[j] < xs[i] {
+            // `xs[j]` is smaller than all the remaining elements of the left
+            // half.
+            inversions += mid - i;
+            buffer.push(xs[j]);
+            j += 1;
+        } else {
+            buffer.push(xs[i]);
+            i += 1;
+        }
+    }
+    buffer.extend_from_slice(&xs[i..mid]);
+    buffer.extend_from_slice(&xs[j..]);
+    xs.copy_from_slice(buffer);
+    inversions
+}
+
+/// Approximate size (in bytes) of a block of centered observations in the
+/// covariance computation, chosen to fit in a typical L2 cache.
+const COV_BLOCK_BYTES: usize = 256 * 1024;
+
+/// Returns the number of observations per block when computing the covariance
+/// matrix of `n_variables` random variables.
+fn cov_block_len<A>(n_variables: usize) -> usize {
+    let observation_bytes = n_variables.max(1) * core::mem::size_of::<A>().max(1);
+    (COV_BLOCK_BYTES / observation_bytes).max(1)
+}
+
+/// Returns the scatter matrix of the observations in `a` around `mean` (the
+/// sum of the outer products of the centered observations), processing the
+/// observations in cache-sized blocks.
+fn blocked_scatter<A, S>(a: &ArrayBase<S, Ix2>, mean: &Array1<A>) -> Array2<A>
+where
+    A: Float + 'static,
+    S: Data<Elem = A>,
+{
+    let block_len = cov_block_len::<A>(a.rows());
+    let mut scatter = Array2::zeros((a.rows(), a.rows()));
+    let mut centered = Array2::zeros((a.rows(), block_len));
+    for start in (0..a.cols()).step_by(block_len) {
+        let block = a.slice(s![.., start..a.cols().min(start + block_len)]);
+        add_block_scatter(&mut scatter, &mut centered, &block, mean);
+    }
+    scatter
+}
+
+/// Returns the scatter matrix of the observations in `a` around their mean,
+/// updating the mean and the co-moments with every observation (the
+/// multivariate version of Welford's algorithm).
+fn welford_scatter<A, S>(a: &ArrayBase<S, Ix2>) -> Array2<A>
+where
+    A: Float + FromPrimitive,
+    S: Data<Elem = A>,
+{
+    let mut mean = Array1::zeros(a.rows());
+    let mut scatter = Array2::zeros((a.rows(), a.rows()));
+    let mut delta = Array1::zeros(a.rows());
+    for (i, observation) in a.gencolumns().into_iter().enumerate() {
+        let n =
+            A::from_usize(i + 1).expect("Converting number of observations to `A` must not fail.");
+        Zip::from(&mut delta)
+            .and(&mut mean)
+            .and(&observation)
+            .apply(|delta, mean, &x| {
+                *delta = x - *mean;
+                *mean = *mean + *delta / n;
+            });
+        // After the update of the mean, `x - mean` is `delta * (n - 1) / n`.
+        let weight = (n - A::one()) / n;
+        Zip::from(scatter.genrows_mut())
+            .and(&delta)
+            .apply(|mut row, &delta_i| {
+                row.zip_mut_with(&delta, |s, &delta_j| *s = *s + delta_i * delta_j * weight)
+            });
+    }
+    scatter
+}
+
+/// Adds the scatter matrix of the observations in `block` (the sum of the
+/// outer products of the observations centered around `mean`) to
+/// `covariance`.
+///
+/// `centered` is a scratch buffer with at least as many columns as `block`;
+/// it is reused across blocks so that only one block of centered
+/// observations is ever materialized, instead of a centered copy of the whole
+/// array.
+fn add_block_scatter<A, S>(
+    covariance: &mut Array2<A>,
+    centered: &mut Array2<A>,
+    block: &ArrayBase<S, Ix2>,
+    mean: &Array1<A>,
+) where
+    A: Float + 'static,
+    S: Data<Elem = A>,
+{
+    let mut centered = centered.slice_mut(s![.., ..block.cols()]);
+    Zip::from(&mut centered)
+        .and(block)
+        .and_broadcast(mean.view().insert_axis(Axis(1)))
+        .apply(|c, &x, &m| *c = x - m);
+    general_mat_mul(A::one(), &centered, &centered.t(), A::one(), covariance);
+}
+
+/// Returns the observations (columns) of `array` that should be used to
+/// compute a covariance matrix according to `policy`.
+///
+/// NaN values propagate through the covariance computation, so nothing
+/// needs to be done for `NanPolicy::Propagate`.
+fn apply_policy_to_observations<A, S>(
+    array: &ArrayBase<S, Ix2>,
+    policy: NanPolicy,
+) -> Result<Array2<A>, ContainsNan>
+where
+    A: MaybeNan + Copy,
+    S: Data<Elem = A>,
+{
+    match policy {
+        NanPolicy::Propagate => Ok(array.to_owned()),
+        NanPolicy::Omit => {
+            let complete_observations: Vec<usize> = array
+                .axis_iter(Axis(1))
+                .enumerate()
+                .filter(|(_, observation)| !observation.has_nan())
+                .map(|(index, _)| index)
+                .collect();
+            Ok(array.select(Axis(1), &complete_observations))
+        }
+        NanPolicy::Error => {
+            if array.has_nan() {
+                Err(ContainsNan)
+            } else {
+                Ok(array.to_owned())
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod cov_tests {
     use super::*;
+    use approx::assert_abs_diff_eq;
     use ndarray::array;
     use ndarray_rand::RandomExt;
     use quickcheck_macros::quickcheck;
-    use rand;
     use rand::distributions::Uniform;
+    use std::f64;
 
     #[quickcheck]
     fn constant_random_variables_have_zero_covariance_matrix(value: f64) -> bool {
@@ -258,6 +1254,45 @@ mod cov_tests {
         assert!(a.cov(1.).all_close(&numpy_covariance, 1e-8));
     }
 
+    #[test]
+    fn test_covariance_over_several_blocks() {
+        // Tall-skinny data, so that the observations span several blocks
+        // (with a shorter last block).
+        let n_observations = 3 * cov_block_len::<f64>(4) + 17;
+        let a = Array::random((4, n_observations), Uniform::new(-1., 1.));
+        let centered = &a - &a.mean_axis(Axis(1)).insert_axis(Axis(1));
+        let expected = centered.dot(&centered.t()) / (n_observations as f64 - 1.);
+        assert!(a.cov(1.).all_close(&expected, 1e-10));
+        #[cfg(feature = "rayon")]
+        assert!(a.par_cov(1.).all_close(&expected, 1e-10));
+    }
+
+    #[test]
+    fn test_covariance_algorithms() {
+        let a = Array::random((3, 50), Uniform::new(-1., 1.));
+        let expected = a.cov(1.);
+        for &algorithm in &[
+            VarianceAlgorithm::OnePass,
+            VarianceAlgorithm::TwoPass,
+            VarianceAlgorithm::Welford,
+        ] {
+            assert!(a.cov_with(1., algorithm).all_close(&expected, 1e-12));
+            assert_eq!(
+                Array2::<f64>::zeros((2, 0)).cov_with(-1., algorithm),
+                Array2::zeros((2, 2))
+            );
+        }
+        // The one-pass algorithm loses every significant digit when the
+        // offset dwarfs the spread of the data; the others don't.
+        let offset = &a + 1e9;
+        let two_pass = offset.cov_with(1., VarianceAlgorithm::TwoPass);
+        let welford = offset.cov_with(1., VarianceAlgorithm::Welford);
+        let one_pass = offset.cov_with(1., VarianceAlgorithm::OnePass);
+        assert!(two_pass.all_close(&expected, 1e-6));
+        assert!(welford.all_close(&expected, 1e-6));
+        assert!(!one_pass.all_close(&expected, 1e-2));
+    }
+
     #[test]
     #[should_panic]
     // We lose precision, hence the failing assert
@@ -266,6 +1301,47 @@ mod cov_tests {
         let expected_covariance = array![[2., 2e-12], [2e-12, 2e-24]];
         assert!(a.cov(1.).all_close(&expected_covariance, 1e-24));
     }
+
+    #[test]
+    fn test_cov_skipnan_pairwise_complete() {
+        let a = array![
+            [1., 2., f64::NAN, 4., 0.],
+            [2., 4., 6., 8., 1.],
+            [f64::NAN, 1., 3., 2., f64::NAN]
+        ];
+        let cov = a.cov_skipnan(1.);
+        assert!(cov.all_close(&cov.t(), 0.));
+        for &(i, j) in &[(0, 0), (0, 1), (0, 2), (1, 1), (1, 2), (2, 2)] {
+            let complete: Vec<usize> = (0..5)
+                .filter(|&t| !a[[i, t]].is_nan() && !a[[j, t]].is_nan())
+                .collect();
+            let pair = array![
+                complete.iter().map(|&t| a[[i, t]]).collect::<Vec<_>>(),
+                complete.iter().map(|&t| a[[j, t]]).collect::<Vec<_>>()
+            ];
+            let pair = Array2::from_shape_fn((2, complete.len()), |(r, t)| pair[r][t]);
+            assert_abs_diff_eq!(cov[[i, j]], pair.cov(1.)[[0, 1]], epsilon = 1e-12);
+        }
+        // Only one pairwise-complete observation is left with `ddof = 1`.
+        let b = array![[1., f64::NAN, 3.], [f64::NAN, 2., 5.]];
+        let cov = b.cov_skipnan(1.);
+        assert!(cov[[0, 1]].is_nan());
+        assert_abs_diff_eq!(cov[[0, 0]], 2., epsilon = 1e-12);
+        assert_abs_diff_eq!(b.cov_skipnan(0.)[[0, 1]], 0., epsilon = 1e-12);
+    }
+
+    #[quickcheck]
+    fn cov_skipnan_matches_cov_without_nan(xs: Vec<(i8, i8)>) -> bool {
+        if xs.len() < 2 {
+            return true;
+        }
+        let a = Array2::from_shape_fn((2, xs.len()), |(i, j)| f64::from([xs[j].0, xs[j].1][i]));
+        a.cov_skipnan(1.).all_close(&a.cov(1.), 1e-9)
+            && a.pearson_correlation_skipnan()
+                .iter()
+                .zip(&a.pearson_correlation())
+                .all(|(x, y)| (x.is_nan() && y.is_nan()) || (x - y).abs() < 1e-9)
+    }
 }
 
 #[cfg(test)]
@@ -275,6 +1351,26 @@ mod pearson_correlation_tests {
     use ndarray_rand::RandomExt;
     use quickcheck_macros::quickcheck;
     use rand::distributions::Uniform;
+    use std::f64;
+
+    #[test]
+    fn test_try_pearson_correlation() {
+        let a = array![[1., 2., 4.], [3., 1., 0.]];
+        assert_eq!(a.try_pearson_correlation(), Ok(a.pearson_correlation()));
+        assert_eq!(
+            Array2::<f64>::zeros((2, 0)).try_pearson_correlation(),
+            Err(StatsError::EmptyInput)
+        );
+        assert!(a.try_cov(f64::NAN).is_err());
+        assert_eq!(
+            a.try_cov_with(1., VarianceAlgorithm::OnePass),
+            Ok(a.cov_with(1., VarianceAlgorithm::OnePass))
+        );
+        assert_eq!(
+            a.try_cov_with(3., VarianceAlgorithm::OnePass),
+            Err(StatsError::InvalidDdof)
+        );
+    }
 
     #[quickcheck]
     fn output_matrix_is_symmetric(bound: f64) -> bool {
@@ -341,4 +1437,197 @@ mod pearson_correlation_tests {
         assert!(a.pearson_correlation().all_close(&numpy_corrcoeff, 1e-7));
     }
 
+    #[test]
+    fn test_with_policy() {
+        let a = array![[1., 2., 3., 4.], [1., 2., f64::NAN, 8.], [4., 3., 2., 1.]];
+
+        let propagated = a
+            .pearson_correlation_with_policy(NanPolicy::Propagate)
+            .unwrap();
+        assert!(propagated.row(1).iter().all(|x| x.is_nan()));
+        assert!(propagated.column(1).iter().all(|x| x.is_nan()));
+        assert!((propagated[[0, 2]] + 1.).abs() < 1e-12);
+
+        let omitted = a.pearson_correlation_with_policy(NanPolicy::Omit).unwrap();
+        let complete = array![[1., 2., 4.], [1., 2., 8.], [4., 3., 1.]];
+        assert!(omitted.all_close(&complete.pearson_correlation(), 1e-12));
+
+        assert_eq!(
+            a.pearson_correlation_with_policy(NanPolicy::Error),
+            Err(ContainsNan)
+        );
+    }
+
+    #[test]
+    fn test_pearson_correlation_skipnan() {
+        let a = array![
+            [1., 2., f64::NAN, 4., 3.],
+            [2., 4., 6., 8., 6.],
+            [f64::NAN, 1., f64::NAN, 1., 1.],
+            [f64::NAN, f64::NAN, 5., f64::NAN, f64::NAN]
+        ];
+        let corr = a.pearson_correlation_skipnan();
+        assert!((corr[[0, 1]] - 1.).abs() < 1e-12);
+        assert!((corr[[1, 0]] - 1.).abs() < 1e-12);
+        assert!((corr[[1, 1]] - 1.).abs() < 1e-12);
+        // Constant over its observations.
+        assert!(corr.row(2).iter().all(|x| x.is_nan()));
+        // No pairwise-complete observations with rows 0 and 2.
+        assert!(corr[[0, 3]].is_nan());
+        assert!(corr[[2, 3]].is_nan());
+    }
+}
+
+#[cfg(test)]
+mod rank_correlation_tests {
+    use super::*;
+    use ndarray::array;
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[test]
+    fn test_against_scipy() {
+        let a = array![[12., 2., 1., 12., 2.], [1., 4., 7., 1., 0.]];
+        // scipy.stats.kendalltau(a[0], a[1])
+        assert!((a.kendall_tau()[[0, 1]] + 0.47140452079103173).abs() < 1e-12);
+        let b = array![[1., 2., 3., 4., 5.], [5., 6., 7., 8., 7.]];
+        // scipy.stats.spearmanr(b[0], b[1])
+        assert!((b.spearman_correlation()[[0, 1]] - 0.8207826816681233).abs() < 1e-12);
+        assert!((b.kendall_tau()[[1, 0]] - 0.7378647873726218).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_nan_and_constant_variables() {
+        let a = array![[1., 2., 3.], [1., f64::NAN, 2.], [5., 5., 5.]];
+        for corr in &[a.spearman_correlation(), a.kendall_tau()] {
+            assert!((corr[[0, 0]] - 1.).abs() < 1e-12);
+            assert!(corr.row(1).iter().all(|x| x.is_nan()));
+            assert!(corr.row(2).iter().all(|x| x.is_nan()));
+        }
+    }
+
+    #[quickcheck]
+    fn kendall_tau_matches_naive(xs: Vec<(i8, i8)>) -> bool {
+        let xs: Vec<(f64, f64)> = xs
+            .into_iter()
+            .map(|(x, y)| (f64::from(x % 4), f64::from(y)))
+            .collect();
+        let a = Array2::from_shape_fn((2, xs.len()), |(i, j)| [xs[j].0, xs[j].1][i]);
+        let sign = |d: f64| if d == 0. { 0. } else { d.signum() };
+        let (mut concordant_minus_discordant, mut untied_x, mut untied_y) = (0., 0., 0.);
+        for i in 0..xs.len() {
+            for j in 0..i {
+                let dx = sign(xs[i].0 - xs[j].0);
+                let dy = sign(xs[i].1 - xs[j].1);
+                concordant_minus_discordant += dx * dy;
+                untied_x += dx.abs();
+                untied_y += dy.abs();
+            }
+        }
+        let naive = concordant_minus_discordant / (untied_x * untied_y).sqrt();
+        let tau = a.kendall_tau()[[0, 1]];
+        (naive.is_nan() && tau.is_nan()) || (tau - naive).abs() < 1e-12
+    }
+}
+
+#[cfg(test)]
+mod lagged_correlation_tests {
+    use super::*;
+    use ndarray::array;
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[test]
+    fn test_autocorrelation_known_values() {
+        let a = array![[1., 2., 3., 4., 5.]];
+        // statsmodels.tsa.stattools.acf(a[0], nlags=2)
+        assert!(a
+            .autocorrelation(2, false)
+            .all_close(&array![[1., 0.4, -0.1]], 1e-12));
+        // statsmodels.tsa.stattools.acf(a[0], nlags=2, adjusted=True)
+        assert!(a
+            .autocorrelation(2, true)
+            .all_close(&array![[1., 0.5, -1. / 6.]], 1e-12));
+    }
+
+    #[test]
+    fn test_cross_correlation_of_shifted_series() {
+        let x = array![[0., 0., 1., 0., 0., 0.]];
+        let y = array![[1., 0., 0., 0., 0., 0.]];
+        let ccf = x.cross_correlation(&y, 3, true).unwrap();
+        let argmax = (0..7)
+            .max_by(|&i, &j| ccf[[0, i]].partial_cmp(&ccf[[0, j]]).unwrap())
+            .unwrap();
+        assert_eq!(argmax, 3 + 2);
+        // Swapping the series mirrors the lags.
+        let mirrored = y.cross_correlation(&x, 3, true).unwrap();
+        assert!(mirrored.slice(s![.., ..;-1]).all_close(&ccf, 1e-12));
+    }
+
+    #[test]
+    fn test_cross_correlation_shape_mismatch() {
+        let x = array![[1., 2., 3.]];
+        let y = array![[1., 2.]];
+        assert!(x.cross_correlation(&y, 1, false).is_err());
+        assert!(x.cross_correlation_skipnan(&y, 1, false).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_max_lag_too_large() {
+        array![[1., 2., 3.]].autocorrelation(3, false);
+    }
+
+    #[test]
+    fn test_autocorrelation_nan() {
+        let a = array![[1., f64::NAN, 3., 4., 5.], [1., 2., 3., 4., 5.]];
+        let acf = a.autocorrelation(2, false);
+        assert!(acf.row(0).iter().all(|x| x.is_nan()));
+        assert!(acf.row(1).all_close(&array![1., 0.4, -0.1], 1e-12));
+        let acf = a.autocorrelation_skipnan(2, true);
+        assert_eq!(acf[[0, 0]], 1.);
+        assert!(acf.row(0).iter().all(|x| !x.is_nan()));
+        // Alternating NaN values leave no pair at lag 1.
+        let b = array![[1., f64::NAN, 2., f64::NAN, 4.]];
+        let acf = b.autocorrelation_skipnan(2, false);
+        assert!(acf[[0, 1]].is_nan());
+        assert!(!acf[[0, 2]].is_nan());
+    }
+
+    #[quickcheck]
+    fn cross_correlation_matches_naive(xs: Vec<(i8, i8)>, max_lag: usize) -> bool {
+        let n = xs.len();
+        if n == 0 {
+            return true;
+        }
+        let max_lag = max_lag % n;
+        let x: Vec<f64> = xs.iter().map(|&(x, _)| f64::from(x)).collect();
+        let y: Vec<f64> = xs.iter().map(|&(_, y)| f64::from(y)).collect();
+        let mean = |v: &[f64]| v.iter().sum::<f64>() / n as f64;
+        let (mx, my) = (mean(&x), mean(&y));
+        let c = |k: isize| {
+            let mut sum = 0.;
+            for t in 0..n as isize {
+                if t + k >= 0 && t + k < n as isize {
+                    sum += (x[(t + k) as usize] - mx) * (y[t as usize] - my);
+                }
+            }
+            sum
+        };
+        let vx = x.iter().map(|v| (v - mx).powi(2)).sum::<f64>() / n as f64;
+        let vy = y.iter().map(|v| (v - my).powi(2)).sum::<f64>() / n as f64;
+        let a = Array2::from_shape_vec((1, n), x.clone()).unwrap();
+        let b = Array2::from_shape_vec((1, n), y.clone()).unwrap();
+        let biased = a.cross_correlation(&b, max_lag, false).unwrap();
+        let corrected = a.cross_correlation_skipnan(&b, max_lag, true).unwrap();
+        (0..2 * max_lag + 1).all(|j| {
+            let k = j as isize - max_lag as isize;
+            let naive = c(k) / (vx * vy).sqrt();
+            let close = |value: f64, expected: f64| {
+                (value.is_nan() && expected.is_nan()) || (value - expected).abs() < 1e-9
+            };
+            close(biased[[0, j]], naive / n as f64)
+                && close(corrected[[0, j]], naive / (n - k.abs() as usize) as f64)
+        })
+    }
 }
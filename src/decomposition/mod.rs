@@ -0,0 +1,15 @@
+//! Decompositions of data matrices (principal component analysis and
+//! whitening).
+//!
+//! Data matrices are `(n_observations, n_features)` arrays whose rows are
+//! observations. The decompositions are fitted once on a data matrix, and the
+//! resulting transforms can then be applied to new observations.
+//!
+//! The eigendecompositions are computed by this crate (with the Jacobi
+//! eigenvalue algorithm), without depending on a LAPACK implementation: they
+//! are accurate, but meant for at most a few hundred features.
+pub use self::pca::Pca;
+pub use self::whitening::{Whitening, WhiteningMethod};
+
+mod pca;
+mod whitening;
@@ -0,0 +1,218 @@
+use crate::errors::{DecompositionError, ShapeMismatch};
+use crate::linalg::symmetric_eigen;
+use crate::CorrelationExt;
+use ndarray::{s, Array1, Array2, ArrayBase, Axis, Data, Ix2};
+use num_traits::{Float, FromPrimitive};
+
+/// Principal component analysis of a data matrix.
+///
+/// The principal components are the eigenvectors of the covariance matrix of
+/// the features, in decreasing order of the variance they explain. A fitted
+/// `Pca` projects observations onto the first components with
+/// [`transform`](#method.transform), so it can be fitted on training data and
+/// then applied to new data.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::decomposition::Pca;
+///
+/// // Observations that vary (almost) only along the diagonal.
+/// let data = array![[1., 1.], [2., 2.1], [3., 2.9], [4., 4.]];
+/// let pca = Pca::fit(&data, 1).unwrap();
+/// assert!(pca.explained_variance_ratio[0] > 0.99);
+/// let h = 0.5f64.sqrt();
+/// assert!(pca.components.all_close(&array![[h, h]], 1e-2));
+/// let projected = pca.transform(&array![[2.5, 2.5]]).unwrap();
+/// assert!(projected.all_close(&array![[0.]], 1e-12));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pca<A> {
+    /// Mean of each feature, subtracted before projecting.
+    pub mean: Array1<A>,
+    /// Standard deviation of each feature (or one for constant features), by
+    /// which the centered features are divided before projecting, if the
+    /// PCA was fitted on standardized data.
+    pub scale: Option<Array1<A>>,
+    /// Principal components, as the rows of a `(n_components, n_features)`
+    /// matrix of orthonormal vectors.
+    pub components: Array2<A>,
+    /// Variance of the data along each component (the corresponding
+    /// eigenvalue of the covariance matrix).
+    pub explained_variance: Array1<A>,
+    /// Fraction of the total variance explained by each component.
+    pub explained_variance_ratio: Array1<A>,
+}
+
+impl<A> Pca<A>
+where
+    A: Float + FromPrimitive + 'static,
+{
+    /// Computes the first `n_components` principal components of `data`, a
+    /// `(n_observations, n_features)` matrix whose rows are observations.
+    ///
+    /// The features are centered, and the covariance matrix is computed with
+    /// `ddof = 1` (like scikit-learn's `PCA`).
+    ///
+    /// Returns `Err(DecompositionError::NotEnoughObservations)` if `data` has
+    /// fewer than two rows and `Err(DecompositionError::TooManyComponents)`
+    /// if `n_components` is greater than the number of features.
+    pub fn fit<S>(data: &ArrayBase<S, Ix2>, n_components: usize) -> Result<Self, DecompositionError>
+    where
+        S: Data<Elem = A>,
+    {
+        fit(data, n_components, false)
+    }
+
+    /// Computes the first `n_components` principal components of `data`
+    /// after dividing each feature by its standard deviation, i.e. the
+    /// eigenvectors of the correlation matrix.
+    ///
+    /// Use this method when the features are measured in different units.
+    /// Constant features are left unscaled.
+    ///
+    /// See [`fit`](#method.fit) for the errors.
+    pub fn fit_standardized<S>(
+        data: &ArrayBase<S, Ix2>,
+        n_components: usize,
+    ) -> Result<Self, DecompositionError>
+    where
+        S: Data<Elem = A>,
+    {
+        fit(data, n_components, true)
+    }
+
+    /// Projects the observations (rows) of `data` onto the principal
+    /// components, returning a `(n_observations, n_components)` matrix.
+    ///
+    /// Returns `Err(ShapeMismatch)` if the number of columns of `data`
+    /// differs from the number of features the PCA was fitted on.
+    pub fn transform<S>(&self, data: &ArrayBase<S, Ix2>) -> Result<Array2<A>, ShapeMismatch>
+    where
+        S: Data<Elem = A>,
+    {
+        if data.cols() != self.mean.len() {
+            return Err(ShapeMismatch {
+                first_shape: vec![data.rows(), self.mean.len()],
+                second_shape: data.shape().to_vec(),
+            });
+        }
+        let centered = data - &self.mean;
+        let centered = match &self.scale {
+            Some(scale) => centered / scale,
+            None => centered,
+        };
+        Ok(centered.dot(&self.components.t()))
+    }
+}
+
+fn fit<A, S>(
+    data: &ArrayBase<S, Ix2>,
+    n_components: usize,
+    standardize: bool,
+) -> Result<Pca<A>, DecompositionError>
+where
+    A: Float + FromPrimitive + 'static,
+    S: Data<Elem = A>,
+{
+    if data.rows() < 2 {
+        return Err(DecompositionError::NotEnoughObservations);
+    }
+    if n_components > data.cols() {
+        return Err(DecompositionError::TooManyComponents);
+    }
+    let mean = data.mean_axis(Axis(0));
+    let (covariance, scale) = if standardize {
+        let std = data.std_axis(Axis(0), A::one());
+        let scale = std.mapv(|s| if s > A::zero() { s } else { A::one() });
+        ((data / &scale).t().cov(A::one()), Some(scale))
+    } else {
+        (data.t().cov(A::one()), None)
+    };
+    let (eigenvalues, eigenvectors) = symmetric_eigen(covariance);
+    // Rounding errors can make the eigenvalues of a singular covariance
+    // matrix slightly negative.
+    let eigenvalues = eigenvalues.mapv(|x| x.max(A::zero()));
+    let total_variance = eigenvalues.sum();
+    let explained_variance = eigenvalues.slice(s![..n_components]).to_owned();
+    Ok(Pca {
+        mean,
+        scale,
+        components: eigenvectors.slice(s![.., ..n_components]).t().to_owned(),
+        explained_variance_ratio: explained_variance.mapv(|x| x / total_variance),
+        explained_variance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use ndarray_rand::RandomExt;
+    use rand::distributions::Uniform;
+
+    #[test]
+    fn test_pca_projections_are_uncorrelated() {
+        let data = array![
+            [2.5, 2.4, 1.0],
+            [0.5, 0.7, 2.0],
+            [2.2, 2.9, 1.5],
+            [1.9, 2.2, 0.5],
+            [3.1, 3.0, 1.0],
+            [2.3, 2.7, 2.5]
+        ];
+        let pca = Pca::fit(&data, 2).unwrap();
+        let (eigenvalues, _) = symmetric_eigen(data.t().cov(1.));
+        assert!(pca
+            .explained_variance
+            .all_close(&eigenvalues.slice(s![..2]), 1e-12));
+        assert!(pca.explained_variance_ratio.sum() < 1.);
+        // The projections are uncorrelated, with the explained variances.
+        let projected = pca.transform(&data).unwrap();
+        let covariance = projected.t().cov(1.);
+        assert!(covariance.all_close(
+            &Array2::from_shape_fn((2, 2), |(i, j)| {
+                if i == j {
+                    pca.explained_variance[i]
+                } else {
+                    0.
+                }
+            }),
+            1e-10
+        ));
+    }
+
+    #[test]
+    fn test_pca_all_components_preserve_distances() {
+        let data = Array2::random((30, 4), Uniform::new(-1., 1.));
+        for pca in &[
+            Pca::fit(&data, 4).unwrap(),
+            Pca::fit_standardized(&data, 4).unwrap(),
+        ] {
+            assert!((pca.explained_variance_ratio.sum() - 1.).abs() < 1e-12);
+            let projected = pca.transform(&data).unwrap();
+            let mut scaled = &data - &pca.mean;
+            if let Some(scale) = &pca.scale {
+                scaled /= scale;
+            }
+            let gram = scaled.dot(&scaled.t());
+            assert!(projected.dot(&projected.t()).all_close(&gram, 1e-10));
+        }
+    }
+
+    #[test]
+    fn test_pca_errors() {
+        let data = array![[1., 2.], [3., 4.]];
+        assert_eq!(
+            Pca::fit(&data.slice(s![..1, ..]), 1),
+            Err(DecompositionError::NotEnoughObservations)
+        );
+        assert_eq!(
+            Pca::fit(&data, 3),
+            Err(DecompositionError::TooManyComponents)
+        );
+        let pca = Pca::fit(&data, 1).unwrap();
+        assert!(pca.transform(&array![[1., 2., 3.]]).is_err());
+    }
+}
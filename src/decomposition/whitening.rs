@@ -0,0 +1,150 @@
+use crate::errors::{DecompositionError, ShapeMismatch};
+use crate::linalg::{is_singular, symmetric_eigen};
+use crate::CorrelationExt;
+use ndarray::{Array1, Array2, ArrayBase, Axis, Data, Ix2};
+use num_traits::{Float, FromPrimitive};
+
+/// The whitening transform to fit with [`Whitening::fit`].
+///
+/// Both variants decorrelate the features and scale them to unit variance;
+/// they differ by a rotation.
+///
+/// [`Whitening::fit`]: struct.Whitening.html#method.fit
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WhiteningMethod {
+    /// Projects the data onto the principal components and scales each of
+    /// them to unit variance (`W = Λ^(-1/2) Vᵀ`, where `V Λ Vᵀ` is the
+    /// eigendecomposition of the covariance matrix). The whitened features
+    /// are ordered by the variance of the corresponding component.
+    Pca,
+    /// Zero-phase component analysis, also known as Mahalanobis whitening
+    /// (`W = V Λ^(-1/2) Vᵀ`): the whitened data stays as close as possible to
+    /// the original data, so each whitened feature still corresponds to an
+    /// original feature.
+    Zca,
+}
+
+/// A fitted whitening (decorrelation) transform.
+///
+/// Whitening centers the data and transforms it linearly so that its
+/// covariance matrix becomes the identity. The transform is fitted once with
+/// [`fit`](#method.fit), and then applied to the same or other (e.g.
+/// held-out) observations with [`transform`](#method.transform).
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{array, Array2};
+/// use ndarray_stats::decomposition::{Whitening, WhiteningMethod};
+/// use ndarray_stats::CorrelationExt;
+///
+/// let data = array![[1., 2.], [2., 3.5], [3., 3.], [4., 6.], [5., 5.5]];
+/// let whitening = Whitening::fit(&data, WhiteningMethod::Zca).unwrap();
+/// let whitened = whitening.transform(&data).unwrap();
+/// assert!(whitened.t().cov(1.).all_close(&Array2::eye(2), 1e-12));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Whitening<A> {
+    /// Mean of each feature, subtracted before transforming.
+    pub mean: Array1<A>,
+    /// The `(n_features, n_features)` matrix by which the centered
+    /// observations (rows) are multiplied: the transpose of the whitening
+    /// matrix `W`.
+    pub matrix: Array2<A>,
+}
+
+impl<A> Whitening<A>
+where
+    A: Float + FromPrimitive + 'static,
+{
+    /// Fits a whitening transform to `data`, a `(n_observations,
+    /// n_features)` matrix whose rows are observations, using its covariance
+    /// matrix computed with `ddof = 1`.
+    ///
+    /// Returns `Err(DecompositionError::NotEnoughObservations)` if `data` has
+    /// fewer than two rows and `Err(DecompositionError::SingularCovariance)`
+    /// if the covariance matrix is (numerically) singular, e.g. if a feature
+    /// is constant or a linear combination of the others.
+    pub fn fit<S>(
+        data: &ArrayBase<S, Ix2>,
+        method: WhiteningMethod,
+    ) -> Result<Self, DecompositionError>
+    where
+        S: Data<Elem = A>,
+    {
+        if data.rows() < 2 {
+            return Err(DecompositionError::NotEnoughObservations);
+        }
+        let (eigenvalues, eigenvectors) = symmetric_eigen(data.t().cov(A::one()));
+        if is_singular(&eigenvalues) {
+            return Err(DecompositionError::SingularCovariance);
+        }
+        let scaled = &eigenvectors / &eigenvalues.mapv(|x| x.sqrt());
+        let matrix = match method {
+            WhiteningMethod::Pca => scaled,
+            WhiteningMethod::Zca => scaled.dot(&eigenvectors.t()),
+        };
+        Ok(Whitening {
+            mean: data.mean_axis(Axis(0)),
+            matrix,
+        })
+    }
+
+    /// Whitens the observations (rows) of `data`.
+    ///
+    /// Returns `Err(ShapeMismatch)` if the number of columns of `data`
+    /// differs from the number of features the transform was fitted on.
+    pub fn transform<S>(&self, data: &ArrayBase<S, Ix2>) -> Result<Array2<A>, ShapeMismatch>
+    where
+        S: Data<Elem = A>,
+    {
+        if data.cols() != self.mean.len() {
+            return Err(ShapeMismatch {
+                first_shape: vec![data.rows(), self.mean.len()],
+                second_shape: data.shape().to_vec(),
+            });
+        }
+        Ok((data - &self.mean).dot(&self.matrix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use ndarray_rand::RandomExt;
+    use rand::distributions::Uniform;
+
+    #[test]
+    fn test_whitened_covariance_is_identity() {
+        let mixing = array![[1., 0.5, 0.], [0., 2., 0.3], [0.2, 0., 0.1]];
+        let data = Array2::random((200, 3), Uniform::new(-1., 1.)).dot(&mixing) + 10.;
+        for &method in &[WhiteningMethod::Pca, WhiteningMethod::Zca] {
+            let whitening = Whitening::fit(&data, method).unwrap();
+            let whitened = whitening.transform(&data).unwrap();
+            assert!(whitened.t().cov(1.).all_close(&Array2::eye(3), 1e-10));
+            assert!(whitened
+                .mean_axis(Axis(0))
+                .all_close(&Array1::zeros(3), 1e-10));
+        }
+        // The ZCA matrix is symmetric.
+        let zca = Whitening::fit(&data, WhiteningMethod::Zca).unwrap();
+        assert!(zca.matrix.all_close(&zca.matrix.t(), 1e-10));
+    }
+
+    #[test]
+    fn test_whitening_errors() {
+        let collinear = array![[1., 2.], [2., 4.], [3., 6.]];
+        assert_eq!(
+            Whitening::fit(&collinear, WhiteningMethod::Zca),
+            Err(DecompositionError::SingularCovariance)
+        );
+        assert_eq!(
+            Whitening::fit(&array![[1., 2.]], WhiteningMethod::Pca),
+            Err(DecompositionError::NotEnoughObservations)
+        );
+        let data = array![[1., 2.], [2., 1.], [0., 0.]];
+        let whitening = Whitening::fit(&data, WhiteningMethod::Pca).unwrap();
+        assert!(whitening.transform(&array![[1.]]).is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! The empirical cumulative distribution function of a sample.
+use crate::errors::{EmptyInput, QuantileError};
+use crate::quantile::check_quantile;
+use core::cmp::Ordering;
+use ndarray::{Array, ArrayBase, Data, Dimension, Ix1};
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "serde-1")]
+use serde::{Deserialize, Serialize};
+
+/// The empirical cumulative distribution function (ECDF) of a sample.
+///
+/// The ECDF evaluated at `x` is the fraction of the observations that are
+/// less than or equal to `x`. It is a step function, which jumps by `1 / n`
+/// at each of the `n` observations (by `k / n` at a value observed `k`
+/// times).
+///
+/// The observations are sorted once by [`new`](#method.new); evaluating the
+/// ECDF then takes `O(log n)` time, and its inverse `O(1)` time.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::Ecdf;
+///
+/// let ecdf = Ecdf::new(&array![3., 1., 2., 2.]).unwrap();
+/// assert_eq!(ecdf.eval(0.), 0.);
+/// assert_eq!(ecdf.eval(2.), 0.75);
+/// assert_eq!(ecdf.eval_array(&array![1.5, 3.]), array![0.25, 1.]);
+/// assert_eq!(ecdf.quantile(0.5), Ok(2.));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+pub struct Ecdf<A> {
+    sorted: Vec<A>,
+}
+
+impl<A> Ecdf<A>
+where
+    A: Float + FromPrimitive,
+{
+    /// Returns the ECDF of the elements of `data`. NaN elements are skipped.
+    ///
+    /// Returns `Err(EmptyInput)` if `data` has no elements other than NaN.
+    pub fn new<S>(data: &ArrayBase<S, Ix1>) -> Result<Self, EmptyInput>
+    where
+        S: Data<Elem = A>,
+    {
+        let mut sorted: Vec<A> = data.iter().cloned().filter(|x| !x.is_nan()).collect();
+        if sorted.is_empty() {
+            return Err(EmptyInput);
+        }
+        sorted.sort_unstable_by(|a, b| a.partial_cmp(b).expect("NaN values have been excluded."));
+        Ok(Ecdf { sorted })
+    }
+
+    /// Returns the observations, in increasing order.
+    pub fn values(&self) -> &[A] {
+        &self.sorted
+    }
+
+    /// Returns the fraction of the observations that are less than or equal
+    /// to `x`, or NaN if `x` is NaN.
+    pub fn eval(&self, x: A) -> A {
+        if x.is_nan() {
+            return A::nan();
+        }
+        // Binary search for the first observation greater than `x`.
+        let n_below = self
+            .sorted
+            .binary_search_by(|&value| {
+                if value <= x {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            })
+            .unwrap_err();
+        self.fraction(n_below)
+    }
+
+    /// Evaluates the ECDF at each element of `xs`.
+    pub fn eval_array<S, D>(&self, xs: &ArrayBase<S, D>) -> Array<A, D>
+    where
+        S: Data<Elem = A>,
+        D: Dimension,
+    {
+        xs.mapv(|x| self.eval(x))
+    }
+
+    /// Returns the `q`th quantile of the sample as the generalized inverse
+    /// of the ECDF: the smallest observation `x` such that
+    /// `self.eval(x) >= q`.
+    ///
+    /// Unlike [`QuantileExt::quantile_axis_mut`], this never interpolates
+    /// between observations (it is NumPy's `inverted_cdf` method).
+    ///
+    /// Returns `Err(InvalidQuantile(q))` if `q` is NaN or not between `0.`
+    /// and `1.` (inclusive).
+    ///
+    /// [`QuantileExt::quantile_axis_mut`]: trait.QuantileExt.html#tymethod.quantile_axis_mut
+    pub fn quantile<Q>(&self, q: Q) -> Result<A, QuantileError>
+    where
+        Q: Float,
+    {
+        let q = check_quantile(q)?.raw();
+        let n = self.sorted.len();
+        let mut rank = (q * n as f64).ceil() as usize;
+        // Rounding errors can make `q * n` slightly greater than an integer.
+        if rank > 0 && (rank - 1) as f64 / n as f64 >= q {
+            rank -= 1;
+        }
+        Ok(self.sorted[rank.max(1) - 1])
+    }
+
+    /// Returns the Kolmogorov-Smirnov distance between the two ECDFs: the
+    /// largest absolute difference between their values, over all `x`.
+    ///
+    /// This is the statistic of the two-sample Kolmogorov-Smirnov test. It is
+    /// between `0.` (identical distributions) and `1.` (samples that don't
+    /// overlap).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::Ecdf;
+    ///
+    /// let first = Ecdf::new(&array![1., 2., 3., 4.]).unwrap();
+    /// let second = Ecdf::new(&array![3., 4., 5., 6.]).unwrap();
+    /// assert_eq!(first.ks_distance(&second), 0.5);
+    /// ```
+    pub fn ks_distance(&self, other: &Ecdf<A>) -> A {
+        let (xs, ys) = (&self.sorted, &other.sorted);
+        let (mut i, mut j) = (0, 0);
+        let mut distance = A::zero();
+        // The difference only changes at the observations, so it is enough to
+        // evaluate it right after each distinct observed value.
+        while i < xs.len() && j < ys.len() {
+            let x = if xs[i] <= ys[j] { xs[i] } else { ys[j] };
+            while i < xs.len() && xs[i] <= x {
+                i += 1;
+            }
+            while j < ys.len() && ys[j] <= x {
+                j += 1;
+            }
+            distance = distance.max((self.fraction(i) - other.fraction(j)).abs());
+        }
+        distance
+    }
+
+    fn fraction(&self, count: usize) -> A {
+        let count = A::from_usize(count).expect("Converting a count to `A` must not fail.");
+        let n = A::from_usize(self.sorted.len())
+            .expect("Converting the number of observations to `A` must not fail.");
+        count / n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::QuantileError;
+    use ndarray::{array, Array1};
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[test]
+    fn test_eval_with_ties_and_nan() {
+        let ecdf = Ecdf::new(&array![2., f64::NAN, 1., 2., 5.]).unwrap();
+        assert_eq!(ecdf.values(), &[1., 2., 2., 5.]);
+        assert_eq!(
+            ecdf.eval_array(&array![0., 1., 1.5, 2., 4.9, 5., 10.]),
+            array![0., 0.25, 0.25, 0.75, 0.75, 1., 1.]
+        );
+        assert!(ecdf.eval(f64::NAN).is_nan());
+        assert_eq!(Ecdf::new(&array![f64::NAN]), Err(EmptyInput));
+        assert_eq!(Ecdf::<f64>::new(&Array1::zeros(0)), Err(EmptyInput));
+    }
+
+    #[test]
+    fn test_quantile() {
+        let ecdf = Ecdf::new(&array![10., 20., 30., 40., 50., 60., 70., 80., 90., 100.]).unwrap();
+        assert_eq!(ecdf.quantile(0.), Ok(10.));
+        assert_eq!(ecdf.quantile(0.1), Ok(10.));
+        assert_eq!(ecdf.quantile(0.3), Ok(30.));
+        assert_eq!(ecdf.quantile(0.31), Ok(40.));
+        assert_eq!(ecdf.quantile(1.), Ok(100.));
+        assert_eq!(ecdf.quantile(1.5), Err(QuantileError::InvalidQuantile(1.5)));
+    }
+
+    #[quickcheck]
+    fn quantile_is_inverse_of_eval(data: Vec<f64>, q: f64) -> bool {
+        let q = q.abs().fract();
+        match Ecdf::new(&Array1::from_vec(data)) {
+            Ok(ecdf) => {
+                let x = ecdf.quantile(q).unwrap();
+                // `x` is the smallest observation at which the ECDF reaches `q`.
+                ecdf.eval(x) >= q && ecdf.values().iter().all(|&y| y >= x || ecdf.eval(y) < q)
+            }
+            Err(EmptyInput) => true,
+        }
+    }
+
+    #[quickcheck]
+    fn ks_distance_is_largest_difference(first: Vec<i8>, second: Vec<i8>) -> bool {
+        let to_ecdf = |xs: Vec<i8>| Ecdf::new(&Array1::from_vec(xs).mapv(f64::from));
+        match (to_ecdf(first), to_ecdf(second)) {
+            (Ok(first), Ok(second)) => {
+                // Both ECDFs are constant between consecutive integers.
+                let expected = (-128..128)
+                    .map(|x| (first.eval(f64::from(x)) - second.eval(f64::from(x))).abs())
+                    .fold(0., f64::max);
+                first.ks_distance(&second) == expected && second.ks_distance(&first) == expected
+            }
+            _ => true,
+        }
+    }
+}
@@ -1,7 +1,45 @@
 //! Information theory (e.g. entropy, KL divergence, etc.).
-use crate::errors::{EmptyInput, MultiInputError, ShapeMismatch};
-use ndarray::{Array, ArrayBase, Data, Dimension, Zip};
-use num_traits::Float;
+use crate::errors::{EmptyInput, EntropyEstimateError, MultiInputError, ShapeMismatch};
+use crate::fit::special::{digamma, ln_gamma};
+use crate::sort::select_nth_by;
+use core::hash::Hash;
+use indexmap::IndexMap;
+use ndarray::{Array, ArrayBase, Axis, Data, Dimension, Ix1, Ix2, Zip};
+use num_traits::{Float, FromPrimitive};
+
+/// The unit of information in which entropies and divergences are measured,
+/// determined by the base of the logarithm in their definition.
+///
+/// The methods without a unit parameter measure information in nats.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InformationUnit {
+    /// Natural logarithm.
+    Nats,
+    /// Base 2 logarithm.
+    Bits,
+    /// Base 10 logarithm (also known as dits or bans).
+    Hartleys,
+}
+
+impl InformationUnit {
+    /// Converts a quantity of information from nats to `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray_stats::InformationUnit;
+    ///
+    /// assert_eq!(InformationUnit::Bits.from_nats(2f64.ln()), 1.);
+    /// ```
+    pub fn from_nats<A: Float>(self, nats: A) -> A {
+        let base = match self {
+            InformationUnit::Nats => return nats,
+            InformationUnit::Bits => A::one() + A::one(),
+            InformationUnit::Hartleys => A::from(10).expect("Converting 10 to `A` must not fail."),
+        };
+        nats / base.ln()
+    }
+}
 
 /// Extension trait for `ArrayBase` providing methods
 /// to compute information theory quantities
@@ -121,6 +159,97 @@ where
         S2: Data<Elem = A>,
         A: Float;
 
+    /// Computes the [Jensen-Shannon divergence] *JSD(p,q)* between two
+    /// arrays, where `self`=*p*.
+    ///
+    /// The Jensen-Shannon divergence is defined as:
+    ///
+    /// ```text
+    /// JSD(p,q) = ½ Dₖₗ(p,m) + ½ Dₖₗ(q,m), with m = ½ (p + q)
+    /// ```
+    ///
+    /// Unlike the Kullback-Leibler divergence, it is symmetric and always
+    /// finite: it is at most *ln(2)*. Its square root is a metric.
+    ///
+    /// If the arrays are empty, `Err(MultiInputError::EmptyInput)` is returned.
+    /// If the array shapes are not identical,
+    /// `Err(MultiInputError::ShapeMismatch)` is returned.
+    ///
+    /// ## Remarks
+    ///
+    /// Like the Kullback-Leibler divergence, it only makes sense when each
+    /// array sums to 1 with entries between 0 and 1 (extremes included), and
+    /// the arrays are **not** normalised by this function.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::EntropyExt;
+    ///
+    /// let p = array![1., 0.];
+    /// let q = array![0., 1.];
+    /// assert_eq!(p.jensen_shannon_divergence(&q).unwrap(), 2f64.ln());
+    /// ```
+    ///
+    /// [Jensen-Shannon divergence]: https://en.wikipedia.org/wiki/Jensen%E2%80%93Shannon_divergence
+    fn jensen_shannon_divergence<S2>(&self, q: &ArrayBase<S2, D>) -> Result<A, MultiInputError>
+    where
+        S2: Data<Elem = A>,
+        A: Float;
+
+    /// Like [`entropy`](#tymethod.entropy), with the result measured in
+    /// `unit`.
+    fn entropy_with_unit(&self, unit: InformationUnit) -> Result<A, EmptyInput>
+    where
+        A: Float;
+
+    /// Like [`kl_divergence`](#tymethod.kl_divergence), with the result
+    /// measured in `unit`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{EntropyExt, InformationUnit};
+    ///
+    /// let p = array![0.5f64, 0.5];
+    /// let q = array![0.25, 0.75];
+    /// let bits = p.kl_divergence_with_unit(&q, InformationUnit::Bits).unwrap();
+    /// assert!((bits - (1. - 0.5 * 3f64.log2())).abs() < 1e-15);
+    /// ```
+    fn kl_divergence_with_unit<S2>(
+        &self,
+        q: &ArrayBase<S2, D>,
+        unit: InformationUnit,
+    ) -> Result<A, MultiInputError>
+    where
+        S2: Data<Elem = A>,
+        A: Float;
+
+    /// Like [`cross_entropy`](#tymethod.cross_entropy), with the result
+    /// measured in `unit`.
+    fn cross_entropy_with_unit<S2>(
+        &self,
+        q: &ArrayBase<S2, D>,
+        unit: InformationUnit,
+    ) -> Result<A, MultiInputError>
+    where
+        S2: Data<Elem = A>,
+        A: Float;
+
+    /// Like [`jensen_shannon_divergence`](#tymethod.jensen_shannon_divergence),
+    /// with the result measured in `unit`. In bits, the divergence is at most
+    /// 1.
+    fn jensen_shannon_divergence_with_unit<S2>(
+        &self,
+        q: &ArrayBase<S2, D>,
+        unit: InformationUnit,
+    ) -> Result<A, MultiInputError>
+    where
+        S2: Data<Elem = A>,
+        A: Float;
+
     private_decl! {}
 }
 
@@ -133,7 +262,7 @@ where
     where
         A: Float,
     {
-        if self.len() == 0 {
+        if self.is_empty() {
             Err(EmptyInput)
         } else {
             let entropy = -self
@@ -154,7 +283,7 @@ where
         A: Float,
         S2: Data<Elem = A>,
     {
-        if self.len() == 0 {
+        if self.is_empty() {
             return Err(MultiInputError::EmptyInput);
         }
         if self.shape() != q.shape() {
@@ -187,7 +316,7 @@ where
         S2: Data<Elem = A>,
         A: Float,
     {
-        if self.len() == 0 {
+        if self.is_empty() {
             return Err(MultiInputError::EmptyInput);
         }
         if self.shape() != q.shape() {
@@ -215,13 +344,448 @@ where
         Ok(cross_entropy)
     }
 
+    fn jensen_shannon_divergence<S2>(&self, q: &ArrayBase<S2, D>) -> Result<A, MultiInputError>
+    where
+        S2: Data<Elem = A>,
+        A: Float,
+    {
+        if self.is_empty() {
+            return Err(MultiInputError::EmptyInput);
+        }
+        if self.shape() != q.shape() {
+            return Err(ShapeMismatch {
+                first_shape: self.shape().to_vec(),
+                second_shape: q.shape().to_vec(),
+            }
+            .into());
+        }
+        let two = A::one() + A::one();
+        let mut m = Array::zeros(self.raw_dim());
+        Zip::from(&mut m)
+            .and(self)
+            .and(q)
+            .apply(|m, &p, &q| *m = (p + q) / two);
+        Ok((self.kl_divergence(&m)? + q.kl_divergence(&m)?) / two)
+    }
+
+    fn entropy_with_unit(&self, unit: InformationUnit) -> Result<A, EmptyInput>
+    where
+        A: Float,
+    {
+        self.entropy().map(|h| unit.from_nats(h))
+    }
+
+    fn kl_divergence_with_unit<S2>(
+        &self,
+        q: &ArrayBase<S2, D>,
+        unit: InformationUnit,
+    ) -> Result<A, MultiInputError>
+    where
+        S2: Data<Elem = A>,
+        A: Float,
+    {
+        self.kl_divergence(q).map(|d| unit.from_nats(d))
+    }
+
+    fn cross_entropy_with_unit<S2>(
+        &self,
+        q: &ArrayBase<S2, D>,
+        unit: InformationUnit,
+    ) -> Result<A, MultiInputError>
+    where
+        S2: Data<Elem = A>,
+        A: Float,
+    {
+        self.cross_entropy(q).map(|h| unit.from_nats(h))
+    }
+
+    fn jensen_shannon_divergence_with_unit<S2>(
+        &self,
+        q: &ArrayBase<S2, D>,
+        unit: InformationUnit,
+    ) -> Result<A, MultiInputError>
+    where
+        S2: Data<Elem = A>,
+        A: Float,
+    {
+        self.jensen_shannon_divergence(q).map(|d| unit.from_nats(d))
+    }
+
+    private_impl! {}
+}
+
+/// Extension trait for two-dimensional `ArrayBase` providing the mutual
+/// information of a joint probability distribution.
+pub trait Entropy2dExt<A, S>
+where
+    S: Data<Elem = A>,
+{
+    /// Computes the [mutual information] *I(X;Y)* of two discrete random
+    /// variables whose joint probability distribution is `self`: element
+    /// *(i, j)* is the probability *pᵢⱼ* that *X* takes its *i*-th value and
+    /// *Y* its *j*-th value.
+    ///
+    /// The mutual information is defined as:
+    ///
+    /// ```text
+    ///            n   m
+    /// I(X;Y) =   ∑   ∑ pᵢⱼ ln(pᵢⱼ / (pᵢ. p.ⱼ))
+    ///           i=1 j=1
+    /// ```
+    ///
+    /// where *pᵢ.* and *p.ⱼ* are the sums of the rows and of the columns (the
+    /// marginal distributions). It is zero if and only if *X* and *Y* are
+    /// independent.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned.
+    ///
+    /// ## Remarks
+    ///
+    /// It only makes sense when the array values sum to 1, with each entry
+    /// between 0 and 1 (extremes included); the array is **not** normalised
+    /// by this function.
+    ///
+    /// By definition, *pᵢⱼ ln(pᵢⱼ / (pᵢ. p.ⱼ))* is set to 0 if *pᵢⱼ* is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::Entropy2dExt;
+    ///
+    /// // Y is a copy of X, so they share all of the entropy of X.
+    /// let joint = array![[0.5, 0.], [0., 0.5]];
+    /// assert_eq!(joint.mutual_information().unwrap(), 2f64.ln());
+    /// let independent = array![[0.1f64, 0.3], [0.15, 0.45]];
+    /// assert!(independent.mutual_information().unwrap().abs() < 1e-15);
+    /// ```
+    ///
+    /// [mutual information]: https://en.wikipedia.org/wiki/Mutual_information
+    fn mutual_information(&self) -> Result<A, EmptyInput>
+    where
+        A: Float;
+
+    /// Like [`mutual_information`](#tymethod.mutual_information), with the
+    /// result measured in `unit`.
+    fn mutual_information_with_unit(&self, unit: InformationUnit) -> Result<A, EmptyInput>
+    where
+        A: Float;
+
+    /// Estimates the [differential entropy] (in nats) of the distribution of
+    /// a sample of continuous observations: each row of `self` is an
+    /// observation of `d` variables.
+    ///
+    /// This is the Kozachenko–Leonenko k-nearest-neighbor estimator
+    ///
+    /// ```text
+    ///                                 n
+    /// Ĥ = ψ(n) - ψ(k) + ln(V_d) + d/n ∑ ln(εᵢ)
+    ///                                i=1
+    /// ```
+    ///
+    /// where `ψ` is the digamma function, `V_d` is the volume of the unit
+    /// ball in `d` dimensions and `εᵢ` is the Euclidean distance between
+    /// observation `i` and its `k`-th nearest neighbor. Unlike plug-in
+    /// estimators, it doesn't need the data to be binned, and it is
+    /// consistent for any `k`; a larger `k` lowers its variance but raises
+    /// its bias. `k = 3` is a common choice.
+    ///
+    /// The distances are computed by brute force, in `O(n² d)` time.
+    /// Duplicated observations make some distances zero and the estimate
+    /// -∞: add a small amount of noise to discrete data first.
+    ///
+    /// Returns `Err(EntropyEstimateError::NotEnoughObservations)` if `self`
+    /// has `k` rows or less, and `Err(EntropyEstimateError::ContainsNan)` if
+    /// it contains NaN.
+    ///
+    /// **Panics** if `k` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::Array2;
+    /// use ndarray_stats::Entropy2dExt;
+    ///
+    /// // A regular grid of the unit square, whose uniform distribution has
+    /// // zero differential entropy.
+    /// let sample = Array2::from_shape_fn((400, 2), |(i, j)| {
+    ///     ((if j == 0 { i / 20 } else { i % 20 }) as f64 + 0.5) / 20.
+    /// });
+    /// let entropy = sample.differential_entropy_knn(4).unwrap();
+    /// assert!(entropy.abs() < 0.2);
+    /// ```
+    ///
+    /// [differential entropy]: https://en.wikipedia.org/wiki/Differential_entropy
+    fn differential_entropy_knn(&self, k: usize) -> Result<A, EntropyEstimateError>
+    where
+        A: Float + FromPrimitive;
+
+    private_decl! {}
+}
+
+impl<A, S> Entropy2dExt<A, S> for ArrayBase<S, Ix2>
+where
+    S: Data<Elem = A>,
+{
+    fn mutual_information(&self) -> Result<A, EmptyInput>
+    where
+        A: Float,
+    {
+        if self.is_empty() {
+            return Err(EmptyInput);
+        }
+        let row_sums = self.fold_axis(Axis(1), A::zero(), |&acc, &p| acc + p);
+        let col_sums = self.fold_axis(Axis(0), A::zero(), |&acc, &p| acc + p);
+        let mut information = A::zero();
+        for ((i, j), &p) in self.indexed_iter() {
+            if p != A::zero() {
+                information = information + p * (p / (row_sums[i] * col_sums[j])).ln();
+            }
+        }
+        Ok(information)
+    }
+
+    fn mutual_information_with_unit(&self, unit: InformationUnit) -> Result<A, EmptyInput>
+    where
+        A: Float,
+    {
+        self.mutual_information().map(|i| unit.from_nats(i))
+    }
+
+    fn differential_entropy_knn(&self, k: usize) -> Result<A, EntropyEstimateError>
+    where
+        A: Float + FromPrimitive,
+    {
+        assert!(k > 0, "`k` must be positive.");
+        let (n, d) = self.dim();
+        if n <= k {
+            return Err(EntropyEstimateError::NotEnoughObservations);
+        }
+        if self.iter().any(|x| x.is_nan()) {
+            return Err(EntropyEstimateError::ContainsNan);
+        }
+        let to_a = |x: usize| A::from_usize(x).expect("Converting a count to `A` must not fail.");
+        let mut squared_distances = Vec::with_capacity(n - 1);
+        let mut sum_ln_distance = A::zero();
+        for (i, x) in self.outer_iter().enumerate() {
+            squared_distances.clear();
+            squared_distances.extend(self.outer_iter().enumerate().filter(|&(j, _)| j != i).map(
+                |(_, y)| {
+                    x.iter()
+                        .zip(&y)
+                        .fold(A::zero(), |acc, (&a, &b)| acc + (a - b) * (a - b))
+                },
+            ));
+            let (_, &mut kth, _) = select_nth_by(&mut squared_distances, k - 1, |a, b| {
+                a.partial_cmp(b).expect("NaN values have been excluded.")
+            });
+            sum_ln_distance = sum_ln_distance + kth.ln() / (A::one() + A::one());
+        }
+        let (n, k, d) = (to_a(n), to_a(k), to_a(d));
+        let half_d = d / (A::one() + A::one());
+        let pi = A::from_f64(core::f64::consts::PI).expect("Converting π to `A` must not fail.");
+        let ln_unit_ball_volume = half_d * pi.ln() - ln_gamma(half_d + A::one());
+        Ok(digamma(n) - digamma(k) + ln_unit_ball_volume + d * sum_ln_distance / n)
+    }
+
+    private_impl! {}
+}
+
+/// Extension trait for one-dimensional `ArrayBase` providing information
+/// theory quantities of samples of discrete values (e.g. labels).
+pub trait Entropy1dExt<A, S>
+where
+    S: Data<Elem = A>,
+{
+    /// Computes the [mutual information] of the empirical joint distribution
+    /// of the pairs `(self[i], other[i])`, in nats.
+    ///
+    /// The probability of each pair of values is estimated by its frequency
+    /// in the sample, and the result is the
+    /// [`mutual_information`](trait.Entropy2dExt.html#tymethod.mutual_information)
+    /// of this joint distribution. The values can be of any type that can be
+    /// compared and hashed, e.g. integer labels or strings.
+    ///
+    /// If the arrays are empty, `Err(MultiInputError::EmptyInput)` is
+    /// returned. If their lengths are different,
+    /// `Err(MultiInputError::ShapeMismatch)` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::Entropy1dExt;
+    ///
+    /// let x = array!["a", "a", "b", "b"];
+    /// let y = array![1, 1, 2, 2];
+    /// // `y` is determined by `x`, and both have one bit of entropy.
+    /// assert_eq!(x.empirical_mutual_information(&y).unwrap(), 2f64.ln());
+    /// ```
+    ///
+    /// [mutual information]: https://en.wikipedia.org/wiki/Mutual_information
+    fn empirical_mutual_information<B, S2>(
+        &self,
+        other: &ArrayBase<S2, Ix1>,
+    ) -> Result<f64, MultiInputError>
+    where
+        A: Eq + Hash,
+        B: Eq + Hash,
+        S2: Data<Elem = B>;
+
+    /// Like [`empirical_mutual_information`](#tymethod.empirical_mutual_information),
+    /// with the result measured in `unit`.
+    fn empirical_mutual_information_with_unit<B, S2>(
+        &self,
+        other: &ArrayBase<S2, Ix1>,
+        unit: InformationUnit,
+    ) -> Result<f64, MultiInputError>
+    where
+        A: Eq + Hash,
+        B: Eq + Hash,
+        S2: Data<Elem = B>;
+
+    /// Estimates the [differential entropy] (in nats) of the distribution of
+    /// a sample of continuous observations from its histogram.
+    ///
+    /// The range of the sample is split into `n_bins` bins of equal width
+    /// `w`, and the density is estimated as constant in each bin, which
+    /// gives the plug-in estimate
+    ///
+    /// ```text
+    ///        m
+    /// Ĥ = -  ∑ pᵢ ln(pᵢ / w)
+    ///       i=1
+    /// ```
+    ///
+    /// where `pᵢ` is the fraction of the observations falling in bin `i`.
+    /// It is biased downwards for small samples; use
+    /// [`differential_entropy_knn`] for multivariate samples or if the
+    /// number of bins is hard to choose. If all the observations are equal,
+    /// the estimate is -∞.
+    ///
+    /// Returns `Err(EntropyEstimateError::NotEnoughObservations)` if `self`
+    /// is empty, and `Err(EntropyEstimateError::ContainsNan)` if it contains
+    /// NaN.
+    ///
+    /// **Panics** if `n_bins` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::Array1;
+    /// use ndarray_stats::Entropy1dExt;
+    ///
+    /// // The uniform distribution on [0, 2] has differential entropy ln(2).
+    /// let sample = Array1::linspace(0., 2., 1000);
+    /// let entropy = sample.differential_entropy_histogram(10).unwrap();
+    /// assert!((entropy - 2f64.ln()).abs() < 1e-3);
+    /// ```
+    ///
+    /// [differential entropy]: https://en.wikipedia.org/wiki/Differential_entropy
+    /// [`differential_entropy_knn`]: trait.Entropy2dExt.html#tymethod.differential_entropy_knn
+    fn differential_entropy_histogram(&self, n_bins: usize) -> Result<A, EntropyEstimateError>
+    where
+        A: Float + FromPrimitive;
+
+    private_decl! {}
+}
+
+impl<A, S> Entropy1dExt<A, S> for ArrayBase<S, Ix1>
+where
+    S: Data<Elem = A>,
+{
+    fn empirical_mutual_information<B, S2>(
+        &self,
+        other: &ArrayBase<S2, Ix1>,
+    ) -> Result<f64, MultiInputError>
+    where
+        A: Eq + Hash,
+        B: Eq + Hash,
+        S2: Data<Elem = B>,
+    {
+        if self.is_empty() {
+            return Err(MultiInputError::EmptyInput);
+        }
+        if self.len() != other.len() {
+            return Err(ShapeMismatch {
+                first_shape: self.shape().to_vec(),
+                second_shape: other.shape().to_vec(),
+            }
+            .into());
+        }
+        let mut x_counts: IndexMap<&A, usize> = IndexMap::new();
+        let mut y_counts: IndexMap<&B, usize> = IndexMap::new();
+        let mut joint_counts: IndexMap<(&A, &B), usize> = IndexMap::new();
+        for (x, y) in self.iter().zip(other) {
+            *x_counts.entry(x).or_default() += 1;
+            *y_counts.entry(y).or_default() += 1;
+            *joint_counts.entry((x, y)).or_default() += 1;
+        }
+        let n = self.len() as f64;
+        let information = joint_counts
+            .iter()
+            .map(|((x, y), &count)| {
+                let count = count as f64;
+                let expected = (x_counts[x] * y_counts[y]) as f64 / n;
+                count / n * (count / expected).ln()
+            })
+            .sum();
+        Ok(information)
+    }
+
+    fn empirical_mutual_information_with_unit<B, S2>(
+        &self,
+        other: &ArrayBase<S2, Ix1>,
+        unit: InformationUnit,
+    ) -> Result<f64, MultiInputError>
+    where
+        A: Eq + Hash,
+        B: Eq + Hash,
+        S2: Data<Elem = B>,
+    {
+        self.empirical_mutual_information(other)
+            .map(|i| unit.from_nats(i))
+    }
+
+    fn differential_entropy_histogram(&self, n_bins: usize) -> Result<A, EntropyEstimateError>
+    where
+        A: Float + FromPrimitive,
+    {
+        assert!(n_bins > 0, "The number of bins must be positive.");
+        if self.is_empty() {
+            return Err(EntropyEstimateError::NotEnoughObservations);
+        }
+        if self.iter().any(|x| x.is_nan()) {
+            return Err(EntropyEstimateError::ContainsNan);
+        }
+        let to_a = |x: usize| A::from_usize(x).expect("Converting a count to `A` must not fail.");
+        let min = self.fold(A::infinity(), |acc, &x| acc.min(x));
+        let max = self.fold(A::neg_infinity(), |acc, &x| acc.max(x));
+        let width = (max - min) / to_a(n_bins);
+        let mut counts = vec![0; n_bins];
+        for &x in self {
+            // The maximum falls in the last bin, and so do all the values if
+            // they are equal.
+            let bin = ((x - min) / width).to_usize().unwrap_or(n_bins - 1);
+            counts[bin.min(n_bins - 1)] += 1;
+        }
+        let n = to_a(self.len());
+        Ok(counts
+            .into_iter()
+            .filter(|&count| count > 0)
+            .fold(A::zero(), |acc, count| {
+                let p = to_a(count) / n;
+                acc - p * (p / width).ln()
+            }))
+    }
+
     private_impl! {}
 }
 
 #[cfg(test)]
 mod tests {
-    use super::EntropyExt;
-    use crate::errors::{EmptyInput, MultiInputError};
+    use super::{Entropy1dExt, Entropy2dExt, EntropyExt, InformationUnit};
+    use crate::errors::{EmptyInput, EntropyEstimateError, MultiInputError};
     use approx::assert_abs_diff_eq;
     use ndarray::{array, Array1};
     use noisy_float::types::n64;
@@ -396,4 +960,172 @@ mod tests {
         assert_abs_diff_eq!(p.kl_divergence(&q)?, expected_kl, epsilon = 1e-6);
         Ok(())
     }
+
+    #[test]
+    fn test_units() -> Result<(), MultiInputError> {
+        let p = array![0.5, 0.25, 0.125, 0.125];
+        assert_eq!(p.entropy_with_unit(InformationUnit::Bits)?, 1.75);
+        assert_abs_diff_eq!(
+            p.entropy_with_unit(InformationUnit::Hartleys)?,
+            p.entropy()? / 10f64.ln(),
+            epsilon = 1e-15
+        );
+        assert_eq!(p.entropy_with_unit(InformationUnit::Nats)?, p.entropy()?);
+        let q = array![0.25, 0.25, 0.25, 0.25];
+        assert_eq!(p.cross_entropy_with_unit(&q, InformationUnit::Bits)?, 2.);
+        assert_abs_diff_eq!(
+            p.kl_divergence_with_unit(&q, InformationUnit::Bits)?,
+            0.25,
+            epsilon = 1e-15
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_jensen_shannon_divergence() -> Result<(), MultiInputError> {
+        let p = array![0.5, 0.5, 0.];
+        let q = array![0., 0.5, 0.5];
+        // m = [0.25, 0.5, 0.25], and both divergences from m are half a bit.
+        let jsd = p.jensen_shannon_divergence_with_unit(&q, InformationUnit::Bits)?;
+        assert_abs_diff_eq!(jsd, 0.5, epsilon = 1e-15);
+        assert_eq!(
+            q.jensen_shannon_divergence(&p)?,
+            p.jensen_shannon_divergence(&q)?
+        );
+        assert_eq!(p.jensen_shannon_divergence(&p)?, 0.);
+        assert!(p.jensen_shannon_divergence(&array![1.]).is_err());
+        let empty: Array1<f64> = array![];
+        assert!(empty
+            .jensen_shannon_divergence(&empty)
+            .unwrap_err()
+            .is_empty_input());
+        Ok(())
+    }
+
+    #[test]
+    fn test_mutual_information() {
+        // X uniform on two values, and Y = X with probability 0.8.
+        let joint = array![[0.4, 0.1], [0.1, 0.4]];
+        let expected = (0.8 * 0.8f64.ln() + 0.2 * 0.2f64.ln()) + 2f64.ln();
+        assert_abs_diff_eq!(
+            joint.mutual_information().unwrap(),
+            expected,
+            epsilon = 1e-15
+        );
+        // I(X;Y) = H(X) + H(Y) - H(X,Y)
+        let joint = array![[0.1, 0.2, 0.05], [0.3, 0.05, 0.3]];
+        let h_x = array![0.35, 0.65].entropy().unwrap();
+        let h_y = array![0.4, 0.25, 0.35].entropy().unwrap();
+        assert_abs_diff_eq!(
+            joint.mutual_information().unwrap(),
+            h_x + h_y - joint.entropy().unwrap(),
+            epsilon = 1e-15
+        );
+        assert_eq!(
+            ndarray::Array2::<f64>::zeros((0, 3)).mutual_information(),
+            Err(EmptyInput)
+        );
+    }
+
+    #[test]
+    fn test_empirical_mutual_information() {
+        let x = array![0, 0, 1, 1, 0, 1, 0, 1, 1, 0];
+        let y = array!['a', 'b', 'b', 'b', 'a', 'b', 'a', 'a', 'b', 'a'];
+        // The counts of the pairs are [[4, 1], [1, 4]].
+        let joint = array![[0.4, 0.1], [0.1, 0.4]];
+        assert_abs_diff_eq!(
+            x.empirical_mutual_information(&y).unwrap(),
+            joint.mutual_information().unwrap(),
+            epsilon = 1e-15
+        );
+        assert_abs_diff_eq!(
+            x.empirical_mutual_information_with_unit(&x, InformationUnit::Bits)
+                .unwrap(),
+            1.,
+            epsilon = 1e-15
+        );
+        assert!(x
+            .empirical_mutual_information(&array![1, 2])
+            .unwrap_err()
+            .is_shape_mismatch());
+        let empty: Array1<i32> = array![];
+        assert!(empty
+            .empirical_mutual_information(&empty)
+            .unwrap_err()
+            .is_empty_input());
+    }
+
+    #[test]
+    fn test_differential_entropy_knn_of_gaussian_sample() {
+        use rand::distributions::StandardNormal;
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(0);
+        let sample = ndarray::Array2::from_shape_fn((2000, 2), |_| rng.sample(StandardNormal));
+        // H = d/2 ln(2πe)
+        let expected = (2. * std::f64::consts::PI * std::f64::consts::E).ln();
+        let entropy = sample.differential_entropy_knn(3).unwrap();
+        assert_abs_diff_eq!(entropy, expected, epsilon = 0.05);
+    }
+
+    #[test]
+    fn test_differential_entropy_knn_of_uniform_sample() {
+        // With k = 1, the nearest neighbor of each point of a regular grid of
+        // [0, 1] is at distance h = 1/n, so Ĥ = ψ(n) - ψ(1) + ln(2) + ln(h).
+        let n = 50;
+        let sample = Array1::linspace(0., 1., n).insert_axis(ndarray::Axis(1));
+        let h = 1. / (n - 1) as f64;
+        let expected = crate::fit::special::digamma(n as f64) - crate::fit::special::digamma(1.)
+            + 2f64.ln()
+            + h.ln();
+        assert_abs_diff_eq!(
+            sample.differential_entropy_knn(1).unwrap(),
+            expected,
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_differential_entropy_estimates_errors() {
+        let sample = array![[0.], [1.], [f64::NAN]];
+        assert_eq!(
+            sample.differential_entropy_knn(3),
+            Err(EntropyEstimateError::NotEnoughObservations)
+        );
+        assert_eq!(
+            sample.differential_entropy_knn(1),
+            Err(EntropyEstimateError::ContainsNan)
+        );
+        let empty: Array1<f64> = array![];
+        assert_eq!(
+            empty.differential_entropy_histogram(3),
+            Err(EntropyEstimateError::NotEnoughObservations)
+        );
+        assert_eq!(
+            array![1., f64::NAN].differential_entropy_histogram(3),
+            Err(EntropyEstimateError::ContainsNan)
+        );
+    }
+
+    #[test]
+    fn test_differential_entropy_histogram() {
+        // Two bins of width 1 with probabilities 0.25 and 0.75.
+        let sample = array![0., 1.5, 1.2, 2.];
+        let expected = -(0.25 * 0.25f64.ln() + 0.75 * 0.75f64.ln());
+        assert_abs_diff_eq!(
+            sample.differential_entropy_histogram(2).unwrap(),
+            expected,
+            epsilon = 1e-15
+        );
+        // Scaling the sample by `c` adds ln(c).
+        let scaled = sample.mapv(|x| 3. * x);
+        assert_abs_diff_eq!(
+            scaled.differential_entropy_histogram(2).unwrap(),
+            expected + 3f64.ln(),
+            epsilon = 1e-14
+        );
+        assert_eq!(
+            array![1., 1.].differential_entropy_histogram(4),
+            Ok(f64::NEG_INFINITY)
+        );
+    }
 }
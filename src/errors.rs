@@ -1,7 +1,33 @@
 //! Custom errors returned from our methods and functions.
-use noisy_float::types::N64;
+//!
+//! Each method returns the most specific error type for its failures, and
+//! every error type converts into [`StatsError`], so that `?` can combine
+//! the results of different statistics in a single function:
+//!
+//! ```
+//! use ndarray::{array, Array1, Axis};
+//! use ndarray_stats::errors::StatsError;
+//! use ndarray_stats::{interpolate::Linear, CorrelationExt, QuantileExt};
+//! use noisy_float::types::n64;
+//!
+//! fn median_and_variance(data: &Array1<f64>) -> Result<(f64, f64), StatsError> {
+//!     let median = data
+//!         .mapv(n64)
+//!         .quantile_axis_mut(Axis(0), n64(0.5), &Linear)?
+//!         .into_scalar();
+//!     let variance = data.view().insert_axis(Axis(0)).try_cov(1.)?[[0, 0]];
+//!     Ok((median.raw(), variance))
+//! }
+//!
+//! assert_eq!(median_and_variance(&array![1., 2., 3.]), Ok((2., 1.)));
+//! assert_eq!(median_and_variance(&Array1::zeros(0)), Err(StatsError::EmptyInput));
+//! assert_eq!(median_and_variance(&array![1.]), Err(StatsError::InvalidDdof));
+//! ```
+//!
+//! [`StatsError`]: enum.StatsError.html
+use crate::histogram::errors::{BinNotFound, BinsBuildError, GridMismatch};
+use core::fmt;
 use std::error::Error;
-use std::fmt;
 
 /// An error that indicates that the input array was empty.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -15,6 +41,82 @@ impl fmt::Display for EmptyInput {
 
 impl Error for EmptyInput {}
 
+/// An error that indicates that the input array contained NaN values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContainsNan;
+
+impl fmt::Display for ContainsNan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Input contains NaN values.")
+    }
+}
+
+impl Error for ContainsNan {}
+
+/// An error computing a statistic according to a [`NanPolicy`].
+///
+/// [`NanPolicy`]: ../enum.NanPolicy.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NanPolicyError {
+    /// The input was empty.
+    EmptyInput,
+    /// The input contained NaN values and the policy was `NanPolicy::Error`.
+    ContainsNan,
+    /// The input only contained NaN values and the policy was
+    /// `NanPolicy::Omit`.
+    AllNan,
+}
+
+impl fmt::Display for NanPolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NanPolicyError::EmptyInput => write!(f, "Empty input."),
+            NanPolicyError::ContainsNan => write!(f, "Input contains NaN values."),
+            NanPolicyError::AllNan => write!(f, "Input only contains NaN values."),
+        }
+    }
+}
+
+impl Error for NanPolicyError {}
+
+impl From<EmptyInput> for NanPolicyError {
+    fn from(_: EmptyInput) -> NanPolicyError {
+        NanPolicyError::EmptyInput
+    }
+}
+
+impl From<ContainsNan> for NanPolicyError {
+    fn from(_: ContainsNan) -> NanPolicyError {
+        NanPolicyError::ContainsNan
+    }
+}
+
+/// An error computing a statistic of the non-NaN elements of an array.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SkipNanError {
+    /// The input was empty.
+    EmptyInput,
+    /// The input only contained NaN values.
+    AllNan,
+}
+
+impl fmt::Display for SkipNanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipNanError::EmptyInput => write!(f, "Empty input."),
+            SkipNanError::AllNan => write!(f, "Input only contains NaN values."),
+        }
+    }
+}
+
+impl Error for SkipNanError {}
+
+impl From<EmptyInput> for SkipNanError {
+    fn from(_: EmptyInput) -> SkipNanError {
+        SkipNanError::EmptyInput
+    }
+}
+
 /// An error computing a minimum/maximum value.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum MinMaxError {
@@ -46,9 +148,15 @@ impl From<EmptyInput> for MinMaxError {
 /// An error used by methods and functions that take two arrays as argument and
 /// expect them to have exactly the same shape
 /// (e.g. `ShapeMismatch` is raised when `a.shape() == b.shape()` evaluates to `False`).
-#[derive(Clone, Debug)]
+///
+/// For methods that write into a preallocated output array, the first shape
+/// is the expected shape of the output and the second one is its actual
+/// shape.
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ShapeMismatch {
+    /// Shape of the first array (or the expected shape).
     pub first_shape: Vec<usize>,
+    /// Shape of the second array (or the actual shape).
     pub second_shape: Vec<usize>,
 }
 
@@ -65,7 +173,7 @@ impl fmt::Display for ShapeMismatch {
 impl Error for ShapeMismatch {}
 
 /// An error for methods that take multiple non-empty array inputs.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum MultiInputError {
     /// One or more of the arrays were empty.
     EmptyInput,
@@ -100,7 +208,14 @@ impl fmt::Display for MultiInputError {
     }
 }
 
-impl Error for MultiInputError {}
+impl Error for MultiInputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MultiInputError::EmptyInput => None,
+            MultiInputError::ShapeMismatch(e) => Some(e),
+        }
+    }
+}
 
 impl From<EmptyInput> for MultiInputError {
     fn from(_: EmptyInput) -> Self {
@@ -115,12 +230,12 @@ impl From<ShapeMismatch> for MultiInputError {
 }
 
 /// An error computing a quantile.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum QuantileError {
     /// The input was empty.
     EmptyInput,
-    /// The `q` was not between `0.` and `1.` (inclusive).
-    InvalidQuantile(N64),
+    /// The `q` was NaN or not between `0.` and `1.` (inclusive).
+    InvalidQuantile(f64),
 }
 
 impl fmt::Display for QuantileError {
@@ -128,7 +243,11 @@ impl fmt::Display for QuantileError {
         match self {
             QuantileError::EmptyInput => write!(f, "Empty input."),
             QuantileError::InvalidQuantile(q) => {
-                write!(f, "{:} is not between 0. and 1. (inclusive).", q)
+                write!(
+                    f,
+                    "The quantile {} is not between 0. and 1. (inclusive).",
+                    q
+                )
             }
         }
     }
@@ -141,3 +260,673 @@ impl From<EmptyInput> for QuantileError {
         QuantileError::EmptyInput
     }
 }
+
+/// An error computing a quantile of the elements selected by a mask.
+///
+/// The underlying error is available through `Error::source`:
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::errors::{MaskedQuantileError, QuantileError};
+/// use ndarray_stats::{interpolate::Lower, MaskedExt};
+/// use std::error::Error;
+///
+/// let err = array![1, 2].quantile_masked(&array![false, false], 0.5, &Lower).unwrap_err();
+/// assert_eq!(err, MaskedQuantileError::Quantile(QuantileError::EmptyInput));
+/// assert_eq!(err.source().unwrap().to_string(), "Empty input.");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum MaskedQuantileError {
+    /// The array and the mask did not have the same shape.
+    ShapeMismatch(ShapeMismatch),
+    /// Computing the quantile of the selected elements failed.
+    Quantile(QuantileError),
+}
+
+impl fmt::Display for MaskedQuantileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MaskedQuantileError::ShapeMismatch(e) => write!(f, "Shape mismatch: {}", e),
+            MaskedQuantileError::Quantile(e) => write!(f, "Masked quantile: {}", e),
+        }
+    }
+}
+
+impl Error for MaskedQuantileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MaskedQuantileError::ShapeMismatch(e) => Some(e),
+            MaskedQuantileError::Quantile(e) => Some(e),
+        }
+    }
+}
+
+impl From<ShapeMismatch> for MaskedQuantileError {
+    fn from(err: ShapeMismatch) -> MaskedQuantileError {
+        MaskedQuantileError::ShapeMismatch(err)
+    }
+}
+
+impl From<QuantileError> for MaskedQuantileError {
+    fn from(err: QuantileError) -> MaskedQuantileError {
+        MaskedQuantileError::Quantile(err)
+    }
+}
+
+/// An error computing a quantile according to a [`NanPolicy`].
+///
+/// [`NanPolicy`]: ../enum.NanPolicy.html
+#[derive(Clone, Debug, PartialEq)]
+pub enum NanPolicyQuantileError {
+    /// The input contained NaN values and the policy was `NanPolicy::Error`.
+    ContainsNan,
+    /// Computing the quantile failed.
+    Quantile(QuantileError),
+}
+
+impl fmt::Display for NanPolicyQuantileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NanPolicyQuantileError::ContainsNan => write!(f, "Input contains NaN values."),
+            NanPolicyQuantileError::Quantile(e) => write!(f, "Quantile: {}", e),
+        }
+    }
+}
+
+impl Error for NanPolicyQuantileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NanPolicyQuantileError::ContainsNan => None,
+            NanPolicyQuantileError::Quantile(e) => Some(e),
+        }
+    }
+}
+
+impl From<ContainsNan> for NanPolicyQuantileError {
+    fn from(_: ContainsNan) -> NanPolicyQuantileError {
+        NanPolicyQuantileError::ContainsNan
+    }
+}
+
+impl From<QuantileError> for NanPolicyQuantileError {
+    fn from(err: QuantileError) -> NanPolicyQuantileError {
+        NanPolicyQuantileError::Quantile(err)
+    }
+}
+
+/// An error fitting a distribution to data.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FitError {
+    /// The input was empty.
+    EmptyInput,
+    /// The input contained a value outside the support of the distribution
+    /// (e.g. a negative value for an exponential distribution).
+    OutOfSupport,
+    /// The input does not determine the parameters of the distribution
+    /// (e.g. all the values are equal, for a gamma distribution).
+    Degenerate,
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitError::EmptyInput => write!(f, "Empty input."),
+            FitError::OutOfSupport => write!(
+                f,
+                "Input contains values outside the support of the distribution."
+            ),
+            FitError::Degenerate => write!(
+                f,
+                "Input does not determine the parameters of the distribution."
+            ),
+        }
+    }
+}
+
+impl Error for FitError {}
+
+impl From<EmptyInput> for FitError {
+    fn from(_: EmptyInput) -> FitError {
+        FitError::EmptyInput
+    }
+}
+
+/// An error sampling lanes of an array according to weights.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WeightedSampleError {
+    /// The number of weights differs from the length of the axis.
+    ShapeMismatch(ShapeMismatch),
+    /// A weight was negative or NaN, all the weights were zero, or (when
+    /// sampling without replacement) fewer weights than the number of
+    /// samples were positive.
+    InvalidWeights,
+}
+
+impl fmt::Display for WeightedSampleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeightedSampleError::ShapeMismatch(e) => write!(f, "Shape mismatch: {}", e),
+            WeightedSampleError::InvalidWeights => write!(f, "Invalid sampling weights."),
+        }
+    }
+}
+
+impl Error for WeightedSampleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WeightedSampleError::ShapeMismatch(e) => Some(e),
+            WeightedSampleError::InvalidWeights => None,
+        }
+    }
+}
+
+impl From<ShapeMismatch> for WeightedSampleError {
+    fn from(err: ShapeMismatch) -> WeightedSampleError {
+        WeightedSampleError::ShapeMismatch(err)
+    }
+}
+
+/// An error fitting a decomposition (e.g. a PCA or a whitening transform) to
+/// a data matrix.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecompositionError {
+    /// The data matrix had fewer than two observations.
+    NotEnoughObservations,
+    /// More components were requested than there are features.
+    TooManyComponents,
+    /// The covariance matrix of the features was (numerically) singular.
+    SingularCovariance,
+}
+
+impl fmt::Display for DecompositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecompositionError::NotEnoughObservations => {
+                write!(f, "At least two observations are needed.")
+            }
+            DecompositionError::TooManyComponents => {
+                write!(f, "More components were requested than there are features.")
+            }
+            DecompositionError::SingularCovariance => {
+                write!(f, "The covariance matrix of the features is singular.")
+            }
+        }
+    }
+}
+
+impl Error for DecompositionError {}
+
+/// An error computing a hypothesis test.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HypothesisTestError {
+    /// The shapes of the inputs were incompatible.
+    ShapeMismatch(ShapeMismatch),
+    /// There were too few observations for the test statistic to be defined.
+    NotEnoughObservations,
+    /// The (sample) covariance matrix was (numerically) singular.
+    SingularCovariance,
+}
+
+impl fmt::Display for HypothesisTestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HypothesisTestError::ShapeMismatch(e) => write!(f, "Shape mismatch: {}", e),
+            HypothesisTestError::NotEnoughObservations => {
+                write!(f, "Not enough observations for the test.")
+            }
+            HypothesisTestError::SingularCovariance => {
+                write!(f, "The covariance matrix is singular.")
+            }
+        }
+    }
+}
+
+impl Error for HypothesisTestError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            HypothesisTestError::ShapeMismatch(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ShapeMismatch> for HypothesisTestError {
+    fn from(err: ShapeMismatch) -> HypothesisTestError {
+        HypothesisTestError::ShapeMismatch(err)
+    }
+}
+
+/// An error building a kernel density estimate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KdeError {
+    /// The shapes of the sample and of the bandwidth matrix were
+    /// incompatible.
+    ShapeMismatch(ShapeMismatch),
+    /// There were too few observations to compute the bandwidth (at least two
+    /// are needed by the rules of thumb, and one with an explicit bandwidth).
+    NotEnoughObservations,
+    /// The bandwidth matrix was not (numerically) positive definite.
+    SingularBandwidth,
+}
+
+impl fmt::Display for KdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KdeError::ShapeMismatch(e) => write!(f, "Shape mismatch: {}", e),
+            KdeError::NotEnoughObservations => {
+                write!(f, "Not enough observations to compute the bandwidth.")
+            }
+            KdeError::SingularBandwidth => {
+                write!(f, "The bandwidth matrix is not positive definite.")
+            }
+        }
+    }
+}
+
+impl Error for KdeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            KdeError::ShapeMismatch(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ShapeMismatch> for KdeError {
+    fn from(err: ShapeMismatch) -> KdeError {
+        KdeError::ShapeMismatch(err)
+    }
+}
+
+/// An error estimating the differential entropy of a sample.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EntropyEstimateError {
+    /// There were too few observations for the estimator (at least one for
+    /// the histogram estimator, and more than `k` for the k-nearest-neighbor
+    /// one).
+    NotEnoughObservations,
+    /// The sample contained NaN values.
+    ContainsNan,
+}
+
+impl fmt::Display for EntropyEstimateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntropyEstimateError::NotEnoughObservations => {
+                write!(f, "Not enough observations for the estimator.")
+            }
+            EntropyEstimateError::ContainsNan => write!(f, "Input contains NaN values."),
+        }
+    }
+}
+
+impl Error for EntropyEstimateError {}
+
+/// An error computing a weighted statistic.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WeightedStatsError {
+    /// The weights could not be broadcast to the shape of the data.
+    ShapeMismatch(ShapeMismatch),
+    /// A weight was negative or NaN.
+    InvalidWeights,
+    /// The weights of the (non-NaN) elements summed to zero, e.g. because
+    /// the array was empty.
+    ZeroWeights,
+    /// The "delta degrees of freedom" were negative, or not smaller than the
+    /// sum of the weights.
+    InvalidDdof,
+}
+
+impl fmt::Display for WeightedStatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeightedStatsError::ShapeMismatch(e) => write!(f, "Shape mismatch: {}", e),
+            WeightedStatsError::InvalidWeights => write!(f, "A weight is negative or NaN."),
+            WeightedStatsError::ZeroWeights => write!(f, "The weights sum to zero."),
+            WeightedStatsError::InvalidDdof => {
+                write!(
+                    f,
+                    "`ddof` is negative or not smaller than the sum of the weights."
+                )
+            }
+        }
+    }
+}
+
+impl Error for WeightedStatsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WeightedStatsError::ShapeMismatch(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ShapeMismatch> for WeightedStatsError {
+    fn from(err: ShapeMismatch) -> WeightedStatsError {
+        WeightedStatsError::ShapeMismatch(err)
+    }
+}
+
+/// An error computing a weighted quantile.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WeightedQuantileError {
+    /// The shapes of the data and of the weights were different.
+    ShapeMismatch(ShapeMismatch),
+    /// The `q` was NaN or not between `0.` and `1.` (inclusive).
+    InvalidQuantile(f64),
+    /// A weight was negative or NaN.
+    InvalidWeights,
+    /// The weights of a lane summed to zero, e.g. because it was empty.
+    ZeroWeights,
+}
+
+impl fmt::Display for WeightedQuantileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeightedQuantileError::ShapeMismatch(e) => write!(f, "Shape mismatch: {}", e),
+            WeightedQuantileError::InvalidQuantile(q) => write!(
+                f,
+                "The quantile {} is not between 0. and 1. (inclusive).",
+                q
+            ),
+            WeightedQuantileError::InvalidWeights => write!(f, "A weight is negative or NaN."),
+            WeightedQuantileError::ZeroWeights => write!(f, "The weights sum to zero."),
+        }
+    }
+}
+
+impl Error for WeightedQuantileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WeightedQuantileError::ShapeMismatch(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ShapeMismatch> for WeightedQuantileError {
+    fn from(err: ShapeMismatch) -> WeightedQuantileError {
+        WeightedQuantileError::ShapeMismatch(err)
+    }
+}
+
+/// An error computing any statistic of this crate.
+///
+/// All the error types of this crate convert into `StatsError`, which makes
+/// it a convenient error type for functions computing several statistics
+/// (see the [module documentation](index.html)). The conversions keep the
+/// kind of the failure, but drop the context given by the specific error
+/// types (e.g. whether a shape mismatch was between weights or masks).
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatsError {
+    /// The input was empty.
+    EmptyInput,
+    /// The input only contained NaN values, for the methods skipping them.
+    AllNan,
+    /// The shapes of the inputs were incompatible.
+    ShapeMismatch(ShapeMismatch),
+    /// A quantile `q` was NaN or not between `0.` and `1.` (inclusive).
+    InvalidQuantile(f64),
+    /// The input contained NaN values.
+    ContainsNan,
+    /// The ordering between a tested pair of values was undefined.
+    UndefinedOrder,
+    /// There were too few observations for the statistic to be defined.
+    NotEnoughObservations,
+    /// The "delta degrees of freedom" were negative, or not smaller than the
+    /// number of observations.
+    InvalidDdof,
+    /// A weight was negative or NaN, or the weights could not be used for
+    /// sampling.
+    InvalidWeights,
+    /// The weights summed to zero.
+    ZeroWeights,
+    /// The input contained a value outside the support of a distribution.
+    OutOfSupport,
+    /// The input did not determine the parameters of a model (e.g. all the
+    /// values were equal).
+    Degenerate,
+    /// A covariance or bandwidth matrix was (numerically) singular.
+    Singular,
+    /// More components were requested than there are features.
+    TooManyComponents,
+    /// No histogram bin was found for an observation.
+    BinNotFound,
+    /// Two histograms with different grids were combined.
+    GridMismatch,
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatsError::EmptyInput => write!(f, "Empty input."),
+            StatsError::AllNan => write!(f, "Input only contains NaN values."),
+            StatsError::ShapeMismatch(e) => write!(f, "Shape mismatch: {}", e),
+            StatsError::InvalidQuantile(q) => write!(
+                f,
+                "The quantile {} is not between 0. and 1. (inclusive).",
+                q
+            ),
+            StatsError::ContainsNan => write!(f, "Input contains NaN values."),
+            StatsError::UndefinedOrder => {
+                write!(f, "Undefined ordering between a tested pair of values.")
+            }
+            StatsError::NotEnoughObservations => write!(f, "Not enough observations."),
+            StatsError::InvalidDdof => {
+                write!(
+                    f,
+                    "`ddof` is negative or not smaller than the number of observations."
+                )
+            }
+            StatsError::InvalidWeights => write!(f, "Invalid weights."),
+            StatsError::ZeroWeights => write!(f, "The weights sum to zero."),
+            StatsError::OutOfSupport => write!(
+                f,
+                "Input contains values outside the support of the distribution."
+            ),
+            StatsError::Degenerate => write!(f, "Input does not determine the parameters."),
+            StatsError::Singular => write!(f, "The matrix is singular."),
+            StatsError::TooManyComponents => {
+                write!(f, "More components were requested than there are features.")
+            }
+            StatsError::BinNotFound => write!(f, "No bin has been found."),
+            StatsError::GridMismatch => write!(f, "The histograms have different grids."),
+            StatsError::__NonExhaustive => write!(f, "Statistics error."),
+        }
+    }
+}
+
+impl Error for StatsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StatsError::ShapeMismatch(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<EmptyInput> for StatsError {
+    fn from(_: EmptyInput) -> StatsError {
+        StatsError::EmptyInput
+    }
+}
+
+impl From<ContainsNan> for StatsError {
+    fn from(_: ContainsNan) -> StatsError {
+        StatsError::ContainsNan
+    }
+}
+
+impl From<ShapeMismatch> for StatsError {
+    fn from(err: ShapeMismatch) -> StatsError {
+        StatsError::ShapeMismatch(err)
+    }
+}
+
+impl From<NanPolicyError> for StatsError {
+    fn from(err: NanPolicyError) -> StatsError {
+        match err {
+            NanPolicyError::EmptyInput => StatsError::EmptyInput,
+            NanPolicyError::ContainsNan => StatsError::ContainsNan,
+            NanPolicyError::AllNan => StatsError::AllNan,
+        }
+    }
+}
+
+impl From<SkipNanError> for StatsError {
+    fn from(err: SkipNanError) -> StatsError {
+        match err {
+            SkipNanError::EmptyInput => StatsError::EmptyInput,
+            SkipNanError::AllNan => StatsError::AllNan,
+        }
+    }
+}
+
+impl From<MinMaxError> for StatsError {
+    fn from(err: MinMaxError) -> StatsError {
+        match err {
+            MinMaxError::EmptyInput => StatsError::EmptyInput,
+            MinMaxError::UndefinedOrder => StatsError::UndefinedOrder,
+        }
+    }
+}
+
+impl From<MultiInputError> for StatsError {
+    fn from(err: MultiInputError) -> StatsError {
+        match err {
+            MultiInputError::EmptyInput => StatsError::EmptyInput,
+            MultiInputError::ShapeMismatch(e) => StatsError::ShapeMismatch(e),
+        }
+    }
+}
+
+impl From<QuantileError> for StatsError {
+    fn from(err: QuantileError) -> StatsError {
+        match err {
+            QuantileError::EmptyInput => StatsError::EmptyInput,
+            QuantileError::InvalidQuantile(q) => StatsError::InvalidQuantile(q),
+        }
+    }
+}
+
+impl From<MaskedQuantileError> for StatsError {
+    fn from(err: MaskedQuantileError) -> StatsError {
+        match err {
+            MaskedQuantileError::ShapeMismatch(e) => StatsError::ShapeMismatch(e),
+            MaskedQuantileError::Quantile(e) => e.into(),
+        }
+    }
+}
+
+impl From<NanPolicyQuantileError> for StatsError {
+    fn from(err: NanPolicyQuantileError) -> StatsError {
+        match err {
+            NanPolicyQuantileError::ContainsNan => StatsError::ContainsNan,
+            NanPolicyQuantileError::Quantile(e) => e.into(),
+        }
+    }
+}
+
+impl From<FitError> for StatsError {
+    fn from(err: FitError) -> StatsError {
+        match err {
+            FitError::EmptyInput => StatsError::EmptyInput,
+            FitError::OutOfSupport => StatsError::OutOfSupport,
+            FitError::Degenerate => StatsError::Degenerate,
+        }
+    }
+}
+
+impl From<WeightedSampleError> for StatsError {
+    fn from(err: WeightedSampleError) -> StatsError {
+        match err {
+            WeightedSampleError::ShapeMismatch(e) => StatsError::ShapeMismatch(e),
+            WeightedSampleError::InvalidWeights => StatsError::InvalidWeights,
+        }
+    }
+}
+
+impl From<DecompositionError> for StatsError {
+    fn from(err: DecompositionError) -> StatsError {
+        match err {
+            DecompositionError::NotEnoughObservations => StatsError::NotEnoughObservations,
+            DecompositionError::TooManyComponents => StatsError::TooManyComponents,
+            DecompositionError::SingularCovariance => StatsError::Singular,
+        }
+    }
+}
+
+impl From<HypothesisTestError> for StatsError {
+    fn from(err: HypothesisTestError) -> StatsError {
+        match err {
+            HypothesisTestError::ShapeMismatch(e) => StatsError::ShapeMismatch(e),
+            HypothesisTestError::NotEnoughObservations => StatsError::NotEnoughObservations,
+            HypothesisTestError::SingularCovariance => StatsError::Singular,
+        }
+    }
+}
+
+impl From<KdeError> for StatsError {
+    fn from(err: KdeError) -> StatsError {
+        match err {
+            KdeError::ShapeMismatch(e) => StatsError::ShapeMismatch(e),
+            KdeError::NotEnoughObservations => StatsError::NotEnoughObservations,
+            KdeError::SingularBandwidth => StatsError::Singular,
+        }
+    }
+}
+
+impl From<EntropyEstimateError> for StatsError {
+    fn from(err: EntropyEstimateError) -> StatsError {
+        match err {
+            EntropyEstimateError::NotEnoughObservations => StatsError::NotEnoughObservations,
+            EntropyEstimateError::ContainsNan => StatsError::ContainsNan,
+        }
+    }
+}
+
+impl From<WeightedStatsError> for StatsError {
+    fn from(err: WeightedStatsError) -> StatsError {
+        match err {
+            WeightedStatsError::ShapeMismatch(e) => StatsError::ShapeMismatch(e),
+            WeightedStatsError::InvalidWeights => StatsError::InvalidWeights,
+            WeightedStatsError::ZeroWeights => StatsError::ZeroWeights,
+            WeightedStatsError::InvalidDdof => StatsError::InvalidDdof,
+        }
+    }
+}
+
+impl From<WeightedQuantileError> for StatsError {
+    fn from(err: WeightedQuantileError) -> StatsError {
+        match err {
+            WeightedQuantileError::ShapeMismatch(e) => StatsError::ShapeMismatch(e),
+            WeightedQuantileError::InvalidQuantile(q) => StatsError::InvalidQuantile(q),
+            WeightedQuantileError::InvalidWeights => StatsError::InvalidWeights,
+            WeightedQuantileError::ZeroWeights => StatsError::ZeroWeights,
+        }
+    }
+}
+
+impl From<BinNotFound> for StatsError {
+    fn from(_: BinNotFound) -> StatsError {
+        StatsError::BinNotFound
+    }
+}
+
+impl From<GridMismatch> for StatsError {
+    fn from(_: GridMismatch) -> StatsError {
+        StatsError::GridMismatch
+    }
+}
+
+impl From<BinsBuildError> for StatsError {
+    fn from(err: BinsBuildError) -> StatsError {
+        match err {
+            BinsBuildError::EmptyInput => StatsError::EmptyInput,
+            BinsBuildError::Strategy | BinsBuildError::__NonExhaustive => StatsError::Degenerate,
+        }
+    }
+}
@@ -0,0 +1,451 @@
+use super::special::{digamma, ln_gamma, trigamma};
+use super::Fit;
+use crate::errors::{EmptyInput, FitError};
+use crate::kernels::sum_by;
+use core::f64::consts::PI;
+use ndarray::{ArrayBase, Data, Dimension};
+use num_traits::{Float, FromPrimitive};
+use rand::distributions;
+
+/// Maximum number of Newton iterations used to fit a gamma distribution.
+const MAX_ITERATIONS: usize = 100;
+
+/// Returns the number of elements of `data` and their mean.
+fn count_and_mean<A, S, D>(data: &ArrayBase<S, D>) -> Result<(A, A), EmptyInput>
+where
+    A: Float + FromPrimitive,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    if data.is_empty() {
+        return Err(EmptyInput);
+    }
+    let n = A::from_usize(data.len()).expect("Converting number of elements to `A` must not fail.");
+    Ok((n, sum_by(data, |&x| x) / n))
+}
+
+/// Returns `Err(FitError::OutOfSupport)` unless `in_support` holds for all
+/// the elements of `data`.
+fn check_support<A, S, D, F>(data: &ArrayBase<S, D>, in_support: F) -> Result<(), FitError>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+    F: Fn(&A) -> bool,
+{
+    if data.iter().all(in_support) {
+        Ok(())
+    } else {
+        Err(FitError::OutOfSupport)
+    }
+}
+
+/// Returns `ln(√(2π))`.
+fn ln_sqrt_2pi<A: Float>() -> A {
+    A::from(0.5 * (2. * PI).ln()).unwrap()
+}
+
+/// Normal distribution with mean `mean` and standard deviation `std_dev`.
+///
+/// The standard deviation is estimated with `n` (rather than `n - 1`) in
+/// the denominator, which is the maximum likelihood estimate.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::fit::{Fit, Normal};
+///
+/// let heights = array![172., 181., 165., 178., 169., 175.];
+/// let normal = Normal::fit(&heights).unwrap();
+/// assert_eq!(normal.mean, 173.33333333333334);
+///
+/// // Sample from the fitted distribution with `rand`.
+/// use rand::distributions::{self, Distribution};
+/// let sample = distributions::Normal::from(normal).sample(&mut rand::thread_rng());
+/// # let _ = sample;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Normal<A> {
+    /// The mean `μ`.
+    pub mean: A,
+    /// The standard deviation `σ`.
+    pub std_dev: A,
+}
+
+impl<A> Fit<A> for Normal<A>
+where
+    A: Float + FromPrimitive,
+{
+    const N_PARAMS: usize = 2;
+
+    /// Returns `Err(FitError::Degenerate)` if all the elements are equal.
+    fn fit<S, D>(data: &ArrayBase<S, D>) -> Result<Self, FitError>
+    where
+        S: Data<Elem = A>,
+        D: Dimension,
+    {
+        let (n, mean) = count_and_mean(data)?;
+        let var = sum_by(data, |&x| (x - mean) * (x - mean)) / n;
+        if var == A::zero() {
+            return Err(FitError::Degenerate);
+        }
+        Ok(Normal {
+            mean,
+            std_dev: var.sqrt(),
+        })
+    }
+
+    fn ln_pdf(&self, x: A) -> A {
+        let z = (x - self.mean) / self.std_dev;
+        -self.std_dev.ln() - ln_sqrt_2pi() - z * z / A::from_u8(2).unwrap()
+    }
+
+    private_impl! {}
+}
+
+impl From<Normal<f64>> for distributions::Normal {
+    fn from(normal: Normal<f64>) -> Self {
+        distributions::Normal::new(normal.mean, normal.std_dev)
+    }
+}
+
+/// Log-normal distribution: the distribution of `exp(X)`, where `X` is
+/// normal with mean `mu` and standard deviation `sigma`.
+///
+/// The support of the distribution is `(0, ∞)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LogNormal<A> {
+    /// The mean `μ` of the logarithm of the values.
+    pub mu: A,
+    /// The standard deviation `σ` of the logarithm of the values.
+    pub sigma: A,
+}
+
+impl<A> Fit<A> for LogNormal<A>
+where
+    A: Float + FromPrimitive,
+{
+    const N_PARAMS: usize = 2;
+
+    /// Returns `Err(FitError::Degenerate)` if all the elements are equal.
+    fn fit<S, D>(data: &ArrayBase<S, D>) -> Result<Self, FitError>
+    where
+        S: Data<Elem = A>,
+        D: Dimension,
+    {
+        if data.is_empty() {
+            return Err(FitError::EmptyInput);
+        }
+        check_support(data, |&x| x > A::zero())?;
+        let normal = Normal::fit(&data.mapv(A::ln))?;
+        Ok(LogNormal {
+            mu: normal.mean,
+            sigma: normal.std_dev,
+        })
+    }
+
+    fn ln_pdf(&self, x: A) -> A {
+        if x <= A::zero() {
+            return A::neg_infinity();
+        }
+        let ln_x = x.ln();
+        let z = (ln_x - self.mu) / self.sigma;
+        -ln_x - self.sigma.ln() - ln_sqrt_2pi() - z * z / A::from_u8(2).unwrap()
+    }
+
+    private_impl! {}
+}
+
+impl From<LogNormal<f64>> for distributions::LogNormal {
+    fn from(log_normal: LogNormal<f64>) -> Self {
+        distributions::LogNormal::new(log_normal.mu, log_normal.sigma)
+    }
+}
+
+/// Exponential distribution with rate `rate` (the inverse of its mean).
+///
+/// The support of the distribution is `[0, ∞)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exponential<A> {
+    /// The rate `λ`.
+    pub rate: A,
+}
+
+impl<A> Fit<A> for Exponential<A>
+where
+    A: Float + FromPrimitive,
+{
+    const N_PARAMS: usize = 1;
+
+    /// Returns `Err(FitError::Degenerate)` if all the elements are zero.
+    fn fit<S, D>(data: &ArrayBase<S, D>) -> Result<Self, FitError>
+    where
+        S: Data<Elem = A>,
+        D: Dimension,
+    {
+        if data.is_empty() {
+            return Err(FitError::EmptyInput);
+        }
+        check_support(data, |&x| x >= A::zero())?;
+        let (_, mean) = count_and_mean(data)?;
+        if mean == A::zero() {
+            return Err(FitError::Degenerate);
+        }
+        Ok(Exponential { rate: mean.recip() })
+    }
+
+    fn ln_pdf(&self, x: A) -> A {
+        if x < A::zero() {
+            return A::neg_infinity();
+        }
+        self.rate.ln() - self.rate * x
+    }
+
+    private_impl! {}
+}
+
+impl From<Exponential<f64>> for distributions::Exp {
+    fn from(exponential: Exponential<f64>) -> Self {
+        distributions::Exp::new(exponential.rate)
+    }
+}
+
+/// Gamma distribution with shape `shape` and scale `scale`.
+///
+/// The support of the distribution is `(0, ∞)`. There is no closed form
+/// for the maximum likelihood estimate of the shape, which is computed with
+/// Newton's method on the digamma equation `ln(k) - ψ(k) = ln(mean(x)) -
+/// mean(ln(x))`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gamma<A> {
+    /// The shape `k`.
+    pub shape: A,
+    /// The scale `θ`.
+    pub scale: A,
+}
+
+impl<A> Fit<A> for Gamma<A>
+where
+    A: Float + FromPrimitive,
+{
+    const N_PARAMS: usize = 2;
+
+    /// Returns `Err(FitError::Degenerate)` if all the elements are equal.
+    fn fit<S, D>(data: &ArrayBase<S, D>) -> Result<Self, FitError>
+    where
+        S: Data<Elem = A>,
+        D: Dimension,
+    {
+        if data.is_empty() {
+            return Err(FitError::EmptyInput);
+        }
+        check_support(data, |&x| x > A::zero())?;
+        let (n, mean) = count_and_mean(data)?;
+        let s = mean.ln() - sum_by(data, |&x| x.ln()) / n;
+        if s.is_nan() || s <= A::zero() {
+            return Err(FitError::Degenerate);
+        }
+        let from_f64 = |x: f64| A::from(x).unwrap();
+        // Closed-form approximation of the solution (Minka, 2002).
+        let mut shape = (from_f64(3.) - s
+            + ((s - from_f64(3.)).powi(2) + from_f64(24.) * s).sqrt())
+            / (from_f64(12.) * s);
+        for _ in 0..MAX_ITERATIONS {
+            let step = (shape.ln() - digamma(shape) - s) / (shape.recip() - trigamma(shape));
+            let next = shape - step;
+            // The solution is positive: don't let the iterate leave the domain.
+            let next = if next > A::zero() {
+                next
+            } else {
+                shape / from_f64(2.)
+            };
+            let converged = (next - shape).abs() <= A::epsilon() * shape;
+            shape = next;
+            if converged {
+                break;
+            }
+        }
+        Ok(Gamma {
+            shape,
+            scale: mean / shape,
+        })
+    }
+
+    fn ln_pdf(&self, x: A) -> A {
+        if x <= A::zero() {
+            return A::neg_infinity();
+        }
+        (self.shape - A::one()) * x.ln()
+            - x / self.scale
+            - ln_gamma(self.shape)
+            - self.shape * self.scale.ln()
+    }
+
+    private_impl! {}
+}
+
+impl From<Gamma<f64>> for distributions::Gamma {
+    fn from(gamma: Gamma<f64>) -> Self {
+        distributions::Gamma::new(gamma.shape, gamma.scale)
+    }
+}
+
+/// Poisson distribution with mean `lambda`.
+///
+/// The support of the distribution is the non-negative integers: the
+/// elements of the data must be integers, stored as floating-point values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Poisson<A> {
+    /// The mean `λ`.
+    pub lambda: A,
+}
+
+impl<A: Float> Poisson<A> {
+    fn in_support(x: A) -> bool {
+        x >= A::zero() && x.fract() == A::zero()
+    }
+}
+
+impl<A> Fit<A> for Poisson<A>
+where
+    A: Float + FromPrimitive,
+{
+    const N_PARAMS: usize = 1;
+
+    /// Returns `Err(FitError::Degenerate)` if all the elements are zero.
+    fn fit<S, D>(data: &ArrayBase<S, D>) -> Result<Self, FitError>
+    where
+        S: Data<Elem = A>,
+        D: Dimension,
+    {
+        if data.is_empty() {
+            return Err(FitError::EmptyInput);
+        }
+        check_support(data, |&x| Self::in_support(x))?;
+        let (_, lambda) = count_and_mean(data)?;
+        if lambda == A::zero() {
+            return Err(FitError::Degenerate);
+        }
+        Ok(Poisson { lambda })
+    }
+
+    /// Returns the natural logarithm of the probability mass at `x`.
+    fn ln_pdf(&self, x: A) -> A {
+        if !Self::in_support(x) {
+            return A::neg_infinity();
+        }
+        x * self.lambda.ln() - self.lambda - ln_gamma(x + A::one())
+    }
+
+    private_impl! {}
+}
+
+impl From<Poisson<f64>> for distributions::Poisson {
+    fn from(poisson: Poisson<f64>) -> Self {
+        distributions::Poisson::new(poisson.lambda)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use ndarray::{array, Array1};
+    use ndarray_rand::RandomExt;
+    use std::f64;
+
+    #[test]
+    fn test_errors() {
+        let empty = Array1::<f64>::zeros(0);
+        assert_eq!(Normal::fit(&empty), Err(FitError::EmptyInput));
+        assert_eq!(Gamma::fit(&empty), Err(FitError::EmptyInput));
+        assert_eq!(Poisson::fit(&empty), Err(FitError::EmptyInput));
+
+        assert_eq!(Normal::fit(&array![2., 2.]), Err(FitError::Degenerate));
+        assert_eq!(Gamma::fit(&array![2., 2.]), Err(FitError::Degenerate));
+        assert_eq!(Exponential::fit(&array![0., 0.]), Err(FitError::Degenerate));
+        assert_eq!(Poisson::fit(&array![0., 0.]), Err(FitError::Degenerate));
+
+        assert_eq!(LogNormal::fit(&array![1., 0.]), Err(FitError::OutOfSupport));
+        assert_eq!(Gamma::fit(&array![1., -2.]), Err(FitError::OutOfSupport));
+        assert_eq!(
+            Exponential::fit(&array![1., -2.]),
+            Err(FitError::OutOfSupport)
+        );
+        assert_eq!(Poisson::fit(&array![1., 2.5]), Err(FitError::OutOfSupport));
+        assert_eq!(
+            Poisson::fit(&array![1., f64::NAN]),
+            Err(FitError::OutOfSupport)
+        );
+    }
+
+    #[test]
+    fn test_closed_form_estimates() {
+        let data = array![1., 2., 4., 8.];
+        let normal = Normal::fit(&data).unwrap();
+        assert_eq!(normal.mean, 3.75);
+        assert_abs_diff_eq!(normal.std_dev, 7.1875f64.sqrt(), epsilon = 1e-12);
+        let log_normal = LogNormal::fit(&data).unwrap();
+        assert_abs_diff_eq!(log_normal.mu, 1.5 * 2f64.ln(), epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            log_normal.sigma,
+            1.25f64.sqrt() * 2f64.ln(),
+            epsilon = 1e-12
+        );
+        assert_eq!(Exponential::fit(&data).unwrap().rate, 1. / 3.75);
+        assert_eq!(Poisson::fit(&data).unwrap().lambda, 3.75);
+    }
+
+    #[test]
+    fn test_gamma_recovers_parameters() {
+        let data = Array1::random(100_000, distributions::Gamma::new(2.5, 3.));
+        let gamma = Gamma::fit(&data).unwrap();
+        assert_abs_diff_eq!(gamma.shape, 2.5, epsilon = 0.05);
+        assert_abs_diff_eq!(gamma.scale, 3., epsilon = 0.1);
+        // The estimate satisfies the likelihood equation.
+        let n = data.len() as f64;
+        let s = (data.sum() / n).ln() - data.mapv(f64::ln).sum() / n;
+        assert_abs_diff_eq!(gamma.shape.ln() - digamma(gamma.shape), s, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_gamma_with_shape_one_matches_exponential() {
+        let gamma = Gamma {
+            shape: 1.,
+            scale: 2.,
+        };
+        let exponential = Exponential { rate: 0.5 };
+        for &x in &[0.1, 1., 3.5, 10.] {
+            assert_abs_diff_eq!(gamma.ln_pdf(x), exponential.ln_pdf(x), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_ln_pdf() {
+        let normal = Normal {
+            mean: 0.,
+            std_dev: 1.,
+        };
+        assert_abs_diff_eq!(normal.ln_pdf(0.), -0.5 * (2. * PI).ln(), epsilon = 1e-12);
+        let poisson = Poisson { lambda: 2. };
+        assert_abs_diff_eq!(
+            poisson.ln_pdf(3.),
+            (8f64 / 6. * (-2f64).exp()).ln(),
+            epsilon = 1e-12
+        );
+        assert_eq!(poisson.ln_pdf(1.5), f64::NEG_INFINITY);
+        assert_eq!(Exponential { rate: 1. }.ln_pdf(-1.), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_aic_prefers_the_true_distribution() {
+        let data = Array1::random(10_000, distributions::Exp::new(0.5));
+        let exponential = Exponential::fit(&data).unwrap();
+        let normal = Normal::fit(&data).unwrap();
+        assert!(exponential.aic(&data) < normal.aic(&data));
+        assert_eq!(
+            exponential.aic(&data),
+            2. - 2. * exponential.log_likelihood(&data)
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! Maximum likelihood fitting of common distributions.
+//!
+//! Each distribution is a plain struct holding its parameters, which
+//! implements [`Fit`]: the parameters are estimated from the data with
+//! [`Fit::fit`], and fitted distributions can be compared on the same data
+//! using their [log-likelihood] or their [AIC].
+//!
+//! ```
+//! use ndarray::array;
+//! use ndarray_stats::fit::{Exponential, Fit, Gamma};
+//!
+//! let waiting_times = array![0.8, 2.1, 0.3, 1.7, 0.9, 3.4, 1.2, 0.5];
+//! let exponential: Exponential<f64> = Exponential::fit(&waiting_times).unwrap();
+//! assert!((exponential.rate - 1. / 1.3625).abs() < 1e-12);
+//! let gamma = Gamma::fit(&waiting_times).unwrap();
+//! // The gamma distribution has an extra parameter, which the AIC penalizes.
+//! let best_aic = gamma.aic(&waiting_times).min(exponential.aic(&waiting_times));
+//! assert!(best_aic.is_finite());
+//! ```
+//!
+//! The fitted parameters use the same parametrization as [`rand`]'s
+//! distributions (and [`statrs`]'), and can be converted into the
+//! corresponding `rand::distributions` types to sample from them.
+//!
+//! NaN values are not skipped: they either propagate to the estimated
+//! parameters or, when the support of the distribution is checked, cause an
+//! `OutOfSupport` error.
+//!
+//! [`Fit`]: trait.Fit.html
+//! [`Fit::fit`]: trait.Fit.html#tymethod.fit
+//! [log-likelihood]: trait.Fit.html#method.log_likelihood
+//! [AIC]: trait.Fit.html#method.aic
+//! [`rand`]: https://docs.rs/rand/0.6
+//! [`statrs`]: https://docs.rs/statrs
+pub use self::distributions::{Exponential, Gamma, LogNormal, Normal, Poisson};
+
+use crate::errors::FitError;
+use ndarray::{ArrayBase, Data, Dimension};
+use num_traits::{Float, FromPrimitive};
+
+/// A distribution whose parameters can be estimated by maximum likelihood.
+pub trait Fit<A>: Sized
+where
+    A: Float + FromPrimitive,
+{
+    /// The number of parameters estimated by [`fit`](#tymethod.fit).
+    const N_PARAMS: usize;
+
+    /// Returns the maximum likelihood estimate of the distribution given the
+    /// elements of `data`, treated as independent observations.
+    ///
+    /// Returns `Err(FitError::EmptyInput)` if `data` is empty,
+    /// `Err(FitError::OutOfSupport)` if any element is outside the support of
+    /// the distribution and `Err(FitError::Degenerate)` if the data does not
+    /// determine the parameters.
+    fn fit<S, D>(data: &ArrayBase<S, D>) -> Result<Self, FitError>
+    where
+        S: Data<Elem = A>,
+        D: Dimension;
+
+    /// Returns the natural logarithm of the probability density (or mass,
+    /// for discrete distributions) at `x`.
+    ///
+    /// Returns negative infinity if `x` is outside the support of the
+    /// distribution.
+    fn ln_pdf(&self, x: A) -> A;
+
+    /// Returns the log-likelihood of the distribution given the elements of
+    /// `data`, i.e. the sum of `ln_pdf` over the elements.
+    fn log_likelihood<S, D>(&self, data: &ArrayBase<S, D>) -> A
+    where
+        S: Data<Elem = A>,
+        D: Dimension,
+    {
+        data.iter().fold(A::zero(), |acc, &x| acc + self.ln_pdf(x))
+    }
+
+    /// Returns the [Akaike information criterion] of the distribution given
+    /// the elements of `data`: `2k - 2 ln L`, where `k` is the number of
+    /// parameters and `L` the likelihood.
+    ///
+    /// When comparing distributions fitted to the same data, lower is better.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of
+    /// parameters.
+    ///
+    /// [Akaike information criterion]: https://en.wikipedia.org/wiki/Akaike_information_criterion
+    fn aic<S, D>(&self, data: &ArrayBase<S, D>) -> A
+    where
+        S: Data<Elem = A>,
+        D: Dimension,
+    {
+        let two = A::from_u8(2).unwrap();
+        let k = A::from_usize(Self::N_PARAMS)
+            .expect("Converting number of parameters to `A` must not fail.");
+        two * k - two * self.log_likelihood(data)
+    }
+
+    private_decl! {}
+}
+
+mod distributions;
+pub(crate) mod special;
@@ -0,0 +1,482 @@
+//! Special functions needed to evaluate and fit the distributions, and to
+//! compute the p-values of hypothesis tests.
+use num_traits::Float;
+
+/// Converts an `f64` constant to `A`.
+fn c<A: Float>(x: f64) -> A {
+    A::from(x).expect("Converting a constant to `A` must not fail.")
+}
+
+/// Coefficients of the Lanczos approximation with `g = 7`, `n = 9`.
+const LANCZOS_G: f64 = 7.;
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Returns the natural logarithm of the gamma function, for `x > 0`.
+pub(crate) fn ln_gamma<A: Float>(x: A) -> A {
+    if x < c(0.5) {
+        // Reflection formula: Γ(x) Γ(1 - x) = π / sin(πx).
+        let pi = c::<A>(core::f64::consts::PI);
+        return (pi / (pi * x).sin()).ln() - ln_gamma(A::one() - x);
+    }
+    let x = x - A::one();
+    let mut sum = c::<A>(LANCZOS_COEFFICIENTS[0]);
+    for (i, &coefficient) in LANCZOS_COEFFICIENTS.iter().enumerate().skip(1) {
+        sum = sum + c::<A>(coefficient) / (x + c(i as f64));
+    }
+    let t = x + c(LANCZOS_G + 0.5);
+    c::<A>(0.5 * (2. * core::f64::consts::PI).ln()) + (x + c(0.5)) * t.ln() - t + sum.ln()
+}
+
+/// Smallest argument for which the asymptotic expansions of `digamma` and
+/// `trigamma` are used.
+const ASYMPTOTIC_THRESHOLD: f64 = 10.;
+
+/// Returns the digamma function `ψ(x) = d/dx ln Γ(x)`, for `x > 0`.
+pub(crate) fn digamma<A: Float>(mut x: A) -> A {
+    // Use the recurrence ψ(x) = ψ(x + 1) - 1/x to reach the range where the
+    // asymptotic expansion is accurate.
+    let mut result = A::zero();
+    while x < c(ASYMPTOTIC_THRESHOLD) {
+        result = result - x.recip();
+        x = x + A::one();
+    }
+    let x2 = (x * x).recip();
+    let series = x2
+        * (c::<A>(1. / 12.)
+            - x2 * (c::<A>(1. / 120.) - x2 * (c::<A>(1. / 252.) - x2 * c::<A>(1. / 240.))));
+    result + x.ln() - c::<A>(0.5) / x - series
+}
+
+/// Returns the trigamma function `ψ'(x)`, for `x > 0`.
+pub(crate) fn trigamma<A: Float>(mut x: A) -> A {
+    // Same strategy as for `digamma`, with ψ'(x) = ψ'(x + 1) + 1/x².
+    let mut result = A::zero();
+    while x < c(ASYMPTOTIC_THRESHOLD) {
+        result = result + (x * x).recip();
+        x = x + A::one();
+    }
+    let x2 = (x * x).recip();
+    let series = c::<A>(1. / 6.)
+        - x2 * (c::<A>(1. / 30.)
+            - x2 * (c::<A>(1. / 42.) - x2 * (c::<A>(1. / 30.) - x2 * c::<A>(5. / 66.))));
+    result + x.recip() + x2 * (c::<A>(0.5) + series / x)
+}
+
+/// Maximum number of terms of the continued fraction of the incomplete beta
+/// function.
+const MAX_CONTINUED_FRACTION_TERMS: usize = 300;
+
+/// Returns the regularized incomplete beta function `I_x(a, b)`, for `a > 0`,
+/// `b > 0` and `0 ≤ x ≤ 1`.
+///
+/// This is the cumulative distribution function of the beta distribution,
+/// from which those of the F and Student's t distributions follow.
+pub(crate) fn regularized_incomplete_beta<A: Float>(a: A, b: A, x: A) -> A {
+    if x <= A::zero() {
+        return A::zero();
+    }
+    if x >= A::one() {
+        return A::one();
+    }
+    let ln_front =
+        ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (A::one() - x).ln();
+    // The continued fraction converges quickly for `x < (a + 1) / (a + b + 2)`;
+    // use the symmetry I_x(a, b) = 1 - I_{1-x}(b, a) otherwise.
+    if x < (a + A::one()) / (a + b + c(2.)) {
+        ln_front.exp() * beta_continued_fraction(a, b, x) / a
+    } else {
+        A::one() - ln_front.exp() * beta_continued_fraction(b, a, A::one() - x) / b
+    }
+}
+
+/// Evaluates the continued fraction of the incomplete beta function with the
+/// modified Lentz algorithm.
+fn beta_continued_fraction<A: Float>(a: A, b: A, x: A) -> A {
+    let tiny = A::min_positive_value() / A::epsilon();
+    let clamp = |d: A| if d.abs() < tiny { tiny } else { d };
+    let one = A::one();
+    let mut c_n = one;
+    let mut d_n = (one - (a + b) * x / (a + one)).recip();
+    let mut result = d_n;
+    for m in 1..=MAX_CONTINUED_FRACTION_TERMS {
+        let m = c::<A>(m as f64);
+        let m2 = m + m;
+        // Even step.
+        let numerator = m * (b - m) * x / ((a + m2 - one) * (a + m2));
+        d_n = clamp(one + numerator * d_n).recip();
+        c_n = clamp(one + numerator / c_n);
+        result = result * d_n * c_n;
+        // Odd step.
+        let numerator = -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + one));
+        d_n = clamp(one + numerator * d_n).recip();
+        c_n = clamp(one + numerator / c_n);
+        let delta = d_n * c_n;
+        result = result * delta;
+        if (delta - one).abs() <= A::epsilon() {
+            break;
+        }
+    }
+    result
+}
+
+/// Returns the survival function `P(X > f)` of the F distribution with
+/// `d1` and `d2` degrees of freedom.
+pub(crate) fn f_sf<A: Float>(f: A, d1: A, d2: A) -> A {
+    if f <= A::zero() {
+        return A::one();
+    }
+    let two = c::<A>(2.);
+    regularized_incomplete_beta(d2 / two, d1 / two, d2 / (d2 + d1 * f))
+}
+
+/// Maximum number of terms of the series and of the continued fraction of
+/// the incomplete gamma function.
+const MAX_GAMMA_TERMS: usize = 500;
+
+/// Returns the regularized upper incomplete gamma function `Q(a, x)`, for
+/// `a > 0` and `x ≥ 0`.
+///
+/// This is the survival function of the gamma distribution, from which those
+/// of the chi-square and normal distributions follow.
+pub(crate) fn regularized_upper_incomplete_gamma<A: Float>(a: A, x: A) -> A {
+    if x <= A::zero() {
+        return A::one();
+    }
+    let ln_front = a * x.ln() - x - ln_gamma(a);
+    if x < a + A::one() {
+        // The series of the lower function P(a, x) converges quickly here.
+        let mut term = a.recip();
+        let mut sum = term;
+        let mut denominator = a;
+        for _ in 0..MAX_GAMMA_TERMS {
+            denominator = denominator + A::one();
+            term = term * x / denominator;
+            sum = sum + term;
+            if term.abs() <= sum.abs() * A::epsilon() {
+                break;
+            }
+        }
+        A::one() - ln_front.exp() * sum
+    } else {
+        // Continued fraction of Q(a, x), with the modified Lentz algorithm.
+        let tiny = A::min_positive_value() / A::epsilon();
+        let clamp = |d: A| if d.abs() < tiny { tiny } else { d };
+        let mut b = x + A::one() - a;
+        let mut c_n = tiny.recip();
+        let mut d_n = b.recip();
+        let mut result = d_n;
+        for i in 1..=MAX_GAMMA_TERMS {
+            let i = c::<A>(i as f64);
+            let numerator = -i * (i - a);
+            b = b + c(2.);
+            d_n = clamp(numerator * d_n + b).recip();
+            c_n = clamp(b + numerator / c_n);
+            let delta = d_n * c_n;
+            result = result * delta;
+            if (delta - A::one()).abs() <= A::epsilon() {
+                break;
+            }
+        }
+        ln_front.exp() * result
+    }
+}
+
+/// Returns the survival function `P(X > x)` of the chi-square distribution
+/// with `k` degrees of freedom.
+pub(crate) fn chi_square_sf<A: Float>(x: A, k: A) -> A {
+    let two = c::<A>(2.);
+    regularized_upper_incomplete_gamma(k / two, x / two)
+}
+
+/// Returns `P(|Z| > |z|)` for a standard normal random variable `Z`.
+pub(crate) fn normal_two_sided_sf<A: Float>(z: A) -> A {
+    regularized_upper_incomplete_gamma(c(0.5), z * z / c(2.))
+}
+
+/// Returns the cumulative distribution function `Φ(z)` of the standard
+/// normal distribution.
+pub(crate) fn normal_cdf<A: Float>(z: A) -> A {
+    let tail = normal_two_sided_sf(z) / c(2.);
+    if z < A::zero() {
+        tail
+    } else {
+        A::one() - tail
+    }
+}
+
+/// Coefficients of Acklam's rational approximations of the normal quantile
+/// function, in the central region and in the tails.
+const ACKLAM_A: [f64; 6] = [
+    -3.969_683_028_665_376e1,
+    2.209_460_984_245_205e2,
+    -2.759_285_104_469_687e2,
+    1.383_577_518_672_69e2,
+    -3.066_479_806_614_716e1,
+    2.506_628_277_459_239,
+];
+const ACKLAM_B: [f64; 5] = [
+    -5.447_609_879_822_406e1,
+    1.615_858_368_580_409e2,
+    -1.556_989_798_598_866e2,
+    6.680_131_188_771_972e1,
+    -1.328_068_155_288_572e1,
+];
+const ACKLAM_C: [f64; 6] = [
+    -7.784_894_002_430_293e-3,
+    -3.223_964_580_411_365e-1,
+    -2.400_758_277_161_838,
+    -2.549_732_539_343_734,
+    4.374_664_141_464_968,
+    2.938_163_982_698_783,
+];
+const ACKLAM_D: [f64; 4] = [
+    7.784_695_709_041_462e-3,
+    3.224_671_290_700_398e-1,
+    2.445_134_137_142_996,
+    3.754_408_661_907_416,
+];
+
+/// Returns the quantile function `Φ⁻¹(p)` of the standard normal
+/// distribution, for `0 ≤ p ≤ 1`.
+pub(crate) fn normal_quantile<A: Float>(p: A) -> A {
+    if p <= A::zero() {
+        return A::neg_infinity();
+    }
+    if p >= A::one() {
+        return A::infinity();
+    }
+    let polynomial = |coefficients: &[f64], x: A| {
+        coefficients
+            .iter()
+            .fold(A::zero(), |acc, &coefficient| acc * x + c(coefficient))
+    };
+    let p_low = c::<A>(0.024_25);
+    let x = if p < p_low || p > A::one() - p_low {
+        // The tails, using the symmetry Φ⁻¹(1 - p) = -Φ⁻¹(p).
+        let q = (c::<A>(-2.) * p.min(A::one() - p).ln()).sqrt();
+        let x = polynomial(&ACKLAM_C, q) / (polynomial(&ACKLAM_D, q) * q + A::one());
+        if p < p_low {
+            x
+        } else {
+            -x
+        }
+    } else {
+        let q = p - c(0.5);
+        let r = q * q;
+        q * polynomial(&ACKLAM_A, r) / (polynomial(&ACKLAM_B, r) * r + A::one())
+    };
+    // One step of Halley's method brings the relative error of the
+    // approximation (about 1e-9) down to the precision of `normal_cdf`.
+    let e = normal_cdf(x) - p;
+    let u = e * c::<A>((2. * core::f64::consts::PI).sqrt()) * (x * x / c(2.)).exp();
+    x - u / (A::one() + x * u / c(2.))
+}
+
+/// Returns `P(|T| > |t|)` for a random variable `T` following Student's t
+/// distribution with `nu` degrees of freedom.
+pub(crate) fn t_two_sided_sf<A: Float>(t: A, nu: A) -> A {
+    // T² follows an F distribution with 1 and `nu` degrees of freedom.
+    f_sf(t * t, A::one(), nu)
+}
+
+/// Maximum number of terms of the series of the Kolmogorov distribution.
+const MAX_KOLMOGOROV_TERMS: usize = 100;
+
+/// Returns the survival function `P(K > lambda)` of the Kolmogorov
+/// distribution, the limiting distribution of `√n` times the
+/// Kolmogorov-Smirnov distance.
+pub(crate) fn kolmogorov_sf<A: Float>(lambda: A) -> A {
+    // The series converges slowly for small `lambda`, where the survival
+    // function is 1 up to rounding.
+    if lambda < c(0.2) {
+        return A::one();
+    }
+    let mut sum = A::zero();
+    let mut sign = A::one();
+    for k in 1..=MAX_KOLMOGOROV_TERMS {
+        let k = c::<A>(k as f64);
+        let term = sign * (c::<A>(-2.) * k * k * lambda * lambda).exp();
+        sum = sum + term;
+        if term.abs() <= sum.abs() * A::epsilon() {
+            break;
+        }
+        sign = -sign;
+    }
+    (sum + sum).max(A::zero()).min(A::one())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use std::f64;
+
+    #[test]
+    fn test_ln_gamma() {
+        // Γ(n) = (n - 1)!
+        let mut factorial = 1f64;
+        for n in 1..20 {
+            assert_abs_diff_eq!(ln_gamma(n as f64), factorial.ln(), epsilon = 1e-10);
+            factorial *= n as f64;
+        }
+        // Γ(1/2) = √π
+        assert_abs_diff_eq!(
+            ln_gamma(0.5f64),
+            0.5 * core::f64::consts::PI.ln(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(ln_gamma(0.1f64), 2.252_712_651_734_206, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_digamma_and_trigamma() {
+        const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+        assert_abs_diff_eq!(digamma(1f64), -EULER_GAMMA, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            digamma(0.5f64),
+            -EULER_GAMMA - 2. * 2f64.ln(),
+            epsilon = 1e-12
+        );
+        let pi2 = core::f64::consts::PI.powi(2);
+        assert_abs_diff_eq!(trigamma(1f64), pi2 / 6., epsilon = 1e-12);
+        assert_abs_diff_eq!(trigamma(0.5f64), pi2 / 2., epsilon = 1e-12);
+        // ψ'(x) is the derivative of ψ(x).
+        let (x, h) = (3.7f64, 1e-5);
+        assert_abs_diff_eq!(
+            trigamma(x),
+            (digamma(x + h) - digamma(x - h)) / (2. * h),
+            epsilon = 1e-8
+        );
+    }
+
+    #[test]
+    fn test_regularized_incomplete_beta() {
+        // I_x(1, 1) = x and I_x(a, 1) = x^a.
+        assert_abs_diff_eq!(
+            regularized_incomplete_beta(1f64, 1., 0.3),
+            0.3,
+            epsilon = 1e-14
+        );
+        assert_abs_diff_eq!(
+            regularized_incomplete_beta(2.5f64, 1., 0.7),
+            0.7f64.powf(2.5),
+            epsilon = 1e-14
+        );
+        // For integers, I_x(a, b) = P(Binomial(a + b - 1, x) ≥ a).
+        assert_abs_diff_eq!(
+            regularized_incomplete_beta(2f64, 3., 0.4),
+            0.5248,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            regularized_incomplete_beta(3f64, 2., 0.6),
+            1. - 0.5248,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_f_sf() {
+        // With d1 = 2, P(F > f) = (1 + 2f / d2)^(-d2 / 2).
+        for &(f, d2) in &[(0.5f64, 3.), (2., 10.), (7., 25.)] {
+            assert_abs_diff_eq!(
+                f_sf(f, 2., d2),
+                (1. + 2. * f / d2).powf(-d2 / 2.),
+                epsilon = 1e-12
+            );
+        }
+        assert_eq!(f_sf(0f64, 3., 4.), 1.);
+    }
+
+    #[test]
+    fn test_regularized_upper_incomplete_gamma() {
+        // Q(1, x) = exp(-x), on both sides of the switch at x = a + 1.
+        for &x in &[0.3f64, 1.5, 2.5, 10.] {
+            assert_abs_diff_eq!(
+                regularized_upper_incomplete_gamma(1., x),
+                (-x).exp(),
+                epsilon = 1e-14
+            );
+        }
+        // For integers, Q(a, x) = P(Poisson(x) < a).
+        let poisson = |k: i32, x: f64| {
+            (0..k)
+                .map(|i| (-x).exp() * x.powi(i) / (1..=i).product::<i32>() as f64)
+                .sum::<f64>()
+        };
+        assert_abs_diff_eq!(
+            regularized_upper_incomplete_gamma(3f64, 1.7),
+            poisson(3, 1.7),
+            epsilon = 1e-13
+        );
+        assert_abs_diff_eq!(
+            regularized_upper_incomplete_gamma(4f64, 9.),
+            poisson(4, 9.),
+            epsilon = 1e-13
+        );
+        assert_eq!(regularized_upper_incomplete_gamma(2f64, 0.), 1.);
+    }
+
+    #[test]
+    fn test_tail_probabilities() {
+        // Two-sided 5% critical values.
+        assert_abs_diff_eq!(
+            normal_two_sided_sf(1.959_963_984_540_054f64),
+            0.05,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            t_two_sided_sf(2.228_138_851_986_273f64, 10.),
+            0.05,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            chi_square_sf(3.841_458_820_694_124f64, 1.),
+            0.05,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            chi_square_sf(11.070_497_693_516_351f64, 5.),
+            0.05,
+            epsilon = 1e-12
+        );
+        // Critical values of the Kolmogorov distribution.
+        assert_abs_diff_eq!(kolmogorov_sf(1.358_098_64f64), 0.05, epsilon = 1e-8);
+        assert_abs_diff_eq!(kolmogorov_sf(1.627_618_8f64), 0.01, epsilon = 1e-6);
+        assert_eq!(kolmogorov_sf(0f64), 1.);
+    }
+
+    #[test]
+    fn test_normal_cdf_and_quantile() {
+        assert_eq!(normal_cdf(0f64), 0.5);
+        assert_abs_diff_eq!(
+            normal_cdf(-1.959_963_984_540_054f64),
+            0.025,
+            epsilon = 1e-14
+        );
+        assert_abs_diff_eq!(
+            normal_quantile(0.975f64),
+            1.959_963_984_540_054,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            normal_quantile(0.001f64),
+            -3.090_232_306_167_813_5,
+            epsilon = 1e-12
+        );
+        for &p in &[1e-10f64, 0.01, 0.3, 0.5, 0.8, 0.99] {
+            assert_abs_diff_eq!(normal_cdf(normal_quantile(p)), p, epsilon = 1e-14);
+        }
+        assert_eq!(normal_quantile(0f64), f64::NEG_INFINITY);
+    }
+}
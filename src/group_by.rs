@@ -0,0 +1,248 @@
+//! Aggregation of the rows of a 2-D array by group label.
+use crate::errors::ShapeMismatch;
+use core::ops::Add;
+use ndarray::{Array1, Array2, ArrayBase, Axis, Data, Ix1, Ix2};
+use num_traits::{Float, FromPrimitive, Zero};
+
+/// Extension trait for 2-D `ArrayBase` providing methods to aggregate the
+/// rows (observations) by group label.
+///
+/// The labels are a 1-D array of `usize` with one element per row: row `i`
+/// belongs to group `labels[i]`. The groups are numbered `0..n_groups`,
+/// where `n_groups` is the largest label plus one, and row `g` of the result
+/// is the aggregate of the rows in group `g`. Integer labels can be obtained
+/// from arbitrary categories by numbering the distinct values.
+///
+/// All methods return `Err(ShapeMismatch)` if the number of labels differs
+/// from the number of rows.
+pub trait GroupByExt<A, S>
+where
+    S: Data<Elem = A>,
+{
+    /// Returns the number of rows in each group.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::GroupByExt;
+    ///
+    /// let a = array![[1., 2.], [3., 4.], [5., 6.]];
+    /// let labels = array![2, 0, 2];
+    /// assert_eq!(a.group_count_by(&labels).unwrap(), array![1, 0, 2]);
+    /// ```
+    fn group_count_by<S2>(
+        &self,
+        labels: &ArrayBase<S2, Ix1>,
+    ) -> Result<Array1<usize>, ShapeMismatch>
+    where
+        S2: Data<Elem = usize>;
+
+    /// Returns the column-wise sums of the rows in each group, in a
+    /// `(n_groups, n_columns)` array. The sums of empty groups are zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::GroupByExt;
+    ///
+    /// let a = array![[1, 2], [3, 4], [5, 6]];
+    /// let labels = array![1, 0, 1];
+    /// assert_eq!(a.group_sum_by(&labels).unwrap(), array![[3, 4], [6, 8]]);
+    /// ```
+    fn group_sum_by<S2>(&self, labels: &ArrayBase<S2, Ix1>) -> Result<Array2<A>, ShapeMismatch>
+    where
+        A: Clone + Add<Output = A> + Zero,
+        S2: Data<Elem = usize>;
+
+    /// Returns the column-wise means of the rows in each group, in a
+    /// `(n_groups, n_columns)` array. The means of empty groups are NaN.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the size of a group.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::GroupByExt;
+    ///
+    /// let a = array![[1., 2.], [3., 4.], [5., 6.]];
+    /// let labels = array![1, 0, 1];
+    /// assert_eq!(a.group_mean_by(&labels).unwrap(), array![[3., 4.], [3., 4.]]);
+    /// ```
+    fn group_mean_by<S2>(&self, labels: &ArrayBase<S2, Ix1>) -> Result<Array2<A>, ShapeMismatch>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = usize>;
+
+    /// Folds the rows of each group column-wise into a `(n_groups,
+    /// n_columns)` array.
+    ///
+    /// Every element of the result starts as a clone of `init`, and `f` is
+    /// called with the accumulator of the corresponding group and column for
+    /// each element of the array, in row order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::GroupByExt;
+    ///
+    /// let a = array![[1, 7], [3, 4], [5, 6]];
+    /// let labels = array![0, 1, 0];
+    /// let max = a
+    ///     .group_reduce_by(&labels, i32::min_value(), |acc, &x| *acc = (*acc).max(x))
+    ///     .unwrap();
+    /// assert_eq!(max, array![[5, 7], [3, 4]]);
+    /// ```
+    fn group_reduce_by<S2, B, F>(
+        &self,
+        labels: &ArrayBase<S2, Ix1>,
+        init: B,
+        f: F,
+    ) -> Result<Array2<B>, ShapeMismatch>
+    where
+        S2: Data<Elem = usize>,
+        B: Clone,
+        F: FnMut(&mut B, &A);
+
+    private_decl! {}
+}
+
+impl<A, S> GroupByExt<A, S> for ArrayBase<S, Ix2>
+where
+    S: Data<Elem = A>,
+{
+    fn group_count_by<S2>(
+        &self,
+        labels: &ArrayBase<S2, Ix1>,
+    ) -> Result<Array1<usize>, ShapeMismatch>
+    where
+        S2: Data<Elem = usize>,
+    {
+        check_labels(self, labels)?;
+        let mut counts = Array1::zeros(n_groups(labels));
+        for &label in labels {
+            counts[label] += 1;
+        }
+        Ok(counts)
+    }
+
+    fn group_sum_by<S2>(&self, labels: &ArrayBase<S2, Ix1>) -> Result<Array2<A>, ShapeMismatch>
+    where
+        A: Clone + Add<Output = A> + Zero,
+        S2: Data<Elem = usize>,
+    {
+        self.group_reduce_by(labels, A::zero(), |sum, x| *sum = sum.clone() + x.clone())
+    }
+
+    fn group_mean_by<S2>(&self, labels: &ArrayBase<S2, Ix1>) -> Result<Array2<A>, ShapeMismatch>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = usize>,
+    {
+        let mut means = self.group_sum_by(labels)?;
+        let counts = self.group_count_by(labels)?;
+        for (mut row, &count) in means.outer_iter_mut().zip(&counts) {
+            let n = A::from_usize(count).expect("Converting group size to `A` must not fail.");
+            row.mapv_inplace(|sum| sum / n);
+        }
+        Ok(means)
+    }
+
+    fn group_reduce_by<S2, B, F>(
+        &self,
+        labels: &ArrayBase<S2, Ix1>,
+        init: B,
+        mut f: F,
+    ) -> Result<Array2<B>, ShapeMismatch>
+    where
+        S2: Data<Elem = usize>,
+        B: Clone,
+        F: FnMut(&mut B, &A),
+    {
+        check_labels(self, labels)?;
+        let mut out = Array2::from_elem((n_groups(labels), self.cols()), init);
+        for (row, &label) in self.outer_iter().zip(labels) {
+            out.row_mut(label).zip_mut_with(&row, |acc, x| f(acc, x));
+        }
+        Ok(out)
+    }
+
+    private_impl! {}
+}
+
+/// Returns an error if the number of labels differs from the number of rows
+/// of `a`.
+fn check_labels<A, S, S2>(
+    a: &ArrayBase<S, Ix2>,
+    labels: &ArrayBase<S2, Ix1>,
+) -> Result<(), ShapeMismatch>
+where
+    S: Data<Elem = A>,
+    S2: Data<Elem = usize>,
+{
+    if labels.len() == a.len_of(Axis(0)) {
+        Ok(())
+    } else {
+        Err(ShapeMismatch {
+            first_shape: vec![a.len_of(Axis(0))],
+            second_shape: labels.shape().to_vec(),
+        })
+    }
+}
+
+/// Returns the number of groups: the largest label plus one.
+fn n_groups<S>(labels: &ArrayBase<S, Ix1>) -> usize
+where
+    S: Data<Elem = usize>,
+{
+    labels.iter().max().map_or(0, |&max| max + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn group_sums_add_up_to_column_sums(rows: Vec<(i32, i32, u8)>) -> bool {
+        let a = Array2::from_shape_fn((rows.len(), 2), |(i, j)| {
+            i64::from(if j == 0 { rows[i].0 } else { rows[i].1 })
+        });
+        let labels: Array1<usize> = rows.iter().map(|r| usize::from(r.2 % 5)).collect();
+        let sums = a.group_sum_by(&labels).unwrap();
+        let counts = a.group_count_by(&labels).unwrap();
+        sums.sum_axis(Axis(0)) == a.sum_axis(Axis(0)) && counts.sum() == rows.len()
+    }
+
+    #[test]
+    fn test_group_mean_by_empty_group() {
+        let a = array![[1., 2.], [3., 4.]];
+        let means = a.group_mean_by(&array![0, 2]).unwrap();
+        assert_eq!(means.row(0), array![1., 2.]);
+        assert!(means.row(1).iter().all(|x| x.is_nan()));
+        assert_eq!(means.row(2), array![3., 4.]);
+    }
+
+    #[test]
+    fn test_group_by_errors() {
+        let a = array![[1., 2.], [3., 4.]];
+        let labels = array![0, 1, 1];
+        assert_eq!(
+            a.group_sum_by(&labels),
+            Err(ShapeMismatch {
+                first_shape: vec![2],
+                second_shape: vec![3]
+            })
+        );
+        assert!(a.group_count_by(&labels).is_err());
+        let empty = Array2::<f64>::zeros((0, 3));
+        assert_eq!(
+            empty.group_mean_by(&Array1::zeros(0)).unwrap().shape(),
+            &[0, 3]
+        );
+    }
+}
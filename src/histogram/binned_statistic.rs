@@ -0,0 +1,269 @@
+use super::grid::Grid;
+use crate::errors::ShapeMismatch;
+use crate::numpy_compat::median;
+use ndarray::{ArrayBase, ArrayD, Data, Ix1, Ix2, Zip};
+use num_traits::{Float, FromPrimitive};
+
+/// The statistic computed in each bin by [`binned_statistic_dd`].
+///
+/// [`binned_statistic_dd`]: fn.binned_statistic_dd.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BinnedStatistic {
+    /// The number of points in the bin (the histogram).
+    Count,
+    /// The sum of the values of the points in the bin; zero for empty bins.
+    Sum,
+    /// The mean of the values of the points in the bin; NaN for empty bins.
+    Mean,
+    /// The median of the values of the points in the bin; NaN for empty
+    /// bins, and for bins containing a NaN value.
+    Median,
+}
+
+/// Computes a statistic of `values` in each bin of `grid`.
+///
+/// `sample` is a `(n, d)` array of `n` points in a `d`-dimensional space,
+/// and `values` holds the value attached to each point. Each point is
+/// assigned to a bin of `grid` as in [`HistogramExt::histogram`], and the
+/// `statistic` of the values of the points in each bin is computed (in a
+/// single pass over the points, except for the median). The result has the shape of the grid
+/// (`grid.shape()`); points outside the grid are ignored.
+///
+/// This is the multi-dimensional equivalent of SciPy's
+/// `binned_statistic_dd`, e.g. to aggregate a quantity over a 2-dimensional
+/// grid of positions into a heatmap.
+///
+/// Returns `Err(ShapeMismatch)` if the length of `values` differs from the
+/// number of points.
+///
+/// **Panics** if `d` is different from `grid.ndim()`, or if
+/// `B::from_usize()` fails to convert a count.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::histogram::{binned_statistic_dd, Bins, BinnedStatistic, Edges, Grid};
+///
+/// let bins = Bins::new(Edges::from(vec![0, 10, 20]));
+/// let grid = Grid::from(vec![bins.clone(), bins]);
+/// let positions = array![[1, 2], [3, 15], [5, 8], [12, 12], [30, 1]];
+/// let temperatures = array![20f64, 24., 22., 19., 100.];
+/// let mean = binned_statistic_dd(&positions, &temperatures, &grid, BinnedStatistic::Mean).unwrap();
+/// assert_eq!(mean[[0, 0]], 21.);
+/// assert_eq!(mean[[0, 1]], 24.);
+/// assert!(mean[[1, 0]].is_nan());
+/// assert_eq!(mean[[1, 1]], 19.);
+/// ```
+///
+/// See [`binned_statistic_dd_with`] to compute other statistics.
+///
+/// [`HistogramExt::histogram`]: ../trait.HistogramExt.html#tymethod.histogram
+/// [`binned_statistic_dd_with`]: fn.binned_statistic_dd_with.html
+pub fn binned_statistic_dd<A, B, S1, S2>(
+    sample: &ArrayBase<S1, Ix2>,
+    values: &ArrayBase<S2, Ix1>,
+    grid: &Grid<A>,
+    statistic: BinnedStatistic,
+) -> Result<ArrayD<B>, ShapeMismatch>
+where
+    A: Ord,
+    B: Float + FromPrimitive,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = B>,
+{
+    if values.len() != sample.rows() {
+        return Err(ShapeMismatch {
+            first_shape: sample.shape().to_vec(),
+            second_shape: values.shape().to_vec(),
+        });
+    }
+    if statistic == BinnedStatistic::Median {
+        return binned_statistic_dd_with(sample, values, grid, |values| {
+            if values.is_empty() {
+                B::nan()
+            } else {
+                median(values)
+            }
+        });
+    }
+    let mut counts = ArrayD::<usize>::zeros(grid.shape());
+    let mut sums = ArrayD::<B>::zeros(grid.shape());
+    for (point, &value) in sample.outer_iter().zip(values) {
+        if let Some(index) = grid.index_of(&point) {
+            counts[&*index] += 1;
+            sums[&*index] = sums[&*index] + value;
+        }
+    }
+    let to_b =
+        |count: usize| B::from_usize(count).expect("Converting a count to `B` must not fail.");
+    Ok(match statistic {
+        BinnedStatistic::Count => counts.mapv(to_b),
+        BinnedStatistic::Sum => sums,
+        BinnedStatistic::Mean => {
+            Zip::from(&mut sums).and(&counts).apply(|sum, &count| {
+                *sum = if count == 0 {
+                    B::nan()
+                } else {
+                    *sum / to_b(count)
+                }
+            });
+            sums
+        }
+        BinnedStatistic::Median => unreachable!("The median has been computed above."),
+    })
+}
+
+/// Computes a user-defined statistic of `values` in each bin of `grid`.
+///
+/// The points are assigned to the bins as in [`binned_statistic_dd`], then
+/// `statistic` is called once for each bin (in arbitrary order) with the
+/// values of the points in the bin, in the order of the points. The values
+/// of empty bins are empty vectors. The result has the shape of the grid.
+///
+/// Returns `Err(ShapeMismatch)` if the length of `values` differs from the
+/// number of points.
+///
+/// **Panics** if the number of columns of `sample` is different from
+/// `grid.ndim()`.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::histogram::{binned_statistic_dd_with, Bins, Edges, Grid};
+///
+/// let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 10, 20]))]);
+/// let positions = array![[1], [3], [15], [5]];
+/// let temperatures = array![20., 24., 19., 22.];
+/// let range = binned_statistic_dd_with(&positions, &temperatures, &grid, |values| {
+///     let max = values.iter().cloned().fold(::std::f64::NEG_INFINITY, f64::max);
+///     let min = values.iter().cloned().fold(::std::f64::INFINITY, f64::min);
+///     max - min
+/// })
+/// .unwrap();
+/// assert_eq!(range, array![4., 0.].into_dyn());
+/// ```
+///
+/// [`binned_statistic_dd`]: fn.binned_statistic_dd.html
+pub fn binned_statistic_dd_with<A, B, C, S1, S2, F>(
+    sample: &ArrayBase<S1, Ix2>,
+    values: &ArrayBase<S2, Ix1>,
+    grid: &Grid<A>,
+    statistic: F,
+) -> Result<ArrayD<C>, ShapeMismatch>
+where
+    A: Ord,
+    B: Clone,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = B>,
+    F: FnMut(Vec<B>) -> C,
+{
+    if values.len() != sample.rows() {
+        return Err(ShapeMismatch {
+            first_shape: sample.shape().to_vec(),
+            second_shape: values.shape().to_vec(),
+        });
+    }
+    let mut groups = ArrayD::from_shape_fn(grid.shape(), |_| Vec::new());
+    for (point, value) in sample.outer_iter().zip(values) {
+        if let Some(index) = grid.index_of(&point) {
+            groups[&*index].push(value.clone());
+        }
+    }
+    // `groups` is in standard layout, so its raw vector is in logical order.
+    let shape = groups.raw_dim();
+    let statistics = groups.into_raw_vec().into_iter().map(statistic).collect();
+    Ok(ArrayD::from_shape_vec(shape, statistics).expect("The shape matches the number of bins."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::histogram::{Bins, Edges};
+    use crate::HistogramExt;
+    use ndarray::{array, s, Array1, Array2};
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[test]
+    fn test_binned_statistics() {
+        let grid = Grid::from(vec![
+            Bins::new(Edges::from(vec![0, 1, 2])),
+            Bins::new(Edges::from(vec![0, 10])),
+            Bins::new(Edges::from(vec![0, 3, 6])),
+        ]);
+        let sample = array![[0, 5, 1], [1, 5, 4], [0, 9, 2], [0, 10, 2], [1, 0, 5]];
+        let values = array![1., 2., 3., 100., 4.];
+        let count = binned_statistic_dd(&sample, &values, &grid, BinnedStatistic::Count).unwrap();
+        assert_eq!(count, array![[[2., 0.]], [[0., 2.]]].into_dyn());
+        let sum = binned_statistic_dd(&sample, &values, &grid, BinnedStatistic::Sum).unwrap();
+        assert_eq!(sum, array![[[4., 0.]], [[0., 6.]]].into_dyn());
+        let mean = binned_statistic_dd(&sample, &values, &grid, BinnedStatistic::Mean).unwrap();
+        assert_eq!(mean[[0, 0, 0]], 2.);
+        assert!(mean[[0, 0, 1]].is_nan());
+        assert!(mean[[1, 0, 0]].is_nan());
+        assert_eq!(mean[[1, 0, 1]], 3.);
+        assert!(
+            binned_statistic_dd(&sample, &values.slice(s![..4]), &grid, BinnedStatistic::Sum)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_binned_median() {
+        let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 10, 20, 30]))]);
+        let sample = array![[1], [12], [5], [3], [14], [29]];
+        let values = array![4., 1., f64::NAN, 2., 3., 7.];
+        let median = binned_statistic_dd(&sample, &values, &grid, BinnedStatistic::Median).unwrap();
+        assert!(median[[0]].is_nan());
+        assert_eq!(median[[1]], 2.);
+        assert_eq!(median[[2]], 7.);
+        let empty = Grid::from(vec![Bins::new(Edges::from(vec![40, 50]))]);
+        let median =
+            binned_statistic_dd(&sample, &values, &empty, BinnedStatistic::Median).unwrap();
+        assert!(median[[0]].is_nan());
+    }
+
+    #[quickcheck]
+    fn closure_matches_builtin_statistics(xs: Vec<(i8, i8)>, values: Vec<i16>) -> bool {
+        let n = xs.len().min(values.len());
+        let bins = Bins::new(Edges::from(vec![-100, -10, 0, 10, 100]));
+        let grid = Grid::from(vec![bins.clone(), bins]);
+        let sample = Array2::from_shape_fn((n, 2), |(i, j)| [xs[i].0, xs[i].1][j]);
+        let values = Array1::from_shape_fn(n, |i| f64::from(values[i]));
+        let sum = binned_statistic_dd(&sample, &values, &grid, BinnedStatistic::Sum).unwrap();
+        let with =
+            binned_statistic_dd_with(&sample, &values, &grid, |group| group.iter().sum::<f64>())
+                .unwrap();
+        let order_kept =
+            binned_statistic_dd_with(&sample, &Array1::from_shape_fn(n, |i| i), &grid, |group| {
+                group.windows(2).all(|w| w[0] < w[1])
+            })
+            .unwrap();
+        sum == with && order_kept.iter().all(|&kept| kept)
+    }
+
+    #[quickcheck]
+    fn count_is_histogram(xs: Vec<(i8, i8)>) -> bool {
+        let bins = Bins::new(Edges::from(vec![-100, -10, 0, 10, 100]));
+        let grid = Grid::from(vec![bins.clone(), bins]);
+        let sample = Array2::from_shape_fn((xs.len(), 2), |(i, j)| [xs[i].0, xs[i].1][j]);
+        let count: ArrayD<f64> = binned_statistic_dd(
+            &sample,
+            &Array1::ones(xs.len()),
+            &grid,
+            BinnedStatistic::Count,
+        )
+        .unwrap();
+        let sum = binned_statistic_dd(
+            &sample,
+            &Array1::ones(xs.len()),
+            &grid,
+            BinnedStatistic::Sum,
+        )
+        .unwrap();
+        let histogram = sample.histogram(grid).counts().mapv(|c| c as f64);
+        count == histogram && sum == histogram
+    }
+}
@@ -1,5 +1,8 @@
+use core::ops::{Index, Range};
 use ndarray::prelude::*;
-use std::ops::{Index, Range};
+use ndarray::Data;
+#[cfg(feature = "serde-1")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// `Edges` is a sorted collection of `A` elements used
 /// to represent the boundaries of intervals ([`Bins`]) on
@@ -58,8 +61,29 @@ impl<A: Ord> From<Vec<A>> for Edges<A> {
     }
 }
 
-impl<A: Ord + Clone> From<Array1<A>> for Edges<A> {
-    /// Get an `Edges` instance from a `Array1<A>`:
+/// `Edges` are serialized as a sequence of edges. When deserializing, the
+/// sequence is sorted and deduplicated, as in `Edges::from(Vec<A>)`.
+#[cfg(feature = "serde-1")]
+impl<A: Ord + Serialize> Serialize for Edges<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.edges.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde-1")]
+impl<'de, A: Ord + Deserialize<'de>> Deserialize<'de> for Edges<A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Edges::from)
+    }
+}
+
+impl<A, S> From<ArrayBase<S, Ix1>> for Edges<A>
+where
+    A: Ord + Clone,
+    S: Data<Elem = A>,
+{
+    /// Get an `Edges` instance from a 1-dimensional array (owned, shared or
+    /// a view):
     /// the array elements will be sorted in increasing order
     /// using an unstable sorting algorithm and duplicates will be removed.
     ///
@@ -75,7 +99,7 @@ impl<A: Ord + Clone> From<Array1<A>> for Edges<A> {
     ///     10
     /// );
     /// ```
-    fn from(edges: Array1<A>) -> Self {
+    fn from(edges: ArrayBase<S, Ix1>) -> Self {
         let edges = edges.to_vec();
         Self::from(edges)
     }
@@ -104,6 +128,7 @@ impl<A: Ord> Index<usize> for Edges<A> {
     }
 }
 
+#[allow(clippy::len_without_is_empty)]
 impl<A: Ord> Edges<A> {
     /// Number of edges in `self`.
     ///
@@ -144,7 +169,7 @@ impl<A: Ord> Edges<A> {
 
     /// Given `value`, it returns an option:
     /// - `Some((left, right))`, where `right=left+1`, if there are two consecutive edges in
-    /// `self` such that `self[left] <= value < self[right]`;
+    ///   `self` such that `self[left] <= value < self[right]`;
     /// - `None`, otherwise.
     ///
     /// # Example:
@@ -206,10 +231,13 @@ impl<A: Ord> Edges<A> {
 /// );
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-1", serde(transparent))]
 pub struct Bins<A: Ord> {
     edges: Edges<A>,
 }
 
+#[allow(clippy::len_without_is_empty)]
 impl<A: Ord> Bins<A> {
     /// Given a collection of [`Edges`], it returns the corresponding `Bins` instance.
     ///
@@ -240,6 +268,11 @@ impl<A: Ord> Bins<A> {
         }
     }
 
+    /// Returns the edges of the bins.
+    pub(crate) fn edges(&self) -> &Edges<A> {
+        &self.edges
+    }
+
     /// Given `value`, it returns:
     /// - `Some(i)`, if the `i`-th bin in `self` contains `value`;
     /// - `None`, if `value` does not belong to any of the bins in `self`.
@@ -267,7 +300,7 @@ impl<A: Ord> Bins<A> {
 
     /// Given `value`, it returns:
     /// - `Some(left_edge..right_edge)`, if there exists a bin in `self` such that
-    ///  `left_edge <= value < right_edge`;
+    ///   `left_edge <= value < right_edge`;
     /// - `None`, otherwise.
     ///
     /// # Example:
@@ -364,7 +397,7 @@ mod edges_tests {
     fn edges_are_right_exclusive(v: Vec<i32>) -> bool {
         let edges = Edges::from(v);
         let view = edges.as_array_view();
-        if view.len() == 0 {
+        if view.is_empty() {
             true
         } else {
             let last = view[view.len() - 1];
@@ -379,7 +412,7 @@ mod edges_tests {
             1 => true,
             _ => {
                 let view = edges.as_array_view();
-                if view.len() == 0 {
+                if view.is_empty() {
                     true
                 } else {
                     let first = view[0];
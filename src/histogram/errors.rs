@@ -1,9 +1,9 @@
 use crate::errors::{EmptyInput, MinMaxError};
+use core::fmt;
 use std::error;
-use std::fmt;
 
 /// Error to denote that no bin has been found for a certain observation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct BinNotFound;
 
 impl fmt::Display for BinNotFound {
@@ -12,14 +12,23 @@ impl fmt::Display for BinNotFound {
     }
 }
 
-impl error::Error for BinNotFound {
-    fn description(&self) -> &str {
-        "No bin has been found."
+impl error::Error for BinNotFound {}
+
+/// Error to denote that two histograms can't be combined because their grids
+/// differ.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GridMismatch;
+
+impl fmt::Display for GridMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The histograms have different grids.")
     }
 }
 
+impl error::Error for GridMismatch {}
+
 /// Error computing the set of histogram bins.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub enum BinsBuildError {
     /// The input array was empty.
     EmptyInput,
@@ -49,15 +58,16 @@ impl BinsBuildError {
 
 impl fmt::Display for BinsBuildError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "The strategy failed to determine a non-zero bin width.")
+        match self {
+            BinsBuildError::EmptyInput => write!(f, "Empty input."),
+            BinsBuildError::Strategy | BinsBuildError::__NonExhaustive => {
+                write!(f, "The strategy failed to determine a non-zero bin width.")
+            }
+        }
     }
 }
 
-impl error::Error for BinsBuildError {
-    fn description(&self) -> &str {
-        "The strategy failed to determine a non-zero bin width."
-    }
-}
+impl error::Error for BinsBuildError {}
 
 impl From<EmptyInput> for BinsBuildError {
     fn from(_: EmptyInput) -> Self {
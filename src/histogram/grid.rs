@@ -1,9 +1,11 @@
 use super::bins::Bins;
 use super::errors::BinsBuildError;
 use super::strategies::BinsBuildingStrategy;
+use core::ops::Range;
 use itertools::izip;
 use ndarray::{ArrayBase, Axis, Data, Ix1, Ix2};
-use std::ops::Range;
+#[cfg(feature = "serde-1")]
+use serde::{Deserialize, Serialize};
 
 /// A `Grid` is a partition of a rectangular region of an *n*-dimensional
 /// space—e.g. [*a*<sub>0</sub>, *b*<sub>0</sub>) × ⋯ × [*a*<sub>*n*−1</sub>,
@@ -63,6 +65,8 @@ use std::ops::Range;
 /// assert_eq!(histogram_matrix, expected.into_dyn());
 /// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-1", serde(transparent))]
 pub struct Grid<A: Ord> {
     projections: Vec<Bins<A>>,
 }
@@ -122,6 +126,27 @@ impl<A: Ord> Grid<A> {
             .map(|(v, e)| e.index_of(v))
             .collect()
     }
+
+    /// Writes the index of the bin containing the point into `index`,
+    /// returning `false` (and leaving `index` in an unspecified state) if the
+    /// point is outside the grid.
+    ///
+    /// **Panics** if `point.len()` or `index.len()` does not equal
+    /// `self.ndim()`.
+    pub(crate) fn index_of_into<S>(&self, point: &ArrayBase<S, Ix1>, index: &mut [usize]) -> bool
+    where
+        S: Data<Elem = A>,
+    {
+        assert_eq!(point.len(), self.ndim());
+        assert_eq!(index.len(), self.ndim());
+        for ((v, e), i) in point.iter().zip(&self.projections).zip(index) {
+            match e.index_of(v) {
+                Some(bin) => *i = bin,
+                None => return false,
+            }
+        }
+        true
+    }
 }
 
 impl<A: Ord + Clone> Grid<A> {
@@ -181,6 +206,16 @@ where
         Ok(Self { bin_builders })
     }
 
+    /// Returns a `GridBuilder` using the given bin builders, one for each
+    /// coordinate axis, e.g. strategies created with explicit parameters such
+    /// as [`Explicit`] or [`EquiProbable::with_n_bins`].
+    ///
+    /// [`Explicit`]: strategies/struct.Explicit.html
+    /// [`EquiProbable::with_n_bins`]: strategies/struct.EquiProbable.html#method.with_n_bins
+    pub fn from_bin_builders(bin_builders: Vec<B>) -> Self {
+        Self { bin_builders }
+    }
+
     /// Returns a [`Grid`] instance, built accordingly to the specified [`strategy`]
     /// using the parameters inferred from observations in [`from_array`].
     ///
@@ -1,14 +1,52 @@
-use super::errors::BinNotFound;
+use super::bins::Bins;
+use super::errors::{BinNotFound, GridMismatch};
 use super::grid::Grid;
+use crate::errors::{ShapeMismatch, StatsError};
+use core::cmp::Ordering;
 use ndarray::prelude::*;
-use ndarray::Data;
+use ndarray::{Data, DataMut};
+use num_traits::ToPrimitive;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "serde-1")]
+use serde::{Deserialize, Serialize};
 
 /// Histogram data structure.
+///
+/// With the `serde-1` feature, histograms are serialized as their grid and
+/// counts. When deserializing, the shape of the counts is checked against the
+/// grid, as in [`Histogram::from_parts`](#method.from_parts).
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde-1",
+    serde(
+        try_from = "HistogramParts<A>",
+        bound(deserialize = "A: Ord + Deserialize<'de>")
+    )
+)]
 pub struct Histogram<A: Ord> {
     counts: ArrayD<usize>,
     grid: Grid<A>,
 }
 
+/// The fields of a `Histogram`, deserialized before their shapes are checked.
+#[cfg(feature = "serde-1")]
+#[derive(Deserialize)]
+struct HistogramParts<A: Ord> {
+    counts: ArrayD<usize>,
+    grid: Grid<A>,
+}
+
+#[cfg(feature = "serde-1")]
+impl<A: Ord> core::convert::TryFrom<HistogramParts<A>> for Histogram<A> {
+    type Error = ShapeMismatch;
+
+    fn try_from(parts: HistogramParts<A>) -> Result<Self, ShapeMismatch> {
+        Histogram::from_parts(parts.grid, parts.counts)
+    }
+}
+
 impl<A: Ord> Histogram<A> {
     /// Returns a new instance of Histogram given a [`Grid`].
     ///
@@ -18,6 +56,39 @@ impl<A: Ord> Histogram<A> {
         Histogram { counts, grid }
     }
 
+    /// Returns a histogram with the given grid and bin counts, e.g. to
+    /// rebuild a histogram computed elsewhere.
+    ///
+    /// Returns `Err(ShapeMismatch)` if the shape of `counts` is not
+    /// `grid.shape()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::histogram::{Bins, Edges, Grid, Histogram};
+    ///
+    /// let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 5, 10]))]);
+    /// let histogram = Histogram::from_parts(grid.clone(), array![3, 1].into_dyn()).unwrap();
+    /// assert_eq!(histogram.counts(), array![3, 1].into_dyn());
+    /// assert!(Histogram::from_parts(grid, array![3, 1, 4].into_dyn()).is_err());
+    /// ```
+    pub fn from_parts(grid: Grid<A>, counts: ArrayD<usize>) -> Result<Self, ShapeMismatch> {
+        if counts.shape() != grid.shape().as_slice() {
+            return Err(ShapeMismatch {
+                first_shape: grid.shape(),
+                second_shape: counts.shape().to_vec(),
+            });
+        }
+        Ok(Histogram { counts, grid })
+    }
+
+    /// Returns the grid and the bin counts of the histogram, the inverse of
+    /// [`from_parts`](#method.from_parts).
+    pub fn into_parts(self) -> (Grid<A>, ArrayD<usize>) {
+        (self.grid, self.counts)
+    }
+
     /// Adds a single observation to the histogram.
     ///
     /// **Panics** if dimensions do not match: `self.ndim() != observation.len()`.
@@ -73,6 +144,252 @@ impl<A: Ord> Histogram<A> {
     pub fn grid(&self) -> &Grid<A> {
         &self.grid
     }
+
+    /// Adds the observations (the rows of `observations`) to the histogram,
+    /// e.g. to fill it chunk by chunk.
+    ///
+    /// Important: observations outside the grid are ignored, as in
+    /// [`HistogramExt::histogram`].
+    ///
+    /// **Panics** if the number of columns of `observations` is different from
+    /// `self.ndim()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::histogram::{Bins, Edges, Grid, Histogram};
+    ///
+    /// let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 5, 10]))]);
+    /// let mut histogram = Histogram::new(grid);
+    /// histogram.add_observations(&array![[1], [6]]);
+    /// histogram.add_observations(&array![[2], [20], [3]]);
+    /// assert_eq!(histogram.counts(), array![3, 1].into_dyn());
+    /// ```
+    ///
+    /// [`HistogramExt::histogram`]: trait.HistogramExt.html#tymethod.histogram
+    pub fn add_observations<S>(&mut self, observations: &ArrayBase<S, Ix2>)
+    where
+        S: Data<Elem = A>,
+    {
+        add_points(observations, &self.grid, &mut self.counts);
+    }
+
+    /// Adds the counts of `other` to the counts of `self`, e.g. to combine
+    /// histograms filled from different chunks of data.
+    ///
+    /// Returns `Err(GridMismatch)` if the grids of the two histograms differ,
+    /// leaving `self` untouched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::histogram::{Bins, Edges, Grid, Histogram};
+    ///
+    /// let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 5, 10]))]);
+    /// let mut first = Histogram::new(grid.clone());
+    /// first.add_observations(&array![[1], [6]]);
+    /// let mut second = Histogram::new(grid);
+    /// second.add_observations(&array![[2], [3]]);
+    /// first.merge(&second).unwrap();
+    /// assert_eq!(first.counts(), array![3, 1].into_dyn());
+    ///
+    /// let other_grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 10]))]);
+    /// assert!(first.merge(&Histogram::new(other_grid)).is_err());
+    /// ```
+    pub fn merge(&mut self, other: &Self) -> Result<(), GridMismatch> {
+        if self.grid != other.grid {
+            return Err(GridMismatch);
+        }
+        self.counts += &other.counts;
+        Ok(())
+    }
+}
+
+impl<A: Ord + ToPrimitive> Histogram<A> {
+    /// Returns the probability density of each bin: its count divided by the
+    /// total count and by the volume of the bin (the product of its widths
+    /// along the coordinate axes), so that the density integrates to 1 over
+    /// the grid.
+    ///
+    /// The densities are NaN if the histogram is empty.
+    ///
+    /// **Panics** if an edge can't be converted to `f64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::histogram::{Bins, Edges, Grid, Histogram};
+    ///
+    /// let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 1, 3]))]);
+    /// let histogram = Histogram::from_parts(grid, array![2, 2].into_dyn()).unwrap();
+    /// assert_eq!(histogram.density(), array![0.5, 0.25].into_dyn());
+    /// ```
+    pub fn density(&self) -> ArrayD<f64> {
+        let total = self.counts.sum() as f64;
+        let mut density = self.counts.mapv(|count| count as f64 / total);
+        for (axis, bins) in self.grid.projections().iter().enumerate() {
+            for (i, width) in bin_widths(bins).into_iter().enumerate() {
+                density
+                    .index_axis_mut(Axis(axis), i)
+                    .mapv_inplace(|d| d / width);
+            }
+        }
+        density
+    }
+
+    /// Returns the probability density at `x` of a 1-dimensional histogram:
+    /// the [density](#method.density) of the bin containing `x`, or zero if
+    /// `x` is outside the grid.
+    ///
+    /// Returns NaN if the histogram is empty.
+    ///
+    /// **Panics** if the histogram is not 1-dimensional or if an edge or `x`
+    /// can't be converted to `f64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::histogram::{Bins, Edges, Grid, Histogram};
+    ///
+    /// let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 1, 3]))]);
+    /// let histogram = Histogram::from_parts(grid, array![2, 2].into_dyn()).unwrap();
+    /// assert_eq!(histogram.pdf(&2), 0.25);
+    /// assert_eq!(histogram.pdf(&3), 0.);
+    /// ```
+    pub fn pdf(&self, x: &A) -> f64 {
+        let bins = self.bins_1d();
+        let total = self.counts.sum() as f64;
+        if total == 0. {
+            return core::f64::NAN;
+        }
+        match bins.index_of(x) {
+            Some(i) => self.counts[[i]] as f64 / total / bin_widths(bins)[i],
+            None => 0.,
+        }
+    }
+
+    /// Returns the cumulative distribution function at `x` of a
+    /// 1-dimensional histogram, assuming that the observations are uniformly
+    /// distributed within each bin: the fraction of the observations in the
+    /// bins to the left of `x`, plus the fraction of the bin containing `x`
+    /// that lies to the left of `x`.
+    ///
+    /// Returns NaN if the histogram is empty.
+    ///
+    /// **Panics** if the histogram is not 1-dimensional or if an edge or `x`
+    /// can't be converted to `f64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::histogram::{Bins, Edges, Grid, Histogram};
+    ///
+    /// let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 1, 3]))]);
+    /// let histogram = Histogram::from_parts(grid, array![2, 2].into_dyn()).unwrap();
+    /// assert_eq!(histogram.cdf(&-1), 0.);
+    /// assert_eq!(histogram.cdf(&2), 0.75);
+    /// assert_eq!(histogram.cdf(&5), 1.);
+    /// ```
+    pub fn cdf(&self, x: &A) -> f64 {
+        let bins = self.bins_1d();
+        let x = x.to_f64().expect("Converting `x` to `f64` must not fail.");
+        let edges = edges_to_f64(bins);
+        let total = self.counts.sum() as f64;
+        let below: f64 = self
+            .counts
+            .iter()
+            .zip(edges.windows(2))
+            .map(|(&count, edges)| {
+                let (left, right) = (edges[0], edges[1]);
+                let fraction = ((x - left) / (right - left)).max(0.).min(1.);
+                count as f64 * fraction
+            })
+            .sum();
+        below / total
+    }
+
+    /// Returns the bins of a 1-dimensional histogram.
+    fn bins_1d(&self) -> &Bins<A> {
+        assert_eq!(self.ndim(), 1, "The histogram must be 1-dimensional.");
+        &self.grid.projections()[0]
+    }
+}
+
+/// Returns the edges of `bins`, converted to `f64`.
+fn edges_to_f64<A: Ord + ToPrimitive>(bins: &Bins<A>) -> Vec<f64> {
+    bins.edges()
+        .iter()
+        .map(|edge| {
+            edge.to_f64()
+                .expect("Converting the edges to `f64` must not fail.")
+        })
+        .collect()
+}
+
+/// Returns the widths of `bins`.
+fn bin_widths<A: Ord + ToPrimitive>(bins: &Bins<A>) -> Vec<f64> {
+    edges_to_f64(bins).windows(2).map(|e| e[1] - e[0]).collect()
+}
+
+/// Adds the observations yielded by the iterator to the histogram.
+///
+/// Observations that don't belong to any bin of the grid are ignored, as in
+/// [`HistogramExt::histogram`]. This makes it possible to fill a histogram
+/// incrementally, e.g. from the rows of successive 2-dimensional chunks.
+///
+/// **Panics** if the dimensionality of any observation is different from
+/// `self.ndim()`.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::histogram::{Bins, Edges, Grid, Histogram};
+///
+/// let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 5, 10]))]);
+/// let mut histogram = Histogram::new(grid);
+/// for chunk in &[array![[1], [6]], array![[2], [20], [3]]] {
+///     histogram.extend(chunk.outer_iter());
+/// }
+/// assert_eq!(histogram.counts(), array![3, 1].into_dyn());
+/// ```
+///
+/// [`HistogramExt::histogram`]: trait.HistogramExt.html#tymethod.histogram
+impl<A, S> Extend<ArrayBase<S, Ix1>> for Histogram<A>
+where
+    A: Ord,
+    S: Data<Elem = A>,
+{
+    fn extend<I: IntoIterator<Item = ArrayBase<S, Ix1>>>(&mut self, observations: I) {
+        for observation in observations {
+            let _ = self.add_observation(&observation);
+        }
+    }
+}
+
+/// Increments the counts of the bins of the points (the rows of `points`),
+/// ignoring the points outside the grid.
+fn add_points<A, S, S2>(
+    points: &ArrayBase<S, Ix2>,
+    grid: &Grid<A>,
+    counts: &mut ArrayBase<S2, IxDyn>,
+) where
+    A: Ord,
+    S: Data<Elem = A>,
+    S2: DataMut<Elem = usize>,
+{
+    // The bin index buffer is reused for all the points.
+    let mut index = vec![0; grid.ndim()];
+    for point in points.axis_iter(Axis(0)) {
+        if grid.index_of_into(&point, &mut index) {
+            counts[IxDyn(&index)] += 1;
+        }
+    }
 }
 
 /// Extension trait for `ArrayBase` providing methods to compute histograms.
@@ -86,6 +403,7 @@ where
     /// Let `(n, d)` be the shape of `M`:
     /// - `n` is the number of points;
     /// - `d` is the number of dimensions of the space those points belong to.
+    ///
     /// It follows that every column in `M` is a `d`-dimensional point.
     ///
     /// For example: a (3, 4) matrix `M` is a collection of 3 points in a
@@ -138,6 +456,116 @@ where
     where
         A: Ord;
 
+    /// Like [`histogram`](#tymethod.histogram), but returns
+    /// `Err(StatsError::ShapeMismatch)` instead of panicking if the
+    /// dimensionality `d` of the points is different from `grid.ndim()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{
+    ///     errors::StatsError,
+    ///     histogram::{Bins, Edges, Grid},
+    ///     HistogramExt,
+    /// };
+    ///
+    /// let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 2, 4]))]);
+    /// assert!(array![[1], [3]].try_histogram(grid.clone()).is_ok());
+    /// match array![[1, 2]].try_histogram(grid) {
+    ///     Err(StatsError::ShapeMismatch(_)) => {}
+    ///     _ => panic!("the points are 2-dimensional"),
+    /// }
+    /// ```
+    fn try_histogram(&self, grid: Grid<A>) -> Result<Histogram<A>, StatsError>
+    where
+        A: Ord;
+
+    /// Computes the [histogram](#tymethod.histogram) for a 2-dimensional
+    /// array of points `M`, writing the bin counts into `counts` instead of
+    /// allocating a new `Histogram`.
+    ///
+    /// The previous content of `counts` is overwritten. If the shape of
+    /// `counts` is not `grid.shape()`, `Err(ShapeMismatch)` is returned and
+    /// `counts` is left untouched.
+    ///
+    /// Important: points outside the grid are ignored!
+    ///
+    /// **Panics** if `d` is different from `grid.ndim()`.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ndarray::{array, ArrayD};
+    /// use ndarray_stats::{
+    ///     HistogramExt,
+    ///     histogram::{Bins, Edges, Grid},
+    /// };
+    ///
+    /// let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 2, 4]))]);
+    /// let mut counts = ArrayD::zeros(grid.shape());
+    /// array![[0], [1], [3]].histogram_into(&grid, &mut counts).unwrap();
+    /// assert_eq!(counts, array![2, 1].into_dyn());
+    /// array![[3], [5]].histogram_into(&grid, &mut counts).unwrap();
+    /// assert_eq!(counts, array![0, 1].into_dyn());
+    /// ```
+    fn histogram_into<S2>(
+        &self,
+        grid: &Grid<A>,
+        counts: &mut ArrayBase<S2, IxDyn>,
+    ) -> Result<(), ShapeMismatch>
+    where
+        A: Ord,
+        S2: DataMut<Elem = usize>;
+
+    /// Returns the [histogram](#tymethod.histogram) for a 2-dimensional
+    /// array of 1-dimensional points `M` (i.e. of shape `(n, 1)`), exploiting
+    /// the fact that the points are sorted.
+    ///
+    /// Instead of searching the bin of each point, the bins are filled with
+    /// a single linear sweep over the edges, which is much faster for long
+    /// monotone sequences (e.g. timestamps). Points that are smaller than the
+    /// previous point are binned with a binary search, so the result is
+    /// always the same as for [`histogram`](#tymethod.histogram), even if the
+    /// points are not (entirely) sorted.
+    ///
+    /// Important: points outside the grid are ignored!
+    ///
+    /// **Panics** if `grid.ndim()` is not 1 or if `d` is different from 1.
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{
+    ///     HistogramExt,
+    ///     histogram::{Bins, Edges, Grid},
+    /// };
+    ///
+    /// let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 10, 20, 30]))]);
+    /// let timestamps = array![[-3], [1], [4], [9], [10], [27], [31]];
+    /// let histogram = timestamps.histogram_sorted(grid.clone());
+    /// assert_eq!(histogram.counts(), array![3, 1, 1].into_dyn());
+    /// assert_eq!(histogram, timestamps.histogram(grid));
+    /// ```
+    fn histogram_sorted(&self, grid: Grid<A>) -> Histogram<A>
+    where
+        A: Ord;
+
+    /// Returns the [histogram](#tymethod.histogram) for a 2-dimensional
+    /// array of points `M`, binning the points in parallel.
+    ///
+    /// Each worker fills a partial histogram for a subset of the points,
+    /// and the partial histograms are then summed.
+    ///
+    /// Important: points outside the grid are ignored!
+    ///
+    /// **Panics** if `d` is different from `grid.ndim()`.
+    #[cfg(feature = "rayon")]
+    fn par_histogram(&self, grid: Grid<A>) -> Histogram<A>
+    where
+        A: Ord + Clone + Send + Sync;
+
     private_decl! {}
 }
 
@@ -148,11 +576,189 @@ where
 {
     fn histogram(&self, grid: Grid<A>) -> Histogram<A> {
         let mut histogram = Histogram::new(grid);
-        for point in self.axis_iter(Axis(0)) {
-            let _ = histogram.add_observation(&point);
+        histogram.add_observations(self);
+        histogram
+    }
+
+    fn try_histogram(&self, grid: Grid<A>) -> Result<Histogram<A>, StatsError> {
+        if self.cols() != grid.ndim() {
+            return Err(ShapeMismatch {
+                first_shape: self.shape().to_vec(),
+                second_shape: vec![self.rows(), grid.ndim()],
+            }
+            .into());
+        }
+        Ok(self.histogram(grid))
+    }
+
+    fn histogram_into<S2>(
+        &self,
+        grid: &Grid<A>,
+        counts: &mut ArrayBase<S2, IxDyn>,
+    ) -> Result<(), ShapeMismatch>
+    where
+        A: Ord,
+        S2: DataMut<Elem = usize>,
+    {
+        if counts.shape() != grid.shape().as_slice() {
+            return Err(ShapeMismatch {
+                first_shape: grid.shape(),
+                second_shape: counts.shape().to_vec(),
+            });
+        }
+        counts.fill(0);
+        add_points(self, grid, counts);
+        Ok(())
+    }
+
+    fn histogram_sorted(&self, grid: Grid<A>) -> Histogram<A> {
+        assert_eq!(grid.ndim(), 1, "The grid must be 1-dimensional.");
+        assert_eq!(
+            self.cols(),
+            1,
+            "Dimension mismatch: the points have dimension {}, the grid has dimension 1.",
+            self.cols()
+        );
+        let mut histogram = Histogram::new(grid);
+        let edges_view = histogram.grid.projections()[0].edges().as_array_view();
+        let edges = edges_view
+            .as_slice()
+            .expect("Edges are stored contiguously.");
+        // Number of edges that are less than or equal to the current point.
+        let mut n_below = 0;
+        let mut previous = None;
+        for point in self.column(0) {
+            if previous.map_or(false, |previous| point < previous) {
+                // Binary search for the first edge greater than the point.
+                n_below = edges
+                    .binary_search_by(|edge| {
+                        if edge <= point {
+                            Ordering::Less
+                        } else {
+                            Ordering::Greater
+                        }
+                    })
+                    .unwrap_err();
+            } else {
+                while n_below < edges.len() && edges[n_below] <= *point {
+                    n_below += 1;
+                }
+            }
+            if n_below > 0 && n_below < edges.len() {
+                histogram.counts[[n_below - 1].as_ref()] += 1;
+            }
+            previous = Some(point);
         }
         histogram
     }
 
+    #[cfg(feature = "rayon")]
+    fn par_histogram(&self, grid: Grid<A>) -> Histogram<A>
+    where
+        A: Ord + Clone + Send + Sync,
+    {
+        let points: Vec<_> = self.axis_iter(Axis(0)).collect();
+        let counts = points
+            .into_par_iter()
+            .fold(
+                || Histogram::new(grid.clone()),
+                |mut histogram, point| {
+                    let _ = histogram.add_observation(&point);
+                    histogram
+                },
+            )
+            .map(|histogram| histogram.counts)
+            .reduce(
+                || ArrayD::zeros(grid.shape()),
+                |mut total, partial| {
+                    total += &partial;
+                    total
+                },
+            );
+        Histogram { counts, grid }
+    }
+
     private_impl! {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::histogram::{Bins, Edges};
+    use ndarray::{array, s};
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn histogram_sorted_matches_histogram(points: Vec<i8>, edges: Vec<i8>, sort: bool) -> bool {
+        let mut points = points;
+        if sort {
+            points.sort();
+        }
+        let grid = Grid::from(vec![Bins::new(Edges::from(edges))]);
+        let points = Array::from_shape_vec((points.len(), 1), points).unwrap();
+        points.histogram_sorted(grid.clone()) == points.histogram(grid)
+    }
+
+    #[quickcheck]
+    fn merged_chunks_match_histogram(points: Vec<(i8, i8)>, split: usize) -> bool {
+        let edges = Edges::from(vec![-100, -10, 0, 10, 100]);
+        let grid = Grid::from(vec![Bins::new(edges); 2]);
+        let flat: Vec<i8> = points.iter().flat_map(|&(x, y)| vec![x, y]).collect();
+        let points = Array::from_shape_vec((points.len(), 2), flat).unwrap();
+        let split = split % (points.rows() + 1);
+        let mut first = Histogram::new(grid.clone());
+        first.add_observations(&points.slice(s![..split, ..]));
+        let mut second = Histogram::new(grid.clone());
+        second.add_observations(&points.slice(s![split.., ..]));
+        first.merge(&second).unwrap();
+        first == points.histogram(grid)
+    }
+
+    #[quickcheck]
+    fn density_integrates_to_one(points: Vec<(i8, i8)>) -> bool {
+        let grid = Grid::from(vec![
+            Bins::new(Edges::from(vec![-128, -3, 0, 50, 127])),
+            Bins::new(Edges::from(vec![-128, 10, 127])),
+        ]);
+        let flat: Vec<i8> = points.iter().flat_map(|&(x, y)| vec![x, y]).collect();
+        let points = Array::from_shape_vec((points.len(), 2), flat).unwrap();
+        let histogram = points.histogram(grid.clone());
+        let density = histogram.density();
+        if histogram.counts().sum() == 0 {
+            return density.iter().all(|d| d.is_nan());
+        }
+        let mut integral = 0.;
+        for ((i, j), &d) in density.into_dimensionality::<Ix2>().unwrap().indexed_iter() {
+            let (x, y) = (
+                grid.projections()[0].index(i),
+                grid.projections()[1].index(j),
+            );
+            let width = f64::from(x.end) - f64::from(x.start);
+            let height = f64::from(y.end) - f64::from(y.start);
+            integral += d * width * height;
+        }
+        (integral - 1.).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_pdf_and_cdf() {
+        let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 2, 4, 8]))]);
+        let histogram = array![[0], [1], [3], [4], [8]].histogram(grid.clone());
+        assert_eq!(histogram.pdf(&1), 2. / 4. / 2.);
+        assert_eq!(histogram.pdf(&7), 1. / 4. / 4.);
+        assert_eq!(histogram.pdf(&8), 0.);
+        assert_eq!(histogram.cdf(&0), 0.);
+        assert_eq!(histogram.cdf(&1), 0.25);
+        assert_eq!(histogram.cdf(&6), 0.875);
+        assert_eq!(histogram.cdf(&8), 1.);
+        let empty = Histogram::new(grid);
+        assert!(empty.pdf(&1).is_nan() && empty.cdf(&1).is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_histogram_sorted_panics_for_multidimensional_points() {
+        let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 1])); 2]);
+        array![[0, 0]].histogram_sorted(grid);
+    }
+}
@@ -1,8 +1,10 @@
 //! Histogram functionalities.
+pub use self::binned_statistic::{binned_statistic_dd, binned_statistic_dd_with, BinnedStatistic};
 pub use self::bins::{Bins, Edges};
 pub use self::grid::{Grid, GridBuilder};
 pub use self::histograms::{Histogram, HistogramExt};
 
+mod binned_statistic;
 mod bins;
 pub mod errors;
 mod grid;
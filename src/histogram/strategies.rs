@@ -14,6 +14,12 @@
 //! if it is necessary to include the maximum value of the array that has been passed as argument
 //! to the `from_array` method.
 //!
+//! Two strategies don't use equally spaced bins: [`EquiProbable`] places the
+//! edges at the empirical quantiles of the data, and [`Explicit`] uses
+//! edges supplied by the user.
+//!
+//! [`EquiProbable`]: struct.EquiProbable.html
+//! [`Explicit`]: struct.Explicit.html
 //! [`Bins`]: ../struct.Bins.html
 //! [`Grid`]: ../struct.Grid.html
 //! [`GridBuilder`]: ../struct.GridBuilder.html
@@ -49,7 +55,7 @@ pub trait BinsBuildingStrategy {
     fn from_array<S>(array: &ArrayBase<S, Ix1>) -> Result<Self, BinsBuildError>
     where
         S: Data<Elem = Self::Elem>,
-        Self: std::marker::Sized;
+        Self: Sized;
 
     /// Returns a [`Bins`] instance, built accordingly to the parameters
     /// inferred from observations in [`from_array`].
@@ -150,6 +156,54 @@ pub struct Auto<T> {
     builder: SturgesOrFD<T>,
 }
 
+/// Quantile-based strategy: the bins have variable widths, chosen so that
+/// they hold (approximately) the same number of observations.
+///
+/// Let `n` be the number of observations. The number of bins is chosen as
+/// for [`Sturges`] (unless it is given to [`with_n_bins`]), and the inner
+/// edges are the empirical quantiles of the observations at `1/n_bins`,
+/// `2/n_bins`, ... The first edge is the minimum, and the last bin is
+/// extended past the maximum so that it contains it. Repeated observations
+/// can make some quantiles coincide, in which case there are fewer bins.
+///
+/// This is well suited to skewed or heavy-tailed data, where equally spaced
+/// bins leave most observations in a few bins.
+///
+/// [`Sturges`]: struct.Sturges.html
+/// [`with_n_bins`]: #method.with_n_bins
+#[derive(Debug)]
+pub struct EquiProbable<T> {
+    edges: Vec<T>,
+}
+
+/// User-supplied bin edges, e.g. to use the same bins for several data sets
+/// or to follow domain-specific thresholds.
+///
+/// The edges can't be inferred from observations, so
+/// [`from_array`](trait.BinsBuildingStrategy.html#tymethod.from_array)
+/// always fails: create the strategy with [`new`](#method.new) instead, and
+/// pass it to [`GridBuilder::from_bin_builders`].
+///
+/// # Example
+///
+/// ```
+/// use ndarray_stats::histogram::{strategies::Explicit, Bins, Edges, GridBuilder};
+///
+/// let builder = GridBuilder::from_bin_builders(vec![
+///     Explicit::new(Edges::from(vec![0, 10, 100])),
+///     Explicit::new(Edges::from(vec![-1, 1])),
+/// ]);
+/// let grid = builder.build();
+/// assert_eq!(grid.shape(), vec![2, 1]);
+/// assert_eq!(grid.projections()[0], Bins::new(Edges::from(vec![0, 10, 100])));
+/// ```
+///
+/// [`GridBuilder::from_bin_builders`]: ../struct.GridBuilder.html#method.from_bin_builders
+#[derive(Debug)]
+pub struct Explicit<T: Ord> {
+    edges: Edges<T>,
+}
+
 impl<T> EquiSpaced<T>
 where
     T: Ord + Clone + FromPrimitive + NumOps + Zero,
@@ -185,7 +239,7 @@ where
             max_edge = max_edge + self.bin_width.clone();
             n_bins += 1;
         }
-        return n_bins;
+        n_bins
     }
 
     fn bin_width(&self) -> T {
@@ -361,8 +415,7 @@ where
 {
     fn compute_bin_width(n_bins: usize, iqr: T) -> T {
         let denominator = (n_bins as f64).powf(1. / 3.);
-        let bin_width = T::from_usize(2).unwrap() * iqr / T::from_f64(denominator).unwrap();
-        bin_width
+        T::from_usize(2).unwrap() * iqr / T::from_f64(denominator).unwrap()
     }
 
     /// The bin width (or bin length) according to the fitted strategy.
@@ -384,8 +437,8 @@ where
     where
         S: Data<Elem = Self::Elem>,
     {
-        let fd_builder = FreedmanDiaconis::from_array(&a);
-        let sturges_builder = Sturges::from_array(&a);
+        let fd_builder = FreedmanDiaconis::from_array(a);
+        let sturges_builder = Sturges::from_array(a);
         match (fd_builder, sturges_builder) {
             (Err(_), Ok(sturges_builder)) => {
                 let builder = SturgesOrFD::Sturges(sturges_builder);
@@ -438,6 +491,112 @@ where
     }
 }
 
+impl<T> BinsBuildingStrategy for EquiProbable<T>
+where
+    T: Ord + Clone + FromPrimitive + NumOps + Zero,
+{
+    type Elem = T;
+
+    /// Returns `Err(BinsBuildError::Strategy)` if the array is constant.
+    /// Returns `Err(BinsBuildError::EmptyInput)` if `a.len()==0`.
+    /// Returns `Ok(Self)` otherwise.
+    fn from_array<S>(a: &ArrayBase<S, Ix1>) -> Result<Self, BinsBuildError>
+    where
+        S: Data<Elem = Self::Elem>,
+    {
+        let n_bins = (a.len() as f64).log2().round() as usize + 1;
+        Self::with_n_bins(a, n_bins)
+    }
+
+    fn build(&self) -> Bins<T> {
+        Bins::new(Edges::from(self.edges.clone()))
+    }
+
+    fn n_bins(&self) -> usize {
+        self.edges.len() - 1
+    }
+}
+
+impl<T> EquiProbable<T>
+where
+    T: Ord + Clone + FromPrimitive + NumOps + Zero,
+{
+    /// Returns the strategy splitting the observations in `a` into (at most)
+    /// `n_bins` bins holding the same number of observations.
+    ///
+    /// Returns `Err(BinsBuildError::Strategy)` if the array is constant.
+    /// Returns `Err(BinsBuildError::EmptyInput)` if `a.len()==0`.
+    ///
+    /// **Panics** if `n_bins` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::histogram::{strategies::{BinsBuildingStrategy, EquiProbable}, Bins, Edges};
+    ///
+    /// let a = array![1, 2, 3, 4, 5, 6, 100, 1000];
+    /// let bins = EquiProbable::with_n_bins(&a, 4).unwrap().build();
+    /// assert_eq!(bins, Bins::new(Edges::from(vec![1, 3, 5, 100, 1249])));
+    /// ```
+    pub fn with_n_bins<S>(a: &ArrayBase<S, Ix1>, n_bins: usize) -> Result<Self, BinsBuildError>
+    where
+        S: Data<Elem = T>,
+    {
+        assert!(n_bins > 0, "The number of bins must be positive.");
+        let mut sorted = a.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let (min, max) = match (sorted.first(), sorted.last()) {
+            (Some(min), Some(max)) => (min.clone(), max.clone()),
+            _ => return Err(BinsBuildError::EmptyInput),
+        };
+        if min >= max {
+            return Err(BinsBuildError::Strategy);
+        }
+        let mut edges: Vec<T> = (0..n_bins)
+            .map(|i| sorted[i * n / n_bins].clone())
+            .collect();
+        // The last bin is extended by its share of the range (or by the whole
+        // range, if the share rounds to zero) to include the maximum.
+        let mut padding = compute_bin_width(min.clone(), max.clone(), n_bins);
+        if padding <= T::zero() {
+            padding = max.clone() - min;
+        }
+        edges.push(max + padding);
+        edges.dedup();
+        Ok(Self { edges })
+    }
+}
+
+impl<T: Ord> Explicit<T> {
+    /// Returns the strategy building bins with the given edges.
+    pub fn new(edges: Edges<T>) -> Self {
+        Explicit { edges }
+    }
+}
+
+impl<T: Ord + Clone> BinsBuildingStrategy for Explicit<T> {
+    type Elem = T;
+
+    /// Always returns `Err(BinsBuildError::Strategy)`: use
+    /// [`Explicit::new`](struct.Explicit.html#method.new) instead.
+    fn from_array<S>(_: &ArrayBase<S, Ix1>) -> Result<Self, BinsBuildError>
+    where
+        S: Data<Elem = Self::Elem>,
+    {
+        Err(BinsBuildError::Strategy)
+    }
+
+    fn build(&self) -> Bins<T> {
+        Bins::new(self.edges.clone())
+    }
+
+    fn n_bins(&self) -> usize {
+        self.edges.len().saturating_sub(1)
+    }
+}
+
 /// Given a range (max, min) and the number of bins, it returns
 /// the associated bin_width:
 ///
@@ -449,8 +608,7 @@ where
     T: Ord + Clone + FromPrimitive + NumOps + Zero,
 {
     let range = max.clone() - min.clone();
-    let bin_width = range / T::from_usize(n_bins).unwrap();
-    bin_width
+    range / T::from_usize(n_bins).unwrap()
 }
 
 #[cfg(test)]
@@ -581,3 +739,59 @@ mod auto_tests {
             .is_empty_input());
     }
 }
+
+#[cfg(test)]
+mod equiprobable_tests {
+    use super::*;
+    use ndarray::{array, Array1};
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn constant_array_are_bad() {
+        assert!(EquiProbable::from_array(&array![1, 1, 1, 1, 1, 1, 1])
+            .unwrap_err()
+            .is_strategy());
+    }
+
+    #[test]
+    fn empty_arrays_are_bad() {
+        assert!(EquiProbable::<usize>::from_array(&array![])
+            .unwrap_err()
+            .is_empty_input());
+    }
+
+    #[quickcheck]
+    fn bins_hold_the_same_number_of_distinct_observations(values: Vec<i32>, n_bins: usize) -> bool {
+        let mut values = values;
+        values.sort();
+        values.dedup();
+        if values.len() < 2 {
+            return true;
+        }
+        let n_bins = n_bins % values.len() + 1;
+        let a = Array1::from_vec(values.clone());
+        let bins = EquiProbable::with_n_bins(&a, n_bins).unwrap().build();
+        let mut counts = vec![0; bins.len()];
+        for v in &values {
+            counts[bins.index_of(v).unwrap()] += 1;
+        }
+        let (min, max) = (counts.iter().min().unwrap(), counts.iter().max().unwrap());
+        bins.len() == n_bins && max - min <= 1
+    }
+}
+
+#[cfg(test)]
+mod explicit_tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn edges_are_not_inferred() {
+        assert!(Explicit::from_array(&array![1, 2, 3])
+            .unwrap_err()
+            .is_strategy());
+        let explicit = Explicit::new(Edges::from(vec![3, 1, 2]));
+        assert_eq!(explicit.n_bins(), 2);
+        assert_eq!(explicit.build(), Bins::new(Edges::from(vec![1, 2, 3])));
+    }
+}
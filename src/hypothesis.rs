@@ -0,0 +1,881 @@
+//! Statistical hypothesis tests.
+//!
+//! Each test returns a [`TestResult`] holding the test statistic and its
+//! p-value: the probability, under the null hypothesis, of a statistic at
+//! least as extreme as the observed one. All p-values are two-sided.
+//!
+//! The univariate tests propagate NaN: if an input contains NaN, both the
+//! statistic and the p-value are NaN. Their `_skipnan` variants leave the NaN
+//! elements out of the test instead.
+//!
+//! [`TestResult`]: struct.TestResult.html
+use crate::errors::{HypothesisTestError, ShapeMismatch};
+use crate::fit::special::{
+    chi_square_sf, f_sf, kolmogorov_sf, normal_two_sided_sf, t_two_sided_sf,
+};
+use crate::linalg::{is_singular, symmetric_eigen};
+use crate::{CorrelationExt, Ecdf};
+use ndarray::{Array1, Array2, ArrayBase, ArrayView1, Axis, Data, Ix1, Ix2};
+use num_traits::{Float, FromPrimitive};
+
+/// The outcome of a hypothesis test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TestResult<A> {
+    /// The value of the test statistic.
+    pub statistic: A,
+    /// The p-value of the test.
+    pub p_value: A,
+}
+
+/// Student's one-sample t-test that the mean of `sample` is `mean`.
+///
+/// With `n` observations, sample mean `x̅` and sample standard deviation `s`,
+/// the statistic is `t = (x̅ - μ) / (s / √n)`, which follows Student's t
+/// distribution with `n - 1` degrees of freedom if the observations are
+/// independent and normally distributed with mean `μ`.
+///
+/// Returns `Err(HypothesisTestError::NotEnoughObservations)` if `sample` has
+/// less than two elements.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::hypothesis::t_test_one_sample;
+///
+/// let sample = array![5.1, 4.9, 5.6, 5.8, 6.0, 5.2];
+/// assert!(t_test_one_sample(&sample, 5.4).unwrap().p_value > 0.05);
+/// assert!(t_test_one_sample(&sample, 4.5).unwrap().p_value < 0.01);
+/// ```
+pub fn t_test_one_sample<A, S>(
+    sample: &ArrayBase<S, Ix1>,
+    mean: A,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S: Data<Elem = A>,
+{
+    // NaN values propagate through the mean and the variance.
+    t_one_sample(&sample.to_vec(), mean)
+}
+
+/// Like [`t_test_one_sample`], but ignores the NaN elements of `sample`.
+///
+/// [`t_test_one_sample`]: fn.t_test_one_sample.html
+pub fn t_test_one_sample_skipnan<A, S>(
+    sample: &ArrayBase<S, Ix1>,
+    mean: A,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S: Data<Elem = A>,
+{
+    t_one_sample(&without_nan(sample), mean)
+}
+
+/// Student's two-sample t-test that `first` and `second` have the same mean,
+/// assuming that they have the same variance.
+///
+/// The statistic is the difference of the sample means divided by its
+/// standard error computed with the pooled sample variance, and follows
+/// Student's t distribution with `n₁ + n₂ - 2` degrees of freedom if the
+/// observations are independent and normally distributed with the same mean
+/// and the same variance. Use [`welch_t_test`] if the variances may differ.
+///
+/// Returns `Err(HypothesisTestError::NotEnoughObservations)` if a sample is
+/// empty or if the samples have less than three elements in total.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::hypothesis::t_test;
+///
+/// let first = array![2.1, 1.8, 2.3, 1.9, 2.4];
+/// let second = array![2.9, 3.1, 2.6, 3.3];
+/// assert!(t_test(&first, &second).unwrap().p_value < 0.01);
+/// ```
+///
+/// [`welch_t_test`]: fn.welch_t_test.html
+pub fn t_test<A, S1, S2>(
+    first: &ArrayBase<S1, Ix1>,
+    second: &ArrayBase<S2, Ix1>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    bivariate(first.to_vec(), second.to_vec(), student_t)
+}
+
+/// Like [`t_test`], but ignores the NaN elements of the samples.
+///
+/// [`t_test`]: fn.t_test.html
+pub fn t_test_skipnan<A, S1, S2>(
+    first: &ArrayBase<S1, Ix1>,
+    second: &ArrayBase<S2, Ix1>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    student_t(&without_nan(first), &without_nan(second))
+}
+
+/// Welch's t-test that `first` and `second` have the same mean, without
+/// assuming that they have the same variance.
+///
+/// The statistic is the difference of the sample means divided by its
+/// standard error `√(s₁² / n₁ + s₂² / n₂)`, and approximately follows
+/// Student's t distribution with the Welch–Satterthwaite degrees of freedom
+/// if the observations are independent and normally distributed.
+///
+/// Returns `Err(HypothesisTestError::NotEnoughObservations)` if a sample has
+/// less than two elements.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::hypothesis::welch_t_test;
+///
+/// let first = array![2.1, 1.8, 2.3, 1.9, 2.4];
+/// let second = array![2.9, 3.9, 1.6, 4.3];
+/// assert!(welch_t_test(&first, &second).unwrap().p_value > 0.05);
+/// ```
+pub fn welch_t_test<A, S1, S2>(
+    first: &ArrayBase<S1, Ix1>,
+    second: &ArrayBase<S2, Ix1>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    bivariate(first.to_vec(), second.to_vec(), welch_t)
+}
+
+/// Like [`welch_t_test`], but ignores the NaN elements of the samples.
+///
+/// [`welch_t_test`]: fn.welch_t_test.html
+pub fn welch_t_test_skipnan<A, S1, S2>(
+    first: &ArrayBase<S1, Ix1>,
+    second: &ArrayBase<S2, Ix1>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    welch_t(&without_nan(first), &without_nan(second))
+}
+
+/// The two-sample Kolmogorov–Smirnov test that `first` and `second` are
+/// drawn from the same continuous distribution.
+///
+/// The statistic is the largest absolute difference between the empirical
+/// distribution functions of the samples (see [`Ecdf::ks_distance`]). The
+/// p-value is computed from the asymptotic Kolmogorov distribution, with
+/// Stephens' correction for the effective sample size
+/// `nₑ = n₁ n₂ / (n₁ + n₂)`; it is accurate when `nₑ` is at least 4 or so.
+///
+/// Returns `Err(HypothesisTestError::NotEnoughObservations)` if a sample is
+/// empty.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::Array1;
+/// use ndarray_stats::hypothesis::ks_test;
+///
+/// let first = Array1::range(0., 40., 1.);
+/// let second = Array1::range(20., 60., 1.);
+/// let result = ks_test(&first, &second).unwrap();
+/// assert_eq!(result.statistic, 0.5);
+/// assert!(result.p_value < 0.001);
+/// ```
+///
+/// [`Ecdf::ks_distance`]: ../struct.Ecdf.html#method.ks_distance
+pub fn ks_test<A, S1, S2>(
+    first: &ArrayBase<S1, Ix1>,
+    second: &ArrayBase<S2, Ix1>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    bivariate(first.to_vec(), second.to_vec(), ks)
+}
+
+/// Like [`ks_test`], but ignores the NaN elements of the samples.
+///
+/// [`ks_test`]: fn.ks_test.html
+pub fn ks_test_skipnan<A, S1, S2>(
+    first: &ArrayBase<S1, Ix1>,
+    second: &ArrayBase<S2, Ix1>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    ks(&without_nan(first), &without_nan(second))
+}
+
+/// The Mann–Whitney U test (Wilcoxon rank-sum test) that `first` and
+/// `second` are drawn from the same distribution, against the alternative
+/// that one of them tends to have larger values.
+///
+/// The statistic is `U = R₁ - n₁ (n₁ + 1) / 2`, where `R₁` is the sum of the
+/// ranks of the elements of `first` in the pooled sample (ties get the
+/// average of their ranks). The p-value is computed from the normal
+/// approximation of the distribution of `U`, with a continuity correction
+/// and a correction of the variance for ties; it is NaN if all the
+/// observations are tied.
+///
+/// Returns `Err(HypothesisTestError::NotEnoughObservations)` if a sample is
+/// empty.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::hypothesis::mann_whitney_u;
+///
+/// let first = array![1.1, 2.3, 0.7, 1.9, 2.0, 1.4];
+/// let second = array![2.8, 3.5, 2.2, 4.1, 3.0, 2.6];
+/// let result = mann_whitney_u(&first, &second).unwrap();
+/// assert_eq!(result.statistic, 1.);
+/// assert!(result.p_value < 0.01);
+/// ```
+pub fn mann_whitney_u<A, S1, S2>(
+    first: &ArrayBase<S1, Ix1>,
+    second: &ArrayBase<S2, Ix1>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    bivariate(first.to_vec(), second.to_vec(), mann_whitney)
+}
+
+/// Like [`mann_whitney_u`], but ignores the NaN elements of the samples.
+///
+/// [`mann_whitney_u`]: fn.mann_whitney_u.html
+pub fn mann_whitney_u_skipnan<A, S1, S2>(
+    first: &ArrayBase<S1, Ix1>,
+    second: &ArrayBase<S2, Ix1>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    mann_whitney(&without_nan(first), &without_nan(second))
+}
+
+/// Pearson's chi-square goodness-of-fit test that the `observed` frequencies
+/// of some categories are drawn from a distribution with the `expected`
+/// frequencies.
+///
+/// The statistic is `χ² = ∑ (oᵢ - eᵢ)² / eᵢ`, which approximately follows a
+/// chi-square distribution with `k - 1` degrees of freedom for `k` categories
+/// if the expected frequencies are large enough (at least 5 or so). The
+/// expected frequencies should sum to the total of the observed ones.
+///
+/// Returns `Err(HypothesisTestError::ShapeMismatch)` if `observed` and
+/// `expected` have different lengths and
+/// `Err(HypothesisTestError::NotEnoughObservations)` if there are less than
+/// two categories.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::hypothesis::chi_square_test;
+///
+/// let observed = array![16., 18., 16., 14., 12., 12.];
+/// let expected = array![16., 16., 16., 16., 16., 8.];
+/// let result = chi_square_test(&observed, &expected).unwrap();
+/// assert_eq!(result.statistic, 3.5);
+/// assert!(result.p_value > 0.05);
+/// ```
+pub fn chi_square_test<A, S1, S2>(
+    observed: &ArrayBase<S1, Ix1>,
+    expected: &ArrayBase<S2, Ix1>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    check_same_len(observed, expected)?;
+    bivariate(observed.to_vec(), expected.to_vec(), chi_square)
+}
+
+/// Like [`chi_square_test`], but ignores the categories whose observed or
+/// expected frequency is NaN.
+///
+/// [`chi_square_test`]: fn.chi_square_test.html
+pub fn chi_square_test_skipnan<A, S1, S2>(
+    observed: &ArrayBase<S1, Ix1>,
+    expected: &ArrayBase<S2, Ix1>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    check_same_len(observed, expected)?;
+    let (observed, expected): (Vec<A>, Vec<A>) = observed
+        .iter()
+        .zip(expected)
+        .filter(|(o, e)| !o.is_nan() && !e.is_nan())
+        .map(|(&o, &e)| (o, e))
+        .unzip();
+    chi_square(&observed, &expected)
+}
+
+/// Hotelling's one-sample T² test that the mean of the observations (rows)
+/// of `sample` is `mean`.
+///
+/// This is the multivariate generalization of the one-sample t-test. With
+/// `n` observations of `p` variables, sample mean `x̅` and sample covariance
+/// matrix `S`, the statistic is
+///
+/// ```text
+/// T² = n (x̅ - μ)ᵀ S⁻¹ (x̅ - μ)
+/// ```
+///
+/// and `(n - p) / (p (n - 1)) T²` follows an F distribution with `p` and
+/// `n - p` degrees of freedom if the observations are independent and
+/// normally distributed with mean `μ`; the p-value is computed from it.
+///
+/// Returns `Err(HypothesisTestError::ShapeMismatch)` if the length of `mean`
+/// differs from the number of columns of `sample`,
+/// `Err(HypothesisTestError::NotEnoughObservations)` if `sample` has no more
+/// observations than variables and `Err(HypothesisTestError::SingularCovariance)`
+/// if the sample covariance matrix is (numerically) singular.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::hypothesis::hotelling_t2_one_sample;
+///
+/// let sample = array![[2.1, 0.9], [1.8, 1.2], [2.3, 1.1], [1.9, 0.7], [2.4, 1.3]];
+/// let result = hotelling_t2_one_sample(&sample, &array![2., 1.]).unwrap();
+/// assert!(result.p_value > 0.05);
+/// let result = hotelling_t2_one_sample(&sample, &array![1., 1.]).unwrap();
+/// assert!(result.p_value < 0.01);
+/// ```
+pub fn hotelling_t2_one_sample<A, S1, S2>(
+    sample: &ArrayBase<S1, Ix2>,
+    mean: &ArrayBase<S2, Ix1>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive + 'static,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    if mean.len() != sample.cols() {
+        return Err(ShapeMismatch {
+            first_shape: vec![sample.cols()],
+            second_shape: mean.shape().to_vec(),
+        }
+        .into());
+    }
+    let (n, p) = (sample.rows(), sample.cols());
+    if n <= p {
+        return Err(HypothesisTestError::NotEnoughObservations);
+    }
+    let difference = sample.mean_axis(Axis(0)) - mean;
+    let covariance = sample.t().cov(A::one());
+    let t2 = to_a::<A>(n) * inverse_quadratic_form(covariance, &difference)?;
+    let (n, p) = (to_a::<A>(n), to_a::<A>(p));
+    let f = (n - p) / (p * (n - A::one())) * t2;
+    Ok(TestResult {
+        statistic: t2,
+        p_value: f_sf(f, p, n - p),
+    })
+}
+
+/// Hotelling's two-sample T² test that the observations (rows) of `first`
+/// and `second` have the same mean.
+///
+/// This is the multivariate generalization of Student's two-sample t-test.
+/// With `n₁` and `n₂` observations of `p` variables, difference of the
+/// sample means `d` and pooled sample covariance matrix `S`, the statistic is
+///
+/// ```text
+/// T² = n₁ n₂ / (n₁ + n₂) dᵀ S⁻¹ d
+/// ```
+///
+/// and `(n₁ + n₂ - p - 1) / (p (n₁ + n₂ - 2)) T²` follows an F distribution
+/// with `p` and `n₁ + n₂ - p - 1` degrees of freedom if the observations
+/// are independent and normally distributed with the same mean and the same
+/// covariance matrix; the p-value is computed from it.
+///
+/// Returns `Err(HypothesisTestError::ShapeMismatch)` if the samples have
+/// different numbers of columns,
+/// `Err(HypothesisTestError::NotEnoughObservations)` if `n₁ + n₂ ≤ p + 1` or
+/// a sample is empty, and `Err(HypothesisTestError::SingularCovariance)` if
+/// the pooled covariance matrix is (numerically) singular.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::hypothesis::hotelling_t2;
+///
+/// let first = array![[2.1, 0.9], [1.8, 1.2], [2.3, 1.1], [1.9, 0.7], [2.4, 1.3]];
+/// let second = array![[2.9, 1.0], [3.1, 1.3], [2.6, 0.8], [3.3, 1.2]];
+/// let result = hotelling_t2(&first, &second).unwrap();
+/// assert!(result.p_value < 0.01);
+/// ```
+pub fn hotelling_t2<A, S1, S2>(
+    first: &ArrayBase<S1, Ix2>,
+    second: &ArrayBase<S2, Ix2>,
+) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive + 'static,
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    if first.cols() != second.cols() {
+        return Err(ShapeMismatch {
+            first_shape: first.shape().to_vec(),
+            second_shape: second.shape().to_vec(),
+        }
+        .into());
+    }
+    let (n1, n2, p) = (first.rows(), second.rows(), first.cols());
+    if n1 == 0 || n2 == 0 || n1 + n2 <= p + 1 {
+        return Err(HypothesisTestError::NotEnoughObservations);
+    }
+    let difference = first.mean_axis(Axis(0)) - second.mean_axis(Axis(0));
+    // With `ddof = n - 1`, `cov` returns the scatter matrix of a sample.
+    let scatter: Array2<A> = first.t().cov(to_a(n1 - 1)) + second.t().cov(to_a(n2 - 1));
+    let (n1, n2, p) = (to_a::<A>(n1), to_a::<A>(n2), to_a::<A>(p));
+    let two = A::one() + A::one();
+    let pooled_covariance = scatter.mapv(|x| x / (n1 + n2 - two));
+    let t2 = n1 * n2 / (n1 + n2) * inverse_quadratic_form(pooled_covariance, &difference)?;
+    let f = (n1 + n2 - p - A::one()) / (p * (n1 + n2 - two)) * t2;
+    Ok(TestResult {
+        statistic: t2,
+        p_value: f_sf(f, p, n1 + n2 - p - A::one()),
+    })
+}
+
+/// Returns `xᵀ M⁻¹ x` for the symmetric positive definite matrix `matrix`.
+fn inverse_quadratic_form<A>(matrix: Array2<A>, x: &Array1<A>) -> Result<A, HypothesisTestError>
+where
+    A: Float + FromPrimitive + 'static,
+{
+    let (eigenvalues, eigenvectors) = symmetric_eigen(matrix);
+    if is_singular(&eigenvalues) {
+        return Err(HypothesisTestError::SingularCovariance);
+    }
+    let projections = eigenvectors.t().dot(x);
+    Ok(projections
+        .iter()
+        .zip(&eigenvalues)
+        .fold(A::zero(), |acc, (&y, &lambda)| acc + y * y / lambda))
+}
+
+/// Runs `test` on `xs` and `ys`, or returns a NaN result if one of them
+/// contains NaN.
+fn bivariate<A, F>(xs: Vec<A>, ys: Vec<A>, test: F) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float,
+    F: FnOnce(&[A], &[A]) -> Result<TestResult<A>, HypothesisTestError>,
+{
+    if xs.iter().chain(&ys).any(|x| x.is_nan()) {
+        // Check the number of observations all the same.
+        let (xs, ys) = (vec![A::zero(); xs.len()], vec![A::one(); ys.len()]);
+        test(&xs, &ys).map(|_| nan_result())
+    } else {
+        test(&xs, &ys)
+    }
+}
+
+fn nan_result<A: Float>() -> TestResult<A> {
+    TestResult {
+        statistic: A::nan(),
+        p_value: A::nan(),
+    }
+}
+
+fn without_nan<A, S>(a: &ArrayBase<S, Ix1>) -> Vec<A>
+where
+    A: Float,
+    S: Data<Elem = A>,
+{
+    a.iter().cloned().filter(|x| !x.is_nan()).collect()
+}
+
+fn check_same_len<A, S1, S2>(
+    first: &ArrayBase<S1, Ix1>,
+    second: &ArrayBase<S2, Ix1>,
+) -> Result<(), ShapeMismatch>
+where
+    S1: Data<Elem = A>,
+    S2: Data<Elem = A>,
+{
+    if first.len() == second.len() {
+        Ok(())
+    } else {
+        Err(ShapeMismatch {
+            first_shape: first.shape().to_vec(),
+            second_shape: second.shape().to_vec(),
+        })
+    }
+}
+
+/// Returns the sample mean and the sample variance (with `ddof = 1`) of `xs`.
+fn mean_var<A: Float + FromPrimitive>(xs: &[A]) -> (A, A) {
+    let n = to_a::<A>(xs.len());
+    let mean = xs.iter().fold(A::zero(), |acc, &x| acc + x) / n;
+    let sum_sq_dev = xs
+        .iter()
+        .fold(A::zero(), |acc, &x| acc + (x - mean) * (x - mean));
+    (mean, sum_sq_dev / (n - A::one()))
+}
+
+fn t_one_sample<A>(xs: &[A], mean: A) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+{
+    if xs.len() < 2 {
+        return Err(HypothesisTestError::NotEnoughObservations);
+    }
+    let n = to_a::<A>(xs.len());
+    let (sample_mean, var) = mean_var(xs);
+    let t = (sample_mean - mean) / (var / n).sqrt();
+    Ok(TestResult {
+        statistic: t,
+        p_value: t_two_sided_sf(t, n - A::one()),
+    })
+}
+
+fn student_t<A>(xs: &[A], ys: &[A]) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+{
+    if xs.is_empty() || ys.is_empty() || xs.len() + ys.len() < 3 {
+        return Err(HypothesisTestError::NotEnoughObservations);
+    }
+    let (n1, n2) = (to_a::<A>(xs.len()), to_a::<A>(ys.len()));
+    // The variance of a single observation is NaN, but it doesn't contribute
+    // to the pooled variance.
+    let scatter = |n: A, var: A| {
+        if n > A::one() {
+            (n - A::one()) * var
+        } else {
+            A::zero()
+        }
+    };
+    let ((mean1, var1), (mean2, var2)) = (mean_var(xs), mean_var(ys));
+    let df = n1 + n2 - A::one() - A::one();
+    let pooled_var = (scatter(n1, var1) + scatter(n2, var2)) / df;
+    let t = (mean1 - mean2) / (pooled_var * (n1.recip() + n2.recip())).sqrt();
+    Ok(TestResult {
+        statistic: t,
+        p_value: t_two_sided_sf(t, df),
+    })
+}
+
+fn welch_t<A>(xs: &[A], ys: &[A]) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+{
+    if xs.len() < 2 || ys.len() < 2 {
+        return Err(HypothesisTestError::NotEnoughObservations);
+    }
+    let (n1, n2) = (to_a::<A>(xs.len()), to_a::<A>(ys.len()));
+    let ((mean1, var1), (mean2, var2)) = (mean_var(xs), mean_var(ys));
+    let (se1, se2) = (var1 / n1, var2 / n2);
+    let t = (mean1 - mean2) / (se1 + se2).sqrt();
+    let df =
+        (se1 + se2) * (se1 + se2) / (se1 * se1 / (n1 - A::one()) + se2 * se2 / (n2 - A::one()));
+    Ok(TestResult {
+        statistic: t,
+        p_value: t_two_sided_sf(t, df),
+    })
+}
+
+fn ks<A>(xs: &[A], ys: &[A]) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+{
+    let (first, second) = match (
+        Ecdf::new(&ArrayView1::from(xs)),
+        Ecdf::new(&ArrayView1::from(ys)),
+    ) {
+        (Ok(first), Ok(second)) => (first, second),
+        _ => return Err(HypothesisTestError::NotEnoughObservations),
+    };
+    let distance = first.ks_distance(&second);
+    let (n1, n2) = (to_a::<A>(xs.len()), to_a::<A>(ys.len()));
+    let sqrt_n = (n1 * n2 / (n1 + n2)).sqrt();
+    let lambda =
+        (sqrt_n + A::from_f64(0.12).unwrap() + A::from_f64(0.11).unwrap() / sqrt_n) * distance;
+    Ok(TestResult {
+        statistic: distance,
+        p_value: kolmogorov_sf(lambda),
+    })
+}
+
+fn mann_whitney<A>(xs: &[A], ys: &[A]) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+{
+    if xs.is_empty() || ys.is_empty() {
+        return Err(HypothesisTestError::NotEnoughObservations);
+    }
+    // Each observation is paired with whether it belongs to the first sample.
+    let mut pooled: Vec<(A, bool)> = xs
+        .iter()
+        .map(|&x| (x, true))
+        .chain(ys.iter().map(|&y| (y, false)))
+        .collect();
+    pooled.sort_unstable_by(|a, b| {
+        a.0.partial_cmp(&b.0)
+            .expect("NaN values have been excluded.")
+    });
+    let mut rank_sum = A::zero();
+    // ∑ (t³ - t) over the groups of `t` tied observations.
+    let mut ties = A::zero();
+    let mut start = 0;
+    while start < pooled.len() {
+        let value = pooled[start].0;
+        let end = start
+            + pooled[start..]
+                .iter()
+                .take_while(|(x, _)| *x == value)
+                .count();
+        // The tied observations span the ranks `start + 1..=end`.
+        let rank = to_a::<A>(start + 1 + end) / (A::one() + A::one());
+        let in_first = pooled[start..end]
+            .iter()
+            .filter(|(_, first)| *first)
+            .count();
+        rank_sum = rank_sum + rank * to_a(in_first);
+        let t = to_a::<A>(end - start);
+        ties = ties + t * t * t - t;
+        start = end;
+    }
+    let (n1, n2) = (to_a::<A>(xs.len()), to_a::<A>(ys.len()));
+    let (n, two) = (n1 + n2, A::one() + A::one());
+    let u = rank_sum - n1 * (n1 + A::one()) / two;
+    let mean = n1 * n2 / two;
+    let var = n1 * n2 / to_a(12) * (n + A::one() - ties / (n * (n - A::one())));
+    let z = ((u - mean).abs() - A::one() / two).max(A::zero()) / var.sqrt();
+    Ok(TestResult {
+        statistic: u,
+        p_value: normal_two_sided_sf(z),
+    })
+}
+
+fn chi_square<A>(observed: &[A], expected: &[A]) -> Result<TestResult<A>, HypothesisTestError>
+where
+    A: Float + FromPrimitive,
+{
+    if observed.len() < 2 {
+        return Err(HypothesisTestError::NotEnoughObservations);
+    }
+    let chi2 = observed
+        .iter()
+        .zip(expected)
+        .fold(A::zero(), |acc, (&o, &e)| acc + (o - e) * (o - e) / e);
+    Ok(TestResult {
+        statistic: chi2,
+        p_value: chi_square_sf(chi2, to_a(observed.len() - 1)),
+    })
+}
+
+fn to_a<A: FromPrimitive>(n: usize) -> A {
+    A::from_usize(n).expect("Converting number of observations to `A` must not fail.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use ndarray::array;
+    use std::f64;
+
+    #[test]
+    fn test_hotelling_t2_one_sample_in_one_dimension_is_squared_t_test() {
+        // With a single variable, T² is the square of the t statistic and
+        // the F distribution of T² is the one of t².
+        let sample = array![[5.1], [4.9], [5.6], [5.8], [6.0], [5.2]];
+        let result = hotelling_t2_one_sample(&sample, &array![5.]).unwrap();
+        let n = 6.;
+        let mean = sample.sum() / n;
+        let var = sample.mapv(|x| (x - mean) * (x - mean)).sum() / (n - 1.);
+        let t = (mean - 5.) / (var / n).sqrt();
+        assert_abs_diff_eq!(result.statistic, t * t, epsilon = 1e-10);
+        assert_abs_diff_eq!(result.p_value, f_sf(t * t, 1., n - 1.), epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_hotelling_t2_known_values() {
+        let first = array![[2.1, 0.9], [1.8, 1.2], [2.3, 1.1], [1.9, 0.7], [2.4, 1.3]];
+        let second = array![[2.9, 1.0], [3.1, 1.3], [2.6, 0.8], [3.3, 1.2]];
+        // T² computed by hand with the pooled covariance matrix.
+        let result = hotelling_t2(&first, &second).unwrap();
+        assert_abs_diff_eq!(result.statistic, 36.210_905_962_543_83, epsilon = 1e-9);
+        let f = 6. / 14. * result.statistic;
+        assert_abs_diff_eq!(result.p_value, f_sf(f, 2., 6.), epsilon = 1e-14);
+        // Swapping the samples doesn't change anything.
+        assert_eq!(
+            hotelling_t2(&second, &first).unwrap().statistic,
+            result.statistic
+        );
+    }
+
+    #[test]
+    fn test_hotelling_t2_errors() {
+        let sample = array![[1., 2.], [2., 1.]];
+        assert_eq!(
+            hotelling_t2_one_sample(&sample, &array![0., 0.]),
+            Err(HypothesisTestError::NotEnoughObservations)
+        );
+        assert!(hotelling_t2_one_sample(&sample, &array![0.]).is_err());
+        let collinear = array![[1., 2.], [2., 4.], [3., 6.], [4., 8.]];
+        assert_eq!(
+            hotelling_t2_one_sample(&collinear, &array![0., 0.]),
+            Err(HypothesisTestError::SingularCovariance)
+        );
+        assert_eq!(
+            hotelling_t2(&sample, &Array2::zeros((0, 2))),
+            Err(HypothesisTestError::NotEnoughObservations)
+        );
+    }
+
+    #[test]
+    fn test_t_test_one_sample_known_value() {
+        // With one degree of freedom, the t distribution is the Cauchy one.
+        let result = t_test_one_sample(&array![1., 3.], 0.).unwrap();
+        assert_abs_diff_eq!(result.statistic, 2., epsilon = 1e-14);
+        let p = 1. - 2. / std::f64::consts::PI * 2f64.atan();
+        assert_abs_diff_eq!(result.p_value, p, epsilon = 1e-12);
+        assert_eq!(
+            t_test_one_sample(&array![1.], 0.),
+            Err(HypothesisTestError::NotEnoughObservations)
+        );
+    }
+
+    #[test]
+    fn test_two_sample_t_tests() {
+        let first = array![5.1, 4.9, 5.6, 5.8, 6.0, 5.2];
+        let second = array![4.1, 5.0, 4.6, 4.4, 4.8, 4.5];
+        // With samples of equal sizes, the statistics are the same.
+        let student = t_test(&first, &second).unwrap();
+        let welch = welch_t_test(&first, &second).unwrap();
+        assert_abs_diff_eq!(student.statistic, welch.statistic, epsilon = 1e-12);
+        let (mean1, var1) = mean_var(first.as_slice().unwrap());
+        let (mean2, var2) = mean_var(second.as_slice().unwrap());
+        let t = (mean1 - mean2) / ((var1 + var2) / 6.).sqrt();
+        assert_abs_diff_eq!(student.statistic, t, epsilon = 1e-12);
+        assert_abs_diff_eq!(student.p_value, t_two_sided_sf(t, 10.), epsilon = 1e-14);
+        let df = 5. * (var1 + var2).powi(2) / (var1 * var1 + var2 * var2);
+        assert_abs_diff_eq!(welch.p_value, t_two_sided_sf(t, df), epsilon = 1e-14);
+        // A single observation in one of the samples is enough for Student's
+        // test, but not for Welch's.
+        assert!(t_test(&array![1.], &array![2., 3.]).is_ok());
+        assert_eq!(
+            welch_t_test(&array![1.], &array![2., 3.]),
+            Err(HypothesisTestError::NotEnoughObservations)
+        );
+    }
+
+    #[test]
+    fn test_ks_test() {
+        let first = array![1., 2., 3., 4.];
+        let second = array![3., 4., 5., 6.];
+        let result = ks_test(&first, &second).unwrap();
+        assert_eq!(result.statistic, 0.5);
+        let sqrt_n = 2f64.sqrt();
+        let lambda = (sqrt_n + 0.12 + 0.11 / sqrt_n) * 0.5;
+        assert_eq!(result.p_value, kolmogorov_sf(lambda));
+        assert_eq!(ks_test(&first, &first).unwrap().p_value, 1.);
+        assert_eq!(
+            ks_test(&first, &Array1::zeros(0)),
+            Err(HypothesisTestError::NotEnoughObservations)
+        );
+    }
+
+    #[test]
+    fn test_mann_whitney_u_known_values() {
+        let result = mann_whitney_u(&array![1., 2., 3.], &array![4., 5., 6.]).unwrap();
+        assert_eq!(result.statistic, 0.);
+        assert_abs_diff_eq!(result.p_value, 0.080_855_598_370_052_1, epsilon = 1e-12);
+        // Ties: the pooled ranks are 1, 2.5, 2.5, 4.5, 4.5, 6.
+        let result = mann_whitney_u(&array![1., 2., 3.], &array![2., 3., 4.]).unwrap();
+        assert_eq!(result.statistic, 2.);
+        let var = 9. / 12. * (7. - 12. / 30.);
+        let z = (2.5 - 0.5) / f64::sqrt(var);
+        assert_abs_diff_eq!(result.p_value, normal_two_sided_sf(z), epsilon = 1e-14);
+        // U is the number of pairs where the first observation is larger.
+        let swapped = mann_whitney_u(&array![2., 3., 4.], &array![1., 2., 3.]).unwrap();
+        assert_eq!(swapped.statistic, 7.);
+        assert_eq!(swapped.p_value, result.p_value);
+    }
+
+    #[test]
+    fn test_chi_square_test_known_value() {
+        let observed = array![16., 18., 16., 14., 12., 12.];
+        let result = chi_square_test(&observed, &Array1::from_elem(6, 88. / 6.)).unwrap();
+        assert_abs_diff_eq!(result.statistic, 2., epsilon = 1e-14);
+        assert_abs_diff_eq!(result.p_value, 0.849_145_036_084_609_8, epsilon = 1e-12);
+        assert!(chi_square_test(&observed, &array![1., 2.]).is_err());
+        assert_eq!(
+            chi_square_test(&array![1.], &array![1.]),
+            Err(HypothesisTestError::NotEnoughObservations)
+        );
+    }
+
+    #[test]
+    fn test_nan_handling() {
+        let first = array![1.2, f64::NAN, 0.4, 2.2, 1.5];
+        let clean_first = array![1.2, 0.4, 2.2, 1.5];
+        let second = array![2.8, 3.5, f64::NAN, 2.1];
+        let clean_second = array![2.8, 3.5, 2.1];
+        type Test = fn(&Array1<f64>, &Array1<f64>) -> Result<TestResult<f64>, HypothesisTestError>;
+        let tests: [(Test, Test); 4] = [
+            (t_test, t_test_skipnan),
+            (welch_t_test, welch_t_test_skipnan),
+            (ks_test, ks_test_skipnan),
+            (mann_whitney_u, mann_whitney_u_skipnan),
+        ];
+        for (test, test_skipnan) in tests.iter() {
+            let result = test(&first, &second).unwrap();
+            assert!(result.statistic.is_nan() && result.p_value.is_nan());
+            assert_eq!(
+                test_skipnan(&first, &second),
+                test(&clean_first, &clean_second)
+            );
+        }
+        assert!(t_test_one_sample(&first, 1.).unwrap().p_value.is_nan());
+        assert_eq!(
+            t_test_one_sample_skipnan(&first, 1.),
+            t_test_one_sample(&clean_first, 1.)
+        );
+        assert!(chi_square_test(&first, &first).unwrap().p_value.is_nan());
+        assert_eq!(
+            chi_square_test_skipnan(&first, &array![1., 1., f64::NAN, 2., 1.]),
+            chi_square_test(&array![1.2, 2.2, 1.5], &array![1., 2., 1.])
+        );
+        // The NaN values still count as observations for the errors.
+        assert_eq!(
+            welch_t_test(&array![f64::NAN], &array![1., 2.]),
+            Err(HypothesisTestError::NotEnoughObservations)
+        );
+    }
+}
@@ -0,0 +1,535 @@
+//! Kernel density estimation.
+//!
+//! A kernel density estimate (KDE) is a smooth estimate of the probability
+//! density function of the distribution a sample was drawn from: the
+//! average of kernels (Gaussian by default) centered on the observations,
+//! whose spread is given by a bandwidth matrix. It is the smooth counterpart
+//! of a [histogram](../histogram/index.html).
+use crate::errors::{KdeError, ShapeMismatch};
+use crate::linalg::{is_singular, symmetric_eigen};
+use crate::CorrelationExt;
+use ndarray::{Array1, Array2, ArrayBase, ArrayD, Axis, Data, Ix1, Ix2, IxDyn};
+use num_traits::{Float, FromPrimitive};
+
+/// Rule of thumb used to scale the covariance matrix of the sample into a
+/// bandwidth matrix.
+///
+/// With `n` observations in `d` dimensions, the bandwidth matrix is the
+/// covariance matrix multiplied by the square of a factor, which is
+/// `n^(-1 / (d + 4))` for Scott's rule and `(n (d + 2) / 4)^(-1 / (d + 4))`
+/// for Silverman's rule (the factors of SciPy's `gaussian_kde`). Both are
+/// optimal for normally distributed data, and oversmooth multimodal data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BandwidthRule {
+    /// Scott's rule of thumb.
+    Scott,
+    /// Silverman's rule of thumb.
+    Silverman,
+}
+
+/// The bandwidth matrix of a [`KernelDensityEstimate`]: the covariance
+/// matrix of each Gaussian kernel, which also scales the other kernels.
+///
+/// [`KernelDensityEstimate`]: struct.KernelDensityEstimate.html
+#[derive(Clone, Debug, PartialEq)]
+pub enum Bandwidth<A> {
+    /// The covariance matrix of the sample, scaled by a rule of thumb. The
+    /// kernels have the same orientation as the data.
+    Full(BandwidthRule),
+    /// The diagonal of the covariance matrix of the sample (the variances of
+    /// the features), scaled by a rule of thumb. The kernels are aligned with
+    /// the axes.
+    Diagonal(BandwidthRule),
+    /// An explicit `(d, d)` symmetric positive definite matrix.
+    Matrix(Array2<A>),
+}
+
+/// The shape of the kernels of a [`KernelDensityEstimate`].
+///
+/// Let `H` be the bandwidth matrix and `r² = (x - xᵢ)ᵀ H⁻¹ (x - xᵢ)` the
+/// squared Mahalanobis distance between a point `x` and an observation
+/// `xᵢ`. The kernel centered on `xᵢ` is proportional to:
+///
+/// - `exp(-r² / 2)` for the Gaussian kernel;
+/// - `1 - r²` for the Epanechnikov kernel, and zero when `r ≥ 1`;
+/// - a constant for the tophat kernel, and zero when `r ≥ 1`.
+///
+/// The same bandwidth gives the compact kernels a much smaller spread than
+/// the Gaussian kernel (as in scikit-learn's `KernelDensity`). The
+/// estimated density is zero at the points farther than `r = 1` from all
+/// the observations.
+///
+/// [`KernelDensityEstimate`]: struct.KernelDensityEstimate.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kernel {
+    /// The Gaussian kernel (the default).
+    Gaussian,
+    /// The Epanechnikov kernel, optimal in the mean squared error sense.
+    Epanechnikov,
+    /// The tophat (uniform) kernel.
+    Tophat,
+}
+
+/// A multivariate kernel density estimate.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::kde::{Bandwidth, BandwidthRule, KernelDensityEstimate};
+///
+/// let sample = array![[0., 0.1], [0.2, -0.1], [-0.1, 0.], [3., 3.1], [2.9, 3.]];
+/// let kde = KernelDensityEstimate::new(&sample, Bandwidth::Full(BandwidthRule::Scott)).unwrap();
+/// let density = kde.evaluate(&array![[0., 0.], [1.5, 1.5], [-5., 5.]]).unwrap();
+/// assert!(density[0] > density[1] && density[1] > density[2]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct KernelDensityEstimate<A> {
+    bandwidth: Array2<A>,
+    // Maps (row) vectors to coordinates in which the kernels are standard
+    // normal distributions.
+    transform: Array2<A>,
+    // The observations, in the coordinates given by `transform`.
+    whitened: Array2<A>,
+    kernel: Kernel,
+    // The logarithm of the normalization constant of the estimate, without
+    // the normalization constant of the kernel.
+    log_normalization: A,
+}
+
+impl<A> KernelDensityEstimate<A>
+where
+    A: Float + FromPrimitive + 'static,
+{
+    /// Returns the kernel density estimate of `sample`, a `(n, d)` array of
+    /// `n` observations (rows) in `d` dimensions, with Gaussian kernels
+    /// whose covariance matrix is given by `bandwidth`.
+    ///
+    /// Returns `Err(KdeError::NotEnoughObservations)` if `sample` has fewer
+    /// than two observations and the bandwidth is computed from a rule of
+    /// thumb, or if `sample` is empty; `Err(KdeError::ShapeMismatch)` if an
+    /// explicit bandwidth matrix is not `(d, d)`; and
+    /// `Err(KdeError::SingularBandwidth)` if the bandwidth matrix is not
+    /// (numerically) positive definite, e.g. if a feature of the sample is
+    /// constant.
+    pub fn new<S>(sample: &ArrayBase<S, Ix2>, bandwidth: Bandwidth<A>) -> Result<Self, KdeError>
+    where
+        S: Data<Elem = A>,
+    {
+        let (n, d) = (sample.rows(), sample.cols());
+        let bandwidth = match bandwidth {
+            Bandwidth::Full(_) | Bandwidth::Diagonal(_) if n < 2 => {
+                return Err(KdeError::NotEnoughObservations);
+            }
+            Bandwidth::Full(rule) => {
+                let factor = rule_factor(rule, n, d);
+                sample.t().cov(A::one()).mapv(|x| x * factor * factor)
+            }
+            Bandwidth::Diagonal(rule) => {
+                let factor = rule_factor(rule, n, d);
+                let covariance = sample.t().cov(A::one());
+                Array2::from_shape_fn((d, d), |(i, j)| {
+                    if i == j {
+                        covariance[(i, i)] * factor * factor
+                    } else {
+                        A::zero()
+                    }
+                })
+            }
+            Bandwidth::Matrix(matrix) => {
+                if matrix.shape() != [d, d] {
+                    return Err(ShapeMismatch {
+                        first_shape: vec![d, d],
+                        second_shape: matrix.shape().to_vec(),
+                    }
+                    .into());
+                }
+                if n == 0 {
+                    return Err(KdeError::NotEnoughObservations);
+                }
+                matrix
+            }
+        };
+        let (eigenvalues, eigenvectors) = symmetric_eigen(bandwidth.clone());
+        if is_singular(&eigenvalues) || eigenvalues.iter().any(|&x| x <= A::zero()) {
+            return Err(KdeError::SingularBandwidth);
+        }
+        let transform = &eigenvectors / &eigenvalues.mapv(|x| x.sqrt());
+        let whitened = sample.dot(&transform);
+        let half = A::from_f64(0.5).expect("Converting 0.5 to `A` must not fail.");
+        let log_normalization = -to_a::<A>(n).ln() - half * eigenvalues.mapv(|x| x.ln()).sum();
+        Ok(KernelDensityEstimate {
+            bandwidth,
+            transform,
+            whitened,
+            kernel: Kernel::Gaussian,
+            log_normalization,
+        })
+    }
+
+    /// Returns the kernel density estimate of a 1-dimensional sample, as
+    /// [`new`](#method.new) for the `(n, 1)` array of the observations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::kde::{Bandwidth, BandwidthRule, Kernel, KernelDensityEstimate};
+    ///
+    /// let sample = array![1., 1.5, 2., 8.];
+    /// let kde = KernelDensityEstimate::from_1d(&sample, Bandwidth::Full(BandwidthRule::Silverman))
+    ///     .unwrap()
+    ///     .with_kernel(Kernel::Epanechnikov);
+    /// let points = array![1.5, 5., 20.];
+    /// let density = kde.evaluate(&points.insert_axis(Axis(1))).unwrap();
+    /// assert!(density[0] > density[1]);
+    /// assert_eq!(density[2], 0.);
+    /// ```
+    pub fn from_1d<S>(sample: &ArrayBase<S, Ix1>, bandwidth: Bandwidth<A>) -> Result<Self, KdeError>
+    where
+        S: Data<Elem = A>,
+    {
+        Self::new(&sample.view().insert_axis(Axis(1)), bandwidth)
+    }
+
+    /// Returns the estimate with the given kernel instead of the current one
+    /// (the Gaussian kernel for a new estimate), keeping the bandwidth.
+    pub fn with_kernel(self, kernel: Kernel) -> Self {
+        KernelDensityEstimate { kernel, ..self }
+    }
+
+    /// Returns the kernel of the estimate.
+    pub fn kernel(&self) -> Kernel {
+        self.kernel
+    }
+
+    /// Returns the number of dimensions of the sample.
+    pub fn ndim(&self) -> usize {
+        self.bandwidth.rows()
+    }
+
+    /// Returns the bandwidth matrix: the covariance matrix of the kernels.
+    pub fn bandwidth(&self) -> &Array2<A> {
+        &self.bandwidth
+    }
+
+    /// Returns the estimated density at each row of `points`, a `(m, d)`
+    /// array of `m` query points.
+    ///
+    /// Evaluating the estimate takes `O(m n d)` time for `n` observations.
+    ///
+    /// Returns `Err(ShapeMismatch)` if the number of columns of `points` is
+    /// not the number of dimensions of the sample.
+    pub fn evaluate<S>(&self, points: &ArrayBase<S, Ix2>) -> Result<Array1<A>, ShapeMismatch>
+    where
+        S: Data<Elem = A>,
+    {
+        Ok(self.log_evaluate(points)?.mapv(A::exp))
+    }
+
+    /// Returns the logarithm of the estimated density at each row of
+    /// `points`.
+    ///
+    /// Far from the sample, the density underflows to zero while its
+    /// logarithm stays accurate, which makes it better suited to scoring
+    /// anomalies (low log-densities).
+    ///
+    /// Returns `Err(ShapeMismatch)` if the number of columns of `points` is
+    /// not the number of dimensions of the sample.
+    pub fn log_evaluate<S>(&self, points: &ArrayBase<S, Ix2>) -> Result<Array1<A>, ShapeMismatch>
+    where
+        S: Data<Elem = A>,
+    {
+        if points.cols() != self.ndim() {
+            return Err(ShapeMismatch {
+                first_shape: vec![points.rows(), self.ndim()],
+                second_shape: points.shape().to_vec(),
+            });
+        }
+        let log_normalization =
+            self.log_normalization + log_kernel_normalization(self.kernel, self.ndim());
+        let queries = points.dot(&self.transform);
+        Ok(queries
+            .outer_iter()
+            .map(|query| {
+                let exponents: Vec<A> = self
+                    .whitened
+                    .outer_iter()
+                    .map(|observation| {
+                        let squared_distance = query
+                            .iter()
+                            .zip(&observation)
+                            .fold(A::zero(), |acc, (&q, &x)| acc + (q - x) * (q - x));
+                        log_kernel(self.kernel, squared_distance)
+                    })
+                    .collect();
+                log_sum_exp(&exponents) + log_normalization
+            })
+            .collect())
+    }
+
+    /// Returns the estimated density on the grid spanned by `coordinates`,
+    /// which holds the coordinates of the grid points along each of the `d`
+    /// dimensions.
+    ///
+    /// The result has shape `(coordinates[0].len(), …,
+    /// coordinates[d - 1].len())`, e.g. a matrix ready to be plotted as a
+    /// heatmap in two dimensions.
+    ///
+    /// Returns `Err(ShapeMismatch)` if the length of `coordinates` is not the
+    /// number of dimensions of the sample.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1};
+    /// use ndarray_stats::kde::{Bandwidth, KernelDensityEstimate};
+    ///
+    /// let sample = array![[0., 0.], [1., 1.]];
+    /// let kde = KernelDensityEstimate::new(&sample, Bandwidth::Matrix(array![[0.5, 0.], [0., 0.5]]))
+    ///     .unwrap();
+    /// let xs = Array1::linspace(-1., 2., 4);
+    /// let ys = Array1::linspace(-1., 2., 7);
+    /// let density = kde.evaluate_grid(&[xs, ys]).unwrap();
+    /// assert_eq!(density.shape(), &[4, 7]);
+    /// ```
+    pub fn evaluate_grid<S>(
+        &self,
+        coordinates: &[ArrayBase<S, Ix1>],
+    ) -> Result<ArrayD<A>, ShapeMismatch>
+    where
+        S: Data<Elem = A>,
+    {
+        if coordinates.len() != self.ndim() {
+            return Err(ShapeMismatch {
+                first_shape: vec![self.ndim()],
+                second_shape: vec![coordinates.len()],
+            });
+        }
+        let shape: Vec<usize> = coordinates.iter().map(|c| c.len()).collect();
+        let n_points = shape.iter().product();
+        // The grid points in row-major order: the last coordinate varies
+        // fastest.
+        let points = Array2::from_shape_fn((n_points, self.ndim()), |(i, j)| {
+            let stride: usize = shape[j + 1..].iter().product();
+            coordinates[j][(i / stride) % shape[j]]
+        });
+        let density = self.evaluate(&points)?;
+        Ok(ArrayD::from_shape_vec(IxDyn(&shape), density.to_vec())
+            .expect("The grid has as many points as the product of its shape."))
+    }
+}
+
+fn rule_factor<A: Float + FromPrimitive>(rule: BandwidthRule, n: usize, d: usize) -> A {
+    let (n, d) = (n as f64, d as f64);
+    let factor = match rule {
+        BandwidthRule::Scott => n.powf(-1. / (d + 4.)),
+        BandwidthRule::Silverman => (n * (d + 2.) / 4.).powf(-1. / (d + 4.)),
+    };
+    A::from_f64(factor).expect("Converting the bandwidth factor to `A` must not fail.")
+}
+
+/// Returns the logarithm of the unnormalized kernel at squared distance
+/// `r2` (in whitened coordinates).
+fn log_kernel<A: Float + FromPrimitive>(kernel: Kernel, r2: A) -> A {
+    match kernel {
+        Kernel::Gaussian => -r2 / (A::one() + A::one()),
+        Kernel::Epanechnikov if r2 < A::one() => (A::one() - r2).ln(),
+        Kernel::Tophat if r2 < A::one() => A::zero(),
+        Kernel::Epanechnikov | Kernel::Tophat => A::neg_infinity(),
+    }
+}
+
+/// Returns the logarithm of the normalization constant of the kernel in `d`
+/// dimensions, which makes it integrate to one in whitened coordinates.
+fn log_kernel_normalization<A: Float + FromPrimitive>(kernel: Kernel, d: usize) -> A {
+    use core::f64::consts::PI;
+    // The volume of the unit ball: `V₀ = 1`, `V₁ = 2` and `V_d = 2π / d V_{d - 2}`.
+    let unit_ball_volume = (1..=d).rev().step_by(2).fold(1., |volume, k| {
+        if k == 1 {
+            2. * volume
+        } else {
+            2. * PI / k as f64 * volume
+        }
+    });
+    let d = d as f64;
+    let log_normalization = match kernel {
+        Kernel::Gaussian => -0.5 * d * (2. * PI).ln(),
+        Kernel::Epanechnikov => ((d + 2.) / (2. * unit_ball_volume)).ln(),
+        Kernel::Tophat => -unit_ball_volume.ln(),
+    };
+    A::from_f64(log_normalization)
+        .expect("Converting the normalization constant to `A` must not fail.")
+}
+
+/// Returns `ln(∑ exp(x))`, without overflowing or underflowing.
+fn log_sum_exp<A: Float>(xs: &[A]) -> A {
+    let max = xs.iter().fold(A::neg_infinity(), |acc, &x| acc.max(x));
+    if max == A::neg_infinity() {
+        return max;
+    }
+    max + xs
+        .iter()
+        .fold(A::zero(), |acc, &x| acc + (x - max).exp())
+        .ln()
+}
+
+fn to_a<A: FromPrimitive>(n: usize) -> A {
+    A::from_usize(n).expect("Converting a number of observations to `A` must not fail.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use ndarray::{array, s, Array};
+    use std::f64;
+    use std::f64::consts::PI;
+
+    fn normal_pdf(x: f64, mean: f64, var: f64) -> f64 {
+        (-(x - mean) * (x - mean) / (2. * var)).exp() / (2. * PI * var).sqrt()
+    }
+
+    #[test]
+    fn test_one_dimensional_kde() {
+        let sample = array![[1.], [2.], [4.], [7.]];
+        // The variance is 7.0, and Scott's factor is 4^(-1/5).
+        let kde =
+            KernelDensityEstimate::new(&sample, Bandwidth::Full(BandwidthRule::Scott)).unwrap();
+        let var = 7. * 4f64.powf(-0.4);
+        assert_abs_diff_eq!(kde.bandwidth()[(0, 0)], var, epsilon = 1e-12);
+        let points = array![[0.], [3.], [10.]];
+        let density = kde.evaluate(&points).unwrap();
+        for (&x, &density) in points.iter().zip(&density) {
+            let expected = sample.iter().map(|&m| normal_pdf(x, m, var)).sum::<f64>() / 4.;
+            assert_abs_diff_eq!(density, expected, epsilon = 1e-14);
+        }
+        let silverman =
+            KernelDensityEstimate::new(&sample, Bandwidth::Full(BandwidthRule::Silverman)).unwrap();
+        assert_abs_diff_eq!(
+            silverman.bandwidth()[(0, 0)],
+            7. * (4f64 * 3. / 4.).powf(-0.4),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_diagonal_bandwidth_is_product_of_kernels() {
+        let sample = array![[0., 1.], [1., 3.], [2., 2.], [4., 5.]];
+        let kde =
+            KernelDensityEstimate::new(&sample, Bandwidth::Diagonal(BandwidthRule::Silverman))
+                .unwrap();
+        let h = kde.bandwidth().clone();
+        assert_eq!(h[(0, 1)], 0.);
+        let point = array![[1.5, 2.5]];
+        let expected = sample
+            .outer_iter()
+            .map(|x| normal_pdf(1.5, x[0], h[(0, 0)]) * normal_pdf(2.5, x[1], h[(1, 1)]))
+            .sum::<f64>()
+            / 4.;
+        assert_abs_diff_eq!(kde.evaluate(&point).unwrap()[0], expected, epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_density_integrates_to_one() {
+        let sample = array![[0., 0.], [1., 0.5], [0.5, 2.], [2., 1.5], [1., 1.]];
+        let kde =
+            KernelDensityEstimate::new(&sample, Bandwidth::Full(BandwidthRule::Scott)).unwrap();
+        let step = 0.05;
+        let xs = Array::range(-6., 8., step);
+        let density = kde.evaluate_grid(&[xs.clone(), xs]).unwrap();
+        assert_abs_diff_eq!(density.sum() * step * step, 1., epsilon = 1e-6);
+        // Grid points are laid out in row-major order.
+        let point = array![[-6. + 3. * step, -6. + 7. * step]];
+        assert_abs_diff_eq!(
+            density[[3, 7]],
+            kde.evaluate(&point).unwrap()[0],
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn test_kernels_integrate_to_one() {
+        let sample = array![[0., 0.], [1., 0.5], [0.5, 2.], [2., 1.5], [1., 1.]];
+        let gaussian =
+            KernelDensityEstimate::new(&sample, Bandwidth::Full(BandwidthRule::Scott)).unwrap();
+        let step = 0.02;
+        let xs = Array::range(-6., 8., step);
+        for &kernel in &[Kernel::Epanechnikov, Kernel::Tophat] {
+            let kde = gaussian.clone().with_kernel(kernel);
+            assert_eq!(kde.kernel(), kernel);
+            let density = kde.evaluate_grid(&[xs.clone(), xs.clone()]).unwrap();
+            assert_abs_diff_eq!(density.sum() * step * step, 1., epsilon = 1e-2);
+            let sample_1d = sample.column(0);
+            let kde = KernelDensityEstimate::from_1d(&sample_1d, Bandwidth::Matrix(array![[0.3]]))
+                .unwrap()
+                .with_kernel(kernel);
+            let density = kde.evaluate_grid(&[xs.view()]).unwrap();
+            assert_abs_diff_eq!(density.sum() * step, 1., epsilon = 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_compact_kernels() {
+        // With a bandwidth of 4, the kernels have a radius of 2.
+        let sample = array![0., 3.];
+        let kde = KernelDensityEstimate::from_1d(&sample, Bandwidth::Matrix(array![[4.]])).unwrap();
+        let epanechnikov = kde.clone().with_kernel(Kernel::Epanechnikov);
+        let tophat = kde.with_kernel(Kernel::Tophat);
+        let points = array![[1.], [2.5], [5.5]];
+        let expected = points.mapv(|x: f64| {
+            sample
+                .iter()
+                .map(|&m| {
+                    let u = (x - m) / 2.;
+                    (0.75 * (1. - u * u) / 2.).max(0.)
+                })
+                .sum::<f64>()
+                / 2.
+        });
+        let density = epanechnikov.evaluate(&points).unwrap();
+        for (&density, &expected) in density.iter().zip(&expected) {
+            assert_abs_diff_eq!(density, expected, epsilon = 1e-14);
+        }
+        let density = tophat.evaluate(&points).unwrap();
+        assert_abs_diff_eq!(density[0], 0.25 / 2., epsilon = 1e-14);
+        assert_abs_diff_eq!(density[1], 0.25 / 2., epsilon = 1e-14);
+        assert_eq!(density[2], 0.);
+        assert_eq!(
+            tophat.log_evaluate(&array![[10.]]).unwrap()[0],
+            f64::NEG_INFINITY
+        );
+    }
+
+    #[test]
+    fn test_log_density_far_from_sample() {
+        let sample = array![[0.], [1.]];
+        let kde = KernelDensityEstimate::new(&sample, Bandwidth::Matrix(array![[1.]])).unwrap();
+        let log_density = kde.log_evaluate(&array![[100.]]).unwrap()[0];
+        // Dominated by the kernel centered on 1.
+        let expected = -0.5 * 99. * 99. - 0.5 * (2. * PI).ln() - 2f64.ln();
+        assert_abs_diff_eq!(log_density, expected, epsilon = 1e-9);
+        assert_eq!(kde.evaluate(&array![[100.]]).unwrap()[0], 0.);
+    }
+
+    #[test]
+    fn test_kde_errors() {
+        let sample = array![[1., 2.], [2., 4.], [3., 6.]];
+        assert_eq!(
+            KernelDensityEstimate::new(&sample, Bandwidth::Full(BandwidthRule::Scott)),
+            Err(KdeError::SingularBandwidth)
+        );
+        assert_eq!(
+            KernelDensityEstimate::new(
+                &sample.slice(s![..1, ..]),
+                Bandwidth::Diagonal(BandwidthRule::Scott)
+            ),
+            Err(KdeError::NotEnoughObservations)
+        );
+        assert!(KernelDensityEstimate::new(&sample, Bandwidth::Matrix(array![[1.]])).is_err());
+        let kde =
+            KernelDensityEstimate::new(&sample, Bandwidth::Diagonal(BandwidthRule::Scott)).unwrap();
+        assert!(kde.evaluate(&array![[1.]]).is_err());
+        assert!(kde.evaluate_grid(&[array![1.]]).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! Reduction kernels shared by the summary statistics.
+//!
+//! Contiguous data is reduced using several independent accumulators. This
+//! breaks the dependency chain between consecutive additions, which lets the
+//! compiler vectorize the loop: it is not allowed to reassociate
+//! floating-point additions on its own.
+use ndarray::{ArrayBase, Axis, Data, Dimension};
+use num_traits::Zero;
+
+/// Number of independent accumulators used by `unrolled_sum_by`.
+const N_ACCUMULATORS: usize = 8;
+
+/// Returns the sum of `f(x)` over all the elements `x` of `xs`.
+pub(crate) fn unrolled_sum_by<A, B, F>(xs: &[A], f: F) -> B
+where
+    B: Copy + Zero,
+    F: Fn(&A) -> B,
+{
+    let mut acc = [B::zero(); N_ACCUMULATORS];
+    let mut chunks = xs.chunks_exact(N_ACCUMULATORS);
+    for chunk in &mut chunks {
+        for (acc, x) in acc.iter_mut().zip(chunk) {
+            *acc = *acc + f(x);
+        }
+    }
+    let rest = chunks
+        .remainder()
+        .iter()
+        .fold(B::zero(), |acc, x| acc + f(x));
+    // Summing the accumulators pairwise also keeps the rounding error low.
+    ((acc[0] + acc[4]) + (acc[1] + acc[5])) + ((acc[2] + acc[6]) + (acc[3] + acc[7])) + rest
+}
+
+/// Returns the sum of `f(x)` over all the elements `x` of `a`.
+///
+/// Contiguous arrays (in any memory order) and arrays whose rows are
+/// contiguous use the unrolled kernel.
+pub(crate) fn sum_by<A, S, D, B, F>(a: &ArrayBase<S, D>, f: F) -> B
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+    B: Copy + Zero,
+    F: Fn(&A) -> B,
+{
+    if let Some(xs) = a.as_slice_memory_order() {
+        return unrolled_sum_by(xs, f);
+    }
+    if a.ndim() == 0 {
+        return a.iter().fold(B::zero(), |acc, x| acc + f(x));
+    }
+    a.genrows().into_iter().fold(B::zero(), |acc, row| {
+        acc + match row.as_slice() {
+            Some(xs) => unrolled_sum_by(xs, &f),
+            None => row.iter().fold(B::zero(), |acc, x| acc + f(x)),
+        }
+    })
+}
+
+/// Returns `true` if the lanes of `a` along `axis` are contiguous in memory
+/// (or have at most one element), so that reducing them one at a time reads
+/// the data in memory order.
+pub(crate) fn lanes_are_contiguous<S, D>(a: &ArrayBase<S, D>, axis: Axis) -> bool
+where
+    S: Data,
+    D: Dimension,
+{
+    a.len_of(axis) <= 1 || a.stride_of(axis).abs() == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{arr0, s, Array, Array1};
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn sum_by_matches_naive_sum(xs: Vec<i64>) -> bool {
+        let a = Array1::from_vec(xs.iter().map(|&x| x % 1000).collect());
+        let naive: i64 = a.iter().sum();
+        sum_by(&a, |&x| x) == naive
+    }
+
+    #[test]
+    fn test_sum_by_non_contiguous() {
+        let a = Array::from_shape_fn((5, 19), |(i, j)| (i * 19 + j) as i64);
+        for view in &[
+            a.slice(s![.., 1..]),
+            a.slice(s![.., ..;2]),
+            a.slice(s![..;-2, ..]),
+            a.t(),
+        ] {
+            let naive: i64 = view.iter().sum();
+            assert_eq!(sum_by(view, |&x| x), naive);
+        }
+        assert_eq!(sum_by(&arr0(3i64), |&x| x), 3);
+    }
+
+    #[test]
+    fn test_lanes_are_contiguous() {
+        let a = Array::<f64, _>::zeros((3, 4));
+        assert!(lanes_are_contiguous(&a, Axis(1)));
+        assert!(!lanes_are_contiguous(&a, Axis(0)));
+        assert!(lanes_are_contiguous(&a.t(), Axis(0)));
+        assert!(lanes_are_contiguous(&a.slice(s![..1, ..]), Axis(0)));
+    }
+}
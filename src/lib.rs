@@ -3,11 +3,51 @@
 //!
 //! Currently available routines include:
 //! - [order statistics] (minimum, maximum, median, quantiles, etc.);
-//! - [summary statistics] (mean, skewness, kurtosis, central moments, etc.)
+//! - [empirical distribution functions] (with the Kolmogorov-Smirnov distance);
+//! - [summary statistics] (`describe`, mean, mode, skewness, kurtosis, central moments, robust estimators of location and scale, etc.)
+//! - [circular statistics] (mean, variance, standard deviation and median of angles);
+//! - [masked statistics] (statistics of the elements selected by a boolean mask);
 //! - [partitioning];
-//! - [correlation analysis] (covariance, pearson correlation);
-//! - [measures from information theory] (entropy, KL divergence, etc.);
-//! - [histogram computation].
+//! - [ranking] (average, minimum, maximum or dense ranks of ties, rank transform to
+//!   empirical quantiles);
+//! - [outlier detection] (Tukey's fences, z-score and modified z-score, masking with NaN);
+//! - [feature scaling] (z-scores, min-max and robust scaling along an axis);
+//! - [correlation analysis] (covariance, Pearson, Spearman and Kendall correlation,
+//!   auto- and cross-correlation);
+//! - [principal component analysis] and [whitening];
+//! - [group-by aggregation] (sums, means and counts of the rows with the same label);
+//! - [measures from information theory] (entropy, KL and Jensen-Shannon divergences,
+//!   [mutual information], differential entropy estimates, etc., in nats, bits or
+//!   hartleys);
+//! - [histogram computation];
+//! - [kernel density estimation] (multivariate, with full or diagonal bandwidth matrices,
+//!   Gaussian, Epanechnikov or tophat kernels);
+//! - [streaming statistics] (mean, variance, extrema and approximate quantiles of data
+//!   that doesn't fit in memory);
+//! - [random sampling] and shuffling of subviews along an axis, [stratified sampling];
+//! - [bootstrap and jackknife] resampling (bias, standard error, percentile and BCa
+//!   confidence intervals);
+//! - [distribution fitting] (maximum likelihood estimates, log-likelihood and AIC);
+//! - [hypothesis tests] (t-tests, Kolmogorov-Smirnov, Mann-Whitney U, chi-square,
+//!   Hotelling's T²);
+//! - [slice-level statistics] (mean, variance, quantiles and histograms of plain slices);
+//! - [time series transforms] (differencing, shifting, rolling windows, exponentially
+//!   weighted moments);
+//! - [moving-window statistics] (rolling mean, variance, extrema and median);
+//! - [NumPy-compatible statistics] (NumPy's defaults and summation order, for
+//!   cross-validation against Python code).
+//!
+//! The extension traits are implemented for every `ArrayBase<S, D>` with
+//! readable data (`S: Data`): owned arrays, shared arrays (`ArcArray`) and
+//! views with arbitrary strides behave the same way. Methods that reorder
+//! the elements in place (e.g. `quantile_mut`) also require `S: DataMut`,
+//! which `ArcArray` implements by cloning its data if it is shared.
+//!
+//! Enabling the `rayon` crate feature adds `par_*` variants of the most
+//! expensive methods (axis-wise statistics, including the skip-NaN ones,
+//! quantiles, covariance, histograms), which process the input in parallel. Enabling the `serde-1`
+//! crate feature implements `Serialize`/`Deserialize` for histograms and the
+//! streaming accumulators.
 //!
 //! Please feel free to contribute new functionality! A roadmap can be found [here].
 //!
@@ -18,22 +58,57 @@
 //! [`ndarray-stats`]: https://github.com/jturner314/ndarray-stats/
 //! [`ndarray`]: https://github.com/rust-ndarray/ndarray
 //! [order statistics]: trait.QuantileExt.html
+//! [empirical distribution functions]: struct.Ecdf.html
 //! [partitioning]: trait.Sort1dExt.html
+//! [ranking]: trait.RankExt.html
+//! [outlier detection]: trait.OutlierExt.html
 //! [summary statistics]: trait.SummaryStatisticsExt.html
+//! [circular statistics]: trait.CircularExt.html
+//! [masked statistics]: trait.MaskedExt.html
+//! [feature scaling]: trait.NormalizationExt.html
 //! [correlation analysis]: trait.CorrelationExt.html
+//! [group-by aggregation]: trait.GroupByExt.html
+//! [principal component analysis]: decomposition/struct.Pca.html
+//! [whitening]: decomposition/struct.Whitening.html
 //! [measures from information theory]: trait.EntropyExt.html
+//! [mutual information]: trait.Entropy2dExt.html
 //! [histogram computation]: histogram/index.html
+//! [kernel density estimation]: kde/index.html
+//! [streaming statistics]: stream/index.html
+//! [random sampling]: trait.SamplingExt.html
+//! [stratified sampling]: trait.Sampling1dExt.html
+//! [bootstrap and jackknife]: resampling/index.html
+//! [distribution fitting]: fit/index.html
+//! [hypothesis tests]: hypothesis/index.html
+//! [slice-level statistics]: slice/index.html
+//! [NumPy-compatible statistics]: trait.NumpyCompatExt.html
+//! [time series transforms]: trait.TimeSeriesExt.html
+//! [moving-window statistics]: trait.RollingExt.html
 //! [here]: https://github.com/jturner314/ndarray-stats/issues/1
 //! [`NumPy`]: https://docs.scipy.org/doc/numpy-1.14.1/reference/routines.statistics.html
 //! [`StatsBase.jl`]: https://juliastats.github.io/StatsBase.jl/latest/
 
+pub use crate::circular::{AngleUnit, CircularExt};
 pub use crate::correlation::CorrelationExt;
-pub use crate::entropy::EntropyExt;
+pub use crate::ecdf::Ecdf;
+pub use crate::entropy::{Entropy1dExt, Entropy2dExt, EntropyExt, InformationUnit};
+pub use crate::group_by::GroupByExt;
 pub use crate::histogram::HistogramExt;
-pub use crate::maybe_nan::{MaybeNan, MaybeNanExt};
+pub use crate::masked::MaskedExt;
+pub use crate::maybe_nan::{
+    DropNanHow, MaskedView, MaybeNan, MaybeNan2dExt, MaybeNanExt, NanPolicy, NanSentinel,
+    NotNanValue, NotNone, NotNoneFloat, SkipNan,
+};
+pub use crate::normalization::NormalizationExt;
+pub use crate::numpy_compat::NumpyCompatExt;
+pub use crate::outliers::{OutlierExt, OutlierMethod};
 pub use crate::quantile::{interpolate, Quantile1dExt, QuantileExt};
+pub use crate::rank::{RankExt, RankMethod};
+pub use crate::rolling::{RollingEdge, RollingExt};
+pub use crate::sampling::{Sampling1dExt, SamplingExt, StratifiedSize};
 pub use crate::sort::Sort1dExt;
-pub use crate::summary_statistics::SummaryStatisticsExt;
+pub use crate::summary_statistics::{SummaryReport, SummaryStatisticsExt, VarianceAlgorithm};
+pub use crate::time_series::TimeSeriesExt;
 
 #[macro_use]
 mod private {
@@ -68,11 +143,33 @@ mod private {
     }
 }
 
+mod circular;
 mod correlation;
+pub mod decomposition;
+mod ecdf;
 mod entropy;
 pub mod errors;
+pub mod fit;
+mod group_by;
 pub mod histogram;
+pub mod hypothesis;
+pub mod kde;
+mod kernels;
+mod linalg;
+mod masked;
 mod maybe_nan;
+mod normalization;
+mod numpy_compat;
+mod outliers;
+#[cfg(feature = "rayon")]
+mod parallel;
 mod quantile;
+mod rank;
+pub mod resampling;
+mod rolling;
+mod sampling;
+pub mod slice;
 mod sort;
+pub mod stream;
 mod summary_statistics;
+mod time_series;
@@ -0,0 +1,140 @@
+//! Linear algebra on the small matrices of statistics (e.g. covariance
+//! matrices).
+use ndarray::{Array1, Array2, Axis};
+use num_traits::{Float, FromPrimitive};
+
+/// Maximum number of sweeps of the Jacobi eigenvalue algorithm; it usually
+/// converges in fewer than 10.
+const MAX_SWEEPS: usize = 100;
+
+/// Returns the eigenvalues of the symmetric matrix `a`, in decreasing order,
+/// and the corresponding unit eigenvectors, as the columns of a matrix.
+///
+/// The decomposition is computed with the cyclic Jacobi algorithm, which is
+/// accurate and simple but takes `O(n³)` operations per sweep, so it is meant
+/// for the small matrices of statistics (e.g. covariance matrices of a few
+/// hundred variables at most). The sign of each eigenvector is chosen so
+/// that its component of largest magnitude is positive.
+///
+/// Only the upper triangle of `a` is assumed to be meaningful.
+pub(crate) fn symmetric_eigen<A: Float>(mut a: Array2<A>) -> (Array1<A>, Array2<A>) {
+    let n = a.rows();
+    debug_assert_eq!(n, a.cols());
+    for p in 0..n {
+        for q in 0..p {
+            a[[p, q]] = a[[q, p]];
+        }
+    }
+    let mut v = Array2::eye(n);
+    let two = A::one() + A::one();
+    let total = a.fold(A::zero(), |acc, &x| acc + x * x);
+    for _ in 0..MAX_SWEEPS {
+        let mut off_diagonal = A::zero();
+        for p in 0..n {
+            for q in p + 1..n {
+                off_diagonal = off_diagonal + a[[p, q]] * a[[p, q]];
+            }
+        }
+        if off_diagonal <= A::epsilon() * A::epsilon() * total {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[[p, q]];
+                if apq == A::zero() {
+                    continue;
+                }
+                // Rotation in the (p, q) plane that cancels `a[[p, q]]`.
+                let theta = (a[[q, q]] - a[[p, p]]) / (two * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + A::one()).sqrt());
+                let c = A::one() / (t * t + A::one()).sqrt();
+                let s = t * c;
+                rotate(&mut a, Axis(1), p, q, c, s);
+                rotate(&mut a, Axis(0), p, q, c, s);
+                rotate(&mut v, Axis(1), p, q, c, s);
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| {
+        a[[j, j]]
+            .partial_cmp(&a[[i, i]])
+            .unwrap_or(core::cmp::Ordering::Equal)
+    });
+    let eigenvalues = order.iter().map(|&i| a[[i, i]]).collect();
+    let mut eigenvectors = v.select(Axis(1), &order);
+    for mut column in eigenvectors.gencolumns_mut() {
+        let largest = column.iter().fold(
+            A::zero(),
+            |acc, &x| if x.abs() > acc.abs() { x } else { acc },
+        );
+        if largest < A::zero() {
+            column.mapv_inplace(|x| -x);
+        }
+    }
+    (eigenvalues, eigenvectors)
+}
+
+/// Returns `true` if the smallest of the `eigenvalues` (in decreasing order)
+/// of a symmetric positive semi-definite matrix is zero up to rounding
+/// errors, i.e. if the matrix can't be inverted reliably.
+pub(crate) fn is_singular<A>(eigenvalues: &Array1<A>) -> bool
+where
+    A: Float + FromPrimitive,
+{
+    let n = eigenvalues.len();
+    if n == 0 {
+        return false;
+    }
+    let tolerance = eigenvalues[0]
+        * A::epsilon()
+        * A::from_usize(n).expect("Converting matrix size to `A` must not fail.");
+    eigenvalues[n - 1] <= tolerance
+}
+
+/// Replaces the lanes `p` and `q` of `a` along `axis` with
+/// `c * lane_p - s * lane_q` and `s * lane_p + c * lane_q`.
+fn rotate<A: Float>(a: &mut Array2<A>, axis: Axis, p: usize, q: usize, c: A, s: A) {
+    for k in 0..a.len_of(axis) {
+        let (kp, kq) = if axis == Axis(1) {
+            ([k, p], [k, q])
+        } else {
+            ([p, k], [q, k])
+        };
+        let (x, y) = (a[kp], a[kq]);
+        a[kp] = c * x - s * y;
+        a[kq] = s * x + c * y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use ndarray_rand::RandomExt;
+    use rand::distributions::Uniform;
+
+    #[test]
+    fn test_symmetric_eigen_reconstructs_matrix() {
+        for &n in &[1, 2, 5, 12] {
+            let b = Array2::random((n, n + 3), Uniform::new(-1., 1.));
+            let a = b.dot(&b.t());
+            let (values, vectors) = symmetric_eigen(a.clone());
+            assert!(values.windows(2).into_iter().all(|w| w[0] >= w[1]));
+            let reconstructed = (&vectors * &values).dot(&vectors.t());
+            assert!(reconstructed.all_close(&a, 1e-10));
+            assert!(vectors.t().dot(&vectors).all_close(&Array2::eye(n), 1e-10));
+        }
+    }
+
+    #[test]
+    fn test_symmetric_eigen_known_values() {
+        let (values, vectors) = symmetric_eigen(array![[2., 1.], [1., 2.]]);
+        assert!(values.all_close(&array![3., 1.], 1e-12));
+        let h = 0.5f64.sqrt();
+        assert!(vectors.all_close(&array![[h, h], [h, -h]], 1e-12));
+        let (values, _) = symmetric_eigen(Array2::<f64>::zeros((0, 0)));
+        assert_eq!(values.len(), 0);
+    }
+}
@@ -0,0 +1,334 @@
+//! Summary statistics of the elements selected by a boolean mask.
+use crate::errors::{EmptyInput, MaskedQuantileError, MultiInputError, ShapeMismatch};
+use crate::quantile::{interpolate::Interpolate, Quantile1dExt};
+use core::ops::{Add, Div};
+use ndarray::{Array1, ArrayBase, Data, Dimension};
+use num_traits::{Float, FromPrimitive, Zero};
+
+/// Extension trait for `ArrayBase` providing methods to compute statistics
+/// of the elements selected by a boolean mask.
+///
+/// The mask must have the same shape as the array: an element is considered
+/// valid, and therefore included in the computation, if the corresponding
+/// mask element is `true`. This is useful when invalid data is flagged by
+/// sentinel values or external quality-control flags rather than NaN.
+///
+/// All methods return `Err(ShapeMismatch)` if the shape of the mask differs
+/// from the shape of the array, and `Err(EmptyInput)` if no element is
+/// valid.
+pub trait MaskedExt<A, S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    /// Returns the sum of the valid elements.
+    ///
+    /// The sum of no elements is zero, so this method only fails if the
+    /// shapes don't match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::MaskedExt;
+    ///
+    /// let a = array![[1., -999.], [3., 4.]];
+    /// let mask = a.mapv(|x| x != -999.);
+    /// assert_eq!(a.sum_masked(&mask).unwrap(), 8.);
+    /// ```
+    fn sum_masked<S2>(&self, mask: &ArrayBase<S2, D>) -> Result<A, ShapeMismatch>
+    where
+        A: Clone + Add<Output = A> + Zero,
+        S2: Data<Elem = bool>;
+
+    /// Returns the arithmetic mean of the valid elements.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of valid
+    /// elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::MaskedExt;
+    ///
+    /// let a = array![1., 2., 100., 3.];
+    /// let mask = array![true, true, false, true];
+    /// assert_eq!(a.mean_masked(&mask).unwrap(), 2.);
+    /// ```
+    fn mean_masked<S2>(&self, mask: &ArrayBase<S2, D>) -> Result<A, MultiInputError>
+    where
+        A: Clone + FromPrimitive + Add<Output = A> + Div<Output = A> + Zero,
+        S2: Data<Elem = bool>;
+
+    /// Returns the variance of the valid elements.
+    ///
+    /// The variance is computed as the sum of the squared deviations from the
+    /// mean, divided by `n - ddof`, where `n` is the number of valid
+    /// elements. Use `ddof = 0` for the population variance and `ddof = 1`
+    /// for the unbiased sample variance.
+    ///
+    /// **Panics** if `ddof` is less than zero or greater than or equal to
+    /// the number of valid elements, or if `A::from_usize()` fails to convert
+    /// the number of valid elements.
+    fn var_masked<S2>(&self, mask: &ArrayBase<S2, D>, ddof: A) -> Result<A, MultiInputError>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = bool>;
+
+    /// Returns the standard deviation of the valid elements.
+    ///
+    /// This is the square root of [`var_masked`](#tymethod.var_masked), and
+    /// has the same panic conditions.
+    fn std_masked<S2>(&self, mask: &ArrayBase<S2, D>, ddof: A) -> Result<A, MultiInputError>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = bool>;
+
+    /// Returns the `q`th quantile of the valid elements.
+    ///
+    /// See [`Quantile1dExt::quantile_mut`] for the definition of the quantile
+    /// and the meaning of `interpolate`. The valid elements are copied into
+    /// a temporary array, so `self` is not modified.
+    ///
+    /// Returns `Err(Quantile(InvalidQuantile(q)))` if `q` is NaN or not
+    /// between `0.` and `1.` (inclusive).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{interpolate::Lower, MaskedExt};
+    /// use noisy_float::types::n64;
+    ///
+    /// let a = array![5, 1, 9, 3];
+    /// let mask = array![true, true, false, true];
+    /// assert_eq!(a.quantile_masked(&mask, n64(0.5), &Lower).unwrap(), 3);
+    /// ```
+    ///
+    /// [`Quantile1dExt::quantile_mut`]: trait.Quantile1dExt.html#tymethod.quantile_mut
+    fn quantile_masked<S2, Q, I>(
+        &self,
+        mask: &ArrayBase<S2, D>,
+        q: Q,
+        interpolate: &I,
+    ) -> Result<A, MaskedQuantileError>
+    where
+        A: Ord + Clone,
+        S2: Data<Elem = bool>,
+        Q: Float,
+        I: Interpolate<A>;
+
+    private_decl! {}
+}
+
+impl<A, S, D> MaskedExt<A, S, D> for ArrayBase<S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn sum_masked<S2>(&self, mask: &ArrayBase<S2, D>) -> Result<A, ShapeMismatch>
+    where
+        A: Clone + Add<Output = A> + Zero,
+        S2: Data<Elem = bool>,
+    {
+        check_mask_shape(self, mask)?;
+        Ok(self
+            .iter()
+            .zip(mask)
+            .filter(|(_, &valid)| valid)
+            .fold(A::zero(), |acc, (elem, _)| acc + elem.clone()))
+    }
+
+    fn mean_masked<S2>(&self, mask: &ArrayBase<S2, D>) -> Result<A, MultiInputError>
+    where
+        A: Clone + FromPrimitive + Add<Output = A> + Div<Output = A> + Zero,
+        S2: Data<Elem = bool>,
+    {
+        let sum = self.sum_masked(mask)?;
+        let n_valid = count_valid(mask)?;
+        let n_valid =
+            A::from_usize(n_valid).expect("Converting number of elements to `A` must not fail.");
+        Ok(sum / n_valid)
+    }
+
+    fn var_masked<S2>(&self, mask: &ArrayBase<S2, D>, ddof: A) -> Result<A, MultiInputError>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = bool>,
+    {
+        let mean = self.mean_masked(mask)?;
+        let n_valid = A::from_usize(count_valid(mask)?)
+            .expect("Converting number of elements to `A` must not fail.");
+        assert!(
+            !(ddof < A::zero() || ddof >= n_valid),
+            "`ddof` must be non-negative and less than the number of valid elements."
+        );
+        let mut sum_sq_dev = A::zero();
+        for (&elem, &valid) in self.iter().zip(mask) {
+            if valid {
+                let dev = elem - mean;
+                sum_sq_dev = sum_sq_dev + dev * dev;
+            }
+        }
+        Ok(sum_sq_dev / (n_valid - ddof))
+    }
+
+    fn std_masked<S2>(&self, mask: &ArrayBase<S2, D>, ddof: A) -> Result<A, MultiInputError>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = bool>,
+    {
+        self.var_masked(mask, ddof).map(|var| var.sqrt())
+    }
+
+    fn quantile_masked<S2, Q, I>(
+        &self,
+        mask: &ArrayBase<S2, D>,
+        q: Q,
+        interpolate: &I,
+    ) -> Result<A, MaskedQuantileError>
+    where
+        A: Ord + Clone,
+        S2: Data<Elem = bool>,
+        Q: Float,
+        I: Interpolate<A>,
+    {
+        check_mask_shape(self, mask)?;
+        let mut valid: Array1<A> = self
+            .iter()
+            .zip(mask)
+            .filter(|(_, &valid)| valid)
+            .map(|(elem, _)| elem.clone())
+            .collect();
+        Ok(valid.quantile_mut(q, interpolate)?)
+    }
+
+    private_impl! {}
+}
+
+/// Returns `Err(ShapeMismatch)` if `mask` doesn't have the same shape as
+/// `array`.
+fn check_mask_shape<S, S2, D>(
+    array: &ArrayBase<S, D>,
+    mask: &ArrayBase<S2, D>,
+) -> Result<(), ShapeMismatch>
+where
+    S: Data,
+    S2: Data<Elem = bool>,
+    D: Dimension,
+{
+    if array.shape() == mask.shape() {
+        Ok(())
+    } else {
+        Err(ShapeMismatch {
+            first_shape: array.shape().to_vec(),
+            second_shape: mask.shape().to_vec(),
+        })
+    }
+}
+
+/// Returns the number of `true` elements in `mask`, or `Err(EmptyInput)` if
+/// there are none.
+fn count_valid<S, D>(mask: &ArrayBase<S, D>) -> Result<usize, EmptyInput>
+where
+    S: Data<Elem = bool>,
+    D: Dimension,
+{
+    match mask.iter().filter(|&&valid| valid).count() {
+        0 => Err(EmptyInput),
+        n_valid => Ok(n_valid),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MaskedExt;
+    use crate::errors::{MaskedQuantileError, QuantileError};
+    use crate::interpolate::Linear;
+    use crate::SummaryStatisticsExt;
+    use approx::assert_abs_diff_eq;
+    use ndarray::{array, Array1};
+    use ndarray_rand::RandomExt;
+    use noisy_float::types::n64;
+    use quickcheck_macros::quickcheck;
+    use rand::distributions::Uniform;
+
+    #[test]
+    fn test_masked_shape_mismatch() {
+        let a = array![[1., 2.], [3., 4.]];
+        let mask = array![[true, false, true], [true, true, true]];
+        assert!(a.sum_masked(&mask).is_err());
+        assert!(a.mean_masked(&mask).unwrap_err().is_shape_mismatch());
+        assert!(a.var_masked(&mask, 0.).unwrap_err().is_shape_mismatch());
+        match a
+            .mapv(n64)
+            .quantile_masked(&mask, n64(0.5), &Linear)
+            .unwrap_err()
+        {
+            MaskedQuantileError::ShapeMismatch(_) => {}
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_masked_no_valid_elements() {
+        let a = array![1., 2., 3.];
+        let mask = array![false, false, false];
+        assert_eq!(a.sum_masked(&mask).unwrap(), 0.);
+        assert!(a.mean_masked(&mask).unwrap_err().is_empty_input());
+        assert!(a.std_masked(&mask, 0.).unwrap_err().is_empty_input());
+        match a
+            .mapv(n64)
+            .quantile_masked(&mask, n64(0.5), &Linear)
+            .unwrap_err()
+        {
+            MaskedQuantileError::Quantile(QuantileError::EmptyInput) => {}
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_var_masked() {
+        let a = array![[2., 4., -1.], [4., 4., 5.], [5., 7., 9.]];
+        let mask = a.mapv(|x| x >= 0.);
+        assert_abs_diff_eq!(a.var_masked(&mask, 0.).unwrap(), 4.);
+        assert_abs_diff_eq!(a.std_masked(&mask, 1.).unwrap(), (32f64 / 7.).sqrt());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_var_masked_ddof_too_large() {
+        let a = array![1., 2., 3.];
+        let mask = array![true, false, true];
+        let _ = a.var_masked(&mask, 2.);
+    }
+
+    #[test]
+    fn test_quantile_masked_does_not_modify_input() {
+        let a = array![n64(5.), n64(1.), n64(9.), n64(3.)];
+        let mask = array![true, true, false, true];
+        assert_eq!(
+            a.quantile_masked(&mask, n64(0.5), &Linear).unwrap(),
+            n64(3.)
+        );
+        assert_eq!(a, array![n64(5.), n64(1.), n64(9.), n64(3.)]);
+    }
+
+    #[quickcheck]
+    fn mean_masked_matches_mean_of_selected(mask: Vec<bool>) -> bool {
+        let a = Array1::random(mask.len(), Uniform::new(-10., 10.));
+        let mask = Array1::from_vec(mask);
+        let selected: Array1<f64> = a
+            .iter()
+            .zip(&mask)
+            .filter(|(_, &valid)| valid)
+            .map(|(&x, _)| x)
+            .collect();
+        match (a.mean_masked(&mask), selected.mean()) {
+            (Ok(masked), Ok(expected)) => (masked - expected).abs() < 1e-12,
+            (Err(masked), Err(_)) => masked.is_empty_input(),
+            _ => false,
+        }
+    }
+}
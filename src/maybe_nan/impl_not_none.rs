@@ -1,15 +1,19 @@
 use super::NotNone;
-use num_traits::{FromPrimitive, ToPrimitive};
-use std::cmp;
-use std::fmt;
-use std::ops::{Add, Deref, DerefMut, Div, Mul, Rem, Sub};
+use core::cmp;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::{
+    Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub,
+    SubAssign,
+};
+use num_traits::{FromPrimitive, ToPrimitive, Zero};
 
 impl<T> Deref for NotNone<T> {
     type Target = T;
     fn deref(&self) -> &T {
         match self.0 {
             Some(ref inner) => inner,
-            None => unsafe { ::std::hint::unreachable_unchecked() },
+            None => unsafe { core::hint::unreachable_unchecked() },
         }
     }
 }
@@ -18,11 +22,29 @@ impl<T> DerefMut for NotNone<T> {
     fn deref_mut(&mut self) -> &mut T {
         match self.0 {
             Some(ref mut inner) => inner,
-            None => unsafe { ::std::hint::unreachable_unchecked() },
+            None => unsafe { core::hint::unreachable_unchecked() },
         }
     }
 }
 
+impl<T> AsRef<T> for NotNone<T> {
+    fn as_ref(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<T> AsMut<T> for NotNone<T> {
+    fn as_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+impl<T> From<T> for NotNone<T> {
+    fn from(value: T) -> NotNone<T> {
+        NotNone::new(value)
+    }
+}
+
 impl<T: fmt::Display> fmt::Display for NotNone<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         self.deref().fmt(f)
@@ -35,8 +57,11 @@ impl<T: PartialEq> PartialEq for NotNone<T> {
     fn eq(&self, other: &Self) -> bool {
         self.deref().eq(other)
     }
-    fn ne(&self, other: &Self) -> bool {
-        self.deref().eq(other)
+}
+
+impl<T: Hash> Hash for NotNone<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
     }
 }
 
@@ -72,6 +97,17 @@ impl<T: Add> Add for NotNone<T> {
     }
 }
 
+impl<T: Zero> Zero for NotNone<T> {
+    #[inline]
+    fn zero() -> Self {
+        NotNone::new(T::zero())
+    }
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.deref().is_zero()
+    }
+}
+
 impl<T: Sub> Sub for NotNone<T> {
     type Output = NotNone<T::Output>;
     #[inline]
@@ -104,6 +140,49 @@ impl<T: Rem> Rem for NotNone<T> {
     }
 }
 
+impl<T: Neg> Neg for NotNone<T> {
+    type Output = NotNone<T::Output>;
+    #[inline]
+    fn neg(self) -> Self::Output {
+        self.map(|v| v.neg())
+    }
+}
+
+impl<T: AddAssign> AddAssign for NotNone<T> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.deref_mut().add_assign(rhs.unwrap())
+    }
+}
+
+impl<T: SubAssign> SubAssign for NotNone<T> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.deref_mut().sub_assign(rhs.unwrap())
+    }
+}
+
+impl<T: MulAssign> MulAssign for NotNone<T> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        self.deref_mut().mul_assign(rhs.unwrap())
+    }
+}
+
+impl<T: DivAssign> DivAssign for NotNone<T> {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        self.deref_mut().div_assign(rhs.unwrap())
+    }
+}
+
+impl<T: RemAssign> RemAssign for NotNone<T> {
+    #[inline]
+    fn rem_assign(&mut self, rhs: Self) {
+        self.deref_mut().rem_assign(rhs.unwrap())
+    }
+}
+
 impl<T: ToPrimitive> ToPrimitive for NotNone<T> {
     #[inline]
     fn to_isize(&self) -> Option<isize> {
@@ -0,0 +1,416 @@
+use crate::errors::ShapeMismatch;
+use core::ops::{Add, Div};
+use ndarray::{Array, ArrayView, ArrayView1, Axis, Dimension, RemoveAxis, Zip};
+use num_traits::{Float, FromPrimitive, Zero};
+
+/// A view of an array paired with a boolean validity mask of the same shape,
+/// for data whose missing values are flagged separately instead of being
+/// stored as NaN (e.g. integers with a sentinel value, or measurements with
+/// quality-control flags).
+///
+/// An element is valid if the corresponding mask element is `true`. The
+/// invalid elements play the role of NaN values: `MaskedView` provides the
+/// reductions of [`MaybeNanExt`] that skip them (`fold_skipnan`,
+/// `sum_axis_skipnan`, `mean_axis_skipnan`, `var_axis_skipnan`,
+/// `std_axis_skipnan`, `min_axis_skipnan` and `max_axis_skipnan`) without
+/// copying the data. The axis reductions return `None` for the lanes without
+/// any valid element, as the NaN-skipping methods do for an array of
+/// options.
+///
+/// For the other methods of [`MaybeNanExt`] and [`QuantileExt`], a
+/// `MaskedView` can be turned into an array that they understand, with the
+/// invalid elements in place of NaN values:
+///
+/// - [`to_options`](#method.to_options) maps the valid elements to `Some`
+///   and the invalid ones to `None`, which works for any element type `A`
+///   such that `Option<A>` implements [`MaybeNan`] (the primitive integers,
+///   `f32`, `f64`, `N32` and `N64`);
+/// - [`fill_invalid`](#method.fill_invalid) replaces the invalid elements by
+///   a given value, e.g. NaN for floating-point data.
+///
+/// For one-off statistics of the valid elements, the methods of
+/// [`MaskedExt`] avoid the copy.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{array, Axis};
+/// use ndarray_stats::{MaskedView, MaybeNanExt, QuantileExt};
+///
+/// // -1 marks a missing reading.
+/// let counts = array![[3, -1, 5], [4, 2, -1]];
+/// let valid = counts.mapv(|x| x != -1);
+/// let view = MaskedView::new(counts.view(), valid.view()).unwrap();
+/// assert_eq!(view.count_valid(), 4);
+/// assert_eq!(view.sum_axis_skipnan(Axis(1)), array![Some(8), Some(6)]);
+///
+/// let options = view.to_options();
+/// assert_eq!(options.max_skipnan(), &Some(5));
+/// ```
+///
+/// [`MaybeNan`]: trait.MaybeNan.html
+/// [`MaybeNanExt`]: trait.MaybeNanExt.html
+/// [`QuantileExt`]: trait.QuantileExt.html
+/// [`MaskedExt`]: trait.MaskedExt.html
+#[derive(Clone, Debug)]
+pub struct MaskedView<'a, A, D: Dimension> {
+    data: ArrayView<'a, A, D>,
+    mask: ArrayView<'a, bool, D>,
+}
+
+impl<'a, A, D: Dimension> MaskedView<'a, A, D> {
+    /// Pairs `data` with the validity `mask`.
+    ///
+    /// Returns `Err(ShapeMismatch)` if the shapes of `data` and `mask`
+    /// differ.
+    pub fn new(
+        data: ArrayView<'a, A, D>,
+        mask: ArrayView<'a, bool, D>,
+    ) -> Result<Self, ShapeMismatch> {
+        if data.shape() != mask.shape() {
+            return Err(ShapeMismatch {
+                first_shape: data.shape().to_vec(),
+                second_shape: mask.shape().to_vec(),
+            });
+        }
+        Ok(MaskedView { data, mask })
+    }
+
+    /// Returns the view of the data, including the invalid elements.
+    pub fn data(&self) -> &ArrayView<'a, A, D> {
+        &self.data
+    }
+
+    /// Returns the view of the validity mask.
+    pub fn mask(&self) -> &ArrayView<'a, bool, D> {
+        &self.mask
+    }
+
+    /// Returns the number of valid elements.
+    pub fn count_valid(&self) -> usize {
+        self.mask.iter().filter(|&&valid| valid).count()
+    }
+
+    /// Returns an iterator over the valid elements, in logical order.
+    pub fn iter_valid<'b>(&'b self) -> impl Iterator<Item = &'b A> + 'b {
+        self.data
+            .iter()
+            .zip(self.mask.iter())
+            .filter_map(|(x, &valid)| if valid { Some(x) } else { None })
+    }
+
+    /// Returns an array with the valid elements wrapped in `Some` and `None`
+    /// in place of the invalid ones.
+    pub fn to_options(&self) -> Array<Option<A>, D>
+    where
+        A: Clone,
+    {
+        let mut options = Array::from_elem(self.data.raw_dim(), None);
+        Zip::from(&mut options)
+            .and(&self.data)
+            .and(&self.mask)
+            .apply(|option, x, &valid| {
+                if valid {
+                    *option = Some(x.clone());
+                }
+            });
+        options
+    }
+
+    /// Returns a copy of the data with the invalid elements replaced by
+    /// `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{MaskedView, MaybeNanExt};
+    ///
+    /// let a = array![[1., 2.], [3., 100.]];
+    /// let valid = array![[true, true], [true, false]];
+    /// let view = MaskedView::new(a.view(), valid.view()).unwrap();
+    /// let filled = view.fill_invalid(::std::f64::NAN);
+    /// assert_eq!(filled.mean_axis_skipnan(Axis(0)), array![2., 2.]);
+    /// ```
+    pub fn fill_invalid(&self, value: A) -> Array<A, D>
+    where
+        A: Clone,
+    {
+        let mut filled = self.data.to_owned();
+        Zip::from(&mut filled).and(&self.mask).apply(|x, &valid| {
+            if !valid {
+                *x = value.clone();
+            }
+        });
+        filled
+    }
+
+    /// Traverses the valid elements and applies a fold, returning the
+    /// resulting value.
+    ///
+    /// Elements are visited in logical order.
+    pub fn fold_skipnan<'b, F, B>(&'b self, init: B, f: F) -> B
+    where
+        F: FnMut(B, &'b A) -> B,
+    {
+        self.iter_valid().fold(init, f)
+    }
+
+    /// Returns the sum of the valid elements of each 1-dimensional lane
+    /// along `axis`, or `None` for the lanes without any valid element.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn sum_axis_skipnan(&self, axis: Axis) -> Array<Option<A>, D::Smaller>
+    where
+        A: Clone + Zero + Add<Output = A>,
+        D: RemoveAxis,
+    {
+        self.map_axis_valid(axis, |data, mask| {
+            let (sum, count) = valid(&data, &mask).fold((A::zero(), 0), |(sum, count), x| {
+                (sum + x.clone(), count + 1)
+            });
+            if count == 0 {
+                None
+            } else {
+                Some(sum)
+            }
+        })
+    }
+
+    /// Returns the arithmetic mean of the valid elements of each
+    /// 1-dimensional lane along `axis`, or `None` for the lanes without any
+    /// valid element.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the number of valid elements of a lane.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::MaskedView;
+    ///
+    /// let a = array![[1., 2.], [3., 100.]];
+    /// let valid = array![[true, false], [true, false]];
+    /// let view = MaskedView::new(a.view(), valid.view()).unwrap();
+    /// assert_eq!(view.mean_axis_skipnan(Axis(0)), array![Some(2.), None]);
+    /// ```
+    pub fn mean_axis_skipnan(&self, axis: Axis) -> Array<Option<A>, D::Smaller>
+    where
+        A: Clone + Zero + FromPrimitive + Add<Output = A> + Div<Output = A>,
+        D: RemoveAxis,
+    {
+        self.map_axis_valid(axis, |data, mask| {
+            let (sum, count) = valid(&data, &mask).fold((A::zero(), 0), |(sum, count), x| {
+                (sum + x.clone(), count + 1)
+            });
+            if count == 0 {
+                None
+            } else {
+                let count = A::from_usize(count)
+                    .expect("Converting the number of elements to `A` must not fail.");
+                Some(sum / count)
+            }
+        })
+    }
+
+    /// Returns the variance of the valid elements of each 1-dimensional lane
+    /// along `axis`, or `None` for the lanes with no more than `ddof` valid
+    /// elements.
+    ///
+    /// The parameter `ddof` specifies the "delta degrees of freedom": use
+    /// `ddof = 0` for the population variance and `ddof = 1` for the
+    /// unbiased sample variance.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `ddof` is less than zero.
+    pub fn var_axis_skipnan(&self, axis: Axis, ddof: A) -> Array<Option<A>, D::Smaller>
+    where
+        A: Float,
+        D: RemoveAxis,
+    {
+        assert!(ddof >= A::zero(), "`ddof` must not be less than zero.");
+        self.map_axis_valid(axis, |data, mask| {
+            // Welford's algorithm, over the valid elements.
+            let mut count = A::zero();
+            let mut mean = A::zero();
+            let mut sum_sq_dev = A::zero();
+            for &x in valid(&data, &mask) {
+                count = count + A::one();
+                let delta = x - mean;
+                mean = mean + delta / count;
+                sum_sq_dev = sum_sq_dev + delta * (x - mean);
+            }
+            if count <= ddof {
+                None
+            } else {
+                Some(sum_sq_dev / (count - ddof))
+            }
+        })
+    }
+
+    /// Returns the standard deviation of the valid elements of each
+    /// 1-dimensional lane along `axis`, the square root of
+    /// [`var_axis_skipnan`](#method.var_axis_skipnan).
+    ///
+    /// **Panics** if `axis` is out of bounds or if `ddof` is less than zero.
+    pub fn std_axis_skipnan(&self, axis: Axis, ddof: A) -> Array<Option<A>, D::Smaller>
+    where
+        A: Float,
+        D: RemoveAxis,
+    {
+        self.var_axis_skipnan(axis, ddof)
+            .mapv(|var| var.map(|var| var.sqrt()))
+    }
+
+    /// Returns the minimum of the valid elements of each 1-dimensional lane
+    /// along `axis`, or `None` for the lanes without any valid element.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn min_axis_skipnan(&self, axis: Axis) -> Array<Option<A>, D::Smaller>
+    where
+        A: Clone + Ord,
+        D: RemoveAxis,
+    {
+        self.map_axis_valid(axis, |data, mask| valid(&data, &mask).min().cloned())
+    }
+
+    /// Returns the maximum of the valid elements of each 1-dimensional lane
+    /// along `axis`, or `None` for the lanes without any valid element.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    pub fn max_axis_skipnan(&self, axis: Axis) -> Array<Option<A>, D::Smaller>
+    where
+        A: Clone + Ord,
+        D: RemoveAxis,
+    {
+        self.map_axis_valid(axis, |data, mask| valid(&data, &mask).max().cloned())
+    }
+
+    /// Applies `mapping` to the data and the mask of each 1-dimensional lane
+    /// along `axis`.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn map_axis_valid<B, F>(&self, axis: Axis, mut mapping: F) -> Array<B, D::Smaller>
+    where
+        D: RemoveAxis,
+        F: FnMut(ArrayView1<'_, A>, ArrayView1<'_, bool>) -> B,
+    {
+        let mut lanes = self.data.lanes(axis).into_iter().zip(self.mask.lanes(axis));
+        Array::from_shape_fn(self.data.raw_dim().remove_axis(axis), |_| {
+            let (data, mask) = lanes.next().expect("There is one lane per output element.");
+            mapping(data, mask)
+        })
+    }
+}
+
+/// Returns an iterator over the valid elements of a lane.
+fn valid<'b, A>(
+    data: &'b ArrayView1<'_, A>,
+    mask: &'b ArrayView1<'_, bool>,
+) -> impl Iterator<Item = &'b A> + 'b {
+    data.iter()
+        .zip(mask.iter())
+        .filter_map(|(x, &valid)| if valid { Some(x) } else { None })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpolate::Lower;
+    use crate::{MaybeNanExt, QuantileExt};
+    use ndarray::{array, Array1, Array2};
+    use noisy_float::types::n64;
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[test]
+    fn test_shape_mismatch() {
+        let a = array![1, 2, 3];
+        let mask = array![true, false];
+        assert!(MaskedView::new(a.view(), mask.view()).is_err());
+    }
+
+    #[test]
+    fn test_quantile_of_options() {
+        let a = array![[5u16, 0, 1], [2, 7, 0]];
+        let valid = a.mapv(|x| x != 0);
+        let view = MaskedView::new(a.view(), valid.view()).unwrap();
+        let mut options = view.to_options();
+        let medians = options
+            .quantile_axis_skipnan_mut(Axis(1), n64(0.5), &Lower)
+            .unwrap();
+        assert_eq!(medians, array![Some(1), Some(2)]);
+        assert_eq!(
+            view.iter_valid().cloned().collect::<Vec<_>>(),
+            vec![5, 1, 2, 7]
+        );
+    }
+
+    #[quickcheck]
+    fn skipnan_sum_is_sum_of_valid(values: Vec<(i32, bool)>) -> bool {
+        let data: Array1<i64> = values.iter().map(|&(x, _)| i64::from(x)).collect();
+        let mask: Array1<bool> = values.iter().map(|&(_, valid)| valid).collect();
+        let view = MaskedView::new(data.view(), mask.view()).unwrap();
+        let expected: i64 = view.iter_valid().sum();
+        let count = mask.iter().filter(|&&valid| valid).count();
+        let sum = view.to_options().sum_axis_skipnan(Axis(0)).into_scalar();
+        view.count_valid() == count
+            && sum == Some(expected).filter(|_| count > 0)
+            && view.fill_invalid(0).sum() == expected
+    }
+
+    #[quickcheck]
+    fn axis_reductions_match_options(values: Vec<(i32, bool)>, ncols: usize) -> bool {
+        let ncols = ncols % 4 + 1;
+        let nrows = values.len() / ncols;
+        let values = &values[..nrows * ncols];
+        let data: Array2<i64> = Array1::from_iter(values.iter().map(|&(x, _)| i64::from(x)))
+            .into_shape((nrows, ncols))
+            .unwrap();
+        let mask: Array2<bool> = Array1::from_iter(values.iter().map(|&(_, valid)| valid))
+            .into_shape((nrows, ncols))
+            .unwrap();
+        let view = MaskedView::new(data.view(), mask.view()).unwrap();
+        let options = view.to_options();
+        (0..2).all(|axis| {
+            let axis = Axis(axis);
+            view.sum_axis_skipnan(axis) == options.sum_axis_skipnan(axis)
+                && view.min_axis_skipnan(axis) == options.min_axis_skipnan(axis)
+                && view.max_axis_skipnan(axis) == options.max_axis_skipnan(axis)
+        }) && view.fold_skipnan(0, |acc, &x| acc + x) == view.iter_valid().sum::<i64>()
+    }
+
+    #[test]
+    fn test_float_axis_reductions() {
+        let a = array![[1., 2., 4.], [3., -1., 6.]];
+        let valid = array![[true, false, true], [true, false, false]];
+        let view = MaskedView::new(a.view(), valid.view()).unwrap();
+        let filled = view.fill_invalid(f64::NAN);
+        let not_nan = |x: f64| if x.is_nan() { None } else { Some(x) };
+        for &axis in &[Axis(0), Axis(1)] {
+            assert_eq!(
+                view.mean_axis_skipnan(axis),
+                filled.mean_axis_skipnan(axis).mapv(not_nan)
+            );
+            for &ddof in &[0., 1.] {
+                assert_eq!(
+                    view.var_axis_skipnan(axis, ddof),
+                    filled.var_axis_skipnan(axis, n64(ddof)).mapv(not_nan)
+                );
+                assert_eq!(
+                    view.std_axis_skipnan(axis, ddof),
+                    filled.std_axis_skipnan(axis, n64(ddof)).mapv(not_nan)
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_var_negative_ddof() {
+        let a = array![1., 2.];
+        let valid = array![true, true];
+        let view = MaskedView::new(a.view(), valid.view()).unwrap();
+        view.var_axis_skipnan(Axis(0), -1.);
+    }
+}
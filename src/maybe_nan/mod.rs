@@ -1,6 +1,15 @@
+use crate::errors::ShapeMismatch;
+#[cfg(feature = "rayon")]
+use crate::parallel::par_map_lanes;
+use core::ops::{Add, Div};
 use ndarray::prelude::*;
-use ndarray::{s, Data, DataMut, RemoveAxis};
+use ndarray::{s, Data, DataMut, RemoveAxis, Zip};
 use noisy_float::types::{N32, N64};
+use num_traits::{Float, FromPrimitive, One, Zero};
+
+pub use self::masked_view::MaskedView;
+pub use self::not_none_float::NotNoneFloat;
+pub use self::sentinel::{NanSentinel, NotNanValue};
 
 /// A number type that can have not-a-number values.
 pub trait MaybeNan: Sized {
@@ -33,17 +42,49 @@ pub trait MaybeNan: Sized {
     /// Returns a view with the NaN values removed.
     ///
     /// This modifies the input view by moving elements as necessary. The final
-    /// order of the elements is unspecified. However, this method is
-    /// idempotent, and given the same input data, the result is always ordered
-    /// the same way.
+    /// order of the elements is unspecified: in general, the relative order of
+    /// the non-NaN elements is **not** preserved (use
+    /// [`remove_nan_mut_stable`](#method.remove_nan_mut_stable) if it
+    /// matters). However, this method is idempotent, and given the same input
+    /// data, the result is always ordered the same way.
     fn remove_nan_mut(_: ArrayViewMut1<'_, Self>) -> ArrayViewMut1<'_, Self::NotNan>;
+
+    /// Returns a view with the NaN values removed, preserving the relative
+    /// order of the non-NaN elements.
+    ///
+    /// This modifies the input view by moving elements as necessary: the
+    /// non-NaN elements are moved to the front of the view, in their original
+    /// order. It is slower than [`remove_nan_mut`](#tymethod.remove_nan_mut),
+    /// since more elements may have to be moved.
+    ///
+    /// The default implementation relies on `remove_nan_mut` leaving views
+    /// without NaN values unchanged, which is the case for all the
+    /// implementations provided by this crate.
+    fn remove_nan_mut_stable(view: ArrayViewMut1<'_, Self>) -> ArrayViewMut1<'_, Self::NotNan> {
+        Self::remove_nan_mut(remove_nan_mut_stable(view))
+    }
+}
+
+/// How NaN values should be handled by the methods accepting a `NanPolicy`
+/// (e.g. [`mean_with_policy`]).
+///
+/// [`mean_with_policy`]: trait.SummaryStatisticsExt.html#tymethod.mean_with_policy
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NanPolicy {
+    /// If any of the input values is NaN, the result is NaN.
+    Propagate,
+    /// NaN values are skipped: the result is computed using only the
+    /// non-NaN values.
+    Omit,
+    /// If any of the input values is NaN, an error is returned.
+    Error,
 }
 
 /// Returns a view with the NaN values removed.
 ///
 /// This modifies the input view by moving elements as necessary.
 fn remove_nan_mut<A: MaybeNan>(mut view: ArrayViewMut1<'_, A>) -> ArrayViewMut1<'_, A> {
-    if view.len() == 0 {
+    if view.is_empty() {
         return view.slice_move(s![..0]);
     }
     let mut i = 0;
@@ -69,6 +110,35 @@ fn remove_nan_mut<A: MaybeNan>(mut view: ArrayViewMut1<'_, A>) -> ArrayViewMut1<
     }
 }
 
+/// Reinterprets the elements of `view` as values of type `B`, keeping its
+/// shape and strides.
+///
+/// **Safety**: `B` must have the same layout as `A`, and each element of
+/// `view` must be a valid value of type `B`.
+unsafe fn cast_view_mut<A, B>(mut view: ArrayViewMut1<'_, A>) -> ArrayViewMut1<'_, B> {
+    let stride = view.strides()[0];
+    // Negative strides are stored as wrapped-around `usize` values.
+    let shape = Ix1(view.len()).strides(Ix1(stride as usize));
+    ArrayViewMut1::from_shape_ptr(shape, view.as_mut_ptr() as *mut B)
+}
+
+/// Returns a view with the NaN values removed, preserving the relative order
+/// of the remaining elements.
+///
+/// This modifies the input view by moving elements as necessary.
+fn remove_nan_mut_stable<A: MaybeNan>(mut view: ArrayViewMut1<'_, A>) -> ArrayViewMut1<'_, A> {
+    let mut n_not_nan = 0;
+    for i in 0..view.len() {
+        // At this point, `view[..n_not_nan]` contains the non-NaN elements of
+        // `view[..i]` in their original order.
+        if !view[i].is_nan() {
+            view.swap(n_not_nan, i);
+            n_not_nan += 1;
+        }
+    }
+    view.slice_move(s![..n_not_nan])
+}
+
 macro_rules! impl_maybenan_for_fxx {
     ($fxx:ident, $Nxx:ident) => {
         impl MaybeNan for $fxx {
@@ -104,9 +174,7 @@ macro_rules! impl_maybenan_for_fxx {
                 let not_nan = remove_nan_mut(view);
                 // This is safe because `remove_nan_mut` has removed the NaN
                 // values, and `$Nxx` is a thin wrapper around `$fxx`.
-                unsafe {
-                    ArrayViewMut1::from_shape_ptr(not_nan.dim(), not_nan.as_ptr() as *mut $Nxx)
-                }
+                unsafe { cast_view_mut(not_nan) }
             }
         }
     };
@@ -154,12 +222,7 @@ macro_rules! impl_maybenan_for_opt_never_nan {
                 let not_nan = remove_nan_mut(view);
                 // This is safe because `remove_nan_mut` has removed the `None`
                 // values, and `NotNone<$ty>` is a thin wrapper around `Option<$ty>`.
-                unsafe {
-                    ArrayViewMut1::from_shape_ptr(
-                        not_nan.dim(),
-                        not_nan.as_ptr() as *mut NotNone<$ty>,
-                    )
-                }
+                unsafe { cast_view_mut(not_nan) }
             }
         }
     };
@@ -179,6 +242,26 @@ impl_maybenan_for_opt_never_nan!(N64);
 
 /// A thin wrapper around `Option` that guarantees that the value is not
 /// `None`.
+///
+/// This is the `NotNan` type of `Option<T>` for integer and noisy float
+/// types `T` ([`NotNoneFloat`] plays this role for `Option<f32>` and
+/// `Option<f64>`). It dereferences to `T` and forwards the arithmetic
+/// operators of `T`, so the values obtained from the NaN-skipping methods
+/// can be used directly in computations.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::{MaybeNanExt, NotNone};
+///
+/// let a = array![Some(1i64), None, Some(4)];
+/// let sum = a.fold_skipnan(NotNone::new(0), |acc, &x| acc + x);
+/// assert_eq!(*sum, 5);
+/// assert_eq!(sum.into_inner(), Some(5));
+/// ```
+///
+/// [`NotNoneFloat`]: struct.NotNoneFloat.html
 #[derive(Clone, Copy, Debug)]
 #[repr(transparent)]
 pub struct NotNone<T>(Option<T>);
@@ -192,6 +275,15 @@ impl<T> NotNone<T> {
     /// Creates a new `NotNone` containing the given value.
     ///
     /// Returns `None` if `value` is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray_stats::NotNone;
+    ///
+    /// assert_eq!(NotNone::try_new(Some(3)), Some(NotNone::new(3)));
+    /// assert_eq!(NotNone::<i32>::try_new(None), None);
+    /// ```
     pub fn try_new(value: Option<T>) -> Option<NotNone<T>> {
         if value.is_some() {
             Some(NotNone(value))
@@ -211,7 +303,7 @@ impl<T> NotNone<T> {
     pub fn unwrap(self) -> T {
         match self.0 {
             Some(inner) => inner,
-            None => unsafe { ::std::hint::unreachable_unchecked() },
+            None => unsafe { core::hint::unreachable_unchecked() },
         }
     }
 
@@ -232,6 +324,53 @@ where
     S: Data<Elem = A>,
     D: Dimension,
 {
+    /// Returns `true` if any of the array elements is NaN.
+    ///
+    /// The traversal stops as soon as a NaN value is found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::MaybeNanExt;
+    ///
+    /// assert!(array![1., ::std::f64::NAN].has_nan());
+    /// assert!(!array![Some(1), Some(2)].has_nan());
+    /// ```
+    fn has_nan(&self) -> bool;
+
+    /// Returns `true` if all of the array elements are NaN.
+    ///
+    /// The traversal stops as soon as a non-NaN value is found. Returns
+    /// `true` if the array is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::MaybeNanExt;
+    ///
+    /// assert!(array![None::<i32>, None].all_nan());
+    /// assert!(!array![1., ::std::f64::NAN].all_nan());
+    /// ```
+    fn all_nan(&self) -> bool;
+
+    /// Returns, for each 1-dimensional lane along `axis`, whether any of its
+    /// elements is NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn has_nan_axis(&self, axis: Axis) -> Array<bool, D::Smaller>
+    where
+        D: RemoveAxis;
+
+    /// Returns, for each 1-dimensional lane along `axis`, whether all of its
+    /// elements are NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn all_nan_axis(&self, axis: Axis) -> Array<bool, D::Smaller>
+    where
+        D: RemoveAxis;
+
     /// Traverse the non-NaN array elements and apply a fold, returning the
     /// resulting value.
     ///
@@ -245,6 +384,21 @@ where
     /// returning the resulting value.
     ///
     /// Elements are visited in arbitrary order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::MaybeNanExt;
+    /// use std::f64::NAN;
+    ///
+    /// let a = array![[1., NAN], [NAN, 4.]];
+    /// let not_nan_indices = a.indexed_fold_skipnan(Vec::new(), |mut indices, (index, _)| {
+    ///     indices.push(index);
+    ///     indices
+    /// });
+    /// assert_eq!(not_nan_indices, vec![(0, 0), (1, 1)]);
+    /// ```
     fn indexed_fold_skipnan<'a, F, B>(&'a self, init: B, f: F) -> B
     where
         A: 'a,
@@ -258,16 +412,118 @@ where
         A: 'a,
         F: FnMut(&'a A::NotNan);
 
+    /// Visit each non-NaN element in the array, together with its index, by
+    /// calling `f` on each element.
+    ///
+    /// Elements are visited in arbitrary order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{MaybeNanExt, NotNone};
+    ///
+    /// let a = array![Some(3), None, Some(7)];
+    /// let mut sparse = Vec::new();
+    /// a.indexed_visit_skipnan(|index, value: &NotNone<i32>| sparse.push((index, **value)));
+    /// assert_eq!(sparse, vec![(0, 3), (2, 7)]);
+    /// ```
+    fn indexed_visit_skipnan<'a, F>(&'a self, f: F)
+    where
+        A: 'a,
+        F: FnMut(D::Pattern, &'a A::NotNan);
+
+    /// Traverse the pairs of corresponding elements of `self` and `other`
+    /// and apply a fold, skipping the pairs where either element is NaN.
+    ///
+    /// Returns the resulting value and the number of pairs that have been
+    /// folded.
+    ///
+    /// Pairs are visited in arbitrary order.
+    ///
+    /// Returns `Err(ShapeMismatch)` if `self` and `other` do not have the
+    /// same shape.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::MaybeNanExt;
+    /// use std::f64::NAN;
+    ///
+    /// let x = array![1., NAN, 3., 4.];
+    /// let y = array![2., 5., NAN, 6.];
+    /// let (dot, n_pairs) = x
+    ///     .zip_fold_skipnan(&y, 0., |acc, &x, &y| acc + (x * y).raw())
+    ///     .unwrap();
+    /// assert_eq!(dot, 26.);
+    /// assert_eq!(n_pairs, 2);
+    /// ```
+    fn zip_fold_skipnan<'a, S2, F, B>(
+        &'a self,
+        other: &'a ArrayBase<S2, D>,
+        init: B,
+        f: F,
+    ) -> Result<(B, usize), ShapeMismatch>
+    where
+        A: 'a,
+        S2: Data<Elem = A>,
+        F: FnMut(B, &'a A::NotNan, &'a A::NotNan) -> B;
+
     /// Fold non-NaN values along an axis.
     ///
     /// Combine the non-NaN elements of each subview with the previous using
     /// the fold function and initial value init.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::MaybeNanExt;
+    ///
+    /// let a = array![[Some(1), None], [Some(4), Some(2)]];
+    /// let n_not_nan = a.fold_axis_skipnan(Axis(0), 0, |&count, _| count + 1);
+    /// assert_eq!(n_not_nan, array![2, 1]);
+    /// ```
     fn fold_axis_skipnan<B, F>(&self, axis: Axis, init: B, fold: F) -> Array<B, D::Smaller>
     where
         D: RemoveAxis,
         F: FnMut(&B, &A::NotNan) -> B,
         B: Clone;
 
+    /// Reduce the non-NaN values along an axis into just one value,
+    /// producing a new array with one less dimension.
+    ///
+    /// `mapping` is called once for each 1-dimensional lane along `axis`,
+    /// with an iterator over the non-NaN elements of the lane (in order). The
+    /// lanes aren't copied, and lanes of length zero are passed as empty
+    /// iterators.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::MaybeNanExt;
+    /// use std::f64::NAN;
+    ///
+    /// let a = array![[1., NAN, -3.], [NAN, NAN, NAN]];
+    /// let max_abs = a.map_axis_skipnan(Axis(1), |lane| {
+    ///     lane.map(|x| x.raw().abs()).fold(None, |max: Option<f64>, x| {
+    ///         Some(max.map_or(x, |max| max.max(x)))
+    ///     })
+    /// });
+    /// assert_eq!(max_abs, array![Some(3.), None]);
+    /// ```
+    fn map_axis_skipnan<'a, B, F>(&'a self, axis: Axis, mapping: F) -> Array<B, D::Smaller>
+    where
+        A: 'a,
+        D: RemoveAxis,
+        F: FnMut(SkipNan<'a, A>) -> B;
+
     /// Reduce the values along an axis into just one value, producing a new
     /// array with one less dimension.
     ///
@@ -290,6 +546,170 @@ where
         D: RemoveAxis,
         F: FnMut(ArrayViewMut1<'a, A::NotNan>) -> B;
 
+    /// Returns the sum of the non-NaN elements of each 1-dimensional lane
+    /// along `axis`.
+    ///
+    /// The sum of a lane whose elements are all NaN (or that is empty) is NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::MaybeNanExt;
+    /// use std::f64::NAN;
+    ///
+    /// let a = array![[1., NAN], [3., NAN]];
+    /// let sum = a.sum_axis_skipnan(Axis(0));
+    /// assert_eq!(sum[0], 4.);
+    /// assert!(sum[1].is_nan());
+    /// ```
+    fn sum_axis_skipnan(&self, axis: Axis) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Clone + Zero + Add<Output = A::NotNan>;
+
+    /// Returns the arithmetic mean of the non-NaN elements of each
+    /// 1-dimensional lane along `axis`, like NumPy's `nanmean`.
+    ///
+    /// The mean of a lane whose elements are all NaN (or that is empty) is
+    /// NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::NotNan::from_usize()`
+    /// fails to convert the number of non-NaN elements of a lane.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::MaybeNanExt;
+    /// use std::f64::NAN;
+    ///
+    /// let a = array![[1., NAN, 3.], [NAN, NAN, NAN]];
+    /// let mean = a.mean_axis_skipnan(Axis(1));
+    /// assert_eq!(mean[0], 2.);
+    /// assert!(mean[1].is_nan());
+    /// ```
+    fn mean_axis_skipnan(&self, axis: Axis) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Clone + Zero + FromPrimitive + Add<Output = A::NotNan> + Div<Output = A::NotNan>;
+
+    /// Returns the variance of the non-NaN elements of each 1-dimensional
+    /// lane along `axis`, like NumPy's `nanvar`.
+    ///
+    /// The parameter `ddof` specifies the "delta degrees of freedom": use
+    /// `ddof = 0` for the population variance and `ddof = 1` for the
+    /// unbiased sample variance. The variance of a lane with no more than
+    /// `ddof` non-NaN elements is NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `ddof` is less than zero or
+    /// if `A::NotNan::from_usize()` fails to convert the number of non-NaN
+    /// elements of a lane.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::MaybeNanExt;
+    /// use noisy_float::types::n64;
+    /// use std::f64::NAN;
+    ///
+    /// let a = array![[1., NAN, 3.], [NAN, 2., NAN]];
+    /// let var = a.var_axis_skipnan(Axis(1), n64(1.));
+    /// assert_eq!(var[0], 2.);
+    /// assert!(var[1].is_nan());
+    /// ```
+    fn var_axis_skipnan(&self, axis: Axis, ddof: A::NotNan) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Float + FromPrimitive;
+
+    /// Returns the standard deviation of the non-NaN elements of each
+    /// 1-dimensional lane along `axis`, the square root of
+    /// [`var_axis_skipnan`](#tymethod.var_axis_skipnan).
+    ///
+    /// **Panics** if `axis` is out of bounds, if `ddof` is less than zero or
+    /// if `A::NotNan::from_usize()` fails to convert the number of non-NaN
+    /// elements of a lane.
+    fn std_axis_skipnan(&self, axis: Axis, ddof: A::NotNan) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Float + FromPrimitive;
+
+    /// Returns the minimum of the non-NaN elements of each 1-dimensional lane
+    /// along `axis`.
+    ///
+    /// The minimum of a lane whose elements are all NaN (or that is empty)
+    /// is NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::MaybeNanExt;
+    ///
+    /// let a = array![[Some(3), None], [Some(1), None]];
+    /// assert_eq!(a.min_axis_skipnan(Axis(0)), array![Some(1), None]);
+    /// ```
+    fn min_axis_skipnan(&self, axis: Axis) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Clone + Ord;
+
+    /// Returns the maximum of the non-NaN elements of each 1-dimensional lane
+    /// along `axis`.
+    ///
+    /// The maximum of a lane whose elements are all NaN (or that is empty)
+    /// is NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn max_axis_skipnan(&self, axis: Axis) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Clone + Ord;
+
+    /// Like [`mean_axis_skipnan`](#tymethod.mean_axis_skipnan), but computes
+    /// the means of the lanes in parallel.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::NotNan::from_usize()`
+    /// fails to convert the number of non-NaN elements of a lane.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::MaybeNanExt;
+    /// use std::f64::NAN;
+    ///
+    /// let a = array![[1., NAN, 3.], [NAN, NAN, NAN]];
+    /// let mean = a.par_mean_axis_skipnan(Axis(1));
+    /// assert_eq!(mean[0], 2.);
+    /// assert!(mean[1].is_nan());
+    /// ```
+    #[cfg(feature = "rayon")]
+    fn par_mean_axis_skipnan(&self, axis: Axis) -> Array<A, D::Smaller>
+    where
+        A: Send + Sync,
+        D: RemoveAxis,
+        A::NotNan: Clone + Zero + FromPrimitive + Add<Output = A::NotNan> + Div<Output = A::NotNan>;
+
+    /// Like [`var_axis_skipnan`](#tymethod.var_axis_skipnan), but computes
+    /// the variances of the lanes in parallel.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `ddof` is less than zero or
+    /// if `A::NotNan::from_usize()` fails to convert the number of non-NaN
+    /// elements of a lane.
+    #[cfg(feature = "rayon")]
+    fn par_var_axis_skipnan(&self, axis: Axis, ddof: A::NotNan) -> Array<A, D::Smaller>
+    where
+        A: Send + Sync,
+        D: RemoveAxis,
+        A::NotNan: Float + FromPrimitive + Sync;
+
     private_decl! {}
 }
 
@@ -299,6 +719,38 @@ where
     S: Data<Elem = A>,
     D: Dimension,
 {
+    fn has_nan(&self) -> bool {
+        self.iter().any(|elem| elem.is_nan())
+    }
+
+    fn all_nan(&self) -> bool {
+        self.iter().all(|elem| elem.is_nan())
+    }
+
+    fn has_nan_axis(&self, axis: Axis) -> Array<bool, D::Smaller>
+    where
+        D: RemoveAxis,
+    {
+        // `map_axis` panics if the length of `axis` is zero.
+        if self.len_of(axis) == 0 {
+            Array::from_elem(self.raw_dim().remove_axis(axis), false)
+        } else {
+            self.map_axis(axis, |lane| lane.has_nan())
+        }
+    }
+
+    fn all_nan_axis(&self, axis: Axis) -> Array<bool, D::Smaller>
+    where
+        D: RemoveAxis,
+    {
+        // `map_axis` panics if the length of `axis` is zero.
+        if self.len_of(axis) == 0 {
+            Array::from_elem(self.raw_dim().remove_axis(axis), true)
+        } else {
+            self.map_axis(axis, |lane| lane.all_nan())
+        }
+    }
+
     fn fold_skipnan<'a, F, B>(&'a self, init: B, mut f: F) -> B
     where
         A: 'a,
@@ -339,6 +791,46 @@ where
         })
     }
 
+    fn indexed_visit_skipnan<'a, F>(&'a self, mut f: F)
+    where
+        A: 'a,
+        F: FnMut(D::Pattern, &'a A::NotNan),
+    {
+        for (index, elem) in self.indexed_iter() {
+            if let Some(not_nan) = elem.try_as_not_nan() {
+                f(index, not_nan)
+            }
+        }
+    }
+
+    fn zip_fold_skipnan<'a, S2, F, B>(
+        &'a self,
+        other: &'a ArrayBase<S2, D>,
+        init: B,
+        mut f: F,
+    ) -> Result<(B, usize), ShapeMismatch>
+    where
+        A: 'a,
+        S2: Data<Elem = A>,
+        F: FnMut(B, &'a A::NotNan, &'a A::NotNan) -> B,
+    {
+        if self.shape() != other.shape() {
+            return Err(ShapeMismatch {
+                first_shape: self.shape().to_vec(),
+                second_shape: other.shape().to_vec(),
+            });
+        }
+        let mut acc = Some(init);
+        let mut n_pairs = 0;
+        Zip::from(self).and(other).apply(|x, y| {
+            if let (Some(x), Some(y)) = (x.try_as_not_nan(), y.try_as_not_nan()) {
+                acc = Some(f(acc.take().unwrap(), x, y));
+                n_pairs += 1;
+            }
+        });
+        Ok((acc.unwrap(), n_pairs))
+    }
+
     fn fold_axis_skipnan<B, F>(&self, axis: Axis, init: B, mut fold: F) -> Array<B, D::Smaller>
     where
         D: RemoveAxis,
@@ -354,6 +846,23 @@ where
         })
     }
 
+    fn map_axis_skipnan<'a, B, F>(&'a self, axis: Axis, mut mapping: F) -> Array<B, D::Smaller>
+    where
+        A: 'a,
+        D: RemoveAxis,
+        F: FnMut(SkipNan<'a, A>) -> B,
+    {
+        // `map_axis` panics if the length of `axis` is zero.
+        if self.len_of(axis) == 0 {
+            let empty: &'a [A] = &[];
+            Array::from_shape_fn(self.raw_dim().remove_axis(axis), |_| {
+                mapping(SkipNan::new(ArrayView1::from(empty)))
+            })
+        } else {
+            self.map_axis(axis, |lane| mapping(SkipNan::new(lane)))
+        }
+    }
+
     fn map_axis_skipnan_mut<'a, B, F>(
         &'a mut self,
         axis: Axis,
@@ -368,13 +877,282 @@ where
         self.map_axis_mut(axis, |lane| mapping(A::remove_nan_mut(lane)))
     }
 
+    fn sum_axis_skipnan(&self, axis: Axis) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Clone + Zero + Add<Output = A::NotNan>,
+    {
+        self.map_axis_skipnan(axis, |lane| {
+            let (sum, count) = lane.fold((A::NotNan::zero(), 0), |(sum, count), x| {
+                (sum + x.clone(), count + 1)
+            });
+            A::from_not_nan_opt(if count == 0 { None } else { Some(sum) })
+        })
+    }
+
+    fn mean_axis_skipnan(&self, axis: Axis) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Clone + Zero + FromPrimitive + Add<Output = A::NotNan> + Div<Output = A::NotNan>,
+    {
+        self.map_axis_skipnan(axis, mean_skipnan)
+    }
+
+    fn var_axis_skipnan(&self, axis: Axis, ddof: A::NotNan) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Float + FromPrimitive,
+    {
+        assert!(
+            ddof >= A::NotNan::zero(),
+            "`ddof` must not be less than zero."
+        );
+        self.map_axis_skipnan(axis, |lane| var_skipnan(lane, ddof))
+    }
+
+    fn std_axis_skipnan(&self, axis: Axis, ddof: A::NotNan) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Float + FromPrimitive,
+    {
+        self.var_axis_skipnan(axis, ddof)
+            .map(|var| A::from_not_nan_opt(var.try_as_not_nan().map(|var| var.sqrt())))
+    }
+
+    fn min_axis_skipnan(&self, axis: Axis) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Clone + Ord,
+    {
+        self.map_axis_skipnan(axis, |lane| A::from_not_nan_opt(lane.min().cloned()))
+    }
+
+    fn max_axis_skipnan(&self, axis: Axis) -> Array<A, D::Smaller>
+    where
+        D: RemoveAxis,
+        A::NotNan: Clone + Ord,
+    {
+        self.map_axis_skipnan(axis, |lane| A::from_not_nan_opt(lane.max().cloned()))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_mean_axis_skipnan(&self, axis: Axis) -> Array<A, D::Smaller>
+    where
+        A: Send + Sync,
+        D: RemoveAxis,
+        A::NotNan: Clone + Zero + FromPrimitive + Add<Output = A::NotNan> + Div<Output = A::NotNan>,
+    {
+        par_map_lanes(self, axis, |lane| mean_skipnan(SkipNan::new(lane)))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_var_axis_skipnan(&self, axis: Axis, ddof: A::NotNan) -> Array<A, D::Smaller>
+    where
+        A: Send + Sync,
+        D: RemoveAxis,
+        A::NotNan: Float + FromPrimitive + Sync,
+    {
+        assert!(
+            ddof >= A::NotNan::zero(),
+            "`ddof` must not be less than zero."
+        );
+        par_map_lanes(self, axis, |lane| var_skipnan(SkipNan::new(lane), ddof))
+    }
+
+    private_impl! {}
+}
+
+/// Returns the arithmetic mean of the non-NaN elements of a lane, or NaN if
+/// there are none.
+fn mean_skipnan<A>(lane: SkipNan<'_, A>) -> A
+where
+    A: MaybeNan,
+    A::NotNan: Clone + Zero + FromPrimitive + Add<Output = A::NotNan> + Div<Output = A::NotNan>,
+{
+    let (sum, count) = lane.fold((A::NotNan::zero(), 0), |(sum, count), x| {
+        (sum + x.clone(), count + 1)
+    });
+    A::from_not_nan_opt(if count == 0 {
+        None
+    } else {
+        let count = A::NotNan::from_usize(count)
+            .expect("Converting the number of elements to `A::NotNan` must not fail.");
+        Some(sum / count)
+    })
+}
+
+/// Returns the variance of the non-NaN elements of a lane, or NaN if there
+/// are no more than `ddof` of them.
+fn var_skipnan<A>(lane: SkipNan<'_, A>, ddof: A::NotNan) -> A
+where
+    A: MaybeNan,
+    A::NotNan: Float + FromPrimitive,
+{
+    // Welford's algorithm, over the non-NaN elements.
+    let mut count = A::NotNan::zero();
+    let mut mean = A::NotNan::zero();
+    let mut sum_sq_dev = A::NotNan::zero();
+    for &x in lane {
+        count = count + A::NotNan::one();
+        let delta = x - mean;
+        mean = mean + delta / count;
+        sum_sq_dev = sum_sq_dev + delta * (x - mean);
+    }
+    A::from_not_nan_opt(if count <= ddof {
+        None
+    } else {
+        Some(sum_sq_dev / (count - ddof))
+    })
+}
+
+/// An iterator over the non-NaN elements of a 1-dimensional lane, created by
+/// [`map_axis_skipnan`].
+///
+/// [`map_axis_skipnan`]: trait.MaybeNanExt.html#tymethod.map_axis_skipnan
+pub struct SkipNan<'a, A> {
+    iter: ndarray::iter::Iter<'a, A, Ix1>,
+}
+
+impl<'a, A> SkipNan<'a, A> {
+    fn new(lane: ArrayView1<'a, A>) -> Self {
+        SkipNan {
+            iter: lane.into_iter(),
+        }
+    }
+}
+
+impl<'a, A> Clone for SkipNan<'a, A> {
+    fn clone(&self) -> Self {
+        SkipNan {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, A: MaybeNan> Iterator for SkipNan<'a, A> {
+    type Item = &'a A::NotNan;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.by_ref().find_map(A::try_as_not_nan)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// Which lanes should be dropped by [`drop_nan_rows`] and [`drop_nan_cols`].
+///
+/// [`drop_nan_rows`]: trait.MaybeNan2dExt.html#tymethod.drop_nan_rows
+/// [`drop_nan_cols`]: trait.MaybeNan2dExt.html#tymethod.drop_nan_cols
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DropNanHow {
+    /// Drop a lane if any of its elements is NaN.
+    Any,
+    /// Drop a lane if all of its elements are NaN.
+    All,
+}
+
+impl DropNanHow {
+    /// Returns `true` if `lane` should be dropped.
+    fn should_drop<A: MaybeNan>(self, lane: ArrayView1<'_, A>) -> bool {
+        match self {
+            DropNanHow::Any => lane.has_nan(),
+            DropNanHow::All => lane.all_nan(),
+        }
+    }
+}
+
+/// Extension trait for 2-D `ArrayBase` providing NaN-related functionality.
+pub trait MaybeNan2dExt<A, S>
+where
+    A: MaybeNan,
+    S: Data<Elem = A>,
+{
+    /// Returns a new array with the rows containing NaN values removed,
+    /// together with the indices (in `self`) of the rows that have been kept.
+    ///
+    /// `how` determines whether a row is removed when it contains any NaN
+    /// value or only when all of its values are NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{DropNanHow, MaybeNan2dExt};
+    /// use std::f64::NAN;
+    ///
+    /// let a = array![[1., 2.], [NAN, 3.], [NAN, NAN]];
+    ///
+    /// let (dropped, kept) = a.drop_nan_rows(DropNanHow::Any);
+    /// assert_eq!(dropped, array![[1., 2.]]);
+    /// assert_eq!(kept, vec![0]);
+    ///
+    /// let (dropped, kept) = a.drop_nan_rows(DropNanHow::All);
+    /// assert_eq!(kept, vec![0, 1]);
+    /// assert_eq!(dropped.shape(), &[2, 2]);
+    /// ```
+    fn drop_nan_rows(&self, how: DropNanHow) -> (Array2<A>, Vec<usize>)
+    where
+        A: Clone;
+
+    /// Returns a new array with the columns containing NaN values removed,
+    /// together with the indices (in `self`) of the columns that have been kept.
+    ///
+    /// See [`drop_nan_rows`](#tymethod.drop_nan_rows) for details.
+    fn drop_nan_cols(&self, how: DropNanHow) -> (Array2<A>, Vec<usize>)
+    where
+        A: Clone;
+
+    private_decl! {}
+}
+
+impl<A, S> MaybeNan2dExt<A, S> for ArrayBase<S, Ix2>
+where
+    A: MaybeNan,
+    S: Data<Elem = A>,
+{
+    fn drop_nan_rows(&self, how: DropNanHow) -> (Array2<A>, Vec<usize>)
+    where
+        A: Clone,
+    {
+        let kept: Vec<usize> = self
+            .outer_iter()
+            .enumerate()
+            .filter(|(_, row)| !how.should_drop(row.view()))
+            .map(|(i, _)| i)
+            .collect();
+        let dropped = Array2::from_shape_fn((kept.len(), self.cols()), |(i, j)| {
+            self[(kept[i], j)].clone()
+        });
+        (dropped, kept)
+    }
+
+    fn drop_nan_cols(&self, how: DropNanHow) -> (Array2<A>, Vec<usize>)
+    where
+        A: Clone,
+    {
+        let kept: Vec<usize> = self
+            .axis_iter(Axis(1))
+            .enumerate()
+            .filter(|(_, col)| !how.should_drop(col.view()))
+            .map(|(j, _)| j)
+            .collect();
+        let dropped = Array2::from_shape_fn((self.rows(), kept.len()), |(i, j)| {
+            self[(i, kept[j])].clone()
+        });
+        (dropped, kept)
+    }
+
     private_impl! {}
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ndarray::array;
     use quickcheck_macros::quickcheck;
+    use std::f64;
 
     #[quickcheck]
     fn remove_nan_mut_idempotent(is_nan: Vec<bool>) -> bool {
@@ -407,6 +1185,250 @@ mod tests {
         let view = ArrayViewMut1::from_shape(values.len(), &mut values).unwrap();
         remove_nan_mut(view).len() == non_nan_count
     }
+
+    #[test]
+    fn test_not_none_ne() {
+        assert!(NotNone::new(1).ne(&NotNone::new(2)));
+        assert!(!NotNone::new(1).ne(&NotNone::new(1)));
+    }
+
+    #[test]
+    fn test_indexed_visit_skipnan_matches_indexed_fold_skipnan() {
+        let a = array![[1., f64::NAN, 3.], [f64::NAN, 5., f64::NAN]];
+        let mut visited = Vec::new();
+        a.indexed_visit_skipnan(|index, value| visited.push((index, value.raw())));
+        let folded = a.indexed_fold_skipnan(Vec::new(), |mut acc, (index, value)| {
+            acc.push((index, value.raw()));
+            acc
+        });
+        assert_eq!(visited, vec![((0, 0), 1.), ((0, 2), 3.), ((1, 1), 5.)]);
+        assert_eq!(visited, folded);
+    }
+
+    #[test]
+    fn test_zip_fold_skipnan() {
+        let x = array![[Some(1), None], [Some(3), Some(4)]];
+        let y = array![[Some(2), Some(5)], [None, Some(6)]];
+        let (sum, n_pairs) = x
+            .zip_fold_skipnan(&y, 0, |acc, x: &NotNone<i32>, y: &NotNone<i32>| {
+                acc + **x * **y
+            })
+            .unwrap();
+        assert_eq!(sum, 26);
+        assert_eq!(n_pairs, 2);
+
+        let all_none: Array2<Option<i32>> = Array::from_elem((2, 2), None);
+        assert_eq!(
+            x.zip_fold_skipnan(&all_none, 0, |acc, _, _| acc + 1)
+                .unwrap(),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn test_zip_fold_skipnan_shape_mismatch() {
+        let x = array![[1., 2.], [3., 4.]];
+        let y = array![[1., 2., 3., 4.]];
+        assert!(x.zip_fold_skipnan(&y, 0, |acc, _, _| acc + 1).is_err());
+    }
+
+    #[test]
+    fn test_drop_nan_cols() {
+        let a = array![[Some(1), None, Some(3)], [Some(4), None, None]];
+
+        let (dropped, kept) = a.drop_nan_cols(DropNanHow::Any);
+        assert_eq!(dropped, array![[Some(1)], [Some(4)]]);
+        assert_eq!(kept, vec![0]);
+
+        let (dropped, kept) = a.drop_nan_cols(DropNanHow::All);
+        assert_eq!(dropped, array![[Some(1), Some(3)], [Some(4), None]]);
+        assert_eq!(kept, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_drop_nan_rows_empty() {
+        let a = Array2::<f64>::zeros((0, 3));
+        let (dropped, kept) = a.drop_nan_rows(DropNanHow::Any);
+        assert_eq!(dropped.shape(), &[0, 3]);
+        assert!(kept.is_empty());
+    }
+
+    #[test]
+    fn test_has_nan_and_all_nan() {
+        let a = array![[1., f64::NAN], [f64::NAN, f64::NAN], [2., 3.]];
+        assert!(a.has_nan());
+        assert!(!a.all_nan());
+        assert_eq!(a.has_nan_axis(Axis(1)), array![true, true, false]);
+        assert_eq!(a.all_nan_axis(Axis(1)), array![false, true, false]);
+        assert_eq!(a.all_nan_axis(Axis(0)), array![false, false]);
+
+        let empty = Array1::<f64>::zeros(0);
+        assert!(!empty.has_nan());
+        assert!(empty.all_nan());
+    }
+
+    #[test]
+    fn test_has_nan_axis_zero_length() {
+        let a = Array2::<f64>::zeros((0, 3));
+        assert_eq!(a.has_nan_axis(Axis(0)), array![false, false, false]);
+        assert!(a.has_nan_axis(Axis(1)).is_empty());
+    }
+
+    #[test]
+    fn test_all_nan_axis_zero_length() {
+        let a = Array2::<f64>::zeros((0, 3));
+        assert_eq!(a.all_nan_axis(Axis(0)), array![true, true, true]);
+        assert!(a.all_nan_axis(Axis(1)).is_empty());
+    }
+
+    #[test]
+    fn test_not_none_operators() {
+        let mut x = NotNone::from(6);
+        x += NotNone::new(4);
+        x -= NotNone::new(2);
+        x *= NotNone::new(3);
+        x /= NotNone::new(4);
+        x %= NotNone::new(4);
+        assert_eq!(*x, 2);
+        assert_eq!(*(-x), -2);
+        assert_eq!(x.as_ref(), &2);
+        *x.as_mut() = 7;
+        assert_eq!(x.into_inner(), Some(7));
+    }
+
+    #[quickcheck]
+    fn remove_nan_mut_stable_preserves_order(values: Vec<Option<i32>>) -> bool {
+        let expected: Vec<_> = values.iter().filter_map(|&v| v).collect();
+        let mut values = values;
+        let view = ArrayViewMut1::from_shape(values.len(), &mut values).unwrap();
+        let removed: Vec<_> = Option::<i32>::remove_nan_mut_stable(view)
+            .iter()
+            .map(|v| **v)
+            .collect();
+        removed == expected
+    }
+
+    #[test]
+    fn test_remove_nan_mut_stable_floats() {
+        let mut a = array![f64::NAN, 3., 1., f64::NAN, f64::NAN, 2., 5.];
+        let removed = f64::remove_nan_mut_stable(a.view_mut());
+        assert_eq!(removed.mapv(|x| x.raw()), array![3., 1., 2., 5.]);
+    }
+
+    #[quickcheck]
+    fn axis_skipnan_match_statistics_of_lanes(values: Vec<Option<i16>>) -> bool {
+        let rows = 3;
+        let cols = values.len() / rows;
+        let a = Array::from_shape_vec((rows, cols), values[..rows * cols].to_vec())
+            .unwrap()
+            .mapv(|x| x.map_or(f64::NAN, f64::from));
+        let sum = a.sum_axis_skipnan(Axis(0));
+        let mean = a.mean_axis_skipnan(Axis(0));
+        let var = a.var_axis_skipnan(Axis(0), N64::new(1.));
+        let std = a.std_axis_skipnan(Axis(0), N64::new(1.));
+        let close = |x: f64, y: f64| (x - y).abs() <= 1e-9 * y.abs().max(1.);
+        a.gencolumns().into_iter().enumerate().all(|(j, col)| {
+            let xs: Vec<f64> = col.iter().cloned().filter(|x| !x.is_nan()).collect();
+            let n = xs.len() as f64;
+            let expected_mean = xs.iter().sum::<f64>() / n;
+            let expected_var =
+                xs.iter().map(|x| (x - expected_mean).powi(2)).sum::<f64>() / (n - 1.);
+            match xs.len() {
+                0 => sum[j].is_nan() && mean[j].is_nan() && var[j].is_nan(),
+                1 => sum[j] == xs[0] && mean[j] == xs[0] && var[j].is_nan() && std[j].is_nan(),
+                _ => {
+                    sum[j] == xs.iter().sum::<f64>()
+                        && close(mean[j], expected_mean)
+                        && close(var[j], expected_var)
+                        && close(std[j], expected_var.sqrt())
+                }
+            }
+        })
+    }
+
+    #[quickcheck]
+    fn map_axis_skipnan_matches_map_axis_skipnan_mut(values: Vec<Option<i8>>) -> bool {
+        if values.len() < 2 {
+            return true;
+        }
+        let mut a = Array::from_shape_vec(
+            (values.len() / 2, 2),
+            values[..values.len() / 2 * 2].to_vec(),
+        )
+        .unwrap();
+        let collect = |lane: SkipNan<'_, Option<i8>>| {
+            let mut lane: Vec<i8> = lane.map(|x| **x).collect();
+            lane.sort();
+            lane
+        };
+        let mapped = a.map_axis_skipnan(Axis(0), collect);
+        let mapped_mut = a.map_axis_skipnan_mut(Axis(0), |lane| {
+            let mut lane: Vec<i8> = lane.iter().map(|x| **x).collect();
+            lane.sort();
+            lane
+        });
+        mapped == mapped_mut
+    }
+
+    #[test]
+    fn test_remove_nan_mut_strided() {
+        let mut a = array![[1., 10.], [f64::NAN, 20.], [3., 30.], [-2., 40.]];
+        let mut column = a.column_mut(0);
+        let removed = f64::remove_nan_mut_stable(column.view_mut());
+        assert_eq!(removed.mapv(|x| x.raw()), array![1., 3., -2.]);
+        let mut b = array![[Some(1), Some(10)], [None, Some(20)], [Some(3), Some(30)]];
+        let sums = b.map_axis_skipnan_mut(Axis(0), |lane| {
+            lane.iter().map(|x: &NotNone<i32>| **x).sum::<i32>()
+        });
+        assert_eq!(sums, array![4, 60]);
+    }
+
+    #[test]
+    fn test_map_axis_skipnan_zero_length_axis() {
+        let a = Array2::<f64>::zeros((2, 0));
+        assert_eq!(
+            a.map_axis_skipnan(Axis(1), |lane| lane.count()),
+            array![0, 0]
+        );
+        assert_eq!(a.map_axis_skipnan(Axis(0), |lane| lane.count()).len(), 0);
+    }
+
+    #[test]
+    fn test_min_max_axis_skipnan() {
+        let a = array![
+            [3., f64::NAN, 2.],
+            [f64::NAN, f64::NAN, 7.],
+            [1., f64::NAN, 4.]
+        ];
+        let min = a.min_axis_skipnan(Axis(0));
+        let max = a.max_axis_skipnan(Axis(0));
+        assert_eq!((min[0], min[2]), (1., 2.));
+        assert_eq!((max[0], max[2]), (3., 7.));
+        assert!(min[1].is_nan() && max[1].is_nan());
+
+        let b = array![[Some(5), None], [Some(-1), Some(2)]];
+        assert_eq!(b.min_axis_skipnan(Axis(1)), array![Some(5), Some(-1)]);
+        assert_eq!(b.max_axis_skipnan(Axis(1)), array![Some(5), Some(2)]);
+        let empty = Array2::<f64>::zeros((0, 2));
+        assert!(empty.mean_axis_skipnan(Axis(0)).iter().all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn test_option_float_axis_skipnan() {
+        let a = array![
+            [Some(1.), None],
+            [Some(f64::NAN), None],
+            [Some(3.), Some(4.)]
+        ];
+        assert_eq!(a.mean_axis_skipnan(Axis(0)), array![Some(2.), Some(4.)]);
+        assert_eq!(
+            a.sum_axis_skipnan(Axis(1)),
+            array![Some(1.), None, Some(7.)]
+        );
+    }
 }
 
 mod impl_not_none;
+mod masked_view;
+mod not_none_float;
+mod sentinel;
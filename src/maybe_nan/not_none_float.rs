@@ -0,0 +1,304 @@
+use super::{cast_view_mut, remove_nan_mut, MaybeNan};
+use core::cmp;
+use core::fmt;
+use core::ops::{
+    Add, AddAssign, Deref, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use ndarray::ArrayViewMut1;
+use num_traits::{Float, FromPrimitive, ToPrimitive, Zero};
+
+/// A thin wrapper around `Option<F>`, for a floating-point type `F`, that
+/// guarantees that the value is neither `None` nor NaN.
+///
+/// This is the `NotNan` type of `Option<f32>` and `Option<f64>`, where both
+/// `None` and `Some(NaN)` are treated as NaN values. Values are ordered by
+/// the wrapped float, which is total since NaN is excluded. It dereferences
+/// to `F` and implements the arithmetic operators, so the values obtained
+/// from the NaN-skipping methods can be used directly in computations.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::{MaybeNanExt, NotNoneFloat};
+///
+/// let a = array![Some(1.5), None, Some(::std::f64::NAN), Some(4.)];
+/// let sum = a.fold_skipnan(NotNoneFloat::new(0.), |acc, &x| acc + x);
+/// assert_eq!(*sum, 5.5);
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct NotNoneFloat<F>(Option<F>);
+
+impl<F: Float> NotNoneFloat<F> {
+    /// Creates a new `NotNoneFloat` containing the given value.
+    ///
+    /// **Panics** if `value` is NaN.
+    pub fn new(value: F) -> NotNoneFloat<F> {
+        assert!(!value.is_nan(), "The value must not be NaN.");
+        NotNoneFloat(Some(value))
+    }
+
+    /// Creates a new `NotNoneFloat` containing the given value.
+    ///
+    /// Returns `None` if `value` is `None` or NaN.
+    pub fn try_new(value: Option<F>) -> Option<NotNoneFloat<F>> {
+        match value {
+            Some(inner) if !inner.is_nan() => Some(NotNoneFloat(value)),
+            _ => None,
+        }
+    }
+
+    /// Wraps the result of an arithmetic operation on non-NaN values.
+    ///
+    /// Like the operators of `N32` and `N64`, only checks that the result is
+    /// not NaN in debug builds.
+    fn from_result(value: F) -> NotNoneFloat<F> {
+        debug_assert!(!value.is_nan(), "The result must not be NaN.");
+        NotNoneFloat(Some(value))
+    }
+}
+
+impl<F> NotNoneFloat<F> {
+    /// Returns the underling option.
+    pub fn into_inner(self) -> Option<F> {
+        self.0
+    }
+
+    /// Moves the value out of the inner option.
+    ///
+    /// This method is guaranteed not to panic.
+    pub fn unwrap(self) -> F {
+        match self.0 {
+            Some(inner) => inner,
+            None => unsafe { core::hint::unreachable_unchecked() },
+        }
+    }
+}
+
+impl<F> Deref for NotNoneFloat<F> {
+    type Target = F;
+    fn deref(&self) -> &F {
+        match self.0 {
+            Some(ref inner) => inner,
+            None => unsafe { core::hint::unreachable_unchecked() },
+        }
+    }
+}
+
+impl<F> AsRef<F> for NotNoneFloat<F> {
+    fn as_ref(&self) -> &F {
+        self.deref()
+    }
+}
+
+impl<F: fmt::Display> fmt::Display for NotNoneFloat<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        self.deref().fmt(f)
+    }
+}
+
+impl<F: PartialEq> PartialEq for NotNoneFloat<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref().eq(other)
+    }
+}
+
+impl<F: PartialEq> Eq for NotNoneFloat<F> {}
+
+impl<F: PartialOrd> PartialOrd for NotNoneFloat<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F: PartialOrd> Ord for NotNoneFloat<F> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.deref()
+            .partial_cmp(other)
+            .expect("Non-NaN values must be comparable.")
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($Op:ident, $op:ident, $OpAssign:ident, $op_assign:ident) => {
+        impl<F: Float> $Op for NotNoneFloat<F> {
+            type Output = NotNoneFloat<F>;
+            #[inline]
+            fn $op(self, rhs: Self) -> Self::Output {
+                NotNoneFloat::from_result(self.unwrap().$op(rhs.unwrap()))
+            }
+        }
+
+        impl<F: Float> $OpAssign for NotNoneFloat<F> {
+            #[inline]
+            fn $op_assign(&mut self, rhs: Self) {
+                *self = (*self).$op(rhs);
+            }
+        }
+    };
+}
+impl_binary_op!(Add, add, AddAssign, add_assign);
+impl_binary_op!(Sub, sub, SubAssign, sub_assign);
+impl_binary_op!(Mul, mul, MulAssign, mul_assign);
+impl_binary_op!(Div, div, DivAssign, div_assign);
+impl_binary_op!(Rem, rem, RemAssign, rem_assign);
+
+impl<F: Float> Neg for NotNoneFloat<F> {
+    type Output = NotNoneFloat<F>;
+    #[inline]
+    fn neg(self) -> Self::Output {
+        NotNoneFloat(Some(-self.unwrap()))
+    }
+}
+
+impl<F: Float> Zero for NotNoneFloat<F> {
+    #[inline]
+    fn zero() -> Self {
+        NotNoneFloat(Some(F::zero()))
+    }
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.deref().is_zero()
+    }
+}
+
+impl<F: Float> ToPrimitive for NotNoneFloat<F> {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        self.deref().to_i64()
+    }
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        self.deref().to_u64()
+    }
+    #[inline]
+    fn to_f32(&self) -> Option<f32> {
+        self.deref().to_f32()
+    }
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        self.deref().to_f64()
+    }
+}
+
+impl<F: Float + FromPrimitive> FromPrimitive for NotNoneFloat<F> {
+    #[inline]
+    fn from_i64(n: i64) -> Option<Self> {
+        Self::try_new(F::from_i64(n))
+    }
+    #[inline]
+    fn from_u64(n: u64) -> Option<Self> {
+        Self::try_new(F::from_u64(n))
+    }
+    #[inline]
+    fn from_usize(n: usize) -> Option<Self> {
+        Self::try_new(F::from_usize(n))
+    }
+    #[inline]
+    fn from_f32(n: f32) -> Option<Self> {
+        Self::try_new(F::from_f32(n))
+    }
+    #[inline]
+    fn from_f64(n: f64) -> Option<Self> {
+        Self::try_new(F::from_f64(n))
+    }
+}
+
+macro_rules! impl_maybenan_for_opt_fxx {
+    ($fxx:ident) => {
+        impl MaybeNan for Option<$fxx> {
+            type NotNan = NotNoneFloat<$fxx>;
+
+            fn is_nan(&self) -> bool {
+                match self {
+                    None => true,
+                    Some(num) => num.is_nan(),
+                }
+            }
+
+            fn try_as_not_nan(&self) -> Option<&NotNoneFloat<$fxx>> {
+                if self.is_nan() {
+                    None
+                } else {
+                    // This is safe because we have checked for the `None` and
+                    // NaN cases, and `NotNoneFloat<$fxx>` is a thin wrapper
+                    // around `Option<$fxx>`.
+                    Some(unsafe { &*(self as *const Option<$fxx> as *const NotNoneFloat<$fxx>) })
+                }
+            }
+
+            fn from_not_nan(value: NotNoneFloat<$fxx>) -> Option<$fxx> {
+                value.0
+            }
+
+            fn from_not_nan_opt(value: Option<NotNoneFloat<$fxx>>) -> Option<$fxx> {
+                value.and_then(|v| v.0)
+            }
+
+            fn from_not_nan_ref_opt(value: Option<&NotNoneFloat<$fxx>>) -> &Option<$fxx> {
+                match value {
+                    None => &None,
+                    Some(num) => &num.0,
+                }
+            }
+
+            fn remove_nan_mut(view: ArrayViewMut1<'_, Self>) -> ArrayViewMut1<'_, Self::NotNan> {
+                let not_nan = remove_nan_mut(view);
+                // This is safe because `remove_nan_mut` has removed the `None`
+                // and NaN values, and `NotNoneFloat<$fxx>` is a thin wrapper
+                // around `Option<$fxx>`.
+                unsafe { cast_view_mut(not_nan) }
+            }
+        }
+    };
+}
+impl_maybenan_for_opt_fxx!(f32);
+impl_maybenan_for_opt_fxx!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MaybeNanExt, QuantileExt};
+    use ndarray::{arr0, array, Axis};
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(NotNoneFloat::try_new(Some(1.)), Some(NotNoneFloat::new(1.)));
+        assert_eq!(NotNoneFloat::try_new(Some(::std::f64::NAN)), None);
+        assert_eq!(NotNoneFloat::<f32>::try_new(None), None);
+    }
+
+    #[quickcheck]
+    fn min_skipnan_matches_filtered_min(values: Vec<Option<f32>>) -> bool {
+        let a = ndarray::Array1::from_vec(values.clone());
+        let expected = values
+            .iter()
+            .filter_map(|&x| x.filter(|x| !x.is_nan()))
+            .fold(None, |min: Option<f32>, x| {
+                Some(min.map_or(x, |min| min.min(x)))
+            });
+        a.min_skipnan() == &expected
+    }
+
+    #[test]
+    fn test_option_float_none_and_nan_are_skipped() {
+        let mut a = array![Some(1.), None, Some(::std::f64::NAN), Some(-2.5)];
+        assert!(a[1].is_nan());
+        assert!(a[2].is_nan());
+        assert!(!a[3].is_nan());
+        assert_eq!(a.fold_skipnan(0., |acc, &x| acc + *x), -1.5);
+        assert_eq!(a.map_axis_skipnan_mut(Axis(0), |view| view.len()), arr0(2));
+    }
+
+    #[test]
+    fn test_option_float_remove_nan_mut() {
+        let mut a = array![None, Some(3f32), Some(::std::f32::NAN), Some(1.)];
+        let mut not_nan = Option::<f32>::remove_nan_mut_stable(a.view_mut());
+        assert_eq!(not_nan.len(), 2);
+        assert_eq!(*not_nan[0], 3.);
+        not_nan[1] = NotNoneFloat::new(2.);
+        assert_eq!(a[1], Some(2.));
+    }
+}
@@ -0,0 +1,211 @@
+use super::{cast_view_mut, remove_nan_mut, MaybeNan};
+use core::cmp;
+use core::fmt;
+use core::ops::Deref;
+use ndarray::ArrayViewMut1;
+
+/// A simpler alternative to implementing [`MaybeNan`] by hand, intended for
+/// user-defined types (e.g. newtypes around floating-point numbers).
+///
+/// Every type implementing `NanSentinel` implements [`MaybeNan`], with
+/// [`NotNanValue<Self>`] as the `NotNan` type, so it can be used with all the
+/// NaN-skipping methods of this crate. No `unsafe` code is required on the
+/// implementor's side.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::{NanSentinel, QuantileExt};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+/// struct Meters(f64);
+///
+/// impl NanSentinel for Meters {
+///     fn is_nan(&self) -> bool {
+///         self.0.is_nan()
+///     }
+///
+///     fn nan() -> &'static Meters {
+///         &Meters(::std::f64::NAN)
+///     }
+/// }
+///
+/// let distances = array![Meters(3.), Meters(::std::f64::NAN), Meters(1.)];
+/// assert_eq!(distances.min_skipnan(), &Meters(1.));
+/// ```
+///
+/// [`MaybeNan`]: trait.MaybeNan.html
+/// [`NotNanValue<Self>`]: struct.NotNanValue.html
+pub trait NanSentinel: Clone + 'static {
+    /// Returns `true` if the value is a NaN value.
+    fn is_nan(&self) -> bool;
+
+    /// Returns a reference to a NaN value.
+    fn nan() -> &'static Self;
+}
+
+/// A thin wrapper around a [`NanSentinel`] type that guarantees that the
+/// value is not NaN.
+///
+/// The ordering of `NotNanValue<T>` is derived from the partial ordering of
+/// `T`: it is total as long as non-NaN values of `T` are always comparable.
+///
+/// [`NanSentinel`]: trait.NanSentinel.html
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct NotNanValue<T>(T);
+
+impl<T: NanSentinel> NotNanValue<T> {
+    /// Creates a new `NotNanValue` containing the given value.
+    ///
+    /// Returns `None` if `value` is NaN.
+    pub fn try_new(value: T) -> Option<NotNanValue<T>> {
+        if value.is_nan() {
+            None
+        } else {
+            Some(NotNanValue(value))
+        }
+    }
+}
+
+impl<T> NotNanValue<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for NotNanValue<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for NotNanValue<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for NotNanValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for NotNanValue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq(&other.0)
+    }
+}
+
+impl<T: PartialEq> Eq for NotNanValue<T> {}
+
+impl<T: PartialOrd> PartialOrd for NotNanValue<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> Ord for NotNanValue<T> {
+    /// **Panics** if the two values are not comparable.
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.0
+            .partial_cmp(&other.0)
+            .expect("Non-NaN values must be comparable.")
+    }
+}
+
+impl<T: NanSentinel> MaybeNan for T {
+    type NotNan = NotNanValue<T>;
+
+    fn is_nan(&self) -> bool {
+        NanSentinel::is_nan(self)
+    }
+
+    fn try_as_not_nan(&self) -> Option<&NotNanValue<T>> {
+        if NanSentinel::is_nan(self) {
+            None
+        } else {
+            // This is safe because we have checked for the NaN case, and
+            // `NotNanValue<T>` is a thin wrapper around `T`.
+            Some(unsafe { &*(self as *const T as *const NotNanValue<T>) })
+        }
+    }
+
+    fn from_not_nan(value: NotNanValue<T>) -> T {
+        value.0
+    }
+
+    fn from_not_nan_opt(value: Option<NotNanValue<T>>) -> T {
+        match value {
+            None => T::nan().clone(),
+            Some(num) => num.0,
+        }
+    }
+
+    fn from_not_nan_ref_opt(value: Option<&NotNanValue<T>>) -> &T {
+        match value {
+            None => T::nan(),
+            Some(num) => &num.0,
+        }
+    }
+
+    fn remove_nan_mut(view: ArrayViewMut1<'_, T>) -> ArrayViewMut1<'_, NotNanValue<T>> {
+        let not_nan = remove_nan_mut(view);
+        // This is safe because `remove_nan_mut` has removed the NaN values,
+        // and `NotNanValue<T>` is a thin wrapper around `T`.
+        unsafe { cast_view_mut(not_nan) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MaybeNanExt, QuantileExt};
+    use ndarray::{array, Axis};
+    use noisy_float::types::n64;
+    use std::f32;
+
+    #[derive(Clone, Debug, PartialEq, PartialOrd)]
+    struct Celsius(f32);
+
+    impl NanSentinel for Celsius {
+        fn is_nan(&self) -> bool {
+            self.0.is_nan()
+        }
+
+        fn nan() -> &'static Celsius {
+            &Celsius(f32::NAN)
+        }
+    }
+
+    #[test]
+    fn test_skipnan_methods_with_sentinel_type() {
+        let a = array![
+            [Celsius(3.), Celsius(f32::NAN), Celsius(-1.)],
+            [Celsius(f32::NAN), Celsius(f32::NAN), Celsius(f32::NAN)]
+        ];
+        assert_eq!(a.fold_skipnan(0., |acc, x| acc + x.as_ref().0), 2.);
+        assert_eq!(a.max_skipnan(), &Celsius(3.));
+        assert_eq!(a.argmin_skipnan(), Ok((0, 2)));
+
+        let median = a
+            .clone()
+            .quantile_axis_skipnan_mut(Axis(1), n64(0.5), &crate::interpolate::Lower)
+            .unwrap();
+        assert_eq!(median[0], Celsius(-1.));
+        assert!(median[1].0.is_nan());
+    }
+
+    #[test]
+    fn test_not_nan_value_try_new() {
+        assert!(NotNanValue::try_new(Celsius(f32::NAN)).is_none());
+        assert_eq!(
+            NotNanValue::try_new(Celsius(1.)).map(NotNanValue::into_inner),
+            Some(Celsius(1.))
+        );
+    }
+}
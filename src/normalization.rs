@@ -0,0 +1,505 @@
+//! Feature scaling: standardization, min-max and robust scaling.
+use crate::numpy_compat::{linear_quantile, median};
+use ndarray::{Array, ArrayBase, ArrayViewMut1, Axis, Data, DataMut, Dimension, Zip};
+use num_traits::{Float, FromPrimitive};
+
+/// Extension trait for `ArrayBase` providing methods to rescale the lanes of
+/// an array, e.g. the features (columns) of a matrix of samples with
+/// `Axis(0)`, before fitting a model.
+///
+/// Each transform comes in four flavours: `*_axis` returns a new array and
+/// `*_axis_inplace` overwrites the elements of the array, while the
+/// `*_skipnan` variants compute the statistics of each lane without its NaN
+/// elements, which are left NaN in the output, instead of propagating them to
+/// the whole lane.
+///
+/// As in scikit-learn's scalers, a lane whose scale (standard deviation,
+/// range or interquartile range) is zero is only shifted, not divided by
+/// zero.
+pub trait NormalizationExt<A, S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    /// Returns the [standard scores] of the elements of each lane along
+    /// `axis`: `(x - x̅) / s`, where `x̅` is the mean of the lane and `s` its
+    /// standard deviation with `ddof` delta degrees of freedom (`ddof = 0`
+    /// for the population standard deviation, `ddof = 1` for the sample
+    /// one).
+    ///
+    /// A lane containing NaN is mapped to NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `ddof` is less than zero or
+    /// not smaller than the length of `axis`, or if the type cast of the
+    /// length of `axis` from `usize` to `A` fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::NormalizationExt;
+    ///
+    /// let a = array![[1., 10.], [2., 20.], [3., 60.]];
+    /// let z = a.zscore_axis(Axis(0), 1.);
+    /// let expected = array![[-1., -0.755928946], [0., -0.377964473], [1., 1.133893419]];
+    /// assert!(z.all_close(&expected, 1e-9));
+    /// ```
+    ///
+    /// [standard scores]: https://en.wikipedia.org/wiki/Standard_score
+    fn zscore_axis(&self, axis: Axis, ddof: A) -> Array<A, D>
+    where
+        A: Float + FromPrimitive;
+
+    /// Like [`zscore_axis`](#tymethod.zscore_axis), but skips the NaN
+    /// elements. A lane with at most `ddof` non-NaN elements is mapped to
+    /// NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `ddof` is less than zero or
+    /// if the type cast of a number of elements from `usize` to `A` fails.
+    fn zscore_axis_skipnan(&self, axis: Axis, ddof: A) -> Array<A, D>
+    where
+        A: Float + FromPrimitive;
+
+    /// Replaces the elements of each lane along `axis` by their
+    /// [standard scores](#tymethod.zscore_axis).
+    ///
+    /// **Panics** under the same conditions as
+    /// [`zscore_axis`](#tymethod.zscore_axis).
+    fn zscore_axis_inplace(&mut self, axis: Axis, ddof: A)
+    where
+        A: Float + FromPrimitive,
+        S: DataMut;
+
+    /// Like [`zscore_axis_inplace`](#tymethod.zscore_axis_inplace), but skips
+    /// the NaN elements as
+    /// [`zscore_axis_skipnan`](#tymethod.zscore_axis_skipnan) does.
+    fn zscore_axis_skipnan_inplace(&mut self, axis: Axis, ddof: A)
+    where
+        A: Float + FromPrimitive,
+        S: DataMut;
+
+    /// Linearly maps the elements of each lane along `axis` from the range
+    /// `[min, max]` of the lane to `range = (lower, upper)`:
+    /// `lower + (x - min) (upper - lower) / (max - min)`.
+    ///
+    /// A constant lane is mapped to `lower`, and a lane containing NaN to
+    /// NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `range.0` is not smaller
+    /// than `range.1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::NormalizationExt;
+    ///
+    /// let a = array![[1., 10.], [2., 20.], [5., 60.]];
+    /// let scaled = a.min_max_scale_axis(Axis(0), (-1., 1.));
+    /// assert!(scaled.all_close(&array![[-1., -1.], [-0.5, -0.6], [1., 1.]], 1e-12));
+    /// ```
+    fn min_max_scale_axis(&self, axis: Axis, range: (A, A)) -> Array<A, D>
+    where
+        A: Float;
+
+    /// Like [`min_max_scale_axis`](#tymethod.min_max_scale_axis), but skips
+    /// the NaN elements.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `range.0` is not smaller
+    /// than `range.1`.
+    fn min_max_scale_axis_skipnan(&self, axis: Axis, range: (A, A)) -> Array<A, D>
+    where
+        A: Float;
+
+    /// Replaces the elements of each lane along `axis` by their
+    /// [min-max scaled](#tymethod.min_max_scale_axis) values.
+    ///
+    /// **Panics** under the same conditions as
+    /// [`min_max_scale_axis`](#tymethod.min_max_scale_axis).
+    fn min_max_scale_axis_inplace(&mut self, axis: Axis, range: (A, A))
+    where
+        A: Float,
+        S: DataMut;
+
+    /// Like [`min_max_scale_axis_inplace`](#tymethod.min_max_scale_axis_inplace),
+    /// but skips the NaN elements.
+    fn min_max_scale_axis_skipnan_inplace(&mut self, axis: Axis, range: (A, A))
+    where
+        A: Float,
+        S: DataMut;
+
+    /// Returns the elements of each lane along `axis` centered on the median
+    /// of the lane and divided by its interquartile range `Q₃ - Q₁`, as
+    /// scikit-learn's `RobustScaler`. The quartiles are computed with linear
+    /// interpolation, like NumPy's `quantile`.
+    ///
+    /// Unlike the [standard scores](#tymethod.zscore_axis), the result is
+    /// barely influenced by outliers. A lane containing NaN is mapped to NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::NormalizationExt;
+    ///
+    /// let a = array![1., 2., 3., 4., 100.];
+    /// let scaled = a.robust_scale_axis(Axis(0));
+    /// assert_eq!(scaled, array![-1., -0.5, 0., 0.5, 48.5]);
+    /// ```
+    fn robust_scale_axis(&self, axis: Axis) -> Array<A, D>
+    where
+        A: Float;
+
+    /// Like [`robust_scale_axis`](#tymethod.robust_scale_axis), but skips the
+    /// NaN elements.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn robust_scale_axis_skipnan(&self, axis: Axis) -> Array<A, D>
+    where
+        A: Float;
+
+    /// Replaces the elements of each lane along `axis` by their
+    /// [robustly scaled](#tymethod.robust_scale_axis) values.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn robust_scale_axis_inplace(&mut self, axis: Axis)
+    where
+        A: Float,
+        S: DataMut;
+
+    /// Like [`robust_scale_axis_inplace`](#tymethod.robust_scale_axis_inplace),
+    /// but skips the NaN elements.
+    fn robust_scale_axis_skipnan_inplace(&mut self, axis: Axis)
+    where
+        A: Float,
+        S: DataMut;
+
+    private_decl! {}
+}
+
+impl<A, S, D> NormalizationExt<A, S, D> for ArrayBase<S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn zscore_axis(&self, axis: Axis, ddof: A) -> Array<A, D>
+    where
+        A: Float + FromPrimitive,
+    {
+        let mut scores = self.to_owned();
+        scores.zscore_axis_inplace(axis, ddof);
+        scores
+    }
+
+    fn zscore_axis_skipnan(&self, axis: Axis, ddof: A) -> Array<A, D>
+    where
+        A: Float + FromPrimitive,
+    {
+        let mut scores = self.to_owned();
+        scores.zscore_axis_skipnan_inplace(axis, ddof);
+        scores
+    }
+
+    fn zscore_axis_inplace(&mut self, axis: Axis, ddof: A)
+    where
+        A: Float + FromPrimitive,
+        S: DataMut,
+    {
+        let n = A::from_usize(self.len_of(axis))
+            .expect("Converting the length of `axis` to `A` must not fail.");
+        assert!(
+            ddof >= A::zero() && ddof < n,
+            "`ddof` must not be less than zero or greater than or equal to the length of `axis`."
+        );
+        map_lanes_inplace(self, axis, |lane| zscore_lane(lane, ddof, false));
+    }
+
+    fn zscore_axis_skipnan_inplace(&mut self, axis: Axis, ddof: A)
+    where
+        A: Float + FromPrimitive,
+        S: DataMut,
+    {
+        assert!(ddof >= A::zero(), "`ddof` must not be less than zero.");
+        map_lanes_inplace(self, axis, |lane| zscore_lane(lane, ddof, true));
+    }
+
+    fn min_max_scale_axis(&self, axis: Axis, range: (A, A)) -> Array<A, D>
+    where
+        A: Float,
+    {
+        let mut scaled = self.to_owned();
+        scaled.min_max_scale_axis_inplace(axis, range);
+        scaled
+    }
+
+    fn min_max_scale_axis_skipnan(&self, axis: Axis, range: (A, A)) -> Array<A, D>
+    where
+        A: Float,
+    {
+        let mut scaled = self.to_owned();
+        scaled.min_max_scale_axis_skipnan_inplace(axis, range);
+        scaled
+    }
+
+    fn min_max_scale_axis_inplace(&mut self, axis: Axis, range: (A, A))
+    where
+        A: Float,
+        S: DataMut,
+    {
+        check_range(range);
+        map_lanes_inplace(self, axis, |lane| min_max_scale_lane(lane, range, false));
+    }
+
+    fn min_max_scale_axis_skipnan_inplace(&mut self, axis: Axis, range: (A, A))
+    where
+        A: Float,
+        S: DataMut,
+    {
+        check_range(range);
+        map_lanes_inplace(self, axis, |lane| min_max_scale_lane(lane, range, true));
+    }
+
+    fn robust_scale_axis(&self, axis: Axis) -> Array<A, D>
+    where
+        A: Float,
+    {
+        let mut scaled = self.to_owned();
+        scaled.robust_scale_axis_inplace(axis);
+        scaled
+    }
+
+    fn robust_scale_axis_skipnan(&self, axis: Axis) -> Array<A, D>
+    where
+        A: Float,
+    {
+        let mut scaled = self.to_owned();
+        scaled.robust_scale_axis_skipnan_inplace(axis);
+        scaled
+    }
+
+    fn robust_scale_axis_inplace(&mut self, axis: Axis)
+    where
+        A: Float,
+        S: DataMut,
+    {
+        map_lanes_inplace(self, axis, |lane| robust_scale_lane(lane, false));
+    }
+
+    fn robust_scale_axis_skipnan_inplace(&mut self, axis: Axis)
+    where
+        A: Float,
+        S: DataMut,
+    {
+        map_lanes_inplace(self, axis, |lane| robust_scale_lane(lane, true));
+    }
+
+    private_impl! {}
+}
+
+fn map_lanes_inplace<A, S, D, F>(a: &mut ArrayBase<S, D>, axis: Axis, f: F)
+where
+    S: DataMut<Elem = A>,
+    D: Dimension,
+    F: FnMut(ArrayViewMut1<'_, A>),
+{
+    assert!(axis.index() < a.ndim(), "`axis` is out of bounds.");
+    Zip::from(a.lanes_mut(axis)).apply(f);
+}
+
+fn check_range<A: Float>(range: (A, A)) {
+    assert!(
+        range.0 < range.1,
+        "The lower bound of the range must be smaller than its upper bound."
+    );
+}
+
+/// Returns the elements of `lane` used to compute its statistics.
+fn values<A: Float>(lane: &ArrayViewMut1<'_, A>, skipnan: bool) -> Vec<A> {
+    lane.iter()
+        .cloned()
+        .filter(|x| !(skipnan && x.is_nan()))
+        .collect()
+}
+
+/// Maps the elements `x` of `lane` to `(x - center) / scale`, or to
+/// `x - center` if `scale` is zero.
+fn shift_and_scale<A: Float>(mut lane: ArrayViewMut1<'_, A>, center: A, scale: A) {
+    let scale = if scale == A::zero() { A::one() } else { scale };
+    lane.mapv_inplace(|x| (x - center) / scale);
+}
+
+fn zscore_lane<A>(mut lane: ArrayViewMut1<'_, A>, ddof: A, skipnan: bool)
+where
+    A: Float + FromPrimitive,
+{
+    let values = values(&lane, skipnan);
+    let n = A::from_usize(values.len())
+        .expect("Converting the number of elements to `A` must not fail.");
+    if n <= ddof {
+        lane.fill(A::nan());
+        return;
+    }
+    let mean = values.iter().fold(A::zero(), |acc, &x| acc + x) / n;
+    let sum_sq_dev = values
+        .iter()
+        .fold(A::zero(), |acc, &x| acc + (x - mean) * (x - mean));
+    shift_and_scale(lane, mean, (sum_sq_dev / (n - ddof)).sqrt());
+}
+
+fn min_max_scale_lane<A: Float>(mut lane: ArrayViewMut1<'_, A>, range: (A, A), skipnan: bool) {
+    let values = values(&lane, skipnan);
+    if values.iter().any(|x| x.is_nan()) {
+        lane.fill(A::nan());
+        return;
+    }
+    let (min, max) = values
+        .iter()
+        .fold((A::infinity(), A::neg_infinity()), |(min, max), &x| {
+            (min.min(x), max.max(x))
+        });
+    let (lower, upper) = range;
+    if min == max {
+        lane.mapv_inplace(|x| if x.is_nan() { x } else { lower });
+    } else {
+        let factor = (upper - lower) / (max - min);
+        lane.mapv_inplace(|x| lower + (x - min) * factor);
+    }
+}
+
+fn robust_scale_lane<A: Float>(mut lane: ArrayViewMut1<'_, A>, skipnan: bool) {
+    let values = values(&lane, skipnan);
+    if values.is_empty() {
+        return;
+    }
+    if values.iter().any(|x| x.is_nan()) {
+        lane.fill(A::nan());
+        return;
+    }
+    let center = median(values.clone());
+    let iqr = linear_quantile(values.clone(), 0.75) - linear_quantile(values, 0.25);
+    shift_and_scale(lane, center, iqr);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, Array1, Array2};
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[quickcheck]
+    fn zscores_have_zero_mean_and_unit_variance(xs: Vec<i8>) -> bool {
+        let a = Array1::from_vec(xs.into_iter().map(f64::from).collect());
+        if a.len() < 2 || a.iter().all(|&x| x == a[0]) {
+            return true;
+        }
+        let z = a.zscore_axis(Axis(0), 1.);
+        z.sum().abs() < 1e-9 && (z.mapv(|x| x * x).sum() / (z.len() - 1) as f64 - 1.).abs() < 1e-9
+    }
+
+    #[quickcheck]
+    fn inplace_and_skipnan_variants_match(xs: Vec<i8>) -> bool {
+        if xs.is_empty() {
+            return true;
+        }
+        let a = Array2::from_shape_fn((xs.len(), 2), |(i, j)| f64::from(xs[i]) * (j + 1) as f64);
+        let check = |copy: Array2<f64>, skipnan: Array2<f64>, f: &dyn Fn(&mut Array2<f64>)| {
+            let mut inplace = a.clone();
+            f(&mut inplace);
+            copy == inplace && copy == skipnan
+        };
+        check(
+            a.zscore_axis(Axis(0), 0.),
+            a.zscore_axis_skipnan(Axis(0), 0.),
+            &|a| a.zscore_axis_inplace(Axis(0), 0.),
+        ) && check(
+            a.min_max_scale_axis(Axis(0), (0., 1.)),
+            a.min_max_scale_axis_skipnan(Axis(0), (0., 1.)),
+            &|a| a.min_max_scale_axis_inplace(Axis(0), (0., 1.)),
+        ) && check(
+            a.robust_scale_axis(Axis(0)),
+            a.robust_scale_axis_skipnan(Axis(0)),
+            &|a| a.robust_scale_axis_inplace(Axis(0)),
+        )
+    }
+
+    #[test]
+    fn test_constant_lanes_are_only_shifted() {
+        let a = array![[3., 1.], [3., 2.], [3., 3.]];
+        assert_eq!(a.zscore_axis(Axis(0), 1.).column(0), array![0., 0., 0.]);
+        assert_eq!(
+            a.min_max_scale_axis(Axis(0), (-1., 1.)).column(0),
+            array![-1., -1., -1.]
+        );
+        assert_eq!(a.robust_scale_axis(Axis(0)).column(0), array![0., 0., 0.]);
+    }
+
+    #[test]
+    fn test_nan() {
+        let a = array![[1., 1.], [f64::NAN, 2.], [3., 3.], [5., 4.]];
+        for scaled in &[
+            a.zscore_axis(Axis(0), 1.),
+            a.min_max_scale_axis(Axis(0), (0., 1.)),
+            a.robust_scale_axis(Axis(0)),
+        ] {
+            assert!(scaled.column(0).iter().all(|x| x.is_nan()));
+            assert!(scaled.column(1).iter().all(|x| !x.is_nan()));
+        }
+        let b = array![1., f64::NAN, 3., 5.];
+        let expected = [
+            (
+                b.zscore_axis_skipnan(Axis(0), 1.),
+                array![-1., f64::NAN, 0., 1.],
+            ),
+            (
+                b.min_max_scale_axis_skipnan(Axis(0), (0., 1.)),
+                array![0., f64::NAN, 0.5, 1.],
+            ),
+            (
+                b.robust_scale_axis_skipnan(Axis(0)),
+                array![-1., f64::NAN, 0., 1.],
+            ),
+        ];
+        for (scaled, expected) in &expected {
+            assert!(scaled[1].is_nan());
+            for &i in &[0, 2, 3] {
+                assert!((scaled[i] - expected[i]).abs() < 1e-12);
+            }
+        }
+        // Not enough non-NaN elements for `ddof`.
+        let c = array![f64::NAN, 2., f64::NAN];
+        assert!(c
+            .zscore_axis_skipnan(Axis(0), 1.)
+            .iter()
+            .all(|x| x.is_nan()));
+        let all_nan = Array1::from_elem(3, f64::NAN);
+        assert!(all_nan
+            .robust_scale_axis_skipnan(Axis(0))
+            .iter()
+            .all(|x| x.is_nan()));
+        assert!(all_nan
+            .min_max_scale_axis_skipnan(Axis(0), (0., 1.))
+            .iter()
+            .all(|x| x.is_nan()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_ddof() {
+        array![1., 2.].zscore_axis(Axis(0), 2.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_range() {
+        array![1., 2.].min_max_scale_axis(Axis(0), (1., 1.));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_axis_out_of_bounds() {
+        array![1., 2.].robust_scale_axis(Axis(1));
+    }
+}
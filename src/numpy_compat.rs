@@ -0,0 +1,447 @@
+//! Summary statistics with NumPy's defaults and summation order.
+use crate::errors::{EmptyInput, QuantileError};
+use crate::quantile::check_quantile;
+use ndarray::{Array, ArrayBase, ArrayView1, Axis, Data, Dimension, RemoveAxis, Zip};
+use num_traits::{Float, FromPrimitive};
+
+/// Block size of NumPy's pairwise summation.
+const PAIRWISE_BLOCK_SIZE: usize = 128;
+
+/// Extension trait for `ArrayBase` providing summary statistics that follow
+/// NumPy's conventions, to cross-validate pipelines ported from Python.
+///
+/// The methods use NumPy's defaults:
+/// - the variance and the standard deviation are the population ones
+///   (`ddof = 0`);
+/// - quantiles use linear interpolation (NumPy's `method="linear"`);
+/// - NaN values propagate: any NaN in the input gives a NaN result.
+///
+/// They also reproduce NumPy's summation order: contiguous lanes are summed
+/// with NumPy's pairwise summation, while reductions along strided axes add
+/// the subviews one at a time. For floating-point arrays with up to two
+/// dimensions the results are therefore bit-identical to those of `np.mean`,
+/// `np.var`, `np.std`, `np.median` and `np.quantile` (NumPy ≥ 1.22) on the
+/// same data; for other inputs they may differ in the last bits.
+///
+/// Unlike NumPy, an empty input gives an error instead of a NaN (with a
+/// warning).
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{array, Axis};
+/// use ndarray_stats::NumpyCompatExt;
+///
+/// let a = array![[1., 2.], [3., 5.]];
+/// // np.var(a), np.var(a, axis=0), np.quantile(a, 0.4)
+/// assert_eq!(a.np_var(), Ok(2.1875));
+/// assert_eq!(a.np_var_axis(Axis(0)), Ok(array![1., 2.25]));
+/// assert_eq!(a.np_quantile(0.4), Ok(2.2));
+/// assert!(array![1., ::std::f64::NAN].np_median().unwrap().is_nan());
+/// ```
+pub trait NumpyCompatExt<A, S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    /// Returns the arithmetic mean of all the elements, as `np.mean(a)`.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of elements.
+    fn np_mean(&self) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the arithmetic mean along `axis`, as `np.mean(a, axis=axis)`.
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    fn np_mean_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the population variance of all the elements, as `np.var(a)`.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of elements.
+    fn np_var(&self) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the population variance along `axis`, as
+    /// `np.var(a, axis=axis)`.
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    fn np_var_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the population standard deviation of all the elements, as
+    /// `np.std(a)`.
+    ///
+    /// See [`np_var`](#tymethod.np_var) for the errors and panics.
+    fn np_std(&self) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the population standard deviation along `axis`, as
+    /// `np.std(a, axis=axis)`.
+    ///
+    /// See [`np_var_axis`](#tymethod.np_var_axis) for the errors and panics.
+    fn np_std_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the median of all the elements, as `np.median(a)`: for an
+    /// even number of elements, the mean of the two middle elements.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned.
+    fn np_median(&self) -> Result<A, EmptyInput>
+    where
+        A: Float;
+
+    /// Returns the `q`th quantile of all the elements with linear
+    /// interpolation, as `np.quantile(a, q)`.
+    ///
+    /// Returns `Err(EmptyInput)` if the array is empty and
+    /// `Err(InvalidQuantile(q))` if `q` is NaN or not between `0.` and `1.`
+    /// (inclusive).
+    fn np_quantile<Q>(&self, q: Q) -> Result<A, QuantileError>
+    where
+        A: Float,
+        Q: Float;
+
+    /// Returns the `q`th quantile along `axis` with linear interpolation, as
+    /// `np.quantile(a, q, axis=axis)`.
+    ///
+    /// Returns `Err(EmptyInput)` if the length of `axis` is zero and
+    /// `Err(InvalidQuantile(q))` if `q` is NaN or not between `0.` and `1.`
+    /// (inclusive).
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn np_quantile_axis<Q>(&self, axis: Axis, q: Q) -> Result<Array<A, D::Smaller>, QuantileError>
+    where
+        A: Float,
+        D: RemoveAxis,
+        Q: Float;
+
+    private_decl! {}
+}
+
+impl<A, S, D> NumpyCompatExt<A, S, D> for ArrayBase<S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn np_mean(&self) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        let n = count(self.len())?;
+        Ok(np_sum_by(self, |&x| x) / n)
+    }
+
+    fn np_mean_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        let n = count(self.len_of(axis))?;
+        Ok(np_sum_axis(self, axis).mapv_into(|sum| sum / n))
+    }
+
+    fn np_var(&self) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        let mean = self.np_mean()?;
+        let n = count(self.len())?;
+        Ok(np_sum_by(self, |&x| (x - mean) * (x - mean)) / n)
+    }
+
+    fn np_var_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        let n = count(self.len_of(axis))?;
+        let mean = self.np_mean_axis(axis)?;
+        let mut squared_deviations = self.to_owned();
+        Zip::from(&mut squared_deviations)
+            .and_broadcast(&mean.insert_axis(axis))
+            .apply(|x, &mean| *x = (*x - mean) * (*x - mean));
+        Ok(np_sum_axis(&squared_deviations, axis).mapv_into(|sum| sum / n))
+    }
+
+    fn np_std(&self) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        self.np_var().map(A::sqrt)
+    }
+
+    fn np_std_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        self.np_var_axis(axis).map(|var| var.mapv_into(A::sqrt))
+    }
+
+    fn np_median(&self) -> Result<A, EmptyInput>
+    where
+        A: Float,
+    {
+        if self.is_empty() {
+            return Err(EmptyInput);
+        }
+        Ok(median(self.iter().cloned().collect()))
+    }
+
+    fn np_quantile<Q>(&self, q: Q) -> Result<A, QuantileError>
+    where
+        A: Float,
+        Q: Float,
+    {
+        let q = check_quantile(q)?.raw();
+        if self.is_empty() {
+            return Err(EmptyInput.into());
+        }
+        Ok(linear_quantile(self.iter().cloned().collect(), q))
+    }
+
+    fn np_quantile_axis<Q>(&self, axis: Axis, q: Q) -> Result<Array<A, D::Smaller>, QuantileError>
+    where
+        A: Float,
+        D: RemoveAxis,
+        Q: Float,
+    {
+        let q = check_quantile(q)?.raw();
+        if self.len_of(axis) == 0 {
+            return Err(EmptyInput.into());
+        }
+        Ok(self.map_axis(axis, |lane| {
+            linear_quantile(lane.iter().cloned().collect(), q)
+        }))
+    }
+
+    private_impl! {}
+}
+
+/// Converts the number of summed elements to `A`, or returns
+/// `Err(EmptyInput)` if it is zero.
+fn count<A: FromPrimitive>(n: usize) -> Result<A, EmptyInput> {
+    if n == 0 {
+        Err(EmptyInput)
+    } else {
+        Ok(A::from_usize(n).expect("Converting number of elements to `A` must not fail."))
+    }
+}
+
+/// Sums `f(x)` over `xs` in the same order as NumPy's `pairwise_sum`.
+fn pairwise_sum_by<A, F>(xs: &[A], f: &F) -> A
+where
+    A: Float,
+    F: Fn(&A) -> A,
+{
+    let n = xs.len();
+    if n < 8 {
+        xs.iter().fold(-A::zero(), |acc, x| acc + f(x))
+    } else if n <= PAIRWISE_BLOCK_SIZE {
+        let mut r = [A::zero(); 8];
+        for (r, x) in r.iter_mut().zip(xs) {
+            *r = f(x);
+        }
+        let mut chunks = xs[8..].chunks_exact(8);
+        for chunk in &mut chunks {
+            for (r, x) in r.iter_mut().zip(chunk) {
+                *r = *r + f(x);
+            }
+        }
+        let res = ((r[0] + r[1]) + (r[2] + r[3])) + ((r[4] + r[5]) + (r[6] + r[7]));
+        chunks.remainder().iter().fold(res, |acc, x| acc + f(x))
+    } else {
+        let half = n / 2;
+        let half = half - half % 8;
+        pairwise_sum_by(&xs[..half], f) + pairwise_sum_by(&xs[half..], f)
+    }
+}
+
+/// Sums `f(x)` over a 1-dimensional lane, as NumPy's reduction inner loop:
+/// the lane is summed pairwise (in memory order) if it is contiguous, and
+/// sequentially otherwise.
+fn lane_sum_by<A, F>(lane: ArrayView1<'_, A>, f: &F) -> A
+where
+    A: Float,
+    F: Fn(&A) -> A,
+{
+    match lane.as_slice_memory_order() {
+        Some(xs) => pairwise_sum_by(xs, f),
+        None => lane.iter().fold(A::zero(), |acc, x| acc + f(x)),
+    }
+}
+
+/// Sums `f(x)` over all the elements of `a` in NumPy's order.
+fn np_sum_by<A, S, D, F>(a: &ArrayBase<S, D>, f: F) -> A
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: Dimension,
+    F: Fn(&A) -> A,
+{
+    // NumPy starts the reduction from the identity, `0.`.
+    if let Some(xs) = a.as_slice_memory_order() {
+        return A::zero() + pairwise_sum_by(xs, &f);
+    }
+    if a.ndim() == 0 {
+        return a.iter().fold(A::zero(), |acc, x| acc + f(x));
+    }
+    a.genrows()
+        .into_iter()
+        .fold(A::zero(), |acc, row| acc + lane_sum_by(row, &f))
+}
+
+/// Sums `a` along `axis` in NumPy's order.
+fn np_sum_axis<A, S, D>(a: &ArrayBase<S, D>, axis: Axis) -> Array<A, D::Smaller>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: RemoveAxis,
+{
+    if a.len_of(axis) <= 1 || a.stride_of(axis).abs() == 1 {
+        a.map_axis(axis, |lane| A::zero() + lane_sum_by(lane, &|&x| x))
+    } else {
+        // NumPy adds the subviews one at a time, in memory order.
+        let mut sum = Array::zeros(a.raw_dim().remove_axis(axis));
+        let mut add = |subview| {
+            Zip::from(&mut sum)
+                .and(subview)
+                .apply(|sum, &x| *sum = *sum + x)
+        };
+        if a.stride_of(axis) < 0 {
+            a.axis_iter(axis).rev().for_each(&mut add);
+        } else {
+            a.axis_iter(axis).for_each(&mut add);
+        }
+        sum
+    }
+}
+
+/// Sorts `values`, or returns `false` if they contain a NaN value.
+fn sort_without_nan<A: Float>(values: &mut [A]) -> bool {
+    if values.iter().any(|x| x.is_nan()) {
+        return false;
+    }
+    values.sort_unstable_by(|a, b| a.partial_cmp(b).expect("NaN values have been excluded."));
+    true
+}
+
+/// Returns the median of `values` as `np.median`; `values` must not be empty.
+pub(crate) fn median<A: Float>(mut values: Vec<A>) -> A {
+    if !sort_without_nan(&mut values) {
+        return A::nan();
+    }
+    let n = values.len();
+    if n % 2 == 1 {
+        values[n / 2]
+    } else {
+        (values[n / 2 - 1] + values[n / 2]) / (A::one() + A::one())
+    }
+}
+
+/// Returns the `q`th quantile of `values` as `np.quantile` with
+/// `method="linear"`; `values` must not be empty.
+pub(crate) fn linear_quantile<A: Float>(mut values: Vec<A>, q: f64) -> A {
+    if !sort_without_nan(&mut values) {
+        return A::nan();
+    }
+    let last = values.len() - 1;
+    let virtual_index = last as f64 * q;
+    if virtual_index >= last as f64 {
+        return values[last];
+    }
+    let previous = virtual_index.floor();
+    let gamma = A::from(virtual_index - previous).expect("Converting `gamma` must not fail.");
+    let (a, b) = (values[previous as usize], values[previous as usize + 1]);
+    // NumPy's `_lerp`, which is monotonic and exact at both ends.
+    let diff = b - a;
+    if gamma >= A::from(0.5).unwrap() {
+        b - diff * (A::one() - gamma)
+    } else {
+        a + diff * gamma
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, s, Array1};
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[quickcheck]
+    fn pairwise_sum_is_a_sum(xs: Vec<i16>) -> bool {
+        // Sums of small integers are exact in any order.
+        let xs: Vec<f64> = xs.into_iter().map(f64::from).collect();
+        pairwise_sum_by(&xs, &|&x| x) == xs.iter().sum::<f64>()
+    }
+
+    #[test]
+    fn test_pairwise_summation_order() {
+        // 0.1 is not representable, so the summation order shows in the
+        // result. This is `np.sum(np.full(1000, 0.1))`.
+        let a = Array1::from_elem(1000, 0.1);
+        assert_eq!(a.np_mean(), Ok(100.00000000000001 / 1000.));
+        assert_eq!(a.iter().sum::<f64>(), 99.9999999999986);
+    }
+
+    #[test]
+    fn test_axis_reductions() {
+        let a = array![[1., 2., 4.], [3., 5., 9.]];
+        assert_eq!(a.np_mean_axis(Axis(0)), Ok(array![2., 3.5, 6.5]));
+        assert_eq!(a.np_mean_axis(Axis(1)), Ok(array![7. / 3., 17. / 3.]));
+        assert_eq!(a.np_var_axis(Axis(0)), Ok(array![1., 2.25, 6.25]));
+        assert_eq!(
+            a.slice(s![..;-1, ..]).np_var_axis(Axis(0)),
+            a.np_var_axis(Axis(0))
+        );
+        assert_eq!(a.np_std_axis(Axis(0)), Ok(array![1., 1.5, 2.5]));
+        assert_eq!(a.np_quantile_axis(Axis(1), 0.25), Ok(array![1.5, 4.]));
+        assert!(array![[1., f64::NAN]].np_mean_axis(Axis(0)).unwrap()[1].is_nan());
+    }
+
+    #[test]
+    fn test_quantiles() {
+        let a = array![3., 1., 4., 1., 5.];
+        assert_eq!(a.np_median(), Ok(3.));
+        assert_eq!(array![1., 4., 2., 3.].np_median(), Ok(2.5));
+        assert_eq!(a.np_quantile(0.), Ok(1.));
+        assert_eq!(a.np_quantile(1.), Ok(5.));
+        assert_eq!(a.np_quantile(0.3), Ok(1.4));
+        assert_eq!(a.np_quantile(0.7), Ok(3.8));
+        assert!(array![1., f64::NAN].np_quantile(0.).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_errors() {
+        let empty = Array1::<f64>::zeros(0);
+        assert_eq!(empty.np_mean(), Err(EmptyInput));
+        assert_eq!(empty.np_var(), Err(EmptyInput));
+        assert_eq!(empty.np_median(), Err(EmptyInput));
+        assert_eq!(empty.np_quantile(0.5), Err(QuantileError::EmptyInput));
+        assert_eq!(
+            array![1.].np_quantile(2.),
+            Err(QuantileError::InvalidQuantile(2.))
+        );
+    }
+}
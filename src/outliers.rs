@@ -0,0 +1,485 @@
+//! Detection of outliers with robust rules.
+use crate::numpy_compat::{linear_quantile, median};
+use ndarray::{Array, ArrayBase, ArrayView1, ArrayViewMut1, Axis, Data, DataMut, Dimension, Zip};
+use num_traits::Float;
+
+/// The rule used by [`OutlierExt`] to decide which elements are outliers.
+///
+/// Both rules are based on robust statistics of the data (quantiles and
+/// medians), so the outliers themselves barely influence the thresholds.
+///
+/// [`OutlierExt`]: trait.OutlierExt.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OutlierMethod {
+    /// Tukey's fences: the outliers are the elements below `Q₁ - 1.5 IQR` or
+    /// above `Q₃ + 1.5 IQR`, where `Q₁` and `Q₃` are the first and third
+    /// quartiles and `IQR = Q₃ - Q₁` is the interquartile range. The
+    /// quartiles are computed with linear interpolation, like NumPy's
+    /// `quantile`.
+    Iqr,
+    /// The modified z-score of Iglewicz and Hoaglin: the outliers are the
+    /// elements whose modified z-score `0.6745 (x - median) / MAD` is
+    /// greater than 3.5 in absolute value, where `MAD` is the median
+    /// absolute deviation from the median. If more than half of the elements
+    /// are equal, the MAD is zero and all the elements different from the
+    /// median are outliers.
+    ModifiedZScore,
+}
+
+/// Extension trait for `ArrayBase` providing methods to detect outliers.
+///
+/// The methods return a boolean mask with the shape of the array, which is
+/// `true` at the outliers; it can be passed directly to the methods of
+/// [`MaskedExt`](trait.MaskedExt.html) (after negating it) to compute
+/// statistics of the remaining elements. NaN elements are ignored when
+/// computing the thresholds, and are never marked as outliers.
+pub trait OutlierExt<A, S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    /// Returns a mask of the outliers of the whole array, according to
+    /// `method`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{OutlierExt, OutlierMethod};
+    ///
+    /// let a = array![[10., 12., 11.], [13., 11., 95.]];
+    /// let expected = array![[false, false, false], [false, false, true]];
+    /// assert_eq!(a.outlier_mask(OutlierMethod::Iqr), expected);
+    /// assert_eq!(a.outlier_mask(OutlierMethod::ModifiedZScore), expected);
+    /// ```
+    fn outlier_mask(&self, method: OutlierMethod) -> Array<bool, D>
+    where
+        A: Float;
+
+    /// Returns a mask of the outliers of each lane along `axis`, according
+    /// to `method`: the thresholds are computed independently for each lane
+    /// (e.g. for each column with `Axis(0)`).
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{OutlierExt, OutlierMethod};
+    ///
+    /// // The columns have different scales.
+    /// let a = array![[1., 100.], [2., 300.], [1.5, 200.], [9., 250.], [1.2, 150.]];
+    /// let mask = a.outlier_mask_axis(Axis(0), OutlierMethod::Iqr);
+    /// assert_eq!(mask.column(0), array![false, false, false, true, false]);
+    /// assert!(mask.column(1).iter().all(|&outlier| !outlier));
+    /// ```
+    fn outlier_mask_axis(&self, axis: Axis, method: OutlierMethod) -> Array<bool, D>
+    where
+        A: Float;
+
+    /// Returns a mask of the outliers of the whole array according to
+    /// Tukey's fences with the multiplier `k`: the elements below
+    /// `Q₁ - k IQR` or above `Q₃ + k IQR`.
+    ///
+    /// `outliers_iqr(1.5)` is the same as
+    /// `outlier_mask(OutlierMethod::Iqr)`; `k = 3` is the usual choice to
+    /// only detect "far out" outliers.
+    ///
+    /// **Panics** if `k` is negative or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::OutlierExt;
+    ///
+    /// // Q₁ = 2.5 and Q₃ = 4.
+    /// let a = array![2., 3., 4., 2., 4., 3., 2., 4., 3., 8., 11.];
+    /// let mask = a.outliers_iqr(1.5);
+    /// assert!(mask[9] && mask[10] && mask.iter().filter(|&&outlier| outlier).count() == 2);
+    /// let far_out = a.outliers_iqr(3.);
+    /// assert!(!far_out[9] && far_out[10]);
+    /// ```
+    fn outliers_iqr(&self, k: A) -> Array<bool, D>
+    where
+        A: Float;
+
+    /// Like [`outliers_iqr`](#tymethod.outliers_iqr), but computes the
+    /// fences independently for each lane along `axis`.
+    ///
+    /// **Panics** if `axis` is out of bounds, or if `k` is negative or NaN.
+    fn outliers_iqr_axis(&self, axis: Axis, k: A) -> Array<bool, D>
+    where
+        A: Float;
+
+    /// Returns a mask of the elements of the whole array whose [standard
+    /// score] `(x - x̅) / σ` is greater than `threshold` in absolute value,
+    /// where `x̅` is the mean of the array and `σ` its population standard
+    /// deviation (as `scipy.stats.zscore`).
+    ///
+    /// Unlike the [modified z-score](enum.OutlierMethod.html), the mean and
+    /// the standard deviation are themselves inflated by the outliers, so
+    /// this rule misses outliers in small samples (there can't be any with
+    /// `threshold = 3` in a sample of at most 10 elements).
+    ///
+    /// **Panics** if `threshold` is negative or NaN, or if the type cast of
+    /// the number of elements from `usize` to `A` fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::Array;
+    /// use ndarray_stats::OutlierExt;
+    ///
+    /// let mut a = Array::from_elem(20, 1.);
+    /// a[7] = 2.;
+    /// a[13] = 100.;
+    /// let mask = a.outliers_zscore(3.);
+    /// assert!(mask[13]);
+    /// assert_eq!(mask.iter().filter(|&&outlier| outlier).count(), 1);
+    /// ```
+    ///
+    /// [standard score]: https://en.wikipedia.org/wiki/Standard_score
+    fn outliers_zscore(&self, threshold: A) -> Array<bool, D>
+    where
+        A: Float;
+
+    /// Like [`outliers_zscore`](#tymethod.outliers_zscore), but computes the
+    /// mean and the standard deviation independently for each lane along
+    /// `axis`.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `threshold` is negative or
+    /// NaN, or if the type cast of the length of `axis` from `usize` to `A`
+    /// fails.
+    fn outliers_zscore_axis(&self, axis: Axis, threshold: A) -> Array<bool, D>
+    where
+        A: Float;
+
+    /// Replaces the elements selected by `mask` (e.g. the outliers found by
+    /// one of the other methods) with NaN, so that the `*_skipnan` methods of
+    /// [`MaybeNanExt`](trait.MaybeNanExt.html) and
+    /// [`SummaryStatisticsExt`](trait.SummaryStatisticsExt.html) ignore
+    /// them.
+    ///
+    /// **Panics** if `mask` doesn't have the shape of the array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{MaybeNanExt, OutlierExt};
+    ///
+    /// let mut a = array![10., 12., 11., 13., 11., 95_f64];
+    /// a.replace_outliers_with_nan(&a.outliers_iqr(1.5));
+    /// assert!(a[5].is_nan());
+    /// assert_eq!(a.mean_axis_skipnan(Axis(0)).into_scalar(), 11.4);
+    /// ```
+    fn replace_outliers_with_nan<S2>(&mut self, mask: &ArrayBase<S2, D>)
+    where
+        A: Float,
+        S: DataMut,
+        S2: Data<Elem = bool>;
+
+    private_decl! {}
+}
+
+impl<A, S, D> OutlierExt<A, S, D> for ArrayBase<S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn outlier_mask(&self, method: OutlierMethod) -> Array<bool, D>
+    where
+        A: Float,
+    {
+        mask_array(self, Rule::from(method))
+    }
+
+    fn outlier_mask_axis(&self, axis: Axis, method: OutlierMethod) -> Array<bool, D>
+    where
+        A: Float,
+    {
+        mask_array_axis(self, axis, Rule::from(method))
+    }
+
+    fn outliers_iqr(&self, k: A) -> Array<bool, D>
+    where
+        A: Float,
+    {
+        mask_array(self, Rule::tukey(k))
+    }
+
+    fn outliers_iqr_axis(&self, axis: Axis, k: A) -> Array<bool, D>
+    where
+        A: Float,
+    {
+        mask_array_axis(self, axis, Rule::tukey(k))
+    }
+
+    fn outliers_zscore(&self, threshold: A) -> Array<bool, D>
+    where
+        A: Float,
+    {
+        mask_array(self, Rule::z_score(threshold))
+    }
+
+    fn outliers_zscore_axis(&self, axis: Axis, threshold: A) -> Array<bool, D>
+    where
+        A: Float,
+    {
+        mask_array_axis(self, axis, Rule::z_score(threshold))
+    }
+
+    fn replace_outliers_with_nan<S2>(&mut self, mask: &ArrayBase<S2, D>)
+    where
+        A: Float,
+        S: DataMut,
+        S2: Data<Elem = bool>,
+    {
+        assert_eq!(
+            self.shape(),
+            mask.shape(),
+            "The mask must have the shape of the array."
+        );
+        Zip::from(self).and(mask).apply(|x, &outlier| {
+            if outlier {
+                *x = A::nan();
+            }
+        });
+    }
+
+    private_impl! {}
+}
+
+/// A rule deciding which elements are outliers, with its parameter.
+#[derive(Clone, Copy)]
+enum Rule<A> {
+    /// Tukey's fences with the given multiplier of the IQR.
+    Tukey(A),
+    /// The standard score, with the given threshold.
+    ZScore(A),
+    ModifiedZScore,
+}
+
+impl<A: Float> Rule<A> {
+    fn tukey(k: A) -> Self {
+        assert!(k >= A::zero(), "The multiplier `k` must not be negative.");
+        Rule::Tukey(k)
+    }
+
+    fn z_score(threshold: A) -> Self {
+        assert!(
+            threshold >= A::zero(),
+            "The threshold must not be negative."
+        );
+        Rule::ZScore(threshold)
+    }
+}
+
+impl<A: Float> From<OutlierMethod> for Rule<A> {
+    fn from(method: OutlierMethod) -> Self {
+        match method {
+            OutlierMethod::Iqr => {
+                Rule::Tukey(A::from(1.5).expect("Converting a constant to `A` must not fail."))
+            }
+            OutlierMethod::ModifiedZScore => Rule::ModifiedZScore,
+        }
+    }
+}
+
+fn mask_array<A, S, D>(a: &ArrayBase<S, D>, rule: Rule<A>) -> Array<bool, D>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    match fences(a.iter().cloned(), rule) {
+        Some((lower, upper)) => a.mapv(|x| x < lower || x > upper),
+        None => Array::from_elem(a.raw_dim(), false),
+    }
+}
+
+fn mask_array_axis<A, S, D>(a: &ArrayBase<S, D>, axis: Axis, rule: Rule<A>) -> Array<bool, D>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    let mut mask = Array::from_elem(a.raw_dim(), false);
+    Zip::from(mask.lanes_mut(axis))
+        .and(a.lanes(axis))
+        .apply(|mask, lane| mask_lane(mask, lane, rule));
+    mask
+}
+
+fn mask_lane<A: Float>(mut mask: ArrayViewMut1<'_, bool>, lane: ArrayView1<'_, A>, rule: Rule<A>) {
+    if let Some((lower, upper)) = fences(lane.iter().cloned(), rule) {
+        Zip::from(&mut mask)
+            .and(&lane)
+            .apply(|m, &x| *m = x < lower || x > upper);
+    }
+}
+
+/// Returns the lower and upper thresholds beyond which the elements of
+/// `values` are outliers, or `None` if all of them are NaN.
+fn fences<A, I>(values: I, rule: Rule<A>) -> Option<(A, A)>
+where
+    A: Float,
+    I: Iterator<Item = A>,
+{
+    let values: Vec<A> = values.filter(|x| !x.is_nan()).collect();
+    if values.is_empty() {
+        return None;
+    }
+    let constant = |x: f64| A::from(x).expect("Converting a constant to `A` must not fail.");
+    match rule {
+        Rule::Tukey(k) => {
+            let q1 = linear_quantile(values.clone(), 0.25);
+            let q3 = linear_quantile(values, 0.75);
+            let margin = k * (q3 - q1);
+            Some((q1 - margin, q3 + margin))
+        }
+        Rule::ZScore(threshold) => {
+            let n = A::from(values.len())
+                .expect("Converting the number of elements to `A` must not fail.");
+            let mean = values.iter().fold(A::zero(), |acc, &x| acc + x) / n;
+            let variance = values
+                .iter()
+                .fold(A::zero(), |acc, &x| acc + (x - mean) * (x - mean))
+                / n;
+            let margin = threshold * variance.sqrt();
+            Some((mean - margin, mean + margin))
+        }
+        Rule::ModifiedZScore => {
+            let center = median(values.clone());
+            let mad = median(values.into_iter().map(|x| (x - center).abs()).collect());
+            let margin = constant(3.5 / 0.6745) * mad;
+            Some((center - margin, center + margin))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, Array1, Array2};
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[test]
+    fn test_iqr_fences() {
+        // Q₁ = 2, Q₃ = 4, so the fences are -1 and 7.
+        let a = array![-1., 2., 3., 4., 7., 2., 4., 3., 7.1, -1.1];
+        assert_eq!(
+            a.outlier_mask(OutlierMethod::Iqr),
+            array![false, false, false, false, false, false, false, false, true, true]
+        );
+    }
+
+    #[test]
+    fn test_modified_z_score() {
+        // The median is 3.5 and the MAD is 1.5, so the thresholds are
+        // 3.5 ± 1.5 × 3.5 / 0.6745 ≈ 3.5 ± 7.784.
+        let a = array![1., 2., 3., 4., 5., 30., f64::NAN];
+        assert_eq!(
+            a.outlier_mask(OutlierMethod::ModifiedZScore),
+            array![false, false, false, false, false, true, false]
+        );
+        let b = array![1., 2., 3., 4., 5., 11.2];
+        assert!(!b.outlier_mask(OutlierMethod::ModifiedZScore)[5]);
+        let c = array![1., 2., 3., 4., 5., 11.3];
+        assert!(c.outlier_mask(OutlierMethod::ModifiedZScore)[5]);
+        // Zero MAD.
+        let d = array![2., 2., 2., 2.0001];
+        assert_eq!(
+            d.outlier_mask(OutlierMethod::ModifiedZScore),
+            array![false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_degenerate_inputs() {
+        for &method in &[OutlierMethod::Iqr, OutlierMethod::ModifiedZScore] {
+            let empty = Array2::<f64>::zeros((0, 3));
+            assert_eq!(empty.outlier_mask(method).shape(), &[0, 3]);
+            assert_eq!(empty.outlier_mask_axis(Axis(0), method).shape(), &[0, 3]);
+            let nan = array![f64::NAN, f64::NAN];
+            assert_eq!(nan.outlier_mask(method), array![false, false]);
+        }
+    }
+
+    #[test]
+    fn test_z_score() {
+        // The mean is 3 and the standard deviation is √2.
+        let a = array![1., 2., 3., 4., 5., f64::NAN];
+        assert_eq!(
+            a.outliers_zscore(1.),
+            array![true, false, false, false, true, false]
+        );
+        assert_eq!(
+            a.outliers_zscore(1.5),
+            array![false, false, false, false, false, false]
+        );
+        let constant = array![2., 2., 2.];
+        assert_eq!(constant.outliers_zscore(0.), array![false, false, false]);
+        let b = array![[1., 10.], [2., 10.], [3., 40.], [4., 10.], [5., 10.]];
+        assert_eq!(
+            b.outliers_zscore_axis(Axis(0), 1.5),
+            array![
+                [false, false],
+                [false, false],
+                [false, true],
+                [false, false],
+                [false, false]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_outliers_with_nan() {
+        let mut a = array![[1., 2.], [3., 4.]];
+        a.replace_outliers_with_nan(&array![[false, true], [true, false]]);
+        assert_eq!(a[[0, 0]], 1.);
+        assert!(a[[0, 1]].is_nan());
+        assert!(a[[1, 0]].is_nan());
+        assert_eq!(a[[1, 1]], 4.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_replace_outliers_with_nan_shape_mismatch() {
+        let mut a = array![[1., 2.], [3., 4.]];
+        a.replace_outliers_with_nan(&array![[false, true]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_multiplier() {
+        array![1., 2.].outliers_iqr(-1.);
+    }
+
+    #[quickcheck]
+    fn outliers_iqr_generalizes_iqr_method(data: Vec<f64>) -> bool {
+        let a = Array1::from_vec(data);
+        let b = a.clone().into_shape((1, a.len())).unwrap();
+        a.outliers_iqr(1.5) == a.outlier_mask(OutlierMethod::Iqr)
+            && b.outliers_iqr_axis(Axis(1), 1.5) == b.outlier_mask_axis(Axis(1), OutlierMethod::Iqr)
+    }
+
+    #[quickcheck]
+    fn outlier_mask_axis_is_outlier_mask_of_lanes(data: Vec<f64>, iqr: bool) -> bool {
+        let method = if iqr {
+            OutlierMethod::Iqr
+        } else {
+            OutlierMethod::ModifiedZScore
+        };
+        let n = data.len() / 3;
+        let a = Array2::from_shape_vec((n, 3), data[..3 * n].to_vec()).unwrap();
+        let mask = a.outlier_mask_axis(Axis(0), method);
+        a.gencolumns()
+            .into_iter()
+            .zip(mask.gencolumns())
+            .all(|(column, mask)| column.outlier_mask(method) == mask)
+    }
+}
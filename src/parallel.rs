@@ -0,0 +1,47 @@
+//! Helpers for processing the lanes of an array in parallel with `rayon`.
+use ndarray::prelude::*;
+use ndarray::{Data, DataMut, RemoveAxis};
+use rayon::prelude::*;
+
+/// Applies `f` to every lane of `array` along `axis` in parallel, returning
+/// an array of the results (with `axis` removed).
+pub(crate) fn par_map_lanes<A, S, D, B, F>(
+    array: &ArrayBase<S, D>,
+    axis: Axis,
+    f: F,
+) -> Array<B, D::Smaller>
+where
+    A: Sync,
+    S: Data<Elem = A>,
+    D: RemoveAxis,
+    B: Send,
+    F: Fn(ArrayView1<'_, A>) -> B + Send + Sync,
+{
+    let lanes: Vec<_> = array.lanes(axis).into_iter().collect();
+    let results: Vec<B> = lanes.into_par_iter().map(f).collect();
+    // `lanes` iterates over the remaining axes in logical order, so the
+    // results are in standard layout.
+    Array::from_shape_vec(array.raw_dim().remove_axis(axis), results)
+        .expect("The number of lanes must match the shape of the result.")
+}
+
+/// Applies `f` to every mutable lane of `array` along `axis` in parallel,
+/// returning an array of the results (with `axis` removed).
+pub(crate) fn par_map_lanes_mut<A, S, D, B, F>(
+    array: &mut ArrayBase<S, D>,
+    axis: Axis,
+    f: F,
+) -> Array<B, D::Smaller>
+where
+    A: Send,
+    S: DataMut<Elem = A>,
+    D: RemoveAxis,
+    B: Send,
+    F: Fn(ArrayViewMut1<'_, A>) -> B + Send + Sync,
+{
+    let result_dim = array.raw_dim().remove_axis(axis);
+    let lanes: Vec<_> = array.lanes_mut(axis).into_iter().collect();
+    let results: Vec<B> = lanes.into_par_iter().map(f).collect();
+    Array::from_shape_vec(result_dim, results)
+        .expect("The number of lanes must match the shape of the result.")
+}
@@ -1,49 +1,106 @@
 //! Interpolation strategies.
-use noisy_float::types::N64;
+//!
+//! Besides the strategies of NumPy's `interpolation` parameter ([`Lower`],
+//! [`Higher`], [`Nearest`], [`Midpoint`] and [`Linear`]), this module
+//! implements the nine quantile definitions of [Hyndman and Fan (1996)],
+//! which are the `type` argument of R's `quantile`, the `method` argument of
+//! NumPy's `quantile` and the `alpha`/`beta` arguments of Julia's
+//! `Statistics.quantile`:
+//!
+//! | Type | Strategy                    | R         | NumPy                         |
+//! |------|-----------------------------|-----------|-------------------------------|
+//! | 1    | [`InvertedCdf`]             | `type=1`  | `"inverted_cdf"`              |
+//! | 2    | [`AveragedInvertedCdf`]     | `type=2`  | `"averaged_inverted_cdf"`     |
+//! | 3    | [`ClosestObservation`]      | `type=3`  | `"closest_observation"`       |
+//! | 4    | [`InterpolatedInvertedCdf`] | `type=4`  | `"interpolated_inverted_cdf"` |
+//! | 5    | [`Hazen`]                   | `type=5`  | `"hazen"`                     |
+//! | 6    | [`Weibull`]                 | `type=6`  | `"weibull"`                   |
+//! | 7    | [`Linear`]                  | `type=7`  | `"linear"` (the default)      |
+//! | 8    | [`MedianUnbiased`]          | `type=8`  | `"median_unbiased"`           |
+//! | 9    | [`NormalUnbiased`]          | `type=9`  | `"normal_unbiased"`           |
+//!
+//! The strategies are selected by type parameter; [`HyndmanFan`] selects one
+//! of them at runtime.
+//!
+//! [`Lower`]: struct.Lower.html
+//! [`Higher`]: struct.Higher.html
+//! [`Nearest`]: struct.Nearest.html
+//! [`Midpoint`]: struct.Midpoint.html
+//! [`Linear`]: struct.Linear.html
+//! [`InvertedCdf`]: struct.InvertedCdf.html
+//! [`AveragedInvertedCdf`]: struct.AveragedInvertedCdf.html
+//! [`ClosestObservation`]: struct.ClosestObservation.html
+//! [`InterpolatedInvertedCdf`]: struct.InterpolatedInvertedCdf.html
+//! [`Hazen`]: struct.Hazen.html
+//! [`Weibull`]: struct.Weibull.html
+//! [`MedianUnbiased`]: struct.MedianUnbiased.html
+//! [`NormalUnbiased`]: struct.NormalUnbiased.html
+//! [`HyndmanFan`]: enum.HyndmanFan.html
+//! [Hyndman and Fan (1996)]: https://doi.org/10.2307/2684934
+use crate::errors::QuantileError;
+use crate::QuantileExt;
+use ndarray::{Array, ArrayBase, Axis, DataMut, RemoveAxis};
+use noisy_float::types::{n64, N64};
 use num_traits::{Float, FromPrimitive, NumOps, ToPrimitive};
 
 fn float_quantile_index(q: N64, len: usize) -> N64 {
     q * ((len - 1) as f64)
 }
 
-/// Returns the fraction that the quantile is between the lower and higher indices.
-///
-/// This ranges from 0, where the quantile exactly corresponds the lower index,
-/// to 1, where the quantile exactly corresponds to the higher index.
-fn float_quantile_index_fraction(q: N64, len: usize) -> N64 {
-    float_quantile_index(q, len).fract()
+/// Returns the 0-based index of the `q`-th quantile of `len` elements for
+/// the continuous Hyndman-Fan definition with parameters `alpha` and `beta`,
+/// i.e. `h - 1` where `h = alpha + q (len + 1 - alpha - beta)`, clamped to
+/// `[1, len]`.
+fn continuous_quantile_index(q: N64, len: usize, alpha: f64, beta: f64) -> N64 {
+    let n = len as f64;
+    let h = alpha + q.raw() * (n + 1. - alpha - beta);
+    n64(h.max(1.).min(n) - 1.)
 }
 
-/// Returns the index of the value on the lower side of the quantile.
-pub(crate) fn lower_index(q: N64, len: usize) -> usize {
-    float_quantile_index(q, len).floor().to_usize().unwrap()
+/// Returns the index of the value on the lower side of the quantile at the
+/// (0-based, fractional) index `index`.
+pub(crate) fn lower_index(index: N64) -> usize {
+    index.floor().to_usize().unwrap()
 }
 
-/// Returns the index of the value on the higher side of the quantile.
-pub(crate) fn higher_index(q: N64, len: usize) -> usize {
-    float_quantile_index(q, len).ceil().to_usize().unwrap()
+/// Returns the index of the value on the higher side of the quantile at the
+/// (0-based, fractional) index `index`.
+pub(crate) fn higher_index(index: N64) -> usize {
+    index.ceil().to_usize().unwrap()
 }
 
 /// Used to provide an interpolation strategy to [`quantile_axis_mut`].
 ///
 /// [`quantile_axis_mut`]: ../trait.QuantileExt.html#tymethod.quantile_axis_mut
 pub trait Interpolate<T> {
+    /// Returns the fractional, 0-based index of the `q`-th quantile of `len`
+    /// sorted values; the values on both sides of it are the lower and the
+    /// higher values.
+    ///
+    /// The default is `q (len - 1)`.
+    #[doc(hidden)]
+    fn float_index(q: N64, len: usize) -> N64 {
+        float_quantile_index(q, len)
+    }
+
     /// Returns `true` iff the lower value is needed to compute the
-    /// interpolated value.
+    /// interpolated value, where `fraction` is the fractional part of the
+    /// index of the quantile.
     #[doc(hidden)]
-    fn needs_lower(q: N64, len: usize) -> bool;
+    fn needs_lower(fraction: N64) -> bool;
 
     /// Returns `true` iff the higher value is needed to compute the
-    /// interpolated value.
+    /// interpolated value, where `fraction` is the fractional part of the
+    /// index of the quantile.
     #[doc(hidden)]
-    fn needs_higher(q: N64, len: usize) -> bool;
+    fn needs_higher(fraction: N64) -> bool;
 
     /// Computes the interpolated value.
     ///
     /// **Panics** if `None` is provided for the lower value when it's needed
     /// or if `None` is provided for the higher value when it's needed.
     #[doc(hidden)]
-    fn interpolate(lower: Option<T>, higher: Option<T>, q: N64, len: usize) -> T;
+    fn interpolate(lower: Option<T>, higher: Option<T>, fraction: N64) -> T;
 
     private_decl! {}
 }
@@ -61,41 +118,88 @@ pub struct Midpoint;
 /// fractional part of the index surrounded by `lower` and `higher`).
 pub struct Linear;
 
+/// Hyndman-Fan type 1: select the lowest value whose empirical cumulative
+/// distribution is at least `q`, i.e. the value of rank `⌈n q⌉` (the inverse
+/// of the empirical distribution function).
+pub struct InvertedCdf;
+/// Hyndman-Fan type 2: like [`InvertedCdf`], but select the midpoint of the
+/// two values where the empirical distribution function is flat at `q`,
+/// i.e. when `n q` is an integer.
+///
+/// [`InvertedCdf`]: struct.InvertedCdf.html
+pub struct AveragedInvertedCdf;
+/// Hyndman-Fan type 3: select the value of rank `n q`, rounded to the
+/// nearest integer, with ties going to the even rank (SAS definition 2).
+pub struct ClosestObservation;
+/// Hyndman-Fan type 4: linearly interpolate the empirical distribution
+/// function, i.e. between the values of rank `⌊h⌋` and `⌊h⌋ + 1` with
+/// `h = n q`.
+pub struct InterpolatedInvertedCdf;
+/// Hyndman-Fan type 5: linearly interpolate with `h = n q + 1/2`, the
+/// piecewise linear function whose knots are the midpoints of the steps of
+/// the empirical distribution function.
+pub struct Hazen;
+/// Hyndman-Fan type 6: linearly interpolate with `h = (n + 1) q`, so that
+/// the value of rank `k` is the `k / (n + 1)`-quantile, its expected
+/// cumulative probability (used by Minitab and SPSS).
+pub struct Weibull;
+/// Hyndman-Fan type 8: linearly interpolate with `h = (n + 1/3) q + 1/3`,
+/// which makes the quantiles approximately median-unbiased whatever the
+/// distribution of the values (recommended by Hyndman and Fan).
+pub struct MedianUnbiased;
+/// Hyndman-Fan type 9: linearly interpolate with `h = (n + 1/4) q + 3/8`,
+/// which makes the quantiles approximately unbiased for normally
+/// distributed values.
+pub struct NormalUnbiased;
+
+/// Returns `lower + (higher - lower) * fraction`.
+fn linear_interpolation<T>(lower: Option<T>, higher: Option<T>, fraction: N64) -> T
+where
+    T: NumOps + Clone + FromPrimitive + ToPrimitive,
+{
+    let fraction = fraction.to_f64().unwrap();
+    let lower = lower.unwrap();
+    let higher = higher.unwrap();
+    let lower_f64 = lower.to_f64().unwrap();
+    let higher_f64 = higher.to_f64().unwrap();
+    lower.clone() + T::from_f64(fraction * (higher_f64 - lower_f64)).unwrap()
+}
+
 impl<T> Interpolate<T> for Higher {
-    fn needs_lower(_q: N64, _len: usize) -> bool {
+    fn needs_lower(_fraction: N64) -> bool {
         false
     }
-    fn needs_higher(_q: N64, _len: usize) -> bool {
+    fn needs_higher(_fraction: N64) -> bool {
         true
     }
-    fn interpolate(_lower: Option<T>, higher: Option<T>, _q: N64, _len: usize) -> T {
+    fn interpolate(_lower: Option<T>, higher: Option<T>, _fraction: N64) -> T {
         higher.unwrap()
     }
     private_impl! {}
 }
 
 impl<T> Interpolate<T> for Lower {
-    fn needs_lower(_q: N64, _len: usize) -> bool {
+    fn needs_lower(_fraction: N64) -> bool {
         true
     }
-    fn needs_higher(_q: N64, _len: usize) -> bool {
+    fn needs_higher(_fraction: N64) -> bool {
         false
     }
-    fn interpolate(lower: Option<T>, _higher: Option<T>, _q: N64, _len: usize) -> T {
+    fn interpolate(lower: Option<T>, _higher: Option<T>, _fraction: N64) -> T {
         lower.unwrap()
     }
     private_impl! {}
 }
 
 impl<T> Interpolate<T> for Nearest {
-    fn needs_lower(q: N64, len: usize) -> bool {
-        float_quantile_index_fraction(q, len) < 0.5
+    fn needs_lower(fraction: N64) -> bool {
+        fraction < 0.5
     }
-    fn needs_higher(q: N64, len: usize) -> bool {
-        !<Self as Interpolate<T>>::needs_lower(q, len)
+    fn needs_higher(fraction: N64) -> bool {
+        !<Self as Interpolate<T>>::needs_lower(fraction)
     }
-    fn interpolate(lower: Option<T>, higher: Option<T>, q: N64, len: usize) -> T {
-        if <Self as Interpolate<T>>::needs_lower(q, len) {
+    fn interpolate(lower: Option<T>, higher: Option<T>, fraction: N64) -> T {
+        if <Self as Interpolate<T>>::needs_lower(fraction) {
             lower.unwrap()
         } else {
             higher.unwrap()
@@ -108,13 +212,13 @@ impl<T> Interpolate<T> for Midpoint
 where
     T: NumOps + Clone + FromPrimitive,
 {
-    fn needs_lower(_q: N64, _len: usize) -> bool {
+    fn needs_lower(_fraction: N64) -> bool {
         true
     }
-    fn needs_higher(_q: N64, _len: usize) -> bool {
+    fn needs_higher(_fraction: N64) -> bool {
         true
     }
-    fn interpolate(lower: Option<T>, higher: Option<T>, _q: N64, _len: usize) -> T {
+    fn interpolate(lower: Option<T>, higher: Option<T>, _fraction: N64) -> T {
         let denom = T::from_u8(2).unwrap();
         let lower = lower.unwrap();
         let higher = higher.unwrap();
@@ -127,19 +231,206 @@ impl<T> Interpolate<T> for Linear
 where
     T: NumOps + Clone + FromPrimitive + ToPrimitive,
 {
-    fn needs_lower(_q: N64, _len: usize) -> bool {
+    fn needs_lower(_fraction: N64) -> bool {
         true
     }
-    fn needs_higher(_q: N64, _len: usize) -> bool {
+    fn needs_higher(_fraction: N64) -> bool {
         true
     }
-    fn interpolate(lower: Option<T>, higher: Option<T>, q: N64, len: usize) -> T {
-        let fraction = float_quantile_index_fraction(q, len).to_f64().unwrap();
-        let lower = lower.unwrap();
-        let higher = higher.unwrap();
-        let lower_f64 = lower.to_f64().unwrap();
-        let higher_f64 = higher.to_f64().unwrap();
-        lower.clone() + T::from_f64(fraction * (higher_f64 - lower_f64)).unwrap()
+    fn interpolate(lower: Option<T>, higher: Option<T>, fraction: N64) -> T {
+        linear_interpolation(lower, higher, fraction)
+    }
+    private_impl! {}
+}
+
+impl<T> Interpolate<T> for InvertedCdf {
+    fn float_index(q: N64, len: usize) -> N64 {
+        let rank = (q.raw() * len as f64).ceil().max(1.);
+        n64(rank - 1.)
+    }
+    fn needs_lower(_fraction: N64) -> bool {
+        true
+    }
+    fn needs_higher(_fraction: N64) -> bool {
+        false
+    }
+    fn interpolate(lower: Option<T>, _higher: Option<T>, _fraction: N64) -> T {
+        lower.unwrap()
     }
     private_impl! {}
 }
+
+impl<T> Interpolate<T> for AveragedInvertedCdf
+where
+    T: NumOps + Clone + FromPrimitive,
+{
+    fn float_index(q: N64, len: usize) -> N64 {
+        let h = q.raw() * len as f64;
+        let rank = h.ceil().max(1.);
+        if h == h.floor() && h >= 1. && h < len as f64 {
+            // Between the values of rank `h` and `h + 1`.
+            n64(h - 0.5)
+        } else {
+            n64(rank - 1.)
+        }
+    }
+    fn needs_lower(_fraction: N64) -> bool {
+        true
+    }
+    fn needs_higher(fraction: N64) -> bool {
+        fraction > 0.
+    }
+    fn interpolate(lower: Option<T>, higher: Option<T>, fraction: N64) -> T {
+        if fraction > 0. {
+            <Midpoint as Interpolate<T>>::interpolate(lower, higher, fraction)
+        } else {
+            lower.unwrap()
+        }
+    }
+    private_impl! {}
+}
+
+impl<T> Interpolate<T> for ClosestObservation {
+    fn float_index(q: N64, len: usize) -> N64 {
+        let h = q.raw() * len as f64;
+        let mut rank = h.round();
+        if h - h.floor() == 0.5 && rank % 2. != 0. {
+            rank -= 1.;
+        }
+        n64(rank.max(1.) - 1.)
+    }
+    fn needs_lower(_fraction: N64) -> bool {
+        true
+    }
+    fn needs_higher(_fraction: N64) -> bool {
+        false
+    }
+    fn interpolate(lower: Option<T>, _higher: Option<T>, _fraction: N64) -> T {
+        lower.unwrap()
+    }
+    private_impl! {}
+}
+
+macro_rules! continuous_interpolate {
+    ($strategy:ty, $alpha:expr, $beta:expr) => {
+        impl<T> Interpolate<T> for $strategy
+        where
+            T: NumOps + Clone + FromPrimitive + ToPrimitive,
+        {
+            fn float_index(q: N64, len: usize) -> N64 {
+                continuous_quantile_index(q, len, $alpha, $beta)
+            }
+            fn needs_lower(_fraction: N64) -> bool {
+                true
+            }
+            fn needs_higher(_fraction: N64) -> bool {
+                true
+            }
+            fn interpolate(lower: Option<T>, higher: Option<T>, fraction: N64) -> T {
+                linear_interpolation(lower, higher, fraction)
+            }
+            private_impl! {}
+        }
+    };
+}
+
+continuous_interpolate!(InterpolatedInvertedCdf, 0., 1.);
+continuous_interpolate!(Hazen, 0.5, 0.5);
+continuous_interpolate!(Weibull, 0., 0.);
+continuous_interpolate!(MedianUnbiased, 1. / 3., 1. / 3.);
+continuous_interpolate!(NormalUnbiased, 3. / 8., 3. / 8.);
+
+/// The nine quantile definitions of Hyndman and Fan, to select an
+/// interpolation strategy at runtime.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{array, Axis};
+/// use ndarray_stats::interpolate::HyndmanFan;
+/// use noisy_float::types::n64;
+///
+/// // R's `quantile(c(10, 20, 30, 40), 0.25, type = 6)`.
+/// let method = HyndmanFan::from_type(6).unwrap();
+/// let mut a = array![10, 20, 30, 40].mapv(|x| n64(x as f64));
+/// let quantile = method.quantile_axis_mut(&mut a, Axis(0), 0.25).unwrap();
+/// assert_eq!(quantile.into_scalar(), n64(12.5));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HyndmanFan {
+    /// Type 1, see [`InvertedCdf`](struct.InvertedCdf.html).
+    InvertedCdf,
+    /// Type 2, see [`AveragedInvertedCdf`](struct.AveragedInvertedCdf.html).
+    AveragedInvertedCdf,
+    /// Type 3, see [`ClosestObservation`](struct.ClosestObservation.html).
+    ClosestObservation,
+    /// Type 4, see [`InterpolatedInvertedCdf`](struct.InterpolatedInvertedCdf.html).
+    InterpolatedInvertedCdf,
+    /// Type 5, see [`Hazen`](struct.Hazen.html).
+    Hazen,
+    /// Type 6, see [`Weibull`](struct.Weibull.html).
+    Weibull,
+    /// Type 7, see [`Linear`](struct.Linear.html).
+    Linear,
+    /// Type 8, see [`MedianUnbiased`](struct.MedianUnbiased.html).
+    MedianUnbiased,
+    /// Type 9, see [`NormalUnbiased`](struct.NormalUnbiased.html).
+    NormalUnbiased,
+}
+
+impl HyndmanFan {
+    /// Returns the definition numbered `number` (from 1 to 9) by Hyndman and
+    /// Fan, as R's `type` argument, or `None` if there is no such definition.
+    pub fn from_type(number: u8) -> Option<Self> {
+        match number {
+            1 => Some(HyndmanFan::InvertedCdf),
+            2 => Some(HyndmanFan::AveragedInvertedCdf),
+            3 => Some(HyndmanFan::ClosestObservation),
+            4 => Some(HyndmanFan::InterpolatedInvertedCdf),
+            5 => Some(HyndmanFan::Hazen),
+            6 => Some(HyndmanFan::Weibull),
+            7 => Some(HyndmanFan::Linear),
+            8 => Some(HyndmanFan::MedianUnbiased),
+            9 => Some(HyndmanFan::NormalUnbiased),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of the definition by Hyndman and Fan, from 1 to 9.
+    pub fn type_number(self) -> u8 {
+        self as u8 + 1
+    }
+
+    /// Returns the `q`-th quantile of each lane along `axis` with this
+    /// definition, see [`QuantileExt::quantile_axis_mut`].
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// [`QuantileExt::quantile_axis_mut`]: ../trait.QuantileExt.html#tymethod.quantile_axis_mut
+    pub fn quantile_axis_mut<A, S, D, Q>(
+        self,
+        a: &mut ArrayBase<S, D>,
+        axis: Axis,
+        q: Q,
+    ) -> Result<Array<A, D::Smaller>, QuantileError>
+    where
+        A: Ord + NumOps + Clone + FromPrimitive + ToPrimitive,
+        S: DataMut<Elem = A>,
+        D: RemoveAxis,
+        Q: Float,
+    {
+        match self {
+            HyndmanFan::InvertedCdf => a.quantile_axis_mut(axis, q, &InvertedCdf),
+            HyndmanFan::AveragedInvertedCdf => a.quantile_axis_mut(axis, q, &AveragedInvertedCdf),
+            HyndmanFan::ClosestObservation => a.quantile_axis_mut(axis, q, &ClosestObservation),
+            HyndmanFan::InterpolatedInvertedCdf => {
+                a.quantile_axis_mut(axis, q, &InterpolatedInvertedCdf)
+            }
+            HyndmanFan::Hazen => a.quantile_axis_mut(axis, q, &Hazen),
+            HyndmanFan::Weibull => a.quantile_axis_mut(axis, q, &Weibull),
+            HyndmanFan::Linear => a.quantile_axis_mut(axis, q, &Linear),
+            HyndmanFan::MedianUnbiased => a.quantile_axis_mut(axis, q, &MedianUnbiased),
+            HyndmanFan::NormalUnbiased => a.quantile_axis_mut(axis, q, &NormalUnbiased),
+        }
+    }
+}
@@ -1,12 +1,15 @@
 use self::interpolate::{higher_index, lower_index, Interpolate};
 use super::sort::get_many_from_sorted_mut_unchecked;
-use crate::errors::QuantileError;
-use crate::errors::{EmptyInput, MinMaxError, MinMaxError::UndefinedOrder};
-use crate::{MaybeNan, MaybeNanExt};
+use crate::errors::{EmptyInput, MinMaxError, MinMaxError::UndefinedOrder, SkipNanError};
+use crate::errors::{NanPolicyQuantileError, QuantileError, ShapeMismatch, WeightedQuantileError};
+#[cfg(feature = "rayon")]
+use crate::parallel::par_map_lanes_mut;
+use crate::{MaybeNan, MaybeNanExt, NanPolicy};
+use core::cmp;
 use ndarray::prelude::*;
 use ndarray::{Data, DataMut, RemoveAxis, Zip};
-use noisy_float::types::N64;
-use std::cmp;
+use noisy_float::types::{n64, N64};
+use num_traits::Float;
 
 /// Quantile methods for `ArrayBase`.
 pub trait QuantileExt<A, S, D>
@@ -42,8 +45,9 @@ where
 
     /// Finds the index of the minimum value of the array skipping NaN values.
     ///
-    /// Returns `Err(EmptyInput)` if the array is empty or none of the values in the array
-    /// are non-NaN values.
+    /// Returns `Err(SkipNanError::EmptyInput)` if the array is empty and
+    /// `Err(SkipNanError::AllNan)` if none of the values in the array are
+    /// non-NaN values.
     ///
     /// Even if there are multiple (equal) elements that are minima, only one
     /// index is returned. (Which one is returned is unspecified and may depend
@@ -59,7 +63,7 @@ where
     ///                [2., 0., 6.]];
     /// assert_eq!(a.argmin_skipnan(), Ok((1, 1)));
     /// ```
-    fn argmin_skipnan(&self) -> Result<D::Pattern, EmptyInput>
+    fn argmin_skipnan(&self) -> Result<D::Pattern, SkipNanError>
     where
         A: MaybeNan,
         A::NotNan: Ord;
@@ -121,8 +125,9 @@ where
 
     /// Finds the index of the maximum value of the array skipping NaN values.
     ///
-    /// Returns `Err(EmptyInput)` if the array is empty or none of the values in the array
-    /// are non-NaN values.
+    /// Returns `Err(SkipNanError::EmptyInput)` if the array is empty and
+    /// `Err(SkipNanError::AllNan)` if none of the values in the array are
+    /// non-NaN values.
     ///
     /// Even if there are multiple (equal) elements that are maxima, only one
     /// index is returned. (Which one is returned is unspecified and may depend
@@ -138,7 +143,7 @@ where
     ///                [2., 0., 6.]];
     /// assert_eq!(a.argmax_skipnan(), Ok((1, 2)));
     /// ```
-    fn argmax_skipnan(&self) -> Result<D::Pattern, EmptyInput>
+    fn argmax_skipnan(&self) -> Result<D::Pattern, SkipNanError>
     where
         A: MaybeNan,
         A::NotNan: Ord;
@@ -186,6 +191,7 @@ where
     /// - `q=0.` returns the minimum along each 1-dimensional lane;
     /// - `q=0.5` returns the median along each 1-dimensional lane;
     /// - `q=1.` returns the maximum along each 1-dimensional lane.
+    ///
     /// (`q=0` and `q=1` are considered improper quantiles)
     ///
     /// The array is shuffled **in place** along each 1-dimensional lane in
@@ -198,23 +204,28 @@ where
     /// Complexity ([quickselect](https://en.wikipedia.org/wiki/Quickselect)):
     /// - average case: O(`m`);
     /// - worst case: O(`m`^2);
+    ///
     /// where `m` is the number of elements in the array.
     ///
     /// Returns `Err(EmptyInput)` when the specified axis has length 0.
     ///
-    /// Returns `Err(InvalidQuantile(q))` if `q` is not between `0.` and `1.` (inclusive).
+    /// `q` can be of any floating-point type implementing `num_traits::Float`
+    /// (e.g. `f32`, `f64` or `N64`), independently of the element type.
+    ///
+    /// Returns `Err(InvalidQuantile(q))` if `q` is NaN or not between `0.` and `1.` (inclusive).
     ///
     /// **Panics** if `axis` is out of bounds.
-    fn quantile_axis_mut<I>(
+    fn quantile_axis_mut<Q, I>(
         &mut self,
         axis: Axis,
-        q: N64,
+        q: Q,
         interpolate: &I,
     ) -> Result<Array<A, D::Smaller>, QuantileError>
     where
         D: RemoveAxis,
         A: Ord + Clone,
         S: DataMut,
+        Q: Float,
         I: Interpolate<A>;
 
     /// A bulk version of [`quantile_axis_mut`], optimized to retrieve multiple
@@ -228,7 +239,7 @@ where
     ///
     /// Returns `Err(EmptyInput)` when the specified axis has length 0.
     ///
-    /// Returns `Err(InvalidQuantile(q))` if any `q` in `qs` is not between `0.` and `1.` (inclusive).
+    /// Returns `Err(InvalidQuantile(q))` if any `q` in `qs` is NaN or not between `0.` and `1.` (inclusive).
     ///
     /// **Panics** if `axis` is out of bounds.
     ///
@@ -249,7 +260,7 @@ where
     ///     assert_eq!(quantile, data.quantile_axis_mut(axis, q, &Nearest).unwrap());
     /// }
     /// ```
-    fn quantiles_axis_mut<S2, I>(
+    fn quantiles_axis_mut<S2, Q, I>(
         &mut self,
         axis: Axis,
         qs: &ArrayBase<S2, Ix1>,
@@ -259,16 +270,17 @@ where
         D: RemoveAxis,
         A: Ord + Clone,
         S: DataMut,
-        S2: Data<Elem = N64>,
+        S2: Data<Elem = Q>,
+        Q: Float,
         I: Interpolate<A>;
 
     /// Return the `q`th quantile of the data along the specified axis, skipping NaN values.
     ///
     /// See [`quantile_axis_mut`](#tymethod.quantile_axis_mut) for details.
-    fn quantile_axis_skipnan_mut<I>(
+    fn quantile_axis_skipnan_mut<Q, I>(
         &mut self,
         axis: Axis,
-        q: N64,
+        q: Q,
         interpolate: &I,
     ) -> Result<Array<A, D::Smaller>, QuantileError>
     where
@@ -276,8 +288,206 @@ where
         A: MaybeNan,
         A::NotNan: Clone + Ord,
         S: DataMut,
+        Q: Float,
         I: Interpolate<A::NotNan>;
 
+    /// Return the `q`th quantile of the data along the specified axis,
+    /// handling NaN values according to `policy`:
+    ///
+    /// - `NanPolicy::Propagate`: the quantile of a lane containing a NaN
+    ///   value is NaN;
+    /// - `NanPolicy::Omit`: NaN values are skipped, as in
+    ///   [`quantile_axis_skipnan_mut`](#tymethod.quantile_axis_skipnan_mut);
+    /// - `NanPolicy::Error`: `Err(NanPolicyQuantileError::ContainsNan)` is
+    ///   returned if the array contains any NaN value.
+    ///
+    /// The other failures are returned as
+    /// `Err(NanPolicyQuantileError::Quantile(_))`.
+    ///
+    /// See [`quantile_axis_mut`](#tymethod.quantile_axis_mut) for details.
+    fn quantile_axis_with_policy_mut<Q, I>(
+        &mut self,
+        axis: Axis,
+        q: Q,
+        interpolate: &I,
+        policy: NanPolicy,
+    ) -> Result<Array<A, D::Smaller>, NanPolicyQuantileError>
+    where
+        D: RemoveAxis,
+        A: MaybeNan,
+        A::NotNan: Clone + Ord,
+        S: DataMut,
+        Q: Float,
+        I: Interpolate<A::NotNan>;
+
+    /// Returns the `q`th weighted quantile of all the elements of the array,
+    /// where `weights` holds the (non-negative) weight of each element.
+    ///
+    /// Elements with a zero weight are ignored. The other ones are sorted,
+    /// and the element of rank `k` is placed at the position
+    ///
+    /// ```text
+    ///      Sₖ - S₀
+    /// pₖ = ―――――――
+    ///      Sₙ₋₁ - S₀
+    /// ```
+    ///
+    /// where `Sₖ` is the sum of the weights of the elements of rank `0` to
+    /// `k`, so that the positions range from `0` for the smallest element to
+    /// `1` for the largest one (this is the definition of [`StatsBase.jl`]).
+    /// If `q` falls between the positions of two elements, the `interpolate`
+    /// strategy selects or interpolates between them as in
+    /// [`quantile_axis_mut`](#tymethod.quantile_axis_mut), with the fraction
+    /// of the distance between the two positions. With equal weights,
+    /// `pₖ = k / (n - 1)` and the weighted quantile is the unweighted one.
+    /// Since the positions are given by the weights, the strategies that
+    /// only differ by the position of the quantiles (e.g. the Hyndman-Fan
+    /// definitions 4 to 9) all interpolate linearly here.
+    ///
+    /// The elements of the array and their weights are sorted **in place**,
+    /// together, so that each element keeps its weight. No assumptions should
+    /// be made on the ordering of the elements after this computation.
+    ///
+    /// Returns `Err(WeightedQuantileError::ShapeMismatch)` if the shapes of
+    /// the array and of `weights` are different,
+    /// `Err(WeightedQuantileError::InvalidQuantile(q))` if `q` is NaN or not
+    /// between `0.` and `1.` (inclusive),
+    /// `Err(WeightedQuantileError::InvalidWeights)` if a weight is negative
+    /// or NaN, and `Err(WeightedQuantileError::ZeroWeights)` if the weights
+    /// sum to zero (in particular if the array is empty).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{interpolate::{Linear, Lower}, QuantileExt};
+    ///
+    /// let mut a = array![[1, 4], [2, 3]];
+    /// let mut weights = array![[2., 1.], [1., 0.]];
+    /// // The positions of 1, 2 and 4 are 0, 0.5 and 1.
+    /// assert_eq!(a.weighted_quantile_mut(&mut weights, 0.6, &Lower), Ok(2));
+    /// assert_eq!(a.weighted_quantile_mut(&mut weights, 0.75, &Linear), Ok(3));
+    /// ```
+    ///
+    /// [`StatsBase.jl`]: https://juliastats.org/StatsBase.jl/stable/scalarstats/#Statistics.quantile
+    fn weighted_quantile_mut<S2, W, Q, I>(
+        &mut self,
+        weights: &mut ArrayBase<S2, D>,
+        q: Q,
+        interpolate: &I,
+    ) -> Result<A, WeightedQuantileError>
+    where
+        A: Ord + Clone,
+        S: DataMut,
+        S2: DataMut<Elem = W>,
+        W: Float,
+        Q: Float,
+        I: Interpolate<A>;
+
+    /// Returns the `q`th weighted quantile of the data along the specified
+    /// axis, where `weights` holds the (non-negative) weight of each element.
+    ///
+    /// Each lane along `axis` is sorted in place together with its weights,
+    /// and its quantile is computed as in
+    /// [`weighted_quantile_mut`](#tymethod.weighted_quantile_mut), which
+    /// describes the errors. `Err(WeightedQuantileError::ZeroWeights)` is
+    /// returned if the weights of any lane sum to zero.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{interpolate::Nearest, QuantileExt};
+    ///
+    /// let mut a = array![[5, 1, 3], [2, 8, 4]];
+    /// let mut weights = array![[1., 1., 1.], [1., 1., 2.]];
+    /// let median = a.weighted_quantile_axis_mut(Axis(1), &mut weights, 0.5, &Nearest).unwrap();
+    /// assert_eq!(median, array![3, 4]);
+    /// ```
+    fn weighted_quantile_axis_mut<S2, W, Q, I>(
+        &mut self,
+        axis: Axis,
+        weights: &mut ArrayBase<S2, D>,
+        q: Q,
+        interpolate: &I,
+    ) -> Result<Array<A, D::Smaller>, WeightedQuantileError>
+    where
+        D: RemoveAxis,
+        A: Ord + Clone,
+        S: DataMut,
+        S2: DataMut<Elem = W>,
+        W: Float,
+        Q: Float,
+        I: Interpolate<A>;
+
+    /// Return the `q`th quantile of the data along the specified axis,
+    /// processing the lanes in parallel.
+    ///
+    /// The result and the errors are the same as for
+    /// [`quantile_axis_mut`](#tymethod.quantile_axis_mut).
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{interpolate::Lower, QuantileExt};
+    /// use noisy_float::types::n64;
+    ///
+    /// let mut a = array![[3, 1, 2], [6, 5, 4]];
+    /// let median = a.par_quantile_axis_mut(Axis(1), n64(0.5), &Lower).unwrap();
+    /// assert_eq!(median, array![2, 5]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    fn par_quantile_axis_mut<Q, I>(
+        &mut self,
+        axis: Axis,
+        q: Q,
+        interpolate: &I,
+    ) -> Result<Array<A, D::Smaller>, QuantileError>
+    where
+        D: RemoveAxis,
+        A: Ord + Clone + Send,
+        S: DataMut,
+        Q: Float,
+        I: Interpolate<A> + Sync;
+
+    /// Like [`quantile_axis_skipnan_mut`](#tymethod.quantile_axis_skipnan_mut),
+    /// but computes the quantiles of the lanes in parallel.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{interpolate::Linear, QuantileExt};
+    /// use noisy_float::types::n64;
+    /// use std::f64::NAN;
+    ///
+    /// let mut a = array![[3., NAN, 1.], [NAN, NAN, NAN]];
+    /// let median = a.par_quantile_axis_skipnan_mut(Axis(1), n64(0.5), &Linear).unwrap();
+    /// assert_eq!(median[0], 2.);
+    /// assert!(median[1].is_nan());
+    /// ```
+    #[cfg(feature = "rayon")]
+    fn par_quantile_axis_skipnan_mut<Q, I>(
+        &mut self,
+        axis: Axis,
+        q: Q,
+        interpolate: &I,
+    ) -> Result<Array<A, D::Smaller>, QuantileError>
+    where
+        D: RemoveAxis,
+        A: MaybeNan + Send,
+        A::NotNan: Clone + Ord,
+        S: DataMut,
+        Q: Float,
+        I: Interpolate<A::NotNan> + Sync;
+
     private_decl! {}
 }
 
@@ -291,6 +501,10 @@ where
         A: PartialOrd,
     {
         let mut current_min = self.first().ok_or(EmptyInput)?;
+        if let Some(xs) = self.as_slice() {
+            let index = arg_extremum(xs, cmp::Ordering::Less)?;
+            return Ok(unravel_index(&self.raw_dim(), index));
+        }
         let mut current_pattern_min = D::zeros(self.ndim()).into_pattern();
 
         for (pattern, elem) in self.indexed_iter() {
@@ -303,7 +517,7 @@ where
         Ok(current_pattern_min)
     }
 
-    fn argmin_skipnan(&self) -> Result<D::Pattern, EmptyInput>
+    fn argmin_skipnan(&self) -> Result<D::Pattern, SkipNanError>
     where
         A: MaybeNan,
         A::NotNan: Ord,
@@ -320,8 +534,10 @@ where
         });
         if min.is_some() {
             Ok(pattern_min)
+        } else if self.is_empty() {
+            Err(SkipNanError::EmptyInput)
         } else {
-            Err(EmptyInput)
+            Err(SkipNanError::AllNan)
         }
     }
 
@@ -358,6 +574,10 @@ where
         A: PartialOrd,
     {
         let mut current_max = self.first().ok_or(EmptyInput)?;
+        if let Some(xs) = self.as_slice() {
+            let index = arg_extremum(xs, cmp::Ordering::Greater)?;
+            return Ok(unravel_index(&self.raw_dim(), index));
+        }
         let mut current_pattern_max = D::zeros(self.ndim()).into_pattern();
 
         for (pattern, elem) in self.indexed_iter() {
@@ -370,7 +590,7 @@ where
         Ok(current_pattern_max)
     }
 
-    fn argmax_skipnan(&self) -> Result<D::Pattern, EmptyInput>
+    fn argmax_skipnan(&self) -> Result<D::Pattern, SkipNanError>
     where
         A: MaybeNan,
         A::NotNan: Ord,
@@ -387,8 +607,10 @@ where
         });
         if max.is_some() {
             Ok(pattern_max)
+        } else if self.is_empty() {
+            Err(SkipNanError::EmptyInput)
         } else {
-            Err(EmptyInput)
+            Err(SkipNanError::AllNan)
         }
     }
 
@@ -420,7 +642,7 @@ where
         }))
     }
 
-    fn quantiles_axis_mut<S2, I>(
+    fn quantiles_axis_mut<S2, Q, I>(
         &mut self,
         axis: Axis,
         qs: &ArrayBase<S2, Ix1>,
@@ -430,7 +652,8 @@ where
         D: RemoveAxis,
         A: Ord + Clone,
         S: DataMut,
-        S2: Data<Elem = N64>,
+        S2: Data<Elem = Q>,
+        Q: Float,
         I: Interpolate<A>,
     {
         // Minimize number of type parameters to avoid monomorphization bloat.
@@ -445,12 +668,6 @@ where
             A: Ord + Clone,
             I: Interpolate<A>,
         {
-            for &q in qs {
-                if !((q >= 0.) && (q <= 1.)) {
-                    return Err(QuantileError::InvalidQuantile(q));
-                }
-            }
-
             let axis_len = data.len_of(axis);
             if axis_len == 0 {
                 return Err(QuantileError::EmptyInput);
@@ -462,13 +679,14 @@ where
                 return Ok(Array::from_shape_vec(results_shape, Vec::new()).unwrap());
             }
 
+            let indexes: Vec<N64> = qs.iter().map(|&q| I::float_index(q, axis_len)).collect();
             let mut searched_indexes = Vec::with_capacity(2 * qs.len());
-            for &q in &qs {
-                if I::needs_lower(q, axis_len) {
-                    searched_indexes.push(lower_index(q, axis_len));
+            for &index in &indexes {
+                if I::needs_lower(index.fract()) {
+                    searched_indexes.push(lower_index(index));
                 }
-                if I::needs_higher(q, axis_len) {
-                    searched_indexes.push(higher_index(q, axis_len));
+                if I::needs_higher(index.fract()) {
+                    searched_indexes.push(higher_index(index));
                 }
             }
             searched_indexes.sort();
@@ -480,46 +698,53 @@ where
                 .apply(|mut results, mut data| {
                     let index_map =
                         get_many_from_sorted_mut_unchecked(&mut data, &searched_indexes);
-                    for (result, &q) in results.iter_mut().zip(qs) {
-                        let lower = if I::needs_lower(q, axis_len) {
-                            Some(index_map[&lower_index(q, axis_len)].clone())
+                    for (result, &index) in results.iter_mut().zip(&indexes) {
+                        let fraction = index.fract();
+                        let lower = if I::needs_lower(fraction) {
+                            Some(index_map[&lower_index(index)].clone())
                         } else {
                             None
                         };
-                        let higher = if I::needs_higher(q, axis_len) {
-                            Some(index_map[&higher_index(q, axis_len)].clone())
+                        let higher = if I::needs_higher(fraction) {
+                            Some(index_map[&higher_index(index)].clone())
                         } else {
                             None
                         };
-                        *result = I::interpolate(lower, higher, q, axis_len);
+                        *result = I::interpolate(lower, higher, fraction);
                     }
                 });
             Ok(results)
         }
 
+        let qs = qs
+            .iter()
+            .map(|&q| check_quantile(q))
+            .collect::<Result<Array1<_>, _>>()?;
         quantiles_axis_mut(self.view_mut(), axis, qs.view(), interpolate)
     }
 
-    fn quantile_axis_mut<I>(
+    fn quantile_axis_mut<Q, I>(
         &mut self,
         axis: Axis,
-        q: N64,
+        q: Q,
         interpolate: &I,
     ) -> Result<Array<A, D::Smaller>, QuantileError>
     where
         D: RemoveAxis,
         A: Ord + Clone,
         S: DataMut,
+        Q: Float,
         I: Interpolate<A>,
     {
+        let q = check_quantile(q)?;
         self.quantiles_axis_mut(axis, &aview1(&[q]), interpolate)
             .map(|a| a.index_axis_move(axis, 0))
     }
 
-    fn quantile_axis_skipnan_mut<I>(
+    fn quantile_axis_skipnan_mut<Q, I>(
         &mut self,
         axis: Axis,
-        q: N64,
+        q: Q,
         interpolate: &I,
     ) -> Result<Array<A, D::Smaller>, QuantileError>
     where
@@ -527,35 +752,318 @@ where
         A: MaybeNan,
         A::NotNan: Clone + Ord,
         S: DataMut,
+        Q: Float,
         I: Interpolate<A::NotNan>,
     {
-        if !((q >= 0.) && (q <= 1.)) {
-            return Err(QuantileError::InvalidQuantile(q));
-        }
+        let q = check_quantile(q)?;
 
         if self.len_of(axis) == 0 {
             return Err(QuantileError::EmptyInput);
         }
 
-        let quantile = self.map_axis_mut(axis, |lane| {
-            let mut not_nan = A::remove_nan_mut(lane);
-            A::from_not_nan_opt(if not_nan.is_empty() {
-                None
-            } else {
-                Some(
-                    not_nan
-                        .quantile_axis_mut::<I>(Axis(0), q, interpolate)
-                        .unwrap()
-                        .into_scalar(),
-                )
-            })
-        });
+        let quantile = self.map_axis_mut(axis, |lane| quantile_skipnan_mut(lane, q, interpolate));
         Ok(quantile)
     }
 
+    fn quantile_axis_with_policy_mut<Q, I>(
+        &mut self,
+        axis: Axis,
+        q: Q,
+        interpolate: &I,
+        policy: NanPolicy,
+    ) -> Result<Array<A, D::Smaller>, NanPolicyQuantileError>
+    where
+        D: RemoveAxis,
+        A: MaybeNan,
+        A::NotNan: Clone + Ord,
+        S: DataMut,
+        Q: Float,
+        I: Interpolate<A::NotNan>,
+    {
+        match policy {
+            NanPolicy::Propagate => {
+                let mut quantile = self.quantile_axis_skipnan_mut(axis, q, interpolate)?;
+                // Partitioning the lanes does not change their contents, so
+                // we can check for NaN values after computing the quantiles.
+                Zip::from(&mut quantile)
+                    .and(self.lanes(axis))
+                    .apply(|quantile, lane| {
+                        if lane.has_nan() {
+                            *quantile = A::from_not_nan_opt(None);
+                        }
+                    });
+                Ok(quantile)
+            }
+            NanPolicy::Omit => Ok(self.quantile_axis_skipnan_mut(axis, q, interpolate)?),
+            NanPolicy::Error => {
+                if self.has_nan() {
+                    Err(NanPolicyQuantileError::ContainsNan)
+                } else {
+                    Ok(self.quantile_axis_skipnan_mut(axis, q, interpolate)?)
+                }
+            }
+        }
+    }
+
+    fn weighted_quantile_mut<S2, W, Q, I>(
+        &mut self,
+        weights: &mut ArrayBase<S2, D>,
+        q: Q,
+        interpolate: &I,
+    ) -> Result<A, WeightedQuantileError>
+    where
+        A: Ord + Clone,
+        S: DataMut,
+        S2: DataMut<Elem = W>,
+        W: Float,
+        Q: Float,
+        I: Interpolate<A>,
+    {
+        let q = check_weighted_quantile(self, weights, q)?;
+        let mut pairs = Vec::with_capacity(self.len());
+        Zip::from(&*self)
+            .and(&*weights)
+            .apply(|x, &w| pairs.push((x.clone(), w)));
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut sorted = pairs.iter();
+        Zip::from(&mut *self).and(&mut *weights).apply(|x, w| {
+            let (sorted_x, sorted_w) = sorted.next().unwrap();
+            *x = sorted_x.clone();
+            *w = *sorted_w;
+        });
+        weighted_quantile_of_sorted(&pairs, q, interpolate)
+            .ok_or(WeightedQuantileError::ZeroWeights)
+    }
+
+    fn weighted_quantile_axis_mut<S2, W, Q, I>(
+        &mut self,
+        axis: Axis,
+        weights: &mut ArrayBase<S2, D>,
+        q: Q,
+        interpolate: &I,
+    ) -> Result<Array<A, D::Smaller>, WeightedQuantileError>
+    where
+        D: RemoveAxis,
+        A: Ord + Clone,
+        S: DataMut,
+        S2: DataMut<Elem = W>,
+        W: Float,
+        Q: Float,
+        I: Interpolate<A>,
+    {
+        let q = check_weighted_quantile(self, weights, q)?;
+        if self.len_of(axis) == 0 {
+            return Err(WeightedQuantileError::ZeroWeights);
+        }
+        let quantile_shape = self.raw_dim().remove_axis(axis);
+        if quantile_shape.size() == 0 {
+            return Ok(Array::from_shape_vec(quantile_shape, Vec::new()).unwrap());
+        }
+        let mut quantile = Array::from_elem(quantile_shape, self.first().unwrap().clone());
+        let mut zero_weights = false;
+        Zip::from(&mut quantile)
+            .and(self.lanes_mut(axis))
+            .and(weights.lanes_mut(axis))
+            .apply(|quantile, mut lane, mut lane_weights| {
+                // The arguments have been checked, so only the weights of the
+                // lane can be invalid.
+                match lane.weighted_quantile_mut(&mut lane_weights, q, interpolate) {
+                    Ok(q) => *quantile = q,
+                    Err(_) => zero_weights = true,
+                }
+            });
+        if zero_weights {
+            Err(WeightedQuantileError::ZeroWeights)
+        } else {
+            Ok(quantile)
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_quantile_axis_mut<Q, I>(
+        &mut self,
+        axis: Axis,
+        q: Q,
+        interpolate: &I,
+    ) -> Result<Array<A, D::Smaller>, QuantileError>
+    where
+        D: RemoveAxis,
+        A: Ord + Clone + Send,
+        S: DataMut,
+        Q: Float,
+        I: Interpolate<A> + Sync,
+    {
+        let q = check_quantile(q)?;
+        if self.len_of(axis) == 0 {
+            return Err(QuantileError::EmptyInput);
+        }
+        // The checks above guarantee that computing the quantile of each
+        // lane can't fail.
+        Ok(par_map_lanes_mut(self, axis, |mut lane| {
+            lane.quantile_mut(q, interpolate).unwrap()
+        }))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_quantile_axis_skipnan_mut<Q, I>(
+        &mut self,
+        axis: Axis,
+        q: Q,
+        interpolate: &I,
+    ) -> Result<Array<A, D::Smaller>, QuantileError>
+    where
+        D: RemoveAxis,
+        A: MaybeNan + Send,
+        A::NotNan: Clone + Ord,
+        S: DataMut,
+        Q: Float,
+        I: Interpolate<A::NotNan> + Sync,
+    {
+        let q = check_quantile(q)?;
+        if self.len_of(axis) == 0 {
+            return Err(QuantileError::EmptyInput);
+        }
+        Ok(par_map_lanes_mut(self, axis, |lane| {
+            quantile_skipnan_mut(lane, q, interpolate)
+        }))
+    }
+
     private_impl! {}
 }
 
+/// Returns the `q`-th quantile of the non-NaN elements of `lane`, or NaN if
+/// there are none; `q` must have been checked to be in `[0, 1]`.
+fn quantile_skipnan_mut<A, I>(lane: ArrayViewMut1<'_, A>, q: N64, interpolate: &I) -> A
+where
+    A: MaybeNan,
+    A::NotNan: Clone + Ord,
+    I: Interpolate<A::NotNan>,
+{
+    let mut not_nan = A::remove_nan_mut(lane);
+    A::from_not_nan_opt(if not_nan.is_empty() {
+        None
+    } else {
+        Some(
+            not_nan
+                .quantile_axis_mut(Axis(0), q, interpolate)
+                .unwrap()
+                .into_scalar(),
+        )
+    })
+}
+
+/// Returns the index of the first element of `xs` such that no other element
+/// compares to it as `target` (i.e. the first minimum for `Ordering::Less`,
+/// the first maximum for `Ordering::Greater`).
+///
+/// This is the fast path of `argmin`/`argmax` for arrays in standard layout,
+/// which avoids computing the index of every element.
+///
+/// `xs` must not be empty.
+fn arg_extremum<A: PartialOrd>(xs: &[A], target: cmp::Ordering) -> Result<usize, MinMaxError> {
+    let mut best = 0;
+    for (i, x) in xs.iter().enumerate() {
+        if x.partial_cmp(&xs[best]).ok_or(UndefinedOrder)? == target {
+            best = i;
+        }
+    }
+    Ok(best)
+}
+
+/// Converts the position of an element in the logical order of an array of
+/// shape `dim` to its index.
+fn unravel_index<D: Dimension>(dim: &D, mut position: usize) -> D::Pattern {
+    let mut index = D::zeros(dim.ndim());
+    for (i, &len) in index.slice_mut().iter_mut().zip(dim.slice()).rev() {
+        *i = position % len;
+        position /= len;
+    }
+    index.into_pattern()
+}
+
+/// Converts the quantile `q` to `N64`.
+///
+/// Returns `Err(InvalidQuantile(q))` if `q` is NaN or not between `0.` and
+/// `1.` (inclusive).
+///
+/// **Panics** if `q` can't be converted to `f64`.
+pub(crate) fn check_quantile<Q: Float>(q: Q) -> Result<N64, QuantileError> {
+    let q = q
+        .to_f64()
+        .expect("Converting the quantile to `f64` must not fail.");
+    if (q >= 0.) && (q <= 1.) {
+        Ok(n64(q))
+    } else {
+        Err(QuantileError::InvalidQuantile(q))
+    }
+}
+
+/// Checks the arguments of the weighted quantile methods, returning the
+/// quantile as `N64`.
+fn check_weighted_quantile<A, S, S2, D, W, Q>(
+    a: &ArrayBase<S, D>,
+    weights: &ArrayBase<S2, D>,
+    q: Q,
+) -> Result<N64, WeightedQuantileError>
+where
+    S: Data<Elem = A>,
+    S2: Data<Elem = W>,
+    D: Dimension,
+    W: Float,
+    Q: Float,
+{
+    if a.shape() != weights.shape() {
+        return Err(ShapeMismatch {
+            first_shape: a.shape().to_vec(),
+            second_shape: weights.shape().to_vec(),
+        }
+        .into());
+    }
+    let q = check_quantile(q)
+        .map_err(|_| WeightedQuantileError::InvalidQuantile(q.to_f64().unwrap()))?;
+    if weights.iter().any(|&w| w < W::zero() || w.is_nan()) {
+        return Err(WeightedQuantileError::InvalidWeights);
+    }
+    Ok(q)
+}
+
+/// Returns the `q`th weighted quantile of `pairs` of elements and weights,
+/// sorted by element, or `None` if the weights sum to zero.
+///
+/// See `QuantileExt::weighted_quantile_mut` for the definition.
+fn weighted_quantile_of_sorted<A, W, I>(pairs: &[(A, W)], q: N64, _interpolate: &I) -> Option<A>
+where
+    A: Clone,
+    W: Float,
+    I: Interpolate<A>,
+{
+    let pairs: Vec<(&A, f64)> = pairs
+        .iter()
+        .filter(|(_, w)| *w > W::zero())
+        .map(|(x, w)| (x, w.to_f64().unwrap()))
+        .collect();
+    let first_weight = pairs.first()?.1;
+    // Position of each element, up to the normalization by the last one.
+    let mut positions = Vec::with_capacity(pairs.len());
+    let mut cumulative = 0.;
+    for &(_, w) in &pairs {
+        cumulative += w;
+        positions.push(cumulative - first_weight);
+    }
+    let target = q.raw() * positions[pairs.len() - 1];
+    // The last element at or before the target.
+    let k = positions.iter().rposition(|&p| p <= target).unwrap_or(0);
+    if k + 1 == pairs.len() || positions[k] == target {
+        return Some(pairs[k].0.clone());
+    }
+    let fraction = n64((target - positions[k]) / (positions[k + 1] - positions[k]));
+    Some(I::interpolate(
+        Some(pairs[k].0.clone()),
+        Some(pairs[k + 1].0.clone()),
+        fraction,
+    ))
+}
+
 /// Quantile methods for 1-D arrays.
 pub trait Quantile1dExt<A, S>
 where
@@ -575,6 +1083,7 @@ where
     /// - `q=0.` returns the minimum;
     /// - `q=0.5` returns the median;
     /// - `q=1.` returns the maximum.
+    ///
     /// (`q=0` and `q=1` are considered improper quantiles)
     ///
     /// The array is shuffled **in place** in order to produce the required quantile
@@ -585,15 +1094,32 @@ where
     /// Complexity ([quickselect](https://en.wikipedia.org/wiki/Quickselect)):
     /// - average case: O(`m`);
     /// - worst case: O(`m`^2);
+    ///
     /// where `m` is the number of elements in the array.
     ///
     /// Returns `Err(EmptyInput)` if the array is empty.
     ///
-    /// Returns `Err(InvalidQuantile(q))` if `q` is not between `0.` and `1.` (inclusive).
-    fn quantile_mut<I>(&mut self, q: N64, interpolate: &I) -> Result<A, QuantileError>
+    /// `q` can be of any floating-point type implementing `num_traits::Float`
+    /// (e.g. `f32`, `f64` or `N64`), independently of the element type.
+    ///
+    /// Returns `Err(InvalidQuantile(q))` if `q` is NaN or not between `0.` and `1.` (inclusive).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{interpolate::Linear, Quantile1dExt};
+    /// use noisy_float::types::n32;
+    ///
+    /// let mut a = array![n32(3.), n32(1.), n32(2.), n32(4.)];
+    /// assert_eq!(a.quantile_mut(0.5, &Linear).unwrap(), n32(2.5));
+    /// assert_eq!(a.quantile_mut(0.75f32, &Linear).unwrap(), n32(3.25));
+    /// ```
+    fn quantile_mut<Q, I>(&mut self, q: Q, interpolate: &I) -> Result<A, QuantileError>
     where
         A: Ord + Clone,
         S: DataMut,
+        Q: Float,
         I: Interpolate<A>;
 
     /// A bulk version of [`quantile_mut`], optimized to retrieve multiple
@@ -605,13 +1131,13 @@ where
     /// Returns `Err(EmptyInput)` if the array is empty.
     ///
     /// Returns `Err(InvalidQuantile(q))` if any `q` in
-    /// `qs` is not between `0.` and `1.` (inclusive).
+    /// `qs` is NaN or not between `0.` and `1.` (inclusive).
     ///
     /// See [`quantile_mut`] for additional details on quantiles and the algorithm
     /// used to retrieve them.
     ///
     /// [`quantile_mut`]: #tymethod.quantile_mut
-    fn quantiles_mut<S2, I>(
+    fn quantiles_mut<S2, Q, I>(
         &mut self,
         qs: &ArrayBase<S2, Ix1>,
         interpolate: &I,
@@ -619,7 +1145,8 @@ where
     where
         A: Ord + Clone,
         S: DataMut,
-        S2: Data<Elem = N64>,
+        S2: Data<Elem = Q>,
+        Q: Float,
         I: Interpolate<A>;
 
     private_decl! {}
@@ -629,10 +1156,11 @@ impl<A, S> Quantile1dExt<A, S> for ArrayBase<S, Ix1>
 where
     S: Data<Elem = A>,
 {
-    fn quantile_mut<I>(&mut self, q: N64, interpolate: &I) -> Result<A, QuantileError>
+    fn quantile_mut<Q, I>(&mut self, q: Q, interpolate: &I) -> Result<A, QuantileError>
     where
         A: Ord + Clone,
         S: DataMut,
+        Q: Float,
         I: Interpolate<A>,
     {
         Ok(self
@@ -640,7 +1168,7 @@ where
             .into_scalar())
     }
 
-    fn quantiles_mut<S2, I>(
+    fn quantiles_mut<S2, Q, I>(
         &mut self,
         qs: &ArrayBase<S2, Ix1>,
         interpolate: &I,
@@ -648,7 +1176,8 @@ where
     where
         A: Ord + Clone,
         S: DataMut,
-        S2: Data<Elem = N64>,
+        S2: Data<Elem = Q>,
+        Q: Float,
         I: Interpolate<A>,
     {
         self.quantiles_axis_mut(Axis(0), qs, interpolate)
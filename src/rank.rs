@@ -0,0 +1,374 @@
+//! Ranks of the elements of an array.
+use ndarray::{Array, ArrayBase, ArrayView1, ArrayViewMut1, Axis, Data, Dimension, Zip};
+use num_traits::{Float, FromPrimitive};
+
+/// How tied elements are ranked by [`RankExt::rank_axis_with`], as in
+/// SciPy's `rankdata`.
+///
+/// [`RankExt::rank_axis_with`]: trait.RankExt.html#tymethod.rank_axis_with
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RankMethod {
+    /// Tied elements get the average of the ranks they span, e.g.
+    /// `[1, 2.5, 2.5, 4]`.
+    Average,
+    /// Tied elements get the lowest of the ranks they span, e.g.
+    /// `[1, 2, 2, 4]`.
+    Min,
+    /// Tied elements get the highest of the ranks they span, e.g.
+    /// `[1, 3, 3, 4]`.
+    Max,
+    /// Tied elements get the same rank, and the next distinct element gets
+    /// the next rank, e.g. `[1, 2, 2, 3]`.
+    Dense,
+    /// Tied elements get distinct ranks, in their order along the lane, e.g.
+    /// `[1, 2, 3, 4]`.
+    Ordinal,
+}
+
+/// Extension trait for `ArrayBase` providing methods to rank the elements
+/// along an axis.
+///
+/// The lanes along the chosen `axis` are ranked independently. Unless
+/// specified otherwise with [`rank_axis_with`], tied elements all get the
+/// average of the ranks they span (like SciPy's `rankdata` with
+/// `method="average"`), and NaN elements are left out of the ranking: their
+/// rank is NaN.
+///
+/// [`rank_axis_with`]: #tymethod.rank_axis_with
+pub trait RankExt<A, S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    /// Returns the rank of each element within its lane along `axis`,
+    /// starting from 1 for the smallest element.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails
+    /// to convert a rank.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::RankExt;
+    ///
+    /// let a = array![[30., 10., 20., 10.], [1., 2., 3., 4.]];
+    /// let ranks = a.rank_axis(Axis(1));
+    /// assert_eq!(ranks, array![[4., 1.5, 3., 1.5], [1., 2., 3., 4.]]);
+    /// ```
+    fn rank_axis(&self, axis: Axis) -> Array<A, D>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the rank of each element within its lane along `axis`,
+    /// starting from 1 for the smallest element, ranking ties according to
+    /// `method`.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails
+    /// to convert a rank.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{RankExt, RankMethod};
+    ///
+    /// let a = array![30., 10., 20., 10.];
+    /// assert_eq!(a.rank_axis_with(Axis(0), RankMethod::Min), array![4., 1., 3., 1.]);
+    /// assert_eq!(a.rank_axis_with(Axis(0), RankMethod::Max), array![4., 2., 3., 2.]);
+    /// assert_eq!(a.rank_axis_with(Axis(0), RankMethod::Dense), array![3., 1., 2., 1.]);
+    /// ```
+    fn rank_axis_with(&self, axis: Axis, method: RankMethod) -> Array<A, D>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the rank of each element within its lane along `axis`,
+    /// ranking ties according to `method` and placing NaN elements last.
+    ///
+    /// The elements that aren't NaN get the same ranks as with
+    /// [`rank_axis_with`](#tymethod.rank_axis_with), and the NaN elements are
+    /// ranked after them, as if they were equal to each other and greater
+    /// than any other element (like pandas' `rank` with
+    /// `na_option="bottom"`).
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails
+    /// to convert a rank.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{RankExt, RankMethod};
+    ///
+    /// let a = array![2., ::std::f64::NAN, 1., ::std::f64::NAN, 2.];
+    /// let average = a.rank_axis_skipnan(Axis(0), RankMethod::Average);
+    /// assert_eq!(average, array![2.5, 4.5, 1., 4.5, 2.5]);
+    /// let ordinal = a.rank_axis_skipnan(Axis(0), RankMethod::Ordinal);
+    /// assert_eq!(ordinal, array![2., 4., 1., 5., 3.]);
+    /// ```
+    fn rank_axis_skipnan(&self, axis: Axis, method: RankMethod) -> Array<A, D>
+    where
+        A: Float + FromPrimitive;
+
+    /// Maps each element to its empirical quantile within its lane along
+    /// `axis`: `(rank - 1) / (n - 1)`, where `n` is the number of elements
+    /// of the lane that aren't NaN.
+    ///
+    /// The results are spread uniformly over `[0, 1]`, from `0.` for the
+    /// smallest element to `1.` for the largest one (a lane with a single
+    /// element is mapped to `0.5`), whatever the distribution of the input.
+    /// This is histogram equalization for images, or the uniform quantile
+    /// transform for features whose scale or outliers would otherwise
+    /// dominate. NaN elements stay NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails
+    /// to convert a rank.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::RankExt;
+    ///
+    /// let a = array![1e6, 3., ::std::f64::NAN, 2., 3., 0.];
+    /// let q = a.rank_transform_axis(Axis(0));
+    /// assert_eq!(q[0], 1.);
+    /// assert!(q[2].is_nan());
+    /// assert_eq!(q[3], 0.25);
+    /// assert_eq!(q[4], 0.625);
+    /// assert_eq!(q[5], 0.);
+    /// ```
+    fn rank_transform_axis(&self, axis: Axis) -> Array<A, D>
+    where
+        A: Float + FromPrimitive;
+
+    private_decl! {}
+}
+
+impl<A, S, D> RankExt<A, S, D> for ArrayBase<S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn rank_axis(&self, axis: Axis) -> Array<A, D>
+    where
+        A: Float + FromPrimitive,
+    {
+        self.rank_axis_with(axis, RankMethod::Average)
+    }
+
+    fn rank_axis_with(&self, axis: Axis, method: RankMethod) -> Array<A, D>
+    where
+        A: Float + FromPrimitive,
+    {
+        let mut ranks = Array::from_elem(self.raw_dim(), A::nan());
+        Zip::from(ranks.lanes_mut(axis))
+            .and(self.lanes(axis))
+            .apply(|ranks, lane| {
+                rank_lane(lane, ranks, method, false);
+            });
+        ranks
+    }
+
+    fn rank_axis_skipnan(&self, axis: Axis, method: RankMethod) -> Array<A, D>
+    where
+        A: Float + FromPrimitive,
+    {
+        let mut ranks = Array::from_elem(self.raw_dim(), A::nan());
+        Zip::from(ranks.lanes_mut(axis))
+            .and(self.lanes(axis))
+            .apply(|ranks, lane| {
+                rank_lane(lane, ranks, method, true);
+            });
+        ranks
+    }
+
+    fn rank_transform_axis(&self, axis: Axis) -> Array<A, D>
+    where
+        A: Float + FromPrimitive,
+    {
+        let mut quantiles = Array::from_elem(self.raw_dim(), A::nan());
+        Zip::from(quantiles.lanes_mut(axis))
+            .and(self.lanes(axis))
+            .apply(|mut quantiles, lane| {
+                match rank_lane(lane, quantiles.view_mut(), RankMethod::Average, false) {
+                    0 => {}
+                    1 => {
+                        let half = A::from_f64(0.5).expect("Converting 0.5 to `A` must not fail.");
+                        quantiles.mapv_inplace(|r| if r.is_nan() { r } else { half });
+                    }
+                    n => {
+                        let last = to_a::<A>(n - 1);
+                        quantiles.mapv_inplace(|r| (r - A::one()) / last);
+                    }
+                }
+            });
+        quantiles
+    }
+
+    private_impl! {}
+}
+
+/// Writes the ranks of the elements of `lane` to `ranks` (which must be
+/// filled with NaN) and returns the number of elements that aren't NaN.
+///
+/// NaN elements are ranked last if `nan_last` is `true`, and left out of the
+/// ranking otherwise.
+fn rank_lane<A>(
+    lane: ArrayView1<'_, A>,
+    mut ranks: ArrayViewMut1<'_, A>,
+    method: RankMethod,
+    nan_last: bool,
+) -> usize
+where
+    A: Float + FromPrimitive,
+{
+    let mut order: Vec<(usize, A)> = lane
+        .iter()
+        .cloned()
+        .enumerate()
+        .filter(|(_, x)| nan_last || !x.is_nan())
+        .collect();
+    // The sort is stable, so that tied elements stay in their order along
+    // the lane for `RankMethod::Ordinal`.
+    order.sort_by(|(_, a), (_, b)| match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.partial_cmp(b).expect("NaN values have been excluded."),
+        (a_is_nan, b_is_nan) => a_is_nan.cmp(&b_is_nan),
+    });
+    let tied = |a: A, b: A| a == b || (a.is_nan() && b.is_nan());
+    let mut start = 0;
+    let mut n_distinct = 0;
+    while start < order.len() {
+        let value = order[start].1;
+        let end = start
+            + order[start..]
+                .iter()
+                .take_while(|(_, x)| tied(*x, value))
+                .count();
+        n_distinct += 1;
+        // The elements of the tie span the ranks `start + 1..=end`.
+        let rank = match method {
+            RankMethod::Average => to_a::<A>(start + 1 + end) / (A::one() + A::one()),
+            RankMethod::Min => to_a(start + 1),
+            RankMethod::Max => to_a(end),
+            RankMethod::Dense => to_a(n_distinct),
+            RankMethod::Ordinal => A::zero(),
+        };
+        for (k, &(i, _)) in order[start..end].iter().enumerate() {
+            ranks[i] = match method {
+                RankMethod::Ordinal => to_a(start + 1 + k),
+                _ => rank,
+            };
+        }
+        start = end;
+    }
+    lane.iter().filter(|x| !x.is_nan()).count()
+}
+
+fn to_a<A: FromPrimitive>(n: usize) -> A {
+    A::from_usize(n).expect("Converting a rank to `A` must not fail.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::izip;
+    use ndarray::{array, s, Array1, Array2};
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[test]
+    fn test_rank_with_nan() {
+        let a = array![[2., f64::NAN], [2., 5.], [1., 5.], [2., 5.]];
+        let ranks = a.rank_axis(Axis(0));
+        assert_eq!(ranks.column(0), array![3., 3., 1., 3.]);
+        assert!(ranks[(0, 1)].is_nan());
+        assert_eq!(ranks.slice(s![1.., 1]), array![2., 2., 2.]);
+        let q = a.rank_transform_axis(Axis(0));
+        assert_eq!(q.column(0), array![2. / 3., 2. / 3., 0., 2. / 3.]);
+        assert_eq!(q.slice(s![1.., 1]), array![0.5, 0.5, 0.5]);
+        let single = array![f64::NAN, 7.];
+        assert_eq!(
+            single.rank_transform_axis(Axis(0)).slice(s![1..]),
+            array![0.5]
+        );
+        assert_eq!(
+            Array2::<f64>::zeros((0, 2)).rank_axis(Axis(0)).shape(),
+            &[0, 2]
+        );
+    }
+
+    #[quickcheck]
+    fn ranks_are_a_permutation_without_ties(xs: Vec<i32>) -> bool {
+        let mut distinct = xs.clone();
+        distinct.sort();
+        distinct.dedup();
+        let a = Array1::from_vec(distinct.iter().rev().map(|&x| f64::from(x)).collect());
+        let ranks = a.rank_axis(Axis(0));
+        let n = a.len();
+        ranks.iter().enumerate().all(|(i, &r)| r == (n - i) as f64)
+    }
+
+    #[quickcheck]
+    fn ranks_sum_to_triangular_number(xs: Vec<i8>) -> bool {
+        let a = Array1::from_vec(xs).mapv(f64::from);
+        let n = a.len() as f64;
+        let quantiles = a.rank_transform_axis(Axis(0));
+        a.rank_axis(Axis(0)).sum() == n * (n + 1.) / 2.
+            && quantiles.iter().all(|&q| q >= 0. && q <= 1.)
+    }
+
+    #[quickcheck]
+    fn tie_methods_bracket_average(xs: Vec<i8>) -> bool {
+        let a = Array1::from_vec(xs).mapv(f64::from);
+        let average = a.rank_axis(Axis(0));
+        let min = a.rank_axis_with(Axis(0), RankMethod::Min);
+        let max = a.rank_axis_with(Axis(0), RankMethod::Max);
+        let dense = a.rank_axis_with(Axis(0), RankMethod::Dense);
+        let mut distinct = a.to_vec();
+        distinct.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        distinct.dedup();
+        (0..a.len()).all(|i| {
+            min[i] <= average[i]
+                && average[i] <= max[i]
+                && average[i] == (min[i] + max[i]) / 2.
+                && dense[i] == (distinct.iter().position(|&x| x == a[i]).unwrap() + 1) as f64
+        })
+    }
+
+    #[quickcheck]
+    fn nan_last_ranks_match_ranks_without_nan(xs: Vec<Option<i8>>) -> bool {
+        let a = Array1::from_iter(xs.iter().map(|x| x.map_or(f64::NAN, f64::from)));
+        let n = xs.len() as f64;
+        [
+            RankMethod::Average,
+            RankMethod::Min,
+            RankMethod::Max,
+            RankMethod::Dense,
+            RankMethod::Ordinal,
+        ]
+        .iter()
+        .all(|&method| {
+            let ranks = a.rank_axis_with(Axis(0), method);
+            let nan_last = a.rank_axis_skipnan(Axis(0), method);
+            let max_rank = ranks
+                .iter()
+                .cloned()
+                .filter(|r| !r.is_nan())
+                .fold(0., f64::max);
+            let ordinal_is_permutation = method != RankMethod::Ordinal || {
+                let mut sorted = nan_last.to_vec();
+                sorted.sort_by(|x, y| x.partial_cmp(y).unwrap());
+                sorted.iter().enumerate().all(|(i, &r)| r == (i + 1) as f64)
+            };
+            ordinal_is_permutation
+                && izip!(&a, &ranks, &nan_last).all(|(x, &r, &r_nan_last)| {
+                    if x.is_nan() {
+                        r.is_nan() && r_nan_last > max_rank && r_nan_last <= n
+                    } else {
+                        r == r_nan_last
+                    }
+                })
+        })
+    }
+}
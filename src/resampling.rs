@@ -0,0 +1,402 @@
+//! Bootstrap and jackknife resampling.
+//!
+//! [`bootstrap`] and [`jackknife`] apply a statistic to resamples of the
+//! subviews of an array along an axis, and return the resulting values
+//! together with the statistic of the whole array. They estimate the bias and
+//! the standard error of the statistic, and the bootstrap gives confidence
+//! intervals for it without assuming the distribution of the data.
+//!
+//! [`bootstrap`]: fn.bootstrap.html
+//! [`jackknife`]: fn.jackknife.html
+use crate::fit::special::{normal_cdf, normal_quantile};
+use crate::numpy_compat::linear_quantile;
+use crate::SamplingExt;
+use ndarray::{Array1, ArrayBase, ArrayView, Axis, Data, RemoveAxis};
+use num_traits::{Float, FromPrimitive};
+use rand::Rng;
+
+/// The values of a statistic on bootstrap resamples, returned by
+/// [`bootstrap`](fn.bootstrap.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bootstrap<A> {
+    estimate: A,
+    replicates: Array1<A>,
+}
+
+impl<A> Bootstrap<A>
+where
+    A: Float + FromPrimitive,
+{
+    /// Returns the statistic of the original data.
+    pub fn estimate(&self) -> A {
+        self.estimate
+    }
+
+    /// Returns the statistic of each resample.
+    pub fn replicates(&self) -> &Array1<A> {
+        &self.replicates
+    }
+
+    /// Returns the bootstrap estimate of the bias of the statistic: the mean
+    /// of the replicates minus the estimate.
+    pub fn bias(&self) -> A {
+        mean(&self.replicates) - self.estimate
+    }
+
+    /// Returns the bootstrap estimate of the standard error of the
+    /// statistic: the sample standard deviation of the replicates.
+    ///
+    /// It is NaN if there is a single resample.
+    pub fn standard_error(&self) -> A {
+        let mean = mean(&self.replicates);
+        let n = to_a::<A>(self.replicates.len());
+        let sum_sq_dev = self
+            .replicates
+            .fold(A::zero(), |acc, &x| acc + (x - mean) * (x - mean));
+        (sum_sq_dev / (n - A::one())).sqrt()
+    }
+
+    /// Returns the percentile confidence interval of the statistic with
+    /// confidence `level` (e.g. `0.95`): the `(1 - level) / 2` and
+    /// `(1 + level) / 2` quantiles of the replicates, with linear
+    /// interpolation.
+    ///
+    /// The bounds are NaN if a replicate is NaN.
+    ///
+    /// **Panics** if `level` is not strictly between `0.` and `1.`.
+    pub fn percentile_interval(&self, level: A) -> (A, A) {
+        let alpha = tail_probability(level);
+        (self.quantile(alpha), self.quantile(A::one() - alpha))
+    }
+
+    /// Returns the bias-corrected and accelerated (BCa) confidence interval
+    /// of the statistic with confidence `level` (e.g. `0.95`).
+    ///
+    /// Like the percentile interval, it is made of quantiles of the
+    /// replicates, but their orders are adjusted for the median bias of the
+    /// replicates and for the skewness of the statistic, estimated from the
+    /// `jackknife` of the same statistic on the same data. This makes it
+    /// more accurate than the percentile interval for biased or skewed
+    /// statistics.
+    ///
+    /// The bounds are NaN if a replicate is NaN.
+    ///
+    /// **Panics** if `level` is not strictly between `0.` and `1.`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{Array1, Axis};
+    /// use ndarray_stats::resampling::{bootstrap, jackknife};
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let data = Array1::from_shape_fn(50, |i| ((i * 37 % 50) as f64 / 10.).exp());
+    /// let mean = |x: ndarray::ArrayView1<f64>| x.mean_axis(Axis(0))[()];
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let boot = bootstrap(&data, Axis(0), 2000, mean, &mut rng);
+    /// let (low, high) = boot.bca_interval(0.95, &jackknife(&data, Axis(0), mean));
+    /// assert!(low < boot.estimate() && boot.estimate() < high);
+    /// ```
+    pub fn bca_interval(&self, level: A, jackknife: &Jackknife<A>) -> (A, A) {
+        let alpha = tail_probability(level);
+        let below = self
+            .replicates
+            .iter()
+            .filter(|&&x| x < self.estimate)
+            .count();
+        let z0 = normal_quantile(to_a::<A>(below) / to_a(self.replicates.len()));
+        let acceleration = jackknife.acceleration();
+        let adjusted = |alpha: A| {
+            let z = z0 + normal_quantile(alpha);
+            normal_cdf(z0 + z / (A::one() - acceleration * z))
+        };
+        (
+            self.quantile(adjusted(alpha)),
+            self.quantile(adjusted(A::one() - alpha)),
+        )
+    }
+
+    fn quantile(&self, q: A) -> A {
+        let q = q
+            .to_f64()
+            .expect("Converting a quantile to `f64` must not fail.");
+        linear_quantile(self.replicates.to_vec(), q)
+    }
+}
+
+/// The values of a statistic on the leave-one-out subsamples, returned by
+/// [`jackknife`](fn.jackknife.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jackknife<A> {
+    estimate: A,
+    replicates: Array1<A>,
+}
+
+impl<A> Jackknife<A>
+where
+    A: Float + FromPrimitive,
+{
+    /// Returns the statistic of the original data.
+    pub fn estimate(&self) -> A {
+        self.estimate
+    }
+
+    /// Returns the statistic of each subsample: element `i` is the statistic
+    /// of the data without subview `i`.
+    pub fn replicates(&self) -> &Array1<A> {
+        &self.replicates
+    }
+
+    /// Returns the jackknife estimate of the bias of the statistic,
+    /// `(n - 1) (θ̅ - θ)`, where `θ̅` is the mean of the replicates and `θ` is
+    /// the estimate.
+    pub fn bias(&self) -> A {
+        let n = to_a::<A>(self.replicates.len());
+        (n - A::one()) * (mean(&self.replicates) - self.estimate)
+    }
+
+    /// Returns the estimate minus its jackknife bias.
+    pub fn bias_corrected_estimate(&self) -> A {
+        self.estimate - self.bias()
+    }
+
+    /// Returns the jackknife estimate of the standard error of the
+    /// statistic, `√((n - 1) / n ∑ (θᵢ - θ̅)²)`.
+    pub fn standard_error(&self) -> A {
+        let mean = mean(&self.replicates);
+        let n = to_a::<A>(self.replicates.len());
+        let sum_sq_dev = self
+            .replicates
+            .fold(A::zero(), |acc, &x| acc + (x - mean) * (x - mean));
+        ((n - A::one()) / n * sum_sq_dev).sqrt()
+    }
+
+    /// Returns the acceleration of the BCa interval, estimated from the
+    /// skewness of the replicates.
+    fn acceleration(&self) -> A {
+        let mean = mean(&self.replicates);
+        let (sum_sq, sum_cube) = self
+            .replicates
+            .fold((A::zero(), A::zero()), |(sq, cube), &x| {
+                let d = mean - x;
+                (sq + d * d, cube + d * d * d)
+            });
+        if sum_sq == A::zero() {
+            return A::zero();
+        }
+        sum_cube / (to_a::<A>(6) * sum_sq.powf(to_a::<A>(3) / to_a(2)))
+    }
+}
+
+/// Applies `statistic` to the original data and to `n_resamples` bootstrap
+/// resamples of `data` along `axis`.
+///
+/// Each resample is made of as many subviews along `axis` as `data`, drawn
+/// uniformly at random with replacement (see
+/// [`SamplingExt::sample_axis`]). The random number generator is provided by
+/// the caller, so a seeded generator makes the results reproducible.
+///
+/// **Panics** if `axis` is out of bounds or empty, or if `n_resamples` is
+/// zero.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{array, Axis};
+/// use ndarray_stats::resampling::bootstrap;
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let data = array![2.1, 3.4, 1.9, 5.6, 4.2, 3.3, 2.8, 3.9];
+/// let mut rng = StdRng::seed_from_u64(42);
+/// let boot = bootstrap(&data, Axis(0), 1000, |x| x.mean_axis(Axis(0))[()], &mut rng);
+/// assert_eq!(boot.replicates().len(), 1000);
+/// let (low, high) = boot.percentile_interval(0.9);
+/// assert!(low < 3.4 && 3.4 < high);
+/// ```
+///
+/// [`SamplingExt::sample_axis`]: ../trait.SamplingExt.html#tymethod.sample_axis
+pub fn bootstrap<A, B, S, D, F, R>(
+    data: &ArrayBase<S, D>,
+    axis: Axis,
+    n_resamples: usize,
+    mut statistic: F,
+    rng: &mut R,
+) -> Bootstrap<B>
+where
+    A: Copy,
+    B: Float + FromPrimitive,
+    S: Data<Elem = A>,
+    D: RemoveAxis,
+    F: FnMut(ArrayView<'_, A, D>) -> B,
+    R: Rng + ?Sized,
+{
+    let len = data.len_of(axis);
+    assert!(len > 0, "Cannot resample an empty axis.");
+    assert!(n_resamples > 0, "The number of resamples must be positive.");
+    let replicates = (0..n_resamples)
+        .map(|_| statistic(data.sample_axis(axis, len, true, rng).view()))
+        .collect();
+    Bootstrap {
+        estimate: statistic(data.view()),
+        replicates,
+    }
+}
+
+/// Applies `statistic` to the original data and to each subsample of `data`
+/// leaving out one subview along `axis`.
+///
+/// **Panics** if `axis` is out of bounds or has less than two subviews.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::{array, Axis};
+/// use ndarray_stats::resampling::jackknife;
+///
+/// let data = array![1f64, 2., 4., 7.];
+/// // The plug-in (`ddof = 0`) variance is biased, and the jackknife
+/// // corrects it to the unbiased one.
+/// let jack = jackknife(&data, Axis(0), |x| x.var_axis(Axis(0), 0.)[()]);
+/// let unbiased = data.var_axis(Axis(0), 1.)[()];
+/// assert!((jack.bias_corrected_estimate() - unbiased).abs() < 1e-12);
+/// ```
+pub fn jackknife<A, B, S, D, F>(
+    data: &ArrayBase<S, D>,
+    axis: Axis,
+    mut statistic: F,
+) -> Jackknife<B>
+where
+    A: Copy,
+    B: Float + FromPrimitive,
+    S: Data<Elem = A>,
+    D: RemoveAxis,
+    F: FnMut(ArrayView<'_, A, D>) -> B,
+{
+    let len = data.len_of(axis);
+    assert!(len >= 2, "The jackknife needs at least two subviews.");
+    let replicates = (0..len)
+        .map(|i| {
+            let indices: Vec<usize> = (0..len).filter(|&j| j != i).collect();
+            statistic(data.select(axis, &indices).view())
+        })
+        .collect();
+    Jackknife {
+        estimate: statistic(data.view()),
+        replicates,
+    }
+}
+
+/// Returns the probability `(1 - level) / 2` of each tail outside of a
+/// confidence interval.
+fn tail_probability<A: Float>(level: A) -> A {
+    assert!(
+        level > A::zero() && level < A::one(),
+        "The confidence level must be strictly between 0 and 1."
+    );
+    (A::one() - level) / (A::one() + A::one())
+}
+
+fn mean<A: Float + FromPrimitive>(xs: &Array1<A>) -> A {
+    xs.fold(A::zero(), |acc, &x| acc + x) / to_a(xs.len())
+}
+
+fn to_a<A: FromPrimitive>(n: usize) -> A {
+    A::from_usize(n).expect("Converting a number of resamples to `A` must not fail.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use ndarray::{array, Array2, ArrayView1};
+    use quickcheck_macros::quickcheck;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn mean_of(x: ArrayView1<'_, f64>) -> f64 {
+        x.mean_axis(Axis(0))[()]
+    }
+
+    #[quickcheck]
+    fn jackknife_of_mean_is_unbiased_with_standard_error_of_mean(xs: Vec<i16>) -> bool {
+        if xs.len() < 2 {
+            return true;
+        }
+        let data = Array1::from_vec(xs).mapv(f64::from);
+        let jack = jackknife(&data, Axis(0), mean_of);
+        let n = data.len() as f64;
+        let standard_error = (data.var_axis(Axis(0), 1.)[()] / n).sqrt();
+        jack.bias().abs() <= 1e-9 * data.mapv(f64::abs).sum().max(1.)
+            && (jack.standard_error() - standard_error).abs() <= 1e-9 * standard_error.max(1.)
+    }
+
+    #[test]
+    fn test_jackknife_replicates_leave_one_out() {
+        let data = array![[1., 10.], [2., 20.], [3., 30.]];
+        let jack = jackknife(&data, Axis(0), |x| x.column(1).sum());
+        assert_eq!(jack.estimate(), 60.);
+        assert_eq!(jack.replicates(), &array![50., 40., 30.]);
+    }
+
+    #[test]
+    fn test_bootstrap_is_reproducible() {
+        let data = Array2::from_shape_fn((20, 3), |(i, j)| (i * j) as f64);
+        let statistic = |x: ArrayView<'_, f64, _>| x.sum();
+        let first = bootstrap(&data, Axis(0), 50, statistic, &mut StdRng::seed_from_u64(7));
+        let second = bootstrap(&data, Axis(0), 50, statistic, &mut StdRng::seed_from_u64(7));
+        assert_eq!(first, second);
+        assert_eq!(first.estimate(), data.sum());
+    }
+
+    #[test]
+    fn test_bootstrap_mean_standard_error() {
+        let data = Array1::from_shape_fn(100, |i| (i * 7 % 100) as f64);
+        let mut rng = StdRng::seed_from_u64(1);
+        let boot = bootstrap(&data, Axis(0), 4000, mean_of, &mut rng);
+        // The plug-in standard error of the mean.
+        let expected = (data.var_axis(Axis(0), 0.)[()] / 100.).sqrt();
+        assert_abs_diff_eq!(boot.standard_error(), expected, epsilon = 0.05 * expected);
+        assert_abs_diff_eq!(boot.bias(), 0., epsilon = 0.1 * expected);
+    }
+
+    #[test]
+    fn test_bca_interval_of_symmetric_statistic_is_close_to_percentile() {
+        let data = Array1::from_shape_fn(60, |i| ((i * 13 % 60) as f64 - 30.).powi(3));
+        let mut rng = StdRng::seed_from_u64(3);
+        let boot = bootstrap(&data, Axis(0), 2000, mean_of, &mut rng);
+        let jack = jackknife(&data, Axis(0), mean_of);
+        let (low, high) = boot.percentile_interval(0.9);
+        let (bca_low, bca_high) = boot.bca_interval(0.9, &jack);
+        let width = high - low;
+        assert!(low < boot.estimate() && boot.estimate() < high);
+        assert_abs_diff_eq!(bca_low, low, epsilon = 0.1 * width);
+        assert_abs_diff_eq!(bca_high, high, epsilon = 0.1 * width);
+    }
+
+    #[test]
+    fn test_bca_interval_shifts_towards_skewness() {
+        // The mean of right-skewed data has a right-skewed distribution, and
+        // the BCa interval is shifted to the right of the percentile one.
+        let data = Array1::from_shape_fn(40, |i| ((i * 17 % 40) as f64 / 8.).exp());
+        let mut rng = StdRng::seed_from_u64(5);
+        let boot = bootstrap(&data, Axis(0), 4000, mean_of, &mut rng);
+        let jack = jackknife(&data, Axis(0), mean_of);
+        assert!(jack.acceleration() > 0.);
+        let (low, high) = boot.percentile_interval(0.95);
+        let (bca_low, bca_high) = boot.bca_interval(0.95, &jack);
+        assert!(bca_low > low && bca_high > high);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_level_panics() {
+        let boot = bootstrap(
+            &array![1., 2.],
+            Axis(0),
+            10,
+            mean_of,
+            &mut StdRng::seed_from_u64(0),
+        );
+        boot.percentile_interval(1.);
+    }
+}
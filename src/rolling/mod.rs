@@ -0,0 +1,316 @@
+//! Moving-window statistics along an axis.
+use self::windows::{slide, Extremum, MeanVar, Median, Window};
+use ndarray::{Array, ArrayBase, Axis, Data, Dimension, Slice, Zip};
+use num_traits::{Float, FromPrimitive};
+
+mod windows;
+
+/// How [`RollingExt`] handles the positions where the window doesn't fit
+/// entirely in the data (the first `window - 1` positions).
+///
+/// [`RollingExt`]: trait.RollingExt.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RollingEdge {
+    /// Only the complete windows are summarized: the result is shorter than
+    /// the input by `window - 1` along the axis (and empty if `window` is
+    /// greater than the length of the axis). Element `i` of the result
+    /// summarizes the input elements `i..i + window`, like NumPy's
+    /// `sliding_window_view`.
+    Truncate,
+    /// The result has the same shape as the input, and its first
+    /// `window - 1` elements along the axis are NaN. Element `i` of the
+    /// result summarizes the window ending at element `i` of the input, like
+    /// pandas' `rolling`.
+    NanPad,
+}
+
+/// Extension trait for `ArrayBase` providing moving-window (rolling)
+/// statistics.
+///
+/// Each lane along the chosen `axis` is summarized independently, over
+/// windows of `window` consecutive elements. The windows are updated
+/// incrementally as they slide, so the cost doesn't grow with the window
+/// length, except for the median (`O(window)` per element). The summary of
+/// a window containing a NaN element is NaN; use
+/// [`TimeSeriesExt::rolling_apply_axis`] to skip NaN values instead.
+///
+/// All methods **panic** if `axis` is out of bounds or if `window` is zero.
+///
+/// [`TimeSeriesExt::rolling_apply_axis`]: trait.TimeSeriesExt.html#tymethod.rolling_apply_axis
+pub trait RollingExt<A, S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    /// Returns the moving average along `axis`.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the window length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, s, Axis};
+    /// use ndarray_stats::{RollingEdge, RollingExt};
+    ///
+    /// let a = array![1f64, 2., 6., 3.];
+    /// let mean = a.rolling_mean_axis(Axis(0), 2, RollingEdge::Truncate);
+    /// assert_eq!(mean, array![1.5, 4., 4.5]);
+    /// let mean = a.rolling_mean_axis(Axis(0), 2, RollingEdge::NanPad);
+    /// assert!(mean[0].is_nan());
+    /// assert_eq!(mean.slice(s![1..]), array![1.5, 4., 4.5]);
+    /// ```
+    fn rolling_mean_axis(&self, axis: Axis, window: usize, edge: RollingEdge) -> Array<A, D>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the moving variance along `axis`.
+    ///
+    /// The parameter `ddof` specifies the "delta degrees of freedom": use
+    /// `ddof = 0` for the population variance and `ddof = 1` for the
+    /// unbiased sample variance.
+    ///
+    /// **Panics** if `ddof` is less than zero or greater than or equal to
+    /// `window`, or if `A::from_usize()` fails to convert the window length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{RollingEdge, RollingExt};
+    ///
+    /// let a = array![[1., 3., 5., 9.]];
+    /// let var = a.rolling_var_axis(Axis(1), 3, 1., RollingEdge::Truncate);
+    /// assert!(var.all_close(&array![[4., 28. / 3.]], 1e-12));
+    /// ```
+    fn rolling_var_axis(
+        &self,
+        axis: Axis,
+        window: usize,
+        ddof: A,
+        edge: RollingEdge,
+    ) -> Array<A, D>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the moving standard deviation along `axis`, the square root
+    /// of [`rolling_var_axis`](#tymethod.rolling_var_axis).
+    ///
+    /// **Panics** if `ddof` is less than zero or greater than or equal to
+    /// `window`, or if `A::from_usize()` fails to convert the window length.
+    fn rolling_std_axis(
+        &self,
+        axis: Axis,
+        window: usize,
+        ddof: A,
+        edge: RollingEdge,
+    ) -> Array<A, D>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the moving minimum along `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{RollingEdge, RollingExt};
+    ///
+    /// let a = array![4., 2., 5., 3., 1.];
+    /// let min = a.rolling_min_axis(Axis(0), 3, RollingEdge::Truncate);
+    /// assert_eq!(min, array![2., 2., 1.]);
+    /// ```
+    fn rolling_min_axis(&self, axis: Axis, window: usize, edge: RollingEdge) -> Array<A, D>
+    where
+        A: Float;
+
+    /// Returns the moving maximum along `axis`.
+    fn rolling_max_axis(&self, axis: Axis, window: usize, edge: RollingEdge) -> Array<A, D>
+    where
+        A: Float;
+
+    /// Returns the moving median along `axis`. The median of a window with
+    /// an even number of elements is the mean of the two middle elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{RollingEdge, RollingExt};
+    ///
+    /// let a = array![1., 100., 2., 3., 4.];
+    /// let median = a.rolling_median_axis(Axis(0), 3, RollingEdge::Truncate);
+    /// assert_eq!(median, array![2., 3., 3.]);
+    /// ```
+    fn rolling_median_axis(&self, axis: Axis, window: usize, edge: RollingEdge) -> Array<A, D>
+    where
+        A: Float;
+
+    private_decl! {}
+}
+
+impl<A, S, D> RollingExt<A, S, D> for ArrayBase<S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn rolling_mean_axis(&self, axis: Axis, window: usize, edge: RollingEdge) -> Array<A, D>
+    where
+        A: Float + FromPrimitive,
+    {
+        rolling(self, axis, window, edge, MeanVar::mean)
+    }
+
+    fn rolling_var_axis(&self, axis: Axis, window: usize, ddof: A, edge: RollingEdge) -> Array<A, D>
+    where
+        A: Float + FromPrimitive,
+    {
+        let n = A::from_usize(window).expect("Converting the window length to `A` must not fail.");
+        assert!(
+            !(ddof < A::zero() || ddof >= n),
+            "`ddof` must be non-negative and less than the window length."
+        );
+        rolling(self, axis, window, edge, || MeanVar::var(ddof))
+    }
+
+    fn rolling_std_axis(&self, axis: Axis, window: usize, ddof: A, edge: RollingEdge) -> Array<A, D>
+    where
+        A: Float + FromPrimitive,
+    {
+        self.rolling_var_axis(axis, window, ddof, edge)
+            .mapv_into(|x| x.sqrt())
+    }
+
+    fn rolling_min_axis(&self, axis: Axis, window: usize, edge: RollingEdge) -> Array<A, D>
+    where
+        A: Float,
+    {
+        rolling(self, axis, window, edge, Extremum::min)
+    }
+
+    fn rolling_max_axis(&self, axis: Axis, window: usize, edge: RollingEdge) -> Array<A, D>
+    where
+        A: Float,
+    {
+        rolling(self, axis, window, edge, Extremum::max)
+    }
+
+    fn rolling_median_axis(&self, axis: Axis, window: usize, edge: RollingEdge) -> Array<A, D>
+    where
+        A: Float,
+    {
+        rolling(self, axis, window, edge, Median::new)
+    }
+
+    private_impl! {}
+}
+
+/// Slides a window created by `new_window` along each lane of `a`.
+fn rolling<A, S, D, W, F>(
+    a: &ArrayBase<S, D>,
+    axis: Axis,
+    window: usize,
+    edge: RollingEdge,
+    new_window: F,
+) -> Array<A, D>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: Dimension,
+    W: Window<A>,
+    F: Fn() -> W,
+{
+    assert!(window > 0, "`window` must be positive.");
+    let len = a.len_of(axis);
+    let n_windows = (len + 1).saturating_sub(window);
+    let mut shape = a.raw_dim();
+    shape[axis.index()] = match edge {
+        RollingEdge::Truncate => n_windows,
+        RollingEdge::NanPad => len,
+    };
+    let mut out = Array::from_elem(shape, A::nan());
+    // The positions of the complete windows in the result.
+    let start = match edge {
+        RollingEdge::Truncate => 0,
+        RollingEdge::NanPad => len - n_windows,
+    };
+    let mut complete = out.slice_axis_mut(axis, Slice::from(start..));
+    let mut lane_buffer = Vec::with_capacity(len);
+    let mut out_buffer = vec![A::nan(); n_windows];
+    Zip::from(complete.lanes_mut(axis))
+        .and(a.lanes(axis))
+        .apply(|mut out, lane| {
+            lane_buffer.clear();
+            lane_buffer.extend(lane.iter().cloned());
+            slide(&lane_buffer, window, new_window(), &mut out_buffer);
+            for (o, &x) in out.iter_mut().zip(&out_buffer) {
+                *o = x;
+            }
+        });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numpy_compat::median as median_of;
+    use crate::{QuantileExt, SummaryStatisticsExt, TimeSeriesExt};
+    use ndarray::{array, s, Array1, Array2};
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[test]
+    fn test_nan_windows() {
+        let a = array![1., f64::NAN, 3., 4., 5.];
+        let mean = a.rolling_mean_axis(Axis(0), 2, RollingEdge::Truncate);
+        assert!(mean[0].is_nan() && mean[1].is_nan());
+        assert_eq!(mean.slice(s![2..]), array![3.5, 4.5]);
+        let max = a.rolling_max_axis(Axis(0), 2, RollingEdge::NanPad);
+        assert!(max.slice(s![..3]).iter().all(|x| x.is_nan()));
+        assert_eq!(max.slice(s![3..]), array![4., 5.]);
+    }
+
+    #[test]
+    fn test_window_longer_than_axis() {
+        let a = Array2::<f64>::ones((2, 3));
+        let truncated = a.rolling_median_axis(Axis(1), 4, RollingEdge::Truncate);
+        assert_eq!(truncated.shape(), &[2, 0]);
+        let padded = a.rolling_median_axis(Axis(1), 4, RollingEdge::NanPad);
+        assert_eq!(padded.shape(), &[2, 3]);
+        assert!(padded.iter().all(|x| x.is_nan()));
+    }
+
+    #[quickcheck]
+    fn rolling_matches_statistics_of_windows(xs: Vec<i16>, window: usize) -> bool {
+        let a = Array1::from_vec(xs).mapv(f64::from);
+        let window = window % 5 + 2;
+        let pad = RollingEdge::NanPad;
+        let mean = a.rolling_mean_axis(Axis(0), window, pad);
+        let var = a.rolling_var_axis(Axis(0), window, 1., pad);
+        let min = a.rolling_min_axis(Axis(0), window, pad);
+        let max = a.rolling_max_axis(Axis(0), window, pad);
+        let median = a.rolling_median_axis(Axis(0), window, pad);
+        let close = |x: f64, y: f64| (x - y).abs() <= 1e-6 * y.abs().max(1.);
+        (0..a.len()).all(|i| {
+            if i + 1 < window {
+                return mean[i].is_nan() && median[i].is_nan();
+            }
+            let w = a.slice(s![i + 1 - window..=i]).to_owned();
+            close(mean[i], w.mean().unwrap())
+                && close(var[i], w.var_axis(Axis(0), 1.)[()])
+                && min[i] == *w.min().unwrap()
+                && max[i] == *w.max().unwrap()
+                && close(median[i], median_of(w.to_vec()))
+        })
+    }
+
+    #[test]
+    fn test_rolling_matches_rolling_apply() {
+        let a = array![[3., 1., 4., 1., 5.], [9., 2., 6., 5., 3.]];
+        let apply = a.rolling_apply_axis(Axis(1), 3, 3, |w| *w.min().unwrap());
+        assert_eq!(
+            a.rolling_min_axis(Axis(1), 3, RollingEdge::NanPad)
+                .slice(s![.., 2..]),
+            apply.slice(s![.., 2..])
+        );
+    }
+}
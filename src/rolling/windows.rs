@@ -0,0 +1,230 @@
+//! Incremental summaries of a sliding window, updated as elements enter and
+//! leave it.
+use core::cmp::Ordering;
+use num_traits::{Float, FromPrimitive};
+use std::collections::VecDeque;
+
+/// A summary of the elements of a window that can be updated in place.
+///
+/// NaN elements are counted separately: the summary is NaN while the window
+/// contains one.
+pub(super) trait Window<A: Float> {
+    fn push(&mut self, x: A);
+    fn pop(&mut self, x: A);
+    fn value(&self) -> A;
+}
+
+/// Applies `window` to every window of `window_len` consecutive elements of
+/// `lane`, writing the summary of the window ending at `lane[i]` to
+/// `out[i + 1 - window_len]`.
+pub(super) fn slide<A, W>(lane: &[A], window_len: usize, mut window: W, out: &mut [A])
+where
+    A: Float,
+    W: Window<A>,
+{
+    debug_assert_eq!(out.len(), (lane.len() + 1).saturating_sub(window_len));
+    for (i, &x) in lane.iter().enumerate() {
+        window.push(x);
+        if i >= window_len {
+            window.pop(lane[i - window_len]);
+        }
+        if i + 1 >= window_len {
+            out[i + 1 - window_len] = window.value();
+        }
+    }
+}
+
+/// Mean and variance, updated with Welford's algorithm.
+pub(super) struct MeanVar<A> {
+    count: usize,
+    n_nan: usize,
+    mean: A,
+    sum_sq_dev: A,
+    // `None` for the mean, `Some(ddof)` for the variance.
+    ddof: Option<A>,
+}
+
+impl<A: Float + FromPrimitive> MeanVar<A> {
+    pub(super) fn mean() -> Self {
+        MeanVar {
+            count: 0,
+            n_nan: 0,
+            mean: A::zero(),
+            sum_sq_dev: A::zero(),
+            ddof: None,
+        }
+    }
+
+    pub(super) fn var(ddof: A) -> Self {
+        MeanVar {
+            ddof: Some(ddof),
+            ..MeanVar::mean()
+        }
+    }
+
+    fn count(&self) -> A {
+        A::from_usize(self.count).expect("Converting the window length to `A` must not fail.")
+    }
+}
+
+impl<A: Float + FromPrimitive> Window<A> for MeanVar<A> {
+    fn push(&mut self, x: A) {
+        if x.is_nan() {
+            self.n_nan += 1;
+            return;
+        }
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean = self.mean + delta / self.count();
+        self.sum_sq_dev = self.sum_sq_dev + delta * (x - self.mean);
+    }
+
+    fn pop(&mut self, x: A) {
+        if x.is_nan() {
+            self.n_nan -= 1;
+            return;
+        }
+        self.count -= 1;
+        if self.count == 0 {
+            self.mean = A::zero();
+            self.sum_sq_dev = A::zero();
+            return;
+        }
+        let delta = x - self.mean;
+        self.mean = self.mean - delta / self.count();
+        // Rounding errors can make the sum slightly negative.
+        self.sum_sq_dev = (self.sum_sq_dev - delta * (x - self.mean)).max(A::zero());
+    }
+
+    fn value(&self) -> A {
+        if self.n_nan > 0 {
+            return A::nan();
+        }
+        match self.ddof {
+            None => self.mean,
+            Some(ddof) => self.sum_sq_dev / (self.count() - ddof),
+        }
+    }
+}
+
+/// Minimum or maximum, with a monotonic queue of the candidates.
+pub(super) struct Extremum<A> {
+    n_nan: usize,
+    // The elements that can still become the extremum, from the oldest to
+    // the newest: each of them is "better" than the ones before it.
+    candidates: VecDeque<A>,
+    is_max: bool,
+}
+
+impl<A> Extremum<A> {
+    pub(super) fn min() -> Self {
+        Extremum {
+            n_nan: 0,
+            candidates: VecDeque::new(),
+            is_max: false,
+        }
+    }
+
+    pub(super) fn max() -> Self {
+        Extremum {
+            is_max: true,
+            ..Extremum::min()
+        }
+    }
+}
+
+impl<A: Float> Window<A> for Extremum<A> {
+    fn push(&mut self, x: A) {
+        if x.is_nan() {
+            self.n_nan += 1;
+            return;
+        }
+        // Ties are kept, so that popping the oldest one leaves the others.
+        while let Some(&last) = self.candidates.back() {
+            if (self.is_max && last < x) || (!self.is_max && last > x) {
+                self.candidates.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.candidates.push_back(x);
+    }
+
+    fn pop(&mut self, x: A) {
+        if x.is_nan() {
+            self.n_nan -= 1;
+        } else if self.candidates.front() == Some(&x) {
+            self.candidates.pop_front();
+        }
+    }
+
+    fn value(&self) -> A {
+        if self.n_nan > 0 {
+            return A::nan();
+        }
+        *self
+            .candidates
+            .front()
+            .expect("The window contains at least one element.")
+    }
+}
+
+/// Median, keeping the elements of the window sorted.
+pub(super) struct Median<A> {
+    n_nan: usize,
+    sorted: Vec<A>,
+}
+
+impl<A> Median<A> {
+    pub(super) fn new() -> Self {
+        Median {
+            n_nan: 0,
+            sorted: Vec::new(),
+        }
+    }
+}
+
+/// Returns the number of elements of `sorted` that are less than `x`.
+fn n_less_than<A: Float>(sorted: &[A], x: A) -> usize {
+    sorted
+        .binary_search_by(|&y| {
+            if y < x {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        })
+        .unwrap_err()
+}
+
+impl<A: Float> Window<A> for Median<A> {
+    fn push(&mut self, x: A) {
+        if x.is_nan() {
+            self.n_nan += 1;
+            return;
+        }
+        let i = n_less_than(&self.sorted, x);
+        self.sorted.insert(i, x);
+    }
+
+    fn pop(&mut self, x: A) {
+        if x.is_nan() {
+            self.n_nan -= 1;
+            return;
+        }
+        let i = n_less_than(&self.sorted, x);
+        self.sorted.remove(i);
+    }
+
+    fn value(&self) -> A {
+        if self.n_nan > 0 {
+            return A::nan();
+        }
+        let n = self.sorted.len();
+        if n % 2 == 1 {
+            self.sorted[n / 2]
+        } else {
+            (self.sorted[n / 2 - 1] + self.sorted[n / 2]) / (A::one() + A::one())
+        }
+    }
+}
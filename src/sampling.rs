@@ -0,0 +1,692 @@
+//! Random sampling and shuffling of the subviews of an array.
+use crate::errors::{ShapeMismatch, WeightedSampleError};
+use core::cmp::Ordering;
+use core::hash::Hash;
+use core::mem;
+use indexmap::IndexMap;
+use ndarray::{Array, Array1, ArrayBase, ArrayView, Axis, Data, DataMut, Ix1, RemoveAxis, Zip};
+use num_traits::Float;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+
+/// Extension trait for `ArrayBase` providing methods to randomly sample and
+/// shuffle subviews (e.g. rows) along an axis.
+///
+/// The random number generator is provided by the caller: use a seeded
+/// generator (e.g. `rand::rngs::StdRng::seed_from_u64`) to get reproducible
+/// results. These methods are the building blocks of bootstrapping,
+/// subsampling, permutation tests and train/test splitting.
+pub trait SamplingExt<A, S, D>
+where
+    S: Data<Elem = A>,
+    D: RemoveAxis,
+{
+    /// Returns a new array made of `n` subviews along `axis`, selected
+    /// uniformly at random, with or without replacement.
+    ///
+    /// Without replacement, the selected subviews are distinct (but their
+    /// order is random).
+    ///
+    /// **Panics** if `axis` is out of bounds, if `replacement` is `false` and
+    /// `n` is greater than the length of `axis`, or if `replacement` is
+    /// `true`, `n > 0` and the length of `axis` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SamplingExt;
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let data = array![[1., 10.], [2., 20.], [3., 30.], [4., 40.]];
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// let bootstrap = data.sample_axis(Axis(0), 4, true, &mut rng);
+    /// assert_eq!(bootstrap.shape(), &[4, 2]);
+    /// // Each sampled row is a row of `data`.
+    /// for row in bootstrap.genrows() {
+    ///     assert_eq!(row[1], row[0] * 10.);
+    /// }
+    /// ```
+    fn sample_axis<R>(&self, axis: Axis, n: usize, replacement: bool, rng: &mut R) -> Array<A, D>
+    where
+        A: Copy,
+        R: Rng + ?Sized;
+
+    /// Returns a new array made of `n` subviews along `axis`, where subview
+    /// `i` is selected with probability proportional to `weights[i]`.
+    ///
+    /// Without replacement, the subviews are selected one at a time, each
+    /// with probability proportional to its weight among those that haven't
+    /// been selected yet.
+    ///
+    /// Returns `Err(ShapeMismatch)` if the length of `weights` differs from
+    /// the length of `axis`, and `Err(InvalidWeights)` if any weight is
+    /// negative or NaN, if all the weights are zero, or if `replacement` is
+    /// `false` and fewer than `n` weights are positive.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SamplingExt;
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let data = array![[1, 2], [3, 4], [5, 6]];
+    /// let weights = array![0., 1., 3.];
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let sample = data
+    ///     .sample_axis_weighted(Axis(0), 2, &weights, false, &mut rng)
+    ///     .unwrap();
+    /// // The first row has a zero weight, so the two others are selected.
+    /// let mut first_column = sample.column(0).to_vec();
+    /// first_column.sort();
+    /// assert_eq!(first_column, vec![3, 5]);
+    /// ```
+    fn sample_axis_weighted<S2, W, R>(
+        &self,
+        axis: Axis,
+        n: usize,
+        weights: &ArrayBase<S2, Ix1>,
+        replacement: bool,
+        rng: &mut R,
+    ) -> Result<Array<A, D>, WeightedSampleError>
+    where
+        A: Copy,
+        S2: Data<Elem = W>,
+        W: Float,
+        R: Rng + ?Sized;
+
+    /// Shuffles the subviews along `axis` in place, so that every
+    /// permutation is equally likely.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SamplingExt;
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let mut data = array![[1, 10], [2, 20], [3, 30]];
+    /// data.shuffle_axis_mut(Axis(0), &mut StdRng::seed_from_u64(0));
+    /// // The rows are moved as a whole.
+    /// for row in data.genrows() {
+    ///     assert_eq!(row[1], row[0] * 10);
+    /// }
+    /// ```
+    fn shuffle_axis_mut<R>(&mut self, axis: Axis, rng: &mut R)
+    where
+        S: DataMut,
+        R: Rng + ?Sized;
+
+    /// Returns a random permutation of the subviews along `axis`, together
+    /// with the permutation itself: subview `i` of the returned array is
+    /// subview `permutation[i]` of `self`.
+    ///
+    /// The permutation can be reused to permute other arrays (e.g. labels)
+    /// consistently with `self`.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SamplingExt;
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let features = array![[0.1, 0.2], [0.3, 0.4], [0.5, 0.6]];
+    /// let labels = array![0, 1, 2];
+    /// let (shuffled, permutation) = features.permute_axis(Axis(0), &mut StdRng::seed_from_u64(0));
+    /// let shuffled_labels = labels.select(Axis(0), &permutation);
+    /// for (row, &label) in shuffled.genrows().into_iter().zip(&shuffled_labels) {
+    ///     assert_eq!(row, features.row(label));
+    /// }
+    /// ```
+    fn permute_axis<R>(&self, axis: Axis, rng: &mut R) -> (Array<A, D>, Vec<usize>)
+    where
+        A: Copy,
+        R: Rng + ?Sized;
+
+    /// Applies `statistic` to `n_resamples` moving block bootstrap resamples
+    /// along `axis`, and returns the resulting values.
+    ///
+    /// Each resample has the same length as `axis`, and is made of
+    /// contiguous blocks of `block_len` subviews starting at positions
+    /// selected uniformly at random (the last block is truncated). Unlike the
+    /// ordinary bootstrap, which samples subviews independently, this keeps
+    /// the serial correlation of the data within each block, so the spread of
+    /// the returned values is a valid estimate of the sampling variability of
+    /// the statistic for time series. The block length should be larger than
+    /// the range of the serial correlation; `block_len = 1` is the ordinary
+    /// bootstrap.
+    ///
+    /// **Panics** if `axis` is out of bounds, or if `block_len` is zero or
+    /// greater than the length of `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{Array1, Axis};
+    /// use ndarray_stats::SamplingExt;
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let series = Array1::from_shape_fn(100, |i| (i as f64 / 5.).sin());
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let means = series.block_bootstrap_axis(Axis(0), 10, 200, |s| s.mean_axis(Axis(0))[()], &mut rng);
+    /// assert_eq!(means.len(), 200);
+    /// assert!(means.iter().all(|m| m.abs() < 1.));
+    /// ```
+    fn block_bootstrap_axis<B, F, R>(
+        &self,
+        axis: Axis,
+        block_len: usize,
+        n_resamples: usize,
+        statistic: F,
+        rng: &mut R,
+    ) -> Array1<B>
+    where
+        A: Copy,
+        F: FnMut(ArrayView<'_, A, D>) -> B,
+        R: Rng + ?Sized;
+
+    private_decl! {}
+}
+
+impl<A, S, D> SamplingExt<A, S, D> for ArrayBase<S, D>
+where
+    S: Data<Elem = A>,
+    D: RemoveAxis,
+{
+    fn sample_axis<R>(&self, axis: Axis, n: usize, replacement: bool, rng: &mut R) -> Array<A, D>
+    where
+        A: Copy,
+        R: Rng + ?Sized,
+    {
+        let len = self.len_of(axis);
+        let indices = if replacement {
+            assert!(
+                n == 0 || len > 0,
+                "Cannot sample with replacement from an empty axis."
+            );
+            (0..n).map(|_| rng.gen_range(0, len)).collect()
+        } else {
+            assert!(
+                n <= len,
+                "Cannot sample more subviews than the length of the axis without replacement."
+            );
+            index::sample(rng, len, n).into_vec()
+        };
+        self.select(axis, &indices)
+    }
+
+    fn sample_axis_weighted<S2, W, R>(
+        &self,
+        axis: Axis,
+        n: usize,
+        weights: &ArrayBase<S2, Ix1>,
+        replacement: bool,
+        rng: &mut R,
+    ) -> Result<Array<A, D>, WeightedSampleError>
+    where
+        A: Copy,
+        S2: Data<Elem = W>,
+        W: Float,
+        R: Rng + ?Sized,
+    {
+        let len = self.len_of(axis);
+        if weights.len() != len {
+            return Err(ShapeMismatch {
+                first_shape: vec![len],
+                second_shape: weights.shape().to_vec(),
+            }
+            .into());
+        }
+        let weights: Vec<f64> = weights
+            .iter()
+            .map(|w| w.to_f64().unwrap_or(core::f64::NAN))
+            .collect();
+        // `WeightedIndex` doesn't reject NaN weights on its own.
+        if weights.iter().any(|w| w.is_nan()) {
+            return Err(WeightedSampleError::InvalidWeights);
+        }
+        let indices = if replacement {
+            let distribution =
+                WeightedIndex::new(&weights).map_err(|_| WeightedSampleError::InvalidWeights)?;
+            (0..n).map(|_| distribution.sample(rng)).collect()
+        } else {
+            weighted_indices_without_replacement(&weights, n, rng)?
+        };
+        Ok(self.select(axis, &indices))
+    }
+
+    fn shuffle_axis_mut<R>(&mut self, axis: Axis, rng: &mut R)
+    where
+        S: DataMut,
+        R: Rng + ?Sized,
+    {
+        // Fisher-Yates shuffle, swapping whole subviews.
+        for i in (1..self.len_of(axis)).rev() {
+            let j = rng.gen_range(0, i + 1);
+            if j != i {
+                let (mut left, mut right) = self.view_mut().split_at(axis, i);
+                Zip::from(left.index_axis_mut(axis, j))
+                    .and(right.index_axis_mut(axis, 0))
+                    .apply(mem::swap);
+            }
+        }
+    }
+
+    fn permute_axis<R>(&self, axis: Axis, rng: &mut R) -> (Array<A, D>, Vec<usize>)
+    where
+        A: Copy,
+        R: Rng + ?Sized,
+    {
+        let mut permutation: Vec<usize> = (0..self.len_of(axis)).collect();
+        permutation.shuffle(rng);
+        (self.select(axis, &permutation), permutation)
+    }
+
+    fn block_bootstrap_axis<B, F, R>(
+        &self,
+        axis: Axis,
+        block_len: usize,
+        n_resamples: usize,
+        mut statistic: F,
+        rng: &mut R,
+    ) -> Array1<B>
+    where
+        A: Copy,
+        F: FnMut(ArrayView<'_, A, D>) -> B,
+        R: Rng + ?Sized,
+    {
+        let len = self.len_of(axis);
+        assert!(
+            block_len > 0 && block_len <= len,
+            "The block length must be positive and at most the length of the axis."
+        );
+        let n_starts = len - block_len + 1;
+        (0..n_resamples)
+            .map(|_| {
+                let mut indices = Vec::with_capacity(len + block_len);
+                while indices.len() < len {
+                    let start = rng.gen_range(0, n_starts);
+                    indices.extend(start..start + block_len);
+                }
+                indices.truncate(len);
+                statistic(self.select(axis, &indices).view())
+            })
+            .collect()
+    }
+
+    private_impl! {}
+}
+
+/// Selects `n` distinct indices of `weights`, with the algorithm of
+/// Efraimidis and Spirakis: each index gets the random key `u^(1/w)`, where
+/// `u` is uniform in `[0, 1)`, and the indices with the largest keys are
+/// selected. This is equivalent to selecting the indices one at a time with
+/// probability proportional to their weight.
+fn weighted_indices_without_replacement<R>(
+    weights: &[f64],
+    n: usize,
+    rng: &mut R,
+) -> Result<Vec<usize>, WeightedSampleError>
+where
+    R: Rng + ?Sized,
+{
+    if weights.iter().any(|&w| w < 0.) || weights.iter().filter(|&&w| w > 0.).count() < n {
+        return Err(WeightedSampleError::InvalidWeights);
+    }
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    // Comparing `ln(u) / w` instead of `u^(1/w)` avoids underflow for small
+    // weights.
+    let mut keys: Vec<(f64, usize)> = weights
+        .iter()
+        .enumerate()
+        .filter(|&(_, &w)| w > 0.)
+        .map(|(i, &w)| (rng.gen::<f64>().ln() / w, i))
+        .collect();
+    keys.sort_unstable_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+    Ok(keys[..n].iter().map(|&(_, i)| i).collect())
+}
+
+/// Size of a stratified sample.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StratifiedSize {
+    /// Select this fraction (between `0.` and `1.`) of all the elements,
+    /// allocated to the classes in proportion to their sizes.
+    Fraction(f64),
+    /// Select this number of elements in total, allocated to the classes in
+    /// proportion to their sizes.
+    Total(usize),
+    /// Select this number of elements from each class (or the whole class,
+    /// if it is smaller), which gives a balanced sample.
+    PerClass(usize),
+}
+
+/// Sampling methods for 1-D arrays of labels.
+pub trait Sampling1dExt<A, S>
+where
+    S: Data<Elem = A>,
+{
+    /// Returns the (sorted) indices of a random sample of the elements,
+    /// stratified by their value: the elements are grouped into classes of
+    /// equal labels, and each class is sampled without replacement.
+    ///
+    /// With `StratifiedSize::Fraction` and `StratifiedSize::Total`, the
+    /// number of elements selected from each class is proportional to its
+    /// size, rounded with the largest remainder method so that the total is
+    /// exact. The indices can be used with `select` to extract the
+    /// corresponding rows of a data array.
+    ///
+    /// **Panics** if the fraction is not between `0.` and `1.`, or if the
+    /// total is greater than the number of elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{Sampling1dExt, StratifiedSize};
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let labels = array!["a", "a", "a", "a", "a", "a", "b", "b", "b", "c"];
+    /// let mut rng = StdRng::seed_from_u64(0);
+    /// let indices = labels.stratified_sample(StratifiedSize::Fraction(0.5), &mut rng);
+    /// let mut sampled = labels.select(Axis(0), &indices).to_vec();
+    /// sampled.sort();
+    /// assert_eq!(sampled, vec!["a", "a", "a", "b", "b"]);
+    /// ```
+    fn stratified_sample<R>(&self, size: StratifiedSize, rng: &mut R) -> Vec<usize>
+    where
+        A: Eq + Hash,
+        R: Rng + ?Sized;
+
+    private_decl! {}
+}
+
+impl<A, S> Sampling1dExt<A, S> for ArrayBase<S, Ix1>
+where
+    S: Data<Elem = A>,
+{
+    fn stratified_sample<R>(&self, size: StratifiedSize, rng: &mut R) -> Vec<usize>
+    where
+        A: Eq + Hash,
+        R: Rng + ?Sized,
+    {
+        // Classes are kept in order of first appearance, so that the sample
+        // only depends on the state of `rng`.
+        let mut classes: IndexMap<&A, Vec<usize>> = IndexMap::new();
+        for (i, label) in self.iter().enumerate() {
+            classes.entry(label).or_default().push(i);
+        }
+        let n = self.len();
+        let class_sizes: Vec<usize> = match size {
+            StratifiedSize::Fraction(fraction) => {
+                assert!(
+                    fraction >= 0. && fraction <= 1.,
+                    "The fraction must be between 0. and 1."
+                );
+                let total = (fraction * n as f64).round() as usize;
+                proportional_allocation(&classes, total.min(n))
+            }
+            StratifiedSize::Total(total) => {
+                assert!(
+                    total <= n,
+                    "Cannot sample more elements than the length of the array."
+                );
+                proportional_allocation(&classes, total)
+            }
+            StratifiedSize::PerClass(count) => classes
+                .values()
+                .map(|class| class.len().min(count))
+                .collect(),
+        };
+        let mut indices: Vec<usize> = classes
+            .values()
+            .zip(class_sizes)
+            .flat_map(|(class, size)| {
+                index::sample(rng, class.len(), size)
+                    .into_iter()
+                    .map(move |i| class[i])
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        indices.sort_unstable();
+        indices
+    }
+
+    private_impl! {}
+}
+
+/// Splits `total` between the classes in proportion to their sizes, using
+/// the largest remainder method (ties are broken by order of appearance).
+fn proportional_allocation<A>(classes: &IndexMap<A, Vec<usize>>, total: usize) -> Vec<usize>
+where
+    A: Eq + Hash,
+{
+    let n: usize = classes.values().map(Vec::len).sum();
+    if n == 0 {
+        return vec![0; classes.len()];
+    }
+    let quotas: Vec<(usize, usize)> = classes
+        .values()
+        .map(|class| {
+            // Exact integer arithmetic: quota = total * len / n.
+            let product = total * class.len();
+            (product / n, product % n)
+        })
+        .collect();
+    let mut sizes: Vec<usize> = quotas.iter().map(|&(floor, _)| floor).collect();
+    let missing = total - sizes.iter().sum::<usize>();
+    let mut by_remainder: Vec<usize> = (0..quotas.len()).collect();
+    by_remainder.sort_by(|&i, &j| quotas[j].1.cmp(&quotas[i].1));
+    for &i in &by_remainder[..missing] {
+        sizes[i] += 1;
+    }
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, Array1, Array2};
+    use quickcheck_macros::quickcheck;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::f64;
+
+    #[quickcheck]
+    fn sample_without_replacement_is_a_permutation_subset(seed: u64, n: usize) -> bool {
+        let data = Array2::from_shape_fn((20, 3), |(i, j)| i * 3 + j);
+        let n = n % 21;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let sample = data.sample_axis(Axis(0), n, false, &mut rng);
+        let mut rows: Vec<usize> = sample.column(0).iter().map(|x| x / 3).collect();
+        rows.sort();
+        rows.dedup();
+        sample.shape() == [n, 3]
+            && rows.len() == n
+            && sample.genrows().into_iter().all(|row| row[1] == row[0] + 1)
+    }
+
+    #[test]
+    fn test_sample_axis_is_reproducible() {
+        let data = Array2::from_shape_fn((50, 4), |(i, j)| (i * 4 + j) as f64);
+        let sample = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            data.sample_axis(Axis(1), 10, true, &mut rng)
+        };
+        assert_eq!(sample(7), sample(7));
+        assert_eq!(sample(7).shape(), &[50, 10]);
+    }
+
+    #[quickcheck]
+    fn shuffle_axis_mut_is_a_permutation(seed: u64) -> bool {
+        let data = Array2::from_shape_fn((3, 17), |(i, j)| i * 17 + j);
+        let mut shuffled = data.clone();
+        shuffled.shuffle_axis_mut(Axis(1), &mut StdRng::seed_from_u64(seed));
+        let mut columns: Vec<usize> = shuffled.row(0).to_vec();
+        columns.sort();
+        columns == data.row(0).to_vec()
+            && shuffled
+                .gencolumns()
+                .into_iter()
+                .all(|column| column[1] == column[0] + 17 && column[2] == column[0] + 34)
+    }
+
+    #[quickcheck]
+    fn block_bootstrap_resamples_contiguous_blocks(seed: u64, block_len: usize) -> bool {
+        let data = Array2::from_shape_fn((2, 23), |(i, j)| i * 100 + j);
+        let block_len = block_len % 23 + 1;
+        let mut rng = StdRng::seed_from_u64(seed);
+        let resamples = data.block_bootstrap_axis(
+            Axis(1),
+            block_len,
+            5,
+            |resample| resample.to_owned(),
+            &mut rng,
+        );
+        resamples.iter().all(|resample| {
+            let row = resample.row(0);
+            resample.shape() == [2, 23]
+                && resample.row(1) == row.mapv(|x| x + 100)
+                // Each block continues the series where it started.
+                && (0..23).all(|j| j % block_len == 0 || row[j] == row[j - 1] + 1)
+        })
+    }
+
+    #[test]
+    fn test_permute_axis_matches_returned_permutation() {
+        let data = Array2::from_shape_fn((30, 2), |(i, j)| (i * 2 + j) as f64);
+        let mut rng = StdRng::seed_from_u64(3);
+        let (permuted, permutation) = data.permute_axis(Axis(0), &mut rng);
+        let mut sorted = permutation.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..30).collect::<Vec<_>>());
+        for (i, &p) in permutation.iter().enumerate() {
+            assert_eq!(permuted.row(i), data.row(p));
+        }
+    }
+
+    #[quickcheck]
+    fn stratified_sample_preserves_proportions(labels: Vec<u8>, total: usize, seed: u64) -> bool {
+        let labels = Array1::from_vec(labels.into_iter().map(|x| x % 4).collect());
+        let total = if labels.is_empty() {
+            0
+        } else {
+            total % (labels.len() + 1)
+        };
+        let mut rng = StdRng::seed_from_u64(seed);
+        let indices = labels.stratified_sample(StratifiedSize::Total(total), &mut rng);
+        let mut distinct = indices.clone();
+        distinct.dedup();
+        if labels.is_empty() {
+            return indices.is_empty();
+        }
+        let n = labels.len() as f64;
+        indices.len() == total
+            && distinct.len() == total
+            && (0..4).all(|class| {
+                let count = |indices: &mut dyn Iterator<Item = usize>| {
+                    indices.filter(|&i| labels[i] == class).count() as f64
+                };
+                let expected = count(&mut (0..labels.len())) * total as f64 / n;
+                (count(&mut indices.iter().cloned()) - expected).abs() < 1.
+            })
+    }
+
+    #[test]
+    fn test_stratified_sample_per_class() {
+        let labels = array![1, 2, 1, 1, 3, 1, 2];
+        let mut rng = StdRng::seed_from_u64(0);
+        let indices = labels.stratified_sample(StratifiedSize::PerClass(2), &mut rng);
+        let mut sampled = labels.select(Axis(0), &indices).to_vec();
+        sampled.sort();
+        assert_eq!(sampled, vec![1, 1, 2, 2, 3]);
+        assert!(indices.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_stratified_sample_invalid_fraction() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let _ = array![1, 2].stratified_sample(StratifiedSize::Fraction(1.5), &mut rng);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_without_replacement_too_many() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let _ = array![1, 2, 3].sample_axis(Axis(0), 4, false, &mut rng);
+    }
+
+    #[test]
+    fn test_sample_axis_weighted_errors() {
+        let data = array![1, 2, 3];
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut sample = |weights: Array1<f64>, replacement| {
+            data.sample_axis_weighted(Axis(0), 2, &weights, replacement, &mut rng)
+        };
+        assert_eq!(
+            sample(array![1., 1.], true),
+            Err(WeightedSampleError::ShapeMismatch(ShapeMismatch {
+                first_shape: vec![3],
+                second_shape: vec![2],
+            }))
+        );
+        for &replacement in &[true, false] {
+            assert_eq!(
+                sample(array![1., -1., 1.], replacement),
+                Err(WeightedSampleError::InvalidWeights)
+            );
+            assert_eq!(
+                sample(array![1., f64::NAN, 1.], replacement),
+                Err(WeightedSampleError::InvalidWeights)
+            );
+            assert_eq!(
+                sample(array![0., 0., 0.], replacement),
+                Err(WeightedSampleError::InvalidWeights)
+            );
+        }
+        assert_eq!(
+            sample(array![0., 0., 1.], false),
+            Err(WeightedSampleError::InvalidWeights)
+        );
+        assert_eq!(sample(array![0., 0., 1.], true), Ok(array![3, 3]));
+    }
+
+    #[test]
+    fn test_sample_axis_weighted_frequencies() {
+        let data = Array1::range(0., 3., 1.);
+        let weights = array![1., 2., 7.];
+        let mut rng = StdRng::seed_from_u64(1);
+        let n = 100_000;
+        let sample = data
+            .sample_axis_weighted(Axis(0), n, &weights, true, &mut rng)
+            .unwrap();
+        for (i, &w) in weights.iter().enumerate() {
+            let frequency = sample.iter().filter(|&&x| x == i as f64).count() as f64 / n as f64;
+            assert!((frequency - w / 10.).abs() < 0.01);
+        }
+
+        // Without replacement, the heaviest element is almost always selected
+        // when drawing a single element.
+        let n_trials = 10_000;
+        let n_heaviest = (0..n_trials)
+            .filter(|_| {
+                let sample = data
+                    .sample_axis_weighted(Axis(0), 1, &weights, false, &mut rng)
+                    .unwrap();
+                sample[0] == 2.
+            })
+            .count();
+        assert!((n_heaviest as f64 / n_trials as f64 - 0.7).abs() < 0.02);
+    }
+}
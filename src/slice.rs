@@ -0,0 +1,192 @@
+//! Statistics of plain slices.
+//!
+//! The functions in this module expose the core algorithms used by the
+//! extension traits on `&[A]`/`&mut [A]`, for callers that don't store their
+//! data in `ndarray` arrays. They give the same results as the corresponding
+//! methods on 1-dimensional arrays.
+//!
+//! ```
+//! use ndarray_stats::{interpolate::Nearest, slice};
+//!
+//! let xs = vec![3., 1., 4., 1., 5.];
+//! assert_eq!(slice::mean(&xs), Ok(2.8));
+//! assert_eq!(slice::var(&xs, 1.), Ok(3.2));
+//! let mut ints = vec![3, 1, 4, 1, 5];
+//! assert_eq!(slice::quantile_mut(&mut ints, 0.5, &Nearest), Ok(3));
+//! ```
+use crate::errors::{EmptyInput, QuantileError, ShapeMismatch};
+use crate::histogram::Bins;
+use crate::kernels::unrolled_sum_by;
+use crate::quantile::interpolate::Interpolate;
+use crate::{Quantile1dExt, Sort1dExt};
+use ndarray::ArrayViewMut1;
+use num_traits::{Float, FromPrimitive, Zero};
+
+/// Returns the sum of the elements of `xs`.
+///
+/// The elements are added with several independent accumulators, which is
+/// both faster and more accurate than a sequential sum.
+pub fn sum<A>(xs: &[A]) -> A
+where
+    A: Copy + Zero,
+{
+    unrolled_sum_by(xs, |&x| x)
+}
+
+/// Returns the arithmetic mean of the elements of `xs`.
+///
+/// If `xs` is empty, `Err(EmptyInput)` is returned.
+///
+/// **Panics** if `A::from_usize()` fails to convert the number of elements.
+pub fn mean<A>(xs: &[A]) -> Result<A, EmptyInput>
+where
+    A: Float + FromPrimitive,
+{
+    if xs.is_empty() {
+        return Err(EmptyInput);
+    }
+    let n = A::from_usize(xs.len()).expect("Converting number of elements to `A` must not fail.");
+    Ok(sum(xs) / n)
+}
+
+/// Returns the variance of the elements of `xs`, computed with the two-pass
+/// algorithm.
+///
+/// The parameter `ddof` specifies the "delta degrees of freedom": use
+/// `ddof = 0` for the population variance and `ddof = 1` for the unbiased
+/// sample variance.
+///
+/// If `xs` is empty, `Err(EmptyInput)` is returned.
+///
+/// **Panics** if `ddof` is less than zero or greater than or equal to the
+/// number of elements, or if `A::from_usize()` fails to convert the number of
+/// elements.
+pub fn var<A>(xs: &[A], ddof: A) -> Result<A, EmptyInput>
+where
+    A: Float + FromPrimitive,
+{
+    let mean = mean(xs)?;
+    let n = A::from_usize(xs.len()).expect("Converting number of elements to `A` must not fail.");
+    assert!(
+        !(ddof < A::zero() || ddof >= n),
+        "`ddof` must be non-negative and less than the number of elements."
+    );
+    let sum_sq_dev = unrolled_sum_by(xs, |&x| (x - mean) * (x - mean));
+    Ok(sum_sq_dev / (n - ddof))
+}
+
+/// Returns the `i`th smallest element of `xs` (starting from 0), partially
+/// sorting `xs` in place; see
+/// [`Sort1dExt::get_from_sorted_mut`](../trait.Sort1dExt.html#tymethod.get_from_sorted_mut).
+///
+/// **Panics** if `i` is greater than or equal to the length of `xs`.
+pub fn select_nth_mut<A>(xs: &mut [A], i: usize) -> A
+where
+    A: Ord + Clone,
+{
+    ArrayViewMut1::from(xs).get_from_sorted_mut(i)
+}
+
+/// Returns the `q`th quantile of the elements of `xs`, partially sorting
+/// `xs` in place; see
+/// [`Quantile1dExt::quantile_mut`](../trait.Quantile1dExt.html#tymethod.quantile_mut).
+///
+/// Returns `Err(EmptyInput)` if `xs` is empty and `Err(InvalidQuantile(q))`
+/// if `q` is NaN or not between `0.` and `1.` (inclusive).
+pub fn quantile_mut<A, Q, I>(xs: &mut [A], q: Q, interpolate: &I) -> Result<A, QuantileError>
+where
+    A: Ord + Clone,
+    Q: Float,
+    I: Interpolate<A>,
+{
+    ArrayViewMut1::from(xs).quantile_mut(q, interpolate)
+}
+
+/// Adds the number of elements of `values` falling in each bin of `bins` to
+/// `counts`. Values outside the bins are ignored.
+///
+/// If the length of `counts` is not the number of bins, `Err(ShapeMismatch)`
+/// is returned and `counts` is left untouched.
+///
+/// # Example
+///
+/// ```
+/// use ndarray_stats::histogram::{Bins, Edges};
+/// use ndarray_stats::slice;
+///
+/// let bins = Bins::new(Edges::from(vec![0, 10, 20]));
+/// let mut counts = vec![0; 2];
+/// slice::histogram_fill(&[3, 12, 15, 25], &bins, &mut counts).unwrap();
+/// slice::histogram_fill(&[9], &bins, &mut counts).unwrap();
+/// assert_eq!(counts, vec![2, 2]);
+/// ```
+pub fn histogram_fill<A>(
+    values: &[A],
+    bins: &Bins<A>,
+    counts: &mut [usize],
+) -> Result<(), ShapeMismatch>
+where
+    A: Ord,
+{
+    if counts.len() != bins.len() {
+        return Err(ShapeMismatch {
+            first_shape: vec![bins.len()],
+            second_shape: vec![counts.len()],
+        });
+    }
+    for value in values {
+        if let Some(i) = bins.index_of(value) {
+            counts[i] += 1;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::histogram::{Edges, Grid};
+    use crate::interpolate::Linear;
+    use crate::{HistogramExt, SummaryStatisticsExt};
+    use ndarray::{Array, Array1, Axis};
+    use noisy_float::types::{n64, N64};
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn matches_array_methods(xs: Vec<i32>, q: u8) -> bool {
+        if xs.len() < 2 {
+            return mean::<f64>(&[]).is_err();
+        }
+        let floats: Vec<f64> = xs.iter().map(|&x| f64::from(x)).collect();
+        let a = Array1::from(floats.clone());
+        let close = |x: f64, y: f64| (x - y).abs() <= 1e-9 * (1. + y.abs());
+        let q = f64::from(q) / 255.;
+        let mut noisy: Vec<N64> = floats.iter().cloned().map(n64).collect();
+        let expected_quantile = Array1::from(noisy.clone()).quantile_mut(q, &Linear);
+        close(mean(&floats).unwrap(), a.mean().unwrap())
+            && close(var(&floats, 1.).unwrap(), a.var_axis(Axis(0), 1.)[()])
+            && quantile_mut(&mut noisy, q, &Linear) == expected_quantile
+    }
+
+    #[test]
+    fn test_select_nth_mut() {
+        let mut xs = vec![5, 3, 9, 1, 7];
+        assert_eq!(select_nth_mut(&mut xs, 0), 1);
+        assert_eq!(select_nth_mut(&mut xs, 3), 7);
+    }
+
+    #[test]
+    fn test_histogram_fill_matches_histogram() {
+        let values = vec![-4, 0, 3, 3, 8, 12, 19, 20];
+        let bins = Bins::new(Edges::from(vec![0, 5, 10, 20]));
+        let mut counts = vec![0; 3];
+        histogram_fill(&values, &bins, &mut counts).unwrap();
+        let points = Array::from_shape_vec((values.len(), 1), values).unwrap();
+        let histogram = points.histogram(Grid::from(vec![bins.clone()]));
+        assert_eq!(
+            counts,
+            histogram.counts().iter().cloned().collect::<Vec<_>>()
+        );
+        assert!(histogram_fill(&[1], &bins, &mut [0; 2]).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use core::cmp::Ordering;
 use indexmap::IndexMap;
 use ndarray::prelude::*;
 use ndarray::{Data, DataMut, Slice};
@@ -25,6 +26,7 @@ where
     /// Complexity ([quickselect](https://en.wikipedia.org/wiki/Quickselect)):
     /// - average case: O(`n`);
     /// - worst case: O(`n`^2);
+    ///
     /// where n is the number of elements in the array.
     ///
     /// **Panics** if `i` is greater than or equal to `n`.
@@ -213,7 +215,7 @@ where
     _get_many_from_sorted_mut_unchecked(array.view_mut(), &mut indexes.to_owned(), &mut values);
 
     // We convert the vector to a more search-friendly `IndexMap`.
-    indexes.iter().cloned().zip(values.into_iter()).collect()
+    indexes.iter().cloned().zip(values).collect()
 }
 
 /// This is the recursive portion of `get_many_from_sorted_mut_unchecked`.
@@ -296,3 +298,86 @@ fn _get_many_from_sorted_mut_unchecked<A>(
         bigger_values,
     );
 }
+
+/// Reorders `xs` so that the element at index `n` is the one that would be
+/// there if `xs` were sorted according to `compare`, with no greater element
+/// before it and no smaller element after it.
+///
+/// Returns the elements before index `n`, the element at index `n` and the
+/// elements after it, like `slice::select_nth_unstable_by`, which requires
+/// Rust 1.49.
+///
+/// **Panics** if `n` is greater than or equal to the length of `xs`.
+pub(crate) fn select_nth_by<A, F>(
+    xs: &mut [A],
+    n: usize,
+    mut compare: F,
+) -> (&mut [A], &mut A, &mut [A])
+where
+    F: FnMut(&A, &A) -> Ordering,
+{
+    assert!(n < xs.len(), "The index must be smaller than the length.");
+    let mut rng = thread_rng();
+    // The element that belongs at index `n` is always in `xs[low..high]`.
+    let (mut low, mut high) = (0, xs.len());
+    while high - low > 1 {
+        // Move a random pivot to `low`, then partition the rest of the range
+        // so that `xs[low + 1..i]` is not greater and `xs[j + 1..high]` is
+        // not smaller than the pivot. Elements equal to the pivot are swapped
+        // too, to split runs of equal elements evenly.
+        xs.swap(low, rng.gen_range(low, high));
+        let (mut i, mut j) = (low + 1, high - 1);
+        loop {
+            while i <= j && compare(&xs[i], &xs[low]) == Ordering::Less {
+                i += 1;
+            }
+            while i <= j && compare(&xs[j], &xs[low]) == Ordering::Greater {
+                j -= 1;
+            }
+            if i >= j {
+                break;
+            }
+            xs.swap(i, j);
+            i += 1;
+            j -= 1;
+        }
+        xs.swap(low, j);
+        match n.cmp(&j) {
+            Ordering::Less => high = j,
+            Ordering::Equal => break,
+            Ordering::Greater => low = j + 1,
+        }
+    }
+    let (lower, rest) = xs.split_at_mut(n);
+    let (nth, upper) = rest
+        .split_first_mut()
+        .expect("`n` is smaller than the length.");
+    (lower, nth, upper)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn select_nth_by_matches_sort(mut xs: Vec<i32>, n: usize) -> bool {
+        if xs.is_empty() {
+            return true;
+        }
+        let n = n % xs.len();
+        let mut sorted = xs.clone();
+        sorted.sort();
+        let (lower, &mut nth, upper) = select_nth_by(&mut xs, n, |a, b| a.cmp(b));
+        nth == sorted[n] && lower.iter().all(|&x| x <= nth) && upper.iter().all(|&x| x >= nth)
+    }
+
+    #[test]
+    fn select_nth_by_equal_elements() {
+        let mut xs = vec![1; 1000];
+        xs[500] = 0;
+        let (_, &mut nth, _) = select_nth_by(&mut xs, 0, |a, b| a.cmp(b));
+        assert_eq!(nth, 0);
+        assert_eq!(xs[0], 0);
+    }
+}
@@ -0,0 +1,326 @@
+use super::{ExactSum, MeanVar, MinMax, OnlineStats, QuantileSketch, ReservoirSampler};
+use crate::histogram::Histogram;
+use crate::kernels::sum_by;
+use ndarray::{ArrayBase, Data, Dimension, Ix0, Ix1, Ix2, Ix3, Ix4, Ix5, Ix6, IxDyn};
+use num_traits::{Float, FromPrimitive};
+
+/// A streaming accumulator that can be updated with chunks of data of type
+/// `C`, and merged with another accumulator of the same type.
+///
+/// Merging two accumulators gives the same result (up to rounding errors and,
+/// for randomized accumulators, up to randomness) as updating a single
+/// accumulator with the chunks of both. Chunks can therefore be processed
+/// independently, e.g. on different threads, and the partial results combined
+/// at the end.
+///
+/// A chunk is always a batch of values: every accumulator adds all the
+/// values of the chunk. For [`OnlineStats`], which tracks the statistics of
+/// each element of a sequence of array observations, the chunk is therefore a
+/// batch of observations stacked along its first axis, not a single
+/// observation. Similarly, [`Histogram`] takes the rows of a 2-dimensional
+/// chunk as its points.
+///
+/// Tuples of accumulators are accumulators as well, so several statistics
+/// can be computed in a single pass over the chunks.
+///
+/// [`OnlineStats`]: struct.OnlineStats.html
+/// [`Histogram`]: ../histogram/struct.Histogram.html
+pub trait Accumulator<C: ?Sized> {
+    /// Adds the values in `chunk`.
+    fn update(&mut self, chunk: &C);
+
+    /// Adds all the values summarized by `other`.
+    fn merge(&mut self, other: &Self);
+}
+
+/// Updates `accumulator` with every chunk yielded by `chunks`, and returns
+/// it.
+///
+/// This is the single entry point for out-of-core statistics: the chunks can
+/// be arrays read one at a time from disk, views into a memory-mapped file,
+/// etc. To process a collection of arrays without consuming it, pass an
+/// iterator over their views.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::Array;
+/// use ndarray_stats::stream::{chunked, MeanVar, MinMax, QuantileSketch};
+///
+/// let data = Array::range(0., 10_000., 1.).into_shape((100, 100)).unwrap();
+/// let (mean_var, min_max, sketch) = chunked(
+///     data.axis_chunks_iter(ndarray::Axis(0), 10),
+///     (MeanVar::new(), MinMax::new(), QuantileSketch::new()),
+/// );
+/// assert_eq!(mean_var.mean(), Ok(4999.5));
+/// assert_eq!(min_max.max(), Ok(&9999.));
+/// assert_eq!(sketch.quantile(1.), Ok(9999.));
+/// ```
+pub fn chunked<I, Acc>(chunks: I, mut accumulator: Acc) -> Acc
+where
+    I: IntoIterator,
+    Acc: Accumulator<I::Item>,
+{
+    for chunk in chunks {
+        accumulator.update(&chunk);
+    }
+    accumulator
+}
+
+impl<A, S, D> Accumulator<ArrayBase<S, D>> for MeanVar<A>
+where
+    A: Float + FromPrimitive,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    /// Summarizes the chunk with a two-pass algorithm before merging it,
+    /// which is faster and more accurate than adding its values one at a
+    /// time.
+    fn update(&mut self, chunk: &ArrayBase<S, D>) {
+        if chunk.is_empty() {
+            return;
+        }
+        let n = A::from_usize(chunk.len())
+            .expect("Converting number of elements to `A` must not fail.");
+        let mean = sum_by(chunk, |&x| x) / n;
+        let m2 = sum_by(chunk, |&x| (x - mean) * (x - mean));
+        MeanVar::merge(self, &MeanVar::from_parts(chunk.len(), mean, m2));
+    }
+
+    fn merge(&mut self, other: &Self) {
+        MeanVar::merge(self, other)
+    }
+}
+
+impl<A, S, D> Accumulator<ArrayBase<S, D>> for ExactSum<A>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn update(&mut self, chunk: &ArrayBase<S, D>) {
+        self.extend(chunk)
+    }
+
+    fn merge(&mut self, other: &Self) {
+        ExactSum::merge(self, other)
+    }
+}
+
+impl<A, S, D> Accumulator<ArrayBase<S, D>> for MinMax<A>
+where
+    A: PartialOrd + Clone,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn update(&mut self, chunk: &ArrayBase<S, D>) {
+        self.extend(chunk)
+    }
+
+    fn merge(&mut self, other: &Self) {
+        MinMax::merge(self, other)
+    }
+}
+
+/// Each chunk is a single observation of every element.
+///
+/// **Panics** if the shape of a chunk (or of the merged accumulator) differs
+/// from the shape of the accumulator.
+macro_rules! impl_accumulator_for_online_stats {
+    ($($obs_dim:ty => $chunk_dim:ty),*) => {
+        $(
+            /// The chunk is a batch of observations stacked along its first
+            /// axis, so that, like for the other accumulators, every value of
+            /// the chunk is added (once per element of the observations).
+            /// To add a single observation `x` as a chunk, pass
+            /// `x.insert_axis(Axis(0))`.
+            ///
+            /// **Panics** if the shape of the observations differs from the
+            /// shape of the accumulator.
+            impl<A, S> Accumulator<ArrayBase<S, $chunk_dim>> for OnlineStats<A, $obs_dim>
+            where
+                A: Float + FromPrimitive,
+                S: Data<Elem = A>,
+            {
+                fn update(&mut self, chunk: &ArrayBase<S, $chunk_dim>) {
+                    for observation in chunk.outer_iter() {
+                        self.observe(&observation).expect(
+                            "The shape of the observations must match the shape of the accumulator.",
+                        )
+                    }
+                }
+
+                fn merge(&mut self, other: &Self) {
+                    OnlineStats::merge(self, other)
+                        .expect("The shapes of the accumulators must match.")
+                }
+            }
+        )*
+    };
+}
+
+impl_accumulator_for_online_stats!(
+    Ix0 => Ix1,
+    Ix1 => Ix2,
+    Ix2 => Ix3,
+    Ix3 => Ix4,
+    Ix4 => Ix5,
+    Ix5 => Ix6,
+    IxDyn => IxDyn
+);
+
+impl<A, S, D> Accumulator<ArrayBase<S, D>> for QuantileSketch<A>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn update(&mut self, chunk: &ArrayBase<S, D>) {
+        self.extend(chunk)
+    }
+
+    fn merge(&mut self, other: &Self) {
+        QuantileSketch::merge(self, other)
+    }
+}
+
+impl<A, S, D> Accumulator<ArrayBase<S, D>> for ReservoirSampler<A>
+where
+    A: Clone,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn update(&mut self, chunk: &ArrayBase<S, D>) {
+        self.extend(chunk)
+    }
+
+    fn merge(&mut self, other: &Self) {
+        ReservoirSampler::merge(self, other)
+    }
+}
+
+/// The rows of the chunks are the observations, as for
+/// [`HistogramExt::histogram`]; observations outside the grid are ignored.
+///
+/// [`HistogramExt::histogram`]: ../trait.HistogramExt.html#tymethod.histogram
+impl<A, S> Accumulator<ArrayBase<S, Ix2>> for Histogram<A>
+where
+    A: Ord,
+    S: Data<Elem = A>,
+{
+    fn update(&mut self, chunk: &ArrayBase<S, Ix2>) {
+        self.add_observations(chunk)
+    }
+
+    fn merge(&mut self, other: &Self) {
+        Histogram::merge(self, other).expect("The grids of the accumulators must match.")
+    }
+}
+
+macro_rules! impl_accumulator_for_tuple {
+    ($($acc:ident $index:tt),+) => {
+        impl<C: ?Sized, $($acc),+> Accumulator<C> for ($($acc,)+)
+        where
+            $($acc: Accumulator<C>),+
+        {
+            fn update(&mut self, chunk: &C) {
+                $(self.$index.update(chunk);)+
+            }
+
+            fn merge(&mut self, other: &Self) {
+                $(self.$index.merge(&other.$index);)+
+            }
+        }
+    };
+}
+impl_accumulator_for_tuple!(A0 0, A1 1);
+impl_accumulator_for_tuple!(A0 0, A1 1, A2 2);
+impl_accumulator_for_tuple!(A0 0, A1 1, A2 2, A3 3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::histogram::{strategies::Sqrt, GridBuilder};
+    use crate::HistogramExt;
+    use approx::assert_abs_diff_eq;
+    use ndarray::{s, Array, ArrayView1, Axis};
+    use ndarray_rand::RandomExt;
+    use rand::distributions::Uniform;
+    use std::f64;
+
+    #[test]
+    fn test_chunked_matches_whole_array() {
+        let data = Array::random((1000, 2), Uniform::new(-5., 5.));
+        let (mean_var, min_max) = chunked(
+            data.axis_chunks_iter(Axis(0), 64),
+            (MeanVar::new(), MinMax::new()),
+        );
+        let mut whole = MeanVar::new();
+        whole.extend(&data);
+        assert_eq!(mean_var.count(), 2000);
+        assert_abs_diff_eq!(
+            mean_var.mean().unwrap(),
+            whole.mean().unwrap(),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            mean_var.var(1.).unwrap(),
+            whole.var(1.).unwrap(),
+            epsilon = 1e-12
+        );
+        let min = data.iter().cloned().fold(f64::INFINITY, f64::min);
+        assert_eq!(min_max.min(), Ok(&min));
+    }
+
+    #[test]
+    fn test_online_stats_chunks_are_batches_of_observations() {
+        let data = Array::random((300, 3), Uniform::new(-5., 5.));
+        let stats = chunked(data.axis_chunks_iter(Axis(0), 32), OnlineStats::new(3));
+        assert_eq!(stats.count(), 300);
+        assert!(stats
+            .mean()
+            .unwrap()
+            .all_close(&data.mean_axis(Axis(0)), 1e-12));
+
+        let column = data.column(0);
+        let (scalars, mean_var) = chunked(
+            column.axis_chunks_iter(Axis(0), 32),
+            (OnlineStats::new(()), MeanVar::new()),
+        );
+        assert_eq!(scalars.count(), mean_var.count());
+        assert_abs_diff_eq!(
+            scalars.mean().unwrap().into_scalar(),
+            mean_var.mean().unwrap(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_merge_reservoirs_with_uneven_capacities() {
+        let data = Array::range(0., 1000., 1.);
+        let mut first = chunked(
+            data.slice(s![..3]).axis_chunks_iter(Axis(0), 2),
+            ReservoirSampler::with_seed(10, 0),
+        );
+        let second = chunked(
+            data.slice(s![3..]).axis_chunks_iter(Axis(0), 100),
+            ReservoirSampler::with_seed(5, 1),
+        );
+        Accumulator::<ArrayView1<'_, f64>>::merge(&mut first, &second);
+        assert_eq!(first.count(), 1000);
+        assert_eq!(first.sample().len(), 8);
+    }
+
+    #[test]
+    fn test_merge_partial_histograms() {
+        let data = Array::random((500, 2), Uniform::new(0u8, 100)).mapv(i32::from);
+        let grid = GridBuilder::<Sqrt<i32>>::from_array(&data).unwrap().build();
+        let chunks: Vec<_> = data.axis_chunks_iter(Axis(0), 100).collect();
+        // Two "workers", each processing half of the chunks.
+        let (first, second) = chunks.split_at(2);
+        let mut histogram = chunked(first.iter().cloned(), Histogram::new(grid.clone()));
+        let other = chunked(second.iter().cloned(), Histogram::new(grid.clone()));
+        histogram.merge(&other).unwrap();
+        assert_eq!(histogram, data.histogram(grid));
+    }
+}
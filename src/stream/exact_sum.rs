@@ -0,0 +1,203 @@
+use core::marker::PhantomData;
+use num_traits::Float;
+#[cfg(feature = "serde-1")]
+use serde::{Deserialize, Serialize};
+
+/// Streaming accumulator for the exact sum of a sequence of floating-point
+/// values.
+///
+/// The values are accumulated without any rounding error, using Shewchuk's
+/// algorithm (the one behind Python's `math.fsum`), and the sum is rounded
+/// only once, to the nearest `f64`. The result therefore doesn't depend on
+/// the order in which the values are added, on how they are split into
+/// chunks, on how partial sums are merged, or on the platform: it is
+/// reproducible bit for bit. This is the basis of the deterministic
+/// reductions of [`SummaryStatisticsExt`].
+///
+/// The price is speed: adding a value costs a few floating-point operations
+/// per partial (usually only a handful of partials are needed).
+///
+/// # Example
+///
+/// ```
+/// use ndarray_stats::stream::ExactSum;
+///
+/// let values = [1e100, 1., -1e100, 1e-100];
+/// let mut forward = ExactSum::new();
+/// forward.extend(&values);
+/// let mut backward = ExactSum::new();
+/// backward.extend(values.iter().rev());
+/// assert_eq!(forward.sum(), 1.);
+/// assert_eq!(forward.sum(), backward.sum());
+/// ```
+///
+/// [`SummaryStatisticsExt`]: ../trait.SummaryStatisticsExt.html
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+pub struct ExactSum<A> {
+    /// Non-overlapping partial sums, in increasing order of magnitude, whose
+    /// exact sum is the sum of the finite values.
+    partials: Vec<f64>,
+    /// Sum of the infinite and NaN values.
+    non_finite: f64,
+    elem: PhantomData<A>,
+}
+
+impl<A: Float> ExactSum<A> {
+    /// Creates an empty accumulator.
+    pub fn new() -> Self {
+        ExactSum {
+            partials: Vec::new(),
+            non_finite: 0.,
+            elem: PhantomData,
+        }
+    }
+
+    /// Adds a single value.
+    ///
+    /// **Panics** if the value can't be converted to `f64`.
+    pub fn push(&mut self, value: A) {
+        self.push_f64(
+            value
+                .to_f64()
+                .expect("Converting the value to `f64` must not fail."),
+        )
+    }
+
+    /// Adds all the values summarized by `other`.
+    pub fn merge(&mut self, other: &Self) {
+        self.non_finite += other.non_finite;
+        for &partial in &other.partials {
+            self.push_f64(partial);
+        }
+    }
+
+    /// Returns the sum of the values, correctly rounded to `f64` and then
+    /// converted to `A`.
+    ///
+    /// The sum of no values is zero. If any value is NaN, or if values of
+    /// infinite magnitude and opposite signs have been added, the sum is NaN.
+    ///
+    /// **Panics** if the sum can't be converted from `f64` to `A`.
+    pub fn sum(&self) -> A {
+        A::from(self.sum_f64()).expect("Converting the sum to `A` must not fail.")
+    }
+
+    fn push_f64(&mut self, mut x: f64) {
+        if !x.is_finite() {
+            self.non_finite += x;
+            return;
+        }
+        let mut i = 0;
+        for j in 0..self.partials.len() {
+            let mut y = self.partials[j];
+            if x.abs() < y.abs() {
+                core::mem::swap(&mut x, &mut y);
+            }
+            let hi = x + y;
+            let lo = y - (hi - x);
+            if lo != 0. {
+                self.partials[i] = lo;
+                i += 1;
+            }
+            x = hi;
+        }
+        self.partials.truncate(i);
+        self.partials.push(x);
+    }
+
+    fn sum_f64(&self) -> f64 {
+        if self.non_finite != 0. {
+            return self.non_finite;
+        }
+        let partials = &self.partials;
+        let mut n = partials.len();
+        if n == 0 {
+            return 0.;
+        }
+        n -= 1;
+        let mut hi = partials[n];
+        let mut lo = 0.;
+        while n > 0 {
+            let x = hi;
+            n -= 1;
+            let y = partials[n];
+            hi = x + y;
+            lo = y - (hi - x);
+            if lo != 0. {
+                break;
+            }
+        }
+        // Round half to even correctly when the rounding error `lo` is
+        // exactly half an ulp, but the remaining partials push it further.
+        if n > 0 && ((lo < 0. && partials[n - 1] < 0.) || (lo > 0. && partials[n - 1] > 0.)) {
+            let y = lo * 2.;
+            let x = hi + y;
+            if y == x - hi {
+                hi = x;
+            }
+        }
+        hi
+    }
+}
+
+impl<A: Float> Default for ExactSum<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Float> Extend<A> for ExactSum<A> {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value)
+        }
+    }
+}
+
+impl<'a, A: Float + 'a> Extend<&'a A> for ExactSum<A> {
+    fn extend<I: IntoIterator<Item = &'a A>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[test]
+    fn test_exact_sums() {
+        let sum = |values: &[f64]| {
+            let mut exact = ExactSum::new();
+            exact.extend(values);
+            exact.sum()
+        };
+        assert_eq!(sum(&[]), 0.);
+        assert_eq!(sum(&[0.1; 10]), 1.);
+        assert_eq!(sum(&[1e16, 1., -1e16, 1e-16]), 1.);
+        // Half-way case: 1 + 2^-53 + 2^-106 rounds up, not to even.
+        assert_eq!(
+            sum(&[1., 2f64.powi(-53), 2f64.powi(-106)]),
+            1. + f64::EPSILON
+        );
+        assert_eq!(sum(&[1., f64::INFINITY]), f64::INFINITY);
+        assert!(sum(&[f64::INFINITY, f64::NEG_INFINITY]).is_nan());
+        assert!(sum(&[1., f64::NAN]).is_nan());
+    }
+
+    #[quickcheck]
+    fn sum_is_independent_of_order_and_chunking(xs: Vec<f64>, split: usize) -> bool {
+        let xs: Vec<f64> = xs.into_iter().filter(|x| x.is_finite()).collect();
+        let mut forward = ExactSum::new();
+        forward.extend(&xs);
+        let split = if xs.is_empty() { 0 } else { split % xs.len() };
+        let mut first = ExactSum::new();
+        first.extend(xs[..split].iter().rev());
+        let mut second = ExactSum::new();
+        second.extend(xs[split..].iter().rev());
+        second.merge(&first);
+        forward.sum() == second.sum()
+    }
+}
@@ -0,0 +1,219 @@
+use crate::errors::EmptyInput;
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "serde-1")]
+use serde::{Deserialize, Serialize};
+
+/// Streaming accumulator for the count, mean and variance of a sequence of
+/// values.
+///
+/// Values are added with Welford's algorithm, and accumulators are merged
+/// with Chan et al.'s pairwise update, both of which are numerically stable.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::stream::MeanVar;
+///
+/// let mut first = MeanVar::new();
+/// first.extend(&array![1., 2., 3.]);
+/// let mut second = MeanVar::new();
+/// second.extend(&array![4., 5.]);
+/// first.merge(&second);
+/// assert_eq!(first.mean(), Ok(3.));
+/// assert_eq!(first.var(1.), Ok(2.5));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+pub struct MeanVar<A> {
+    count: usize,
+    mean: A,
+    /// Sum of the squared deviations from the mean.
+    m2: A,
+}
+
+impl<A: Float + FromPrimitive> MeanVar<A> {
+    /// Creates an empty accumulator.
+    pub fn new() -> Self {
+        MeanVar {
+            count: 0,
+            mean: A::zero(),
+            m2: A::zero(),
+        }
+    }
+
+    /// Creates an accumulator from the number of values, their mean and the
+    /// sum of their squared deviations from the mean.
+    pub(crate) fn from_parts(count: usize, mean: A, m2: A) -> Self {
+        MeanVar { count, mean, m2 }
+    }
+
+    /// Adds a single value.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of values.
+    pub fn push(&mut self, value: A) {
+        self.count += 1;
+        let count =
+            A::from_usize(self.count).expect("Converting number of elements to `A` must not fail.");
+        let delta = value - self.mean;
+        self.mean = self.mean + delta / count;
+        self.m2 = self.m2 + delta * (value - self.mean);
+    }
+
+    /// Adds all the values summarized by `other`.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of values.
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = other.clone();
+            return;
+        }
+        let to_a =
+            |n| A::from_usize(n).expect("Converting number of elements to `A` must not fail.");
+        let (n_self, n_other) = (to_a(self.count), to_a(other.count));
+        let count = self.count + other.count;
+        let n = to_a(count);
+        let delta = other.mean - self.mean;
+        self.mean = self.mean + delta * n_other / n;
+        self.m2 = self.m2 + other.m2 + delta * delta * n_self * n_other / n;
+        self.count = count;
+    }
+
+    /// Returns the sum of the squared deviations of the values from their
+    /// mean.
+    pub(crate) fn sum_sq_dev(&self) -> A {
+        self.m2
+    }
+
+    /// Returns the number of values that have been added.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the arithmetic mean of the values.
+    ///
+    /// If no value has been added, `Err(EmptyInput)` is returned.
+    pub fn mean(&self) -> Result<A, EmptyInput> {
+        if self.count == 0 {
+            Err(EmptyInput)
+        } else {
+            Ok(self.mean)
+        }
+    }
+
+    /// Returns the variance of the values.
+    ///
+    /// The parameter `ddof` specifies the "delta degrees of freedom": use
+    /// `ddof = 0` for the population variance and `ddof = 1` for the
+    /// unbiased sample variance.
+    ///
+    /// If no value has been added, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `ddof` is less than zero or greater than or equal to the
+    /// number of values.
+    pub fn var(&self, ddof: A) -> Result<A, EmptyInput> {
+        if self.count == 0 {
+            return Err(EmptyInput);
+        }
+        let n =
+            A::from_usize(self.count).expect("Converting number of elements to `A` must not fail.");
+        assert!(
+            !(ddof < A::zero() || ddof >= n),
+            "`ddof` must be non-negative and less than the number of values."
+        );
+        Ok(self.m2 / (n - ddof))
+    }
+
+    /// Returns the standard deviation of the values.
+    ///
+    /// See [`var`](#method.var) for the meaning of `ddof`, the errors and the
+    /// panics.
+    pub fn std(&self, ddof: A) -> Result<A, EmptyInput> {
+        self.var(ddof).map(|var| var.sqrt())
+    }
+}
+
+impl<A: Float + FromPrimitive> Default for MeanVar<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Float + FromPrimitive> Extend<A> for MeanVar<A> {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value)
+        }
+    }
+}
+
+impl<'a, A: 'a + Float + FromPrimitive> Extend<&'a A> for MeanVar<A> {
+    fn extend<I: IntoIterator<Item = &'a A>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SummaryStatisticsExt;
+    use ndarray::Array1;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn test_empty() {
+        let mean_var = MeanVar::<f64>::new();
+        assert_eq!(mean_var.count(), 0);
+        assert_eq!(mean_var.mean(), Err(EmptyInput));
+        assert_eq!(mean_var.var(0.), Err(EmptyInput));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_var_ddof_too_large() {
+        let mut mean_var = MeanVar::new();
+        mean_var.push(1.);
+        let _ = mean_var.var(1.);
+    }
+
+    #[quickcheck]
+    fn merge_matches_extend(xs: Vec<i32>, split: usize) -> bool {
+        let xs: Vec<f64> = xs.into_iter().map(f64::from).collect();
+        let split = if xs.is_empty() { 0 } else { split % xs.len() };
+        let mut all = MeanVar::new();
+        all.extend(&xs);
+        let mut first = MeanVar::new();
+        first.extend(&xs[..split]);
+        let mut second = MeanVar::new();
+        second.extend(&xs[split..]);
+        first.merge(&second);
+
+        let close = |a: f64, b: f64| (a - b).abs() <= 1e-6 * (1. + a.abs().max(b.abs()));
+        first.count() == all.count()
+            && match (first.mean(), all.mean()) {
+                (Ok(m1), Ok(m2)) => {
+                    close(m1, m2) && close(first.var(0.).unwrap(), all.var(0.).unwrap())
+                }
+                (Err(_), Err(_)) => true,
+                _ => false,
+            }
+    }
+
+    #[quickcheck]
+    fn matches_array_statistics(xs: Vec<i32>) -> bool {
+        let a: Array1<f64> = xs.into_iter().map(f64::from).collect();
+        let mut mean_var = MeanVar::new();
+        mean_var.extend(&a);
+        match a.mean() {
+            Ok(mean) => {
+                let var = a.central_moment(2).unwrap();
+                (mean_var.mean().unwrap() - mean).abs() <= 1e-6 * (1. + mean.abs())
+                    && (mean_var.var(0.).unwrap() - var).abs() <= 1e-6 * (1. + var)
+            }
+            Err(_) => mean_var.mean().is_err(),
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::errors::{EmptyInput, MinMaxError};
+use core::cmp::Ordering;
+#[cfg(feature = "serde-1")]
+use serde::{Deserialize, Serialize};
+
+/// Streaming accumulator for the minimum and maximum of a sequence of values.
+///
+/// As for [`QuantileExt::min`], the ordering of the values is only required
+/// to be partial: if any of the tested pairs of values is not comparable
+/// (e.g. because one of them is NaN), `min` and `max` return
+/// `Err(MinMaxError::UndefinedOrder)`.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::stream::MinMax;
+///
+/// let mut min_max = MinMax::new();
+/// min_max.extend(&array![[3, 7], [-2, 5]]);
+/// min_max.extend(vec![4, 10]);
+/// assert_eq!(min_max.min(), Ok(&-2));
+/// assert_eq!(min_max.max(), Ok(&10));
+/// ```
+///
+/// [`QuantileExt::min`]: ../trait.QuantileExt.html#tymethod.min
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+pub struct MinMax<A> {
+    min_max: Option<(A, A)>,
+    undefined_order: bool,
+}
+
+impl<A: PartialOrd + Clone> MinMax<A> {
+    /// Creates an empty accumulator.
+    pub fn new() -> Self {
+        MinMax {
+            min_max: None,
+            undefined_order: false,
+        }
+    }
+
+    /// Adds a single value.
+    pub fn push(&mut self, value: A) {
+        match &mut self.min_max {
+            None => {
+                // A value that is not comparable with itself (e.g. NaN) is
+                // not comparable with anything else either.
+                if value.partial_cmp(&value).is_none() {
+                    self.undefined_order = true;
+                }
+                self.min_max = Some((value.clone(), value))
+            }
+            Some((min, max)) => {
+                match value.partial_cmp(min) {
+                    Some(Ordering::Less) => *min = value.clone(),
+                    Some(_) => {}
+                    None => self.undefined_order = true,
+                }
+                match value.partial_cmp(max) {
+                    Some(Ordering::Greater) => *max = value,
+                    Some(_) => {}
+                    None => self.undefined_order = true,
+                }
+            }
+        }
+    }
+
+    /// Adds all the values summarized by `other`.
+    pub fn merge(&mut self, other: &Self) {
+        self.undefined_order |= other.undefined_order;
+        if let Some((min, max)) = &other.min_max {
+            self.push(min.clone());
+            self.push(max.clone());
+        }
+    }
+
+    /// Returns the minimum of the values.
+    ///
+    /// Returns `Err(MinMaxError::EmptyInput)` if no value has been added and
+    /// `Err(MinMaxError::UndefinedOrder)` if any of the tested pairs of
+    /// values was not comparable.
+    pub fn min(&self) -> Result<&A, MinMaxError> {
+        self.min_max().map(|(min, _)| min)
+    }
+
+    /// Returns the maximum of the values.
+    ///
+    /// See [`min`](#method.min) for the errors.
+    pub fn max(&self) -> Result<&A, MinMaxError> {
+        self.min_max().map(|(_, max)| max)
+    }
+
+    fn min_max(&self) -> Result<&(A, A), MinMaxError> {
+        let min_max = self.min_max.as_ref().ok_or(EmptyInput)?;
+        if self.undefined_order {
+            Err(MinMaxError::UndefinedOrder)
+        } else {
+            Ok(min_max)
+        }
+    }
+}
+
+impl<A: PartialOrd + Clone> Default for MinMax<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: PartialOrd + Clone> Extend<A> for MinMax<A> {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value)
+        }
+    }
+}
+
+impl<'a, A: PartialOrd + Clone + 'a> Extend<&'a A> for MinMax<A> {
+    fn extend<I: IntoIterator<Item = &'a A>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[test]
+    fn test_empty() {
+        let min_max = MinMax::<i32>::new();
+        assert_eq!(min_max.min(), Err(MinMaxError::EmptyInput));
+        assert_eq!(min_max.max(), Err(MinMaxError::EmptyInput));
+    }
+
+    #[test]
+    fn test_undefined_order() {
+        let mut min_max = MinMax::new();
+        min_max.extend(vec![1., f64::NAN, 3.]);
+        assert_eq!(min_max.min(), Err(MinMaxError::UndefinedOrder));
+
+        let mut min_max = MinMax::new();
+        min_max.push(f64::NAN);
+        assert_eq!(min_max.max(), Err(MinMaxError::UndefinedOrder));
+
+        let mut other = MinMax::new();
+        other.push(2.);
+        other.merge(&min_max);
+        assert_eq!(other.max(), Err(MinMaxError::UndefinedOrder));
+    }
+
+    #[quickcheck]
+    fn merge_matches_iterator_min_max(xs: Vec<i32>, ys: Vec<i32>) -> bool {
+        let mut first = MinMax::new();
+        first.extend(&xs);
+        let mut second = MinMax::new();
+        second.extend(&ys);
+        first.merge(&second);
+        let all = || xs.iter().chain(&ys);
+        first.min().ok() == all().min() && first.max().ok() == all().max()
+    }
+}
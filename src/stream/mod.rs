@@ -0,0 +1,66 @@
+//! Streaming statistics.
+//!
+//! The accumulators in this module summarize data incrementally, so that
+//! datasets that don't fit in memory (e.g. read from disk chunk by chunk)
+//! can be summarized without building one giant array. All of them implement
+//! `Extend`, both for owned elements and for references, so they can consume
+//! any iterator as well as successive array chunks:
+//!
+//! ```
+//! use ndarray::array;
+//! use ndarray_stats::stream::MeanVar;
+//!
+//! let mut mean_var = MeanVar::new();
+//! for chunk in &[array![1., 2.], array![3., 4., 5.]] {
+//!     mean_var.extend(chunk);
+//! }
+//! mean_var.extend(vec![6., 7.]);
+//! assert_eq!(mean_var.count(), 7);
+//! assert_eq!(mean_var.mean(), Ok(4.));
+//! ```
+//!
+//! Accumulators that were filled independently (e.g. on different threads
+//! or machines) can be combined with their `merge` method. The results of
+//! [`MeanVar`] depend (in the last bits) on how the data was split; use
+//! [`ExactSum`] when results must be reproducible bit for bit.
+//!
+//! [`OnlineStats`] summarizes a sequence of arrays element by element (e.g.
+//! the per-pixel mean and variance of a stack of images), including the
+//! third and fourth moments.
+//!
+//! [`ReservoirSampler`] keeps a uniform random sample of fixed size instead
+//! of a summary, for statistics that need the values themselves.
+//!
+//! [`Histogram`] can be filled incrementally as well: it implements `Extend`
+//! for observations (points), so the rows of successive 2-dimensional chunks
+//! can be added with `histogram.extend(chunk.outer_iter())`.
+//!
+//! The [`chunked`] combinator drives any [`Accumulator`] (or tuple of
+//! accumulators) over an iterator of array chunks, and partial results are
+//! combined with [`Accumulator::merge`]. A chunk is always a batch of values;
+//! for [`OnlineStats`] it is a batch of observations stacked along the first
+//! axis.
+//!
+//! [`Accumulator`]: trait.Accumulator.html
+//! [`Accumulator::merge`]: trait.Accumulator.html#tymethod.merge
+//! [`chunked`]: fn.chunked.html
+//! [`ExactSum`]: struct.ExactSum.html
+//! [`Histogram`]: ../histogram/struct.Histogram.html
+//! [`MeanVar`]: struct.MeanVar.html
+//! [`OnlineStats`]: struct.OnlineStats.html
+//! [`ReservoirSampler`]: struct.ReservoirSampler.html
+pub use self::chunked::{chunked, Accumulator};
+pub use self::exact_sum::ExactSum;
+pub use self::mean_var::MeanVar;
+pub use self::min_max::MinMax;
+pub use self::online::OnlineStats;
+pub use self::reservoir::ReservoirSampler;
+pub use self::sketch::QuantileSketch;
+
+mod chunked;
+mod exact_sum;
+mod mean_var;
+mod min_max;
+mod online;
+mod reservoir;
+mod sketch;
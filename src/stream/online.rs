@@ -0,0 +1,428 @@
+use crate::errors::{EmptyInput, ShapeMismatch};
+use ndarray::{Array, ArrayBase, Data, Dimension, IntoDimension, Ix0, Zip};
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "serde-1")]
+use serde::{Deserialize, Serialize};
+
+/// Streaming accumulator for element-wise statistics of a sequence of arrays
+/// with the same shape.
+///
+/// Each call to [`observe`] adds one observation per element, so that, for
+/// example, the mean of a stack of images can be computed one image at a
+/// time. The count, mean, variance, minimum, maximum, skewness and kurtosis
+/// of each element are available as arrays with the shape of the
+/// observations, and [`pooled`] summarizes all the elements of all the
+/// observations together.
+///
+/// The central moments are updated with Welford's algorithm, generalized to
+/// the third and fourth moments by [Pébay (2008)], and accumulators are
+/// merged with the corresponding pairwise update, so accumulators filled with
+/// different chunks of the data can be combined with [`merge`]. NaN values
+/// propagate to all the statistics of their element.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::array;
+/// use ndarray_stats::stream::OnlineStats;
+///
+/// let mut stats = OnlineStats::new(2);
+/// for observation in &[array![1., 10.], array![2., 30.], array![6., 20.]] {
+///     stats.observe(observation).unwrap();
+/// }
+/// assert_eq!(stats.count(), 3);
+/// assert_eq!(stats.mean(), Ok(array![3., 20.]));
+/// assert_eq!(stats.var(1.), Ok(array![7., 100.]));
+/// assert_eq!(stats.max(), Ok(array![6., 30.]));
+///
+/// let pooled = stats.pooled();
+/// assert_eq!(pooled.count(), 6);
+/// assert_eq!(pooled.mean().unwrap().into_scalar(), 11.5);
+/// ```
+///
+/// [`observe`]: #method.observe
+/// [`pooled`]: #method.pooled
+/// [`merge`]: #method.merge
+/// [Pébay (2008)]: https://www.osti.gov/servlets/purl/1028931
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+pub struct OnlineStats<A, D: Dimension> {
+    count: usize,
+    moments: Array<Moments<A>, D>,
+}
+
+/// Moments and extrema of the values of a single element.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+struct Moments<A> {
+    mean: A,
+    /// Sums of the powers of the deviations from the mean.
+    m2: A,
+    m3: A,
+    m4: A,
+    min: A,
+    max: A,
+}
+
+impl<A: Float> Moments<A> {
+    fn first(value: A) -> Self {
+        Moments {
+            mean: value,
+            m2: A::zero(),
+            m3: A::zero(),
+            m4: A::zero(),
+            min: value,
+            max: value,
+        }
+    }
+
+    /// Adds `value` to the `count - 1` values summarized by `self`.
+    fn push(&mut self, value: A, count: A) {
+        let one = A::one();
+        let (two, three) = (one + one, one + one + one);
+        let delta = value - self.mean;
+        let delta_n = delta / count;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * (count - one);
+        self.mean = self.mean + delta_n;
+        self.m4 = self.m4
+            + term * delta_n2 * (count * count - three * count + three)
+            + two * three * delta_n2 * self.m2
+            - two * two * delta_n * self.m3;
+        self.m3 = self.m3 + term * delta_n * (count - two) - three * delta_n * self.m2;
+        self.m2 = self.m2 + term;
+        // Comparisons with NaN are false, so a NaN extremum is kept.
+        if value.is_nan() || value < self.min {
+            self.min = value;
+        }
+        if value.is_nan() || value > self.max {
+            self.max = value;
+        }
+    }
+
+    /// Combines the `n_self` values summarized by `self` with the `n_other`
+    /// values summarized by `other`. Both counts must be positive.
+    fn merge(&mut self, n_self: A, other: &Self, n_other: A) {
+        let one = A::one();
+        let (two, three) = (one + one, one + one + one);
+        let n = n_self + n_other;
+        let delta = other.mean - self.mean;
+        let delta2 = delta * delta;
+        self.mean = self.mean + delta * n_other / n;
+        self.m4 = self.m4
+            + other.m4
+            + delta2
+                * delta2
+                * n_self
+                * n_other
+                * (n_self * n_self - n_self * n_other + n_other * n_other)
+                / (n * n * n)
+            + two * three * delta2 * (n_self * n_self * other.m2 + n_other * n_other * self.m2)
+                / (n * n)
+            + two * two * delta * (n_self * other.m3 - n_other * self.m3) / n;
+        self.m3 = self.m3
+            + other.m3
+            + delta2 * delta * n_self * n_other * (n_self - n_other) / (n * n)
+            + three * delta * (n_self * other.m2 - n_other * self.m2) / n;
+        self.m2 = self.m2 + other.m2 + delta2 * n_self * n_other / n;
+        if other.min.is_nan() || other.min < self.min {
+            self.min = other.min;
+        }
+        if other.max.is_nan() || other.max > self.max {
+            self.max = other.max;
+        }
+    }
+}
+
+impl<A, D> OnlineStats<A, D>
+where
+    A: Float + FromPrimitive,
+    D: Dimension,
+{
+    /// Creates an empty accumulator for observations of the given shape.
+    pub fn new<Sh>(shape: Sh) -> Self
+    where
+        Sh: IntoDimension<Dim = D>,
+    {
+        OnlineStats {
+            count: 0,
+            moments: Array::from_elem(shape, Moments::first(A::zero())),
+        }
+    }
+
+    /// Adds an observation, i.e. one value for each element.
+    ///
+    /// Returns `Err(ShapeMismatch)` if the shape of `observation` differs
+    /// from the shape of the accumulator, which is then left untouched.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of
+    /// observations.
+    pub fn observe<S>(&mut self, observation: &ArrayBase<S, D>) -> Result<(), ShapeMismatch>
+    where
+        S: Data<Elem = A>,
+    {
+        self.check_shape(observation.shape())?;
+        self.count += 1;
+        if self.count == 1 {
+            Zip::from(&mut self.moments)
+                .and(observation)
+                .apply(|moments, &x| *moments = Moments::first(x));
+        } else {
+            let count = to_a(self.count);
+            Zip::from(&mut self.moments)
+                .and(observation)
+                .apply(|moments, &x| moments.push(x, count));
+        }
+        Ok(())
+    }
+
+    /// Adds all the observations summarized by `other`.
+    ///
+    /// Returns `Err(ShapeMismatch)` if the shapes of the two accumulators
+    /// differ, and `self` is then left untouched.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of
+    /// observations.
+    pub fn merge(&mut self, other: &Self) -> Result<(), ShapeMismatch> {
+        self.check_shape(other.moments.shape())?;
+        if other.count == 0 {
+            return Ok(());
+        }
+        if self.count == 0 {
+            *self = other.clone();
+            return Ok(());
+        }
+        let (n_self, n_other) = (to_a(self.count), to_a(other.count));
+        Zip::from(&mut self.moments)
+            .and(&other.moments)
+            .apply(|moments, other| moments.merge(n_self, other, n_other));
+        self.count += other.count;
+        Ok(())
+    }
+
+    /// Returns an accumulator summarizing all the values of all the elements
+    /// of the observations together.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of
+    /// values.
+    pub fn pooled(&self) -> OnlineStats<A, Ix0> {
+        let mut pooled = OnlineStats::new(());
+        if self.count == 0 {
+            return pooled;
+        }
+        let n = to_a(self.count);
+        for moments in self.moments.iter() {
+            if pooled.count == 0 {
+                pooled.moments[()] = *moments;
+            } else {
+                pooled.moments[()].merge(to_a(pooled.count), moments, n);
+            }
+            pooled.count += self.count;
+        }
+        pooled
+    }
+
+    /// Returns the number of observations that have been added.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the arithmetic mean of each element.
+    ///
+    /// If no observation has been added, `Err(EmptyInput)` is returned.
+    pub fn mean(&self) -> Result<Array<A, D>, EmptyInput> {
+        self.map(|m| m.mean)
+    }
+
+    /// Returns the variance of each element.
+    ///
+    /// The parameter `ddof` specifies the "delta degrees of freedom": use
+    /// `ddof = 0` for the population variance and `ddof = 1` for the
+    /// unbiased sample variance.
+    ///
+    /// If no observation has been added, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `ddof` is less than zero or greater than or equal to the
+    /// number of observations.
+    pub fn var(&self, ddof: A) -> Result<Array<A, D>, EmptyInput> {
+        let n = to_a(self.count);
+        if self.count > 0 {
+            assert!(
+                !(ddof < A::zero() || ddof >= n),
+                "`ddof` must be non-negative and less than the number of observations."
+            );
+        }
+        self.map(|m| m.m2 / (n - ddof))
+    }
+
+    /// Returns the standard deviation of each element.
+    ///
+    /// See [`var`](#method.var) for the meaning of `ddof`, the errors and the
+    /// panics.
+    pub fn std(&self, ddof: A) -> Result<Array<A, D>, EmptyInput> {
+        self.var(ddof).map(|var| var.mapv_into(A::sqrt))
+    }
+
+    /// Returns the minimum of each element.
+    ///
+    /// If no observation has been added, `Err(EmptyInput)` is returned.
+    pub fn min(&self) -> Result<Array<A, D>, EmptyInput> {
+        self.map(|m| m.min)
+    }
+
+    /// Returns the maximum of each element.
+    ///
+    /// If no observation has been added, `Err(EmptyInput)` is returned.
+    pub fn max(&self) -> Result<Array<A, D>, EmptyInput> {
+        self.map(|m| m.max)
+    }
+
+    /// Returns the [skewness] `μ₃ / σ³` of each element, as
+    /// [`SummaryStatisticsExt::skewness`].
+    ///
+    /// If no observation has been added, `Err(EmptyInput)` is returned.
+    ///
+    /// [skewness]: https://en.wikipedia.org/wiki/Skewness
+    /// [`SummaryStatisticsExt::skewness`]: ../trait.SummaryStatisticsExt.html#tymethod.skewness
+    pub fn skewness(&self) -> Result<Array<A, D>, EmptyInput> {
+        let n = to_a(self.count);
+        self.map(|m| {
+            let var = m.m2 / n;
+            m.m3 / n / (var * var.sqrt())
+        })
+    }
+
+    /// Returns the (Pearson's) [kurtosis] `μ₄ / σ⁴` of each element, as
+    /// [`SummaryStatisticsExt::kurtosis`].
+    ///
+    /// If no observation has been added, `Err(EmptyInput)` is returned.
+    ///
+    /// [kurtosis]: https://en.wikipedia.org/wiki/Kurtosis
+    /// [`SummaryStatisticsExt::kurtosis`]: ../trait.SummaryStatisticsExt.html#tymethod.kurtosis
+    pub fn kurtosis(&self) -> Result<Array<A, D>, EmptyInput> {
+        let n = to_a(self.count);
+        self.map(|m| {
+            let var = m.m2 / n;
+            m.m4 / n / (var * var)
+        })
+    }
+
+    fn map<F>(&self, f: F) -> Result<Array<A, D>, EmptyInput>
+    where
+        F: Fn(&Moments<A>) -> A,
+    {
+        if self.count == 0 {
+            Err(EmptyInput)
+        } else {
+            Ok(self.moments.map(f))
+        }
+    }
+
+    fn check_shape(&self, shape: &[usize]) -> Result<(), ShapeMismatch> {
+        if self.moments.shape() == shape {
+            Ok(())
+        } else {
+            Err(ShapeMismatch {
+                first_shape: self.moments.shape().to_vec(),
+                second_shape: shape.to_vec(),
+            })
+        }
+    }
+}
+
+fn to_a<A: FromPrimitive>(n: usize) -> A {
+    A::from_usize(n).expect("Converting the number of observations to `A` must not fail.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{QuantileExt, SummaryStatisticsExt};
+    use ndarray::{array, Array1, Array2, Axis};
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-6 * (1. + a.abs().max(b.abs()))
+    }
+
+    /// Returns the observations as the rows of a `(n, 3)` array.
+    fn observations(xs: Vec<(i16, i16, i16)>) -> Array2<f64> {
+        Array2::from_shape_fn((xs.len(), 3), |(i, j)| {
+            f64::from([xs[i].0, xs[i].1, xs[i].2][j])
+        })
+    }
+
+    #[quickcheck]
+    fn matches_array_statistics(xs: Vec<(i16, i16, i16)>, split: usize) -> bool {
+        let data = observations(xs);
+        let n = data.rows();
+        let split = if n == 0 { 0 } else { split % n };
+        let mut stats = OnlineStats::new(3);
+        let mut first = OnlineStats::new(3);
+        let mut second = OnlineStats::new(3);
+        for (i, row) in data.outer_iter().enumerate() {
+            stats.observe(&row).unwrap();
+            if i < split { &mut first } else { &mut second }
+                .observe(&row)
+                .unwrap();
+        }
+        first.merge(&second).unwrap();
+        if n < 2 {
+            return stats.count() == n && first.count() == n && stats.mean().is_ok() == (n == 1);
+        }
+        [&stats, &first].iter().all(|stats| {
+            let pooled = stats.pooled();
+            let all = Array1::from_iter(data.iter().cloned());
+            let columns_match = data
+                .gencolumns()
+                .into_iter()
+                .enumerate()
+                .all(|(j, column)| {
+                    let constant = column.iter().all(|&x| x == column[0]);
+                    close(stats.mean().unwrap()[j], column.mean().unwrap())
+                        && close(stats.var(1.).unwrap()[j], column.var_axis(Axis(0), 1.)[()])
+                        && stats.min().unwrap()[j] == *column.min().unwrap()
+                        && stats.max().unwrap()[j] == *column.max().unwrap()
+                        && (constant
+                            || (close(stats.skewness().unwrap()[j], column.skewness().unwrap())
+                                && close(stats.kurtosis().unwrap()[j], column.kurtosis().unwrap())))
+                });
+            let constant = all.iter().all(|&x| x == all[0]);
+            columns_match
+                && pooled.count() == 3 * n
+                && close(pooled.mean().unwrap()[()], all.mean().unwrap())
+                && close(pooled.var(0.).unwrap()[()], all.central_moment(2).unwrap())
+                && pooled.max().unwrap()[()] == *all.max().unwrap()
+                && (constant || close(pooled.kurtosis().unwrap()[()], all.kurtosis().unwrap()))
+        })
+    }
+
+    #[test]
+    fn test_empty_and_shape_mismatch() {
+        let mut stats = OnlineStats::<f64, _>::new((2, 2));
+        assert_eq!(stats.mean(), Err(EmptyInput));
+        assert_eq!(stats.pooled().var(0.), Err(EmptyInput));
+        assert_eq!(
+            stats.observe(&array![[1., 2.]]),
+            Err(ShapeMismatch {
+                first_shape: vec![2, 2],
+                second_shape: vec![1, 2],
+            })
+        );
+        assert_eq!(stats.count(), 0);
+        assert!(stats.merge(&OnlineStats::new((2, 1))).is_err());
+    }
+
+    #[test]
+    fn test_nan_propagates() {
+        let mut stats = OnlineStats::new(2);
+        stats.observe(&array![1., f64::NAN]).unwrap();
+        stats.observe(&array![3., 2.]).unwrap();
+        assert_eq!(stats.mean().unwrap()[0], 2.);
+        assert!(stats.mean().unwrap()[1].is_nan());
+        assert!(stats.min().unwrap()[1].is_nan());
+        assert!(stats.max().unwrap()[1].is_nan());
+        assert!(stats.pooled().max().unwrap()[()].is_nan());
+    }
+}
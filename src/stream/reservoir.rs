@@ -0,0 +1,226 @@
+use ndarray::{aview1, ArrayView1};
+use rand::rngs::StdRng;
+use rand::{FromEntropy, Rng, SeedableRng};
+
+/// Streaming accumulator keeping a uniform random sample of fixed size of
+/// a sequence of values ([reservoir sampling]).
+///
+/// After `n` values have been added, the sample contains `min(k, n)` of
+/// them, and every subset of that size is equally likely. The sample can
+/// then be used to compute exact statistics (e.g. quantiles) of a
+/// representative subset of unbounded data.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::Array;
+/// use ndarray_stats::stream::ReservoirSampler;
+/// use ndarray_stats::SummaryStatisticsExt;
+///
+/// let mut sampler = ReservoirSampler::with_seed(1000, 42);
+/// for chunk in Array::range(0., 100_000., 1.).exact_chunks(10_000) {
+///     sampler.extend(&chunk);
+/// }
+/// assert_eq!(sampler.count(), 100_000);
+/// assert_eq!(sampler.sample().len(), 1000);
+/// let mean: f64 = sampler.sample().mean().unwrap();
+/// assert!((mean - 50_000.).abs() < 5_000.);
+/// ```
+///
+/// [reservoir sampling]: https://en.wikipedia.org/wiki/Reservoir_sampling
+#[derive(Clone, Debug)]
+pub struct ReservoirSampler<A> {
+    capacity: usize,
+    sample: Vec<A>,
+    count: usize,
+    rng: StdRng,
+}
+
+impl<A> ReservoirSampler<A> {
+    /// Creates an empty sampler keeping at most `capacity` values, seeded
+    /// from the operating system's entropy source.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_rng(capacity, StdRng::from_entropy())
+    }
+
+    /// Creates an empty sampler keeping at most `capacity` values, seeded
+    /// with `seed` to get reproducible samples.
+    pub fn with_seed(capacity: usize, seed: u64) -> Self {
+        Self::with_rng(capacity, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(capacity: usize, rng: StdRng) -> Self {
+        ReservoirSampler {
+            capacity,
+            sample: Vec::with_capacity(capacity),
+            count: 0,
+            rng,
+        }
+    }
+
+    /// Adds a single value.
+    pub fn push(&mut self, value: A) {
+        self.count += 1;
+        if self.sample.len() < self.capacity {
+            self.sample.push(value);
+        } else {
+            // The new value is kept with probability `capacity / count`,
+            // in place of a uniformly chosen value of the sample.
+            let j = self.rng.gen_range(0, self.count);
+            if j < self.capacity {
+                self.sample[j] = value;
+            }
+        }
+    }
+
+    /// Adds all the values summarized by `other`, so that the sample is a
+    /// uniform sample of the union of the two sequences.
+    ///
+    /// The capacity of `self` is kept.
+    pub fn merge(&mut self, other: &Self)
+    where
+        A: Clone,
+    {
+        let mut own = self.sample.split_off(0);
+        let mut others = other.sample.clone();
+        let (mut own_remaining, mut others_remaining) = (self.count, other.count);
+        let size = self.capacity.min(own.len() + others.len());
+        // Both samples are uniform: drawing from them in random order, and
+        // choosing the source with probability proportional to the number of
+        // values it summarizes that haven't been drawn yet, samples the union
+        // without replacement. A source whose sample is exhausted (because its
+        // capacity is smaller than the number of values drawn from it) can't
+        // be chosen anymore.
+        for _ in 0..size {
+            let from_own = if own.is_empty() {
+                false
+            } else if others.is_empty() {
+                true
+            } else {
+                // Both counts are at least the (non-zero) lengths of the
+                // samples.
+                self.rng.gen_range(0, own_remaining + others_remaining) < own_remaining
+            };
+            let (source, remaining) = if from_own {
+                (&mut own, &mut own_remaining)
+            } else {
+                (&mut others, &mut others_remaining)
+            };
+            let i = self.rng.gen_range(0, source.len());
+            self.sample.push(source.swap_remove(i));
+            *remaining -= 1;
+        }
+        self.count += other.count;
+    }
+
+    /// Returns the number of values that have been added.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the maximum number of values kept in the sample.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the current sample, in no particular order.
+    pub fn sample(&self) -> ArrayView1<'_, A> {
+        aview1(&self.sample)
+    }
+}
+
+impl<A> Extend<A> for ReservoirSampler<A> {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value)
+        }
+    }
+}
+
+impl<'a, A: Clone + 'a> Extend<&'a A> for ReservoirSampler<A> {
+    fn extend<I: IntoIterator<Item = &'a A>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn sample_is_a_subset_of_the_values(xs: Vec<i32>, capacity: usize, seed: u64) -> bool {
+        let capacity = capacity % 20;
+        let mut sampler = ReservoirSampler::with_seed(capacity, seed);
+        sampler.extend(&xs);
+        sampler.count() == xs.len()
+            && sampler.sample().len() == capacity.min(xs.len())
+            && sampler.sample().iter().all(|x| xs.contains(x))
+    }
+
+    #[test]
+    fn test_sample_is_uniform() {
+        // Each of the 10 values should be kept in a sample of size 3 with
+        // probability 3/10.
+        let mut kept = [0usize; 10];
+        let n_trials = 10_000;
+        for seed in 0..n_trials {
+            let mut sampler = ReservoirSampler::with_seed(3, seed);
+            sampler.extend(0..10);
+            for &x in sampler.sample() {
+                kept[x] += 1;
+            }
+        }
+        for &k in &kept {
+            assert!((k as f64 / n_trials as f64 - 0.3).abs() < 0.025);
+        }
+    }
+
+    #[test]
+    fn test_merge_uneven_capacities() {
+        // The other sampler summarizes many more values than it holds.
+        let mut first = ReservoirSampler::with_seed(10, 0);
+        first.extend(0..3);
+        let mut second = ReservoirSampler::with_seed(5, 1);
+        second.extend(3..1003);
+        for seed in 0..100 {
+            let mut merged = first.clone();
+            merged.rng = StdRng::seed_from_u64(seed);
+            merged.merge(&second);
+            assert_eq!(merged.count(), 1003);
+            assert_eq!(merged.sample().len(), 8);
+            assert!(merged
+                .sample()
+                .iter()
+                .all(|x| first.sample.contains(x) || second.sample.contains(x)));
+        }
+        // And the other way around.
+        let mut merged = second.clone();
+        merged.merge(&first);
+        assert_eq!(merged.count(), 1003);
+        assert_eq!(merged.sample().len(), 5);
+    }
+
+    #[test]
+    fn test_merge_is_uniform() {
+        // Merging a sampler of 2 values with a sampler of 8 values: each of
+        // the 10 values should be kept with probability 4/10.
+        let mut kept = [0usize; 10];
+        let n_trials = 10_000;
+        for seed in 0..n_trials {
+            let mut first = ReservoirSampler::with_seed(4, 2 * seed);
+            first.extend(0..2);
+            let mut second = ReservoirSampler::with_seed(4, 2 * seed + 1);
+            second.extend(2..10);
+            first.merge(&second);
+            assert_eq!(first.count(), 10);
+            assert_eq!(first.sample().len(), 4);
+            for &x in first.sample() {
+                kept[x] += 1;
+            }
+        }
+        for &k in &kept {
+            assert!((k as f64 / n_trials as f64 - 0.4).abs() < 0.025);
+        }
+    }
+}
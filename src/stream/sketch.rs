@@ -0,0 +1,312 @@
+use crate::errors::QuantileError;
+use crate::quantile::check_quantile;
+use core::cmp::Ordering;
+use core::f64::consts::PI;
+use core::marker::PhantomData;
+use num_traits::Float;
+#[cfg(feature = "serde-1")]
+use serde::{Deserialize, Serialize};
+
+/// Default compression parameter of a `QuantileSketch`.
+const DEFAULT_COMPRESSION: f64 = 100.;
+
+/// A cluster of values, summarized by their mean and their number.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Streaming accumulator for approximate quantiles, based on the merging
+/// [t-digest] of Dunning and Ertl.
+///
+/// The values are summarized by a bounded number of clusters (centroids),
+/// which are kept small near the tails of the distribution: the error of the
+/// estimated quantiles is smallest for `q` close to `0.` or `1.`. The memory
+/// footprint is controlled by the compression parameter `δ` (roughly `δ`
+/// centroids are kept); larger values give more accurate estimates.
+///
+/// NaN values are ignored.
+///
+/// # Example
+///
+/// ```
+/// use ndarray::Array;
+/// use ndarray_stats::stream::QuantileSketch;
+/// use noisy_float::types::n64;
+///
+/// let mut sketch = QuantileSketch::new();
+/// for chunk in Array::range(0., 10_000., 1.).exact_chunks(1000) {
+///     sketch.extend(&chunk);
+/// }
+/// let median: f64 = sketch.quantile(n64(0.5)).unwrap();
+/// assert!((median - 5000.).abs() < 50.);
+/// assert_eq!(sketch.quantile(n64(1.)).unwrap(), 9999.);
+/// ```
+///
+/// [t-digest]: https://arxiv.org/abs/1902.04023
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+pub struct QuantileSketch<A> {
+    compression: f64,
+    /// Compressed centroids, sorted by mean.
+    centroids: Vec<Centroid>,
+    /// Centroids that haven't been compressed yet, in arbitrary order.
+    buffer: Vec<Centroid>,
+    count: usize,
+    min: f64,
+    max: f64,
+    elem: PhantomData<A>,
+}
+
+impl<A: Float> QuantileSketch<A> {
+    /// Creates an empty sketch with the default compression parameter
+    /// (`δ = 100`).
+    pub fn new() -> Self {
+        Self::with_compression(DEFAULT_COMPRESSION)
+    }
+
+    /// Creates an empty sketch with compression parameter `compression`.
+    ///
+    /// **Panics** if `compression` is smaller than `1.` (or NaN).
+    pub fn with_compression(compression: f64) -> Self {
+        assert!(
+            compression >= 1.,
+            "The compression parameter must be greater than or equal to 1."
+        );
+        QuantileSketch {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            count: 0,
+            min: core::f64::INFINITY,
+            max: core::f64::NEG_INFINITY,
+            elem: PhantomData,
+        }
+    }
+
+    /// Adds a single value.
+    ///
+    /// **Panics** if the value can't be converted to `f64`.
+    pub fn push(&mut self, value: A) {
+        let value = value
+            .to_f64()
+            .expect("Converting the value to `f64` must not fail.");
+        if value.is_nan() {
+            return;
+        }
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.count += 1;
+        self.buffer.push(Centroid {
+            mean: value,
+            weight: 1.,
+        });
+        if self.buffer.len() >= self.buffer_capacity() {
+            self.compress();
+        }
+    }
+
+    /// Adds all the values summarized by `other`.
+    ///
+    /// The compression parameter of `self` is kept.
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.count += other.count;
+        self.buffer.extend(&other.centroids);
+        self.buffer.extend(&other.buffer);
+        if self.buffer.len() >= self.buffer_capacity() {
+            self.compress();
+        }
+    }
+
+    /// Returns the number of (non-NaN) values that have been added.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns an estimate of the `q`th quantile of the values.
+    ///
+    /// The estimate for `q = 0.` (resp. `q = 1.`) is exactly the minimum
+    /// (resp. maximum) of the values.
+    ///
+    /// Returns `Err(EmptyInput)` if no value has been added.
+    ///
+    /// Returns `Err(InvalidQuantile(q))` if `q` is NaN or not between `0.`
+    /// and `1.` (inclusive).
+    ///
+    /// **Panics** if the estimate can't be converted from `f64` to `A`.
+    pub fn quantile<Q: Float>(&self, q: Q) -> Result<A, QuantileError> {
+        let q = check_quantile(q)?;
+        if self.count == 0 {
+            return Err(QuantileError::EmptyInput);
+        }
+        let estimate = if self.buffer.is_empty() {
+            self.estimate_quantile(&self.centroids, q.raw())
+        } else {
+            let mut compressed = self.clone();
+            compressed.compress();
+            self.estimate_quantile(&compressed.centroids, q.raw())
+        };
+        Ok(A::from(estimate).expect("Converting the estimate to `A` must not fail."))
+    }
+
+    fn buffer_capacity(&self) -> usize {
+        (5. * self.compression) as usize
+    }
+
+    /// Scale function mapping a quantile to its "index" in the digest.
+    fn k(&self, q: f64) -> f64 {
+        self.compression / (2. * PI) * (2. * q - 1.).asin()
+    }
+
+    /// Inverse of the scale function `k`.
+    fn k_inv(&self, k: f64) -> f64 {
+        let angle = (2. * PI * k / self.compression).min(PI / 2.);
+        (angle.sin() + 1.) / 2.
+    }
+
+    /// Merges the buffered centroids into the compressed ones.
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut centroids = self.buffer.split_off(0);
+        centroids.append(&mut self.centroids);
+        // NaN values are never added, so the means are comparable.
+        centroids.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap_or(Ordering::Equal));
+        let total_weight: f64 = centroids.iter().map(|c| c.weight).sum();
+
+        let mut merged = Vec::with_capacity(self.compression as usize);
+        let mut current = centroids[0];
+        let mut weight_so_far = 0.;
+        let mut q_limit = self.k_inv(self.k(0.) + 1.);
+        for &centroid in &centroids[1..] {
+            let q = (weight_so_far + current.weight + centroid.weight) / total_weight;
+            if q <= q_limit {
+                current.weight += centroid.weight;
+                current.mean += (centroid.mean - current.mean) * centroid.weight / current.weight;
+            } else {
+                weight_so_far += current.weight;
+                merged.push(current);
+                q_limit = self.k_inv(self.k(weight_so_far / total_weight) + 1.);
+                current = centroid;
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// Estimates the `q`th quantile by interpolating linearly between the
+    /// centers of the centroids (and the minimum/maximum at the tails).
+    fn estimate_quantile(&self, centroids: &[Centroid], q: f64) -> f64 {
+        let total_weight: f64 = centroids.iter().map(|c| c.weight).sum();
+        let target = q * total_weight;
+        let first = centroids[0];
+        let last = centroids[centroids.len() - 1];
+        if target <= first.weight / 2. {
+            return self.min + (first.mean - self.min) * target / (first.weight / 2.);
+        }
+        if target >= total_weight - last.weight / 2. {
+            return self.max
+                - (self.max - last.mean) * (total_weight - target) / (last.weight / 2.);
+        }
+        let mut center = first.weight / 2.;
+        for pair in centroids.windows(2) {
+            let distance = (pair[0].weight + pair[1].weight) / 2.;
+            if target <= center + distance {
+                return pair[0].mean + (pair[1].mean - pair[0].mean) * (target - center) / distance;
+            }
+            center += distance;
+        }
+        last.mean
+    }
+}
+
+impl<A: Float> Default for QuantileSketch<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Float> Extend<A> for QuantileSketch<A> {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value)
+        }
+    }
+}
+
+impl<'a, A: Float + 'a> Extend<&'a A> for QuantileSketch<A> {
+    fn extend<I: IntoIterator<Item = &'a A>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpolate::Linear;
+    use crate::Quantile1dExt;
+    use ndarray::Array1;
+    use ndarray_rand::RandomExt;
+    use noisy_float::types::n64;
+    use rand::distributions::Uniform;
+    use std::f64;
+
+    #[test]
+    fn test_errors() {
+        let mut sketch = QuantileSketch::<f64>::new();
+        assert_eq!(sketch.quantile(n64(0.5)), Err(QuantileError::EmptyInput));
+        sketch.push(f64::NAN);
+        assert_eq!(sketch.count(), 0);
+        assert_eq!(sketch.quantile(n64(0.5)), Err(QuantileError::EmptyInput));
+        sketch.push(1.);
+        assert_eq!(
+            sketch.quantile(n64(1.5)),
+            Err(QuantileError::InvalidQuantile(1.5))
+        );
+        assert_eq!(sketch.quantile(n64(0.3)), Ok(1.));
+    }
+
+    #[test]
+    fn test_accuracy_against_exact_quantiles() {
+        let data = Array1::random(100_000, Uniform::new(-1., 1.));
+        let mut sketch = QuantileSketch::new();
+        sketch.extend(&data);
+        assert!(sketch.centroids.len() + sketch.buffer.len() < 1000);
+        for &q in &[0., 0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999, 1.] {
+            let exact = data.mapv(n64).quantile_mut(n64(q), &Linear).unwrap().raw();
+            let estimate = sketch.quantile(n64(q)).unwrap();
+            assert!(
+                (estimate - exact).abs() < 0.01,
+                "q = {}: estimated {}, exact {}",
+                q,
+                estimate,
+                exact
+            );
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let data = Array1::random(50_000, Uniform::new(0., 100.));
+        let mut merged = QuantileSketch::new();
+        for chunk in data.exact_chunks(5_000) {
+            let mut partial = QuantileSketch::new();
+            partial.extend(&chunk);
+            merged.merge(&partial);
+        }
+        assert_eq!(merged.count(), 50_000);
+        for &q in &[0., 0.05, 0.5, 0.95, 1.] {
+            let exact = data.mapv(n64).quantile_mut(n64(q), &Linear).unwrap().raw();
+            let estimate = merged.quantile(n64(q)).unwrap();
+            assert!((estimate - exact).abs() < 0.5);
+        }
+    }
+}
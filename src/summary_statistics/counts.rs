@@ -0,0 +1,57 @@
+//! Frequencies of the distinct values of discrete data.
+use core::hash::Hash;
+use indexmap::IndexMap;
+
+/// Returns the distinct values yielded by `elements` with the number of times
+/// each one occurs, ordered by decreasing count and, among values with the
+/// same count, by increasing value.
+pub(super) fn value_counts<'a, A, I>(elements: I) -> IndexMap<A, usize>
+where
+    A: 'a + Clone + Hash + Ord,
+    I: IntoIterator<Item = &'a A>,
+{
+    let mut counts: IndexMap<&A, usize> = IndexMap::new();
+    for elem in elements {
+        *counts.entry(elem).or_insert(0) += 1;
+    }
+    let mut counts: Vec<(&A, usize)> = counts.into_iter().collect();
+    counts.sort_unstable_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    counts
+        .into_iter()
+        .map(|(elem, count)| (elem.clone(), count))
+        .collect()
+}
+
+/// Returns the most frequent value yielded by `elements`, the smallest one if
+/// several values are the most frequent, or `None` if there are no elements.
+pub(super) fn mode<'a, A, I>(elements: I) -> Option<A>
+where
+    A: 'a + Clone + Hash + Ord,
+    I: IntoIterator<Item = &'a A>,
+{
+    let mut counts: IndexMap<&A, usize> = IndexMap::new();
+    for elem in elements {
+        *counts.entry(elem).or_insert(0) += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+        .map(|(elem, _)| elem.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn mode_is_the_first_value_count(xs: Vec<u8>) -> bool {
+        let counts = value_counts(&xs);
+        counts.values().sum::<usize>() == xs.len()
+            && counts
+                .values()
+                .zip(counts.values().skip(1))
+                .all(|(a, b)| a >= b)
+            && mode(&xs) == counts.keys().next().cloned()
+    }
+}
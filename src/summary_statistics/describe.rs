@@ -0,0 +1,219 @@
+//! A summary of the distribution of the elements of an array, in the spirit
+//! of pandas' `describe`.
+use crate::sort::select_nth_by;
+use crate::stream::MeanVar;
+use core::cmp::Ordering;
+use ndarray::{Array, ArrayBase, Axis, Data, RemoveAxis};
+use num_traits::{Float, FromPrimitive};
+#[cfg(feature = "serde-1")]
+use serde::{Deserialize, Serialize};
+
+/// Descriptive statistics of a collection of floating point values, as
+/// returned by [`SummaryStatisticsExt::describe`].
+///
+/// NaN values are counted in `nan_count` and excluded from all the other
+/// statistics. If there are no other values, all statistics but the counts
+/// are NaN; `std` is also NaN if there is a single value.
+///
+/// [`SummaryStatisticsExt::describe`]: trait.SummaryStatisticsExt.html#tymethod.describe
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+pub struct SummaryReport<A> {
+    /// The number of values that are not NaN.
+    pub count: usize,
+    /// The number of NaN values.
+    pub nan_count: usize,
+    /// The arithmetic mean.
+    pub mean: A,
+    /// The sample standard deviation, with `ddof = 1`.
+    pub std: A,
+    /// The minimum.
+    pub min: A,
+    /// The first quartile, i.e. the 0.25-quantile.
+    pub lower_quartile: A,
+    /// The median, i.e. the 0.5-quantile.
+    pub median: A,
+    /// The third quartile, i.e. the 0.75-quantile.
+    pub upper_quartile: A,
+    /// The maximum.
+    pub max: A,
+}
+
+/// Returns the summary report of `elements`.
+pub(super) fn describe<'a, A, I>(elements: I) -> SummaryReport<A>
+where
+    A: 'a + Float + FromPrimitive,
+    I: IntoIterator<Item = &'a A>,
+{
+    let mut nan_count = 0;
+    let mut mean_var = MeanVar::new();
+    let mut xs = Vec::new();
+    for &x in elements {
+        if x.is_nan() {
+            nan_count += 1;
+        } else {
+            mean_var.push(x);
+            xs.push(x);
+        }
+    }
+    let count = xs.len();
+    if count == 0 {
+        let nan = A::nan();
+        return SummaryReport {
+            count,
+            nan_count,
+            mean: nan,
+            std: nan,
+            min: nan,
+            lower_quartile: nan,
+            median: nan,
+            upper_quartile: nan,
+            max: nan,
+        };
+    }
+
+    // The quartiles are linearly interpolated between the order statistics
+    // around `q (n - 1)`, as in `numpy.quantile`. All the order statistics
+    // are selected together, each selection only scanning the part of `xs`
+    // left by the previous ones.
+    let positions: Vec<(usize, A)> = [0.25, 0.5, 0.75]
+        .iter()
+        .map(|&q| {
+            let position = q * (count - 1) as f64;
+            let lower = position.floor();
+            (
+                lower as usize,
+                A::from_f64(position - lower).expect("Converting a fraction to `A` must not fail."),
+            )
+        })
+        .collect();
+    let mut indexes: Vec<usize> = vec![0, count - 1];
+    for &(lower, _) in &positions {
+        indexes.push(lower);
+        indexes.push((lower + 1).min(count - 1));
+    }
+    indexes.sort_unstable();
+    indexes.dedup();
+    let mut order_statistics = vec![A::nan(); count];
+    select_many(&mut xs, 0, &indexes, &mut order_statistics);
+    let quartiles: Vec<A> = positions
+        .iter()
+        .map(|&(lower, fraction)| {
+            let below = order_statistics[lower];
+            if fraction == A::zero() {
+                below
+            } else {
+                below + fraction * (order_statistics[lower + 1] - below)
+            }
+        })
+        .collect();
+
+    SummaryReport {
+        count,
+        nan_count,
+        mean: mean_var.mean().unwrap(),
+        std: if count > 1 {
+            mean_var.std(A::one()).unwrap()
+        } else {
+            A::nan()
+        },
+        min: order_statistics[0],
+        lower_quartile: quartiles[0],
+        median: quartiles[1],
+        upper_quartile: quartiles[2],
+        max: order_statistics[count - 1],
+    }
+}
+
+/// Writes into `out[i]` the value that would be at index `i` of `xs` if it
+/// were sorted, for every `i` in `indexes`; `xs` is reordered and `offset` is
+/// the index of its first element in the whole slice.
+///
+/// `indexes` must be sorted and unique; selecting the middle one first splits
+/// `xs` for the selection of the other ones.
+fn select_many<A: Float>(xs: &mut [A], offset: usize, indexes: &[usize], out: &mut [A]) {
+    if indexes.is_empty() {
+        return;
+    }
+    let middle = indexes.len() / 2;
+    let index = indexes[middle];
+    let (lower, &mut value, upper) = select_nth_by(xs, index - offset, |a, b| {
+        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+    });
+    out[index] = value;
+    select_many(lower, offset, &indexes[..middle], out);
+    select_many(upper, index + 1, &indexes[middle + 1..], out);
+}
+
+/// Returns the summary report of each lane of `a` along `axis`.
+pub(super) fn describe_axis<A, S, D>(
+    a: &ArrayBase<S, D>,
+    axis: Axis,
+) -> Array<SummaryReport<A>, D::Smaller>
+where
+    A: Float + FromPrimitive,
+    S: Data<Elem = A>,
+    D: RemoveAxis,
+{
+    if a.len_of(axis) == 0 {
+        return Array::from_elem(a.raw_dim().remove_axis(axis), describe(&[]));
+    }
+    a.map_axis(axis, |lane| describe(lane))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interpolate::Linear;
+    use crate::Quantile1dExt;
+    use ndarray::Array1;
+    use noisy_float::types::n64;
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[quickcheck]
+    fn quartiles_match_quantiles(xs: Vec<i16>) -> bool {
+        if xs.is_empty() {
+            return true;
+        }
+        let report = describe(&xs.iter().map(|&x| f64::from(x)).collect::<Vec<_>>());
+        let mut sorted: Array1<_> = xs.iter().map(|&x| n64(f64::from(x))).collect();
+        let quantiles = sorted
+            .quantiles_mut(
+                &ndarray::array![n64(0.), n64(0.25), n64(0.5), n64(0.75), n64(1.)],
+                &Linear,
+            )
+            .unwrap()
+            .mapv(f64::from);
+        let found = [
+            report.min,
+            report.lower_quartile,
+            report.median,
+            report.upper_quartile,
+            report.max,
+        ];
+        quantiles
+            .iter()
+            .zip(found.iter())
+            .all(|(expected, found)| (expected - found).abs() <= 1e-9 * expected.abs().max(1.))
+    }
+
+    #[test]
+    fn test_describe_few_values() {
+        let report = describe(&[f64::NAN, 2., f64::NAN]);
+        assert_eq!((report.count, report.nan_count), (1, 2));
+        assert!(report.std.is_nan());
+        assert_eq!(
+            [report.mean, report.min, report.median, report.max],
+            [2.; 4]
+        );
+
+        let report = describe(&[f64::NAN]);
+        assert_eq!((report.count, report.nan_count), (0, 1));
+        assert!(report.mean.is_nan() && report.median.is_nan() && report.max.is_nan());
+
+        let reports = describe_axis(&Array1::<f64>::zeros(0).insert_axis(Axis(0)), Axis(1));
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].count, 0);
+    }
+}
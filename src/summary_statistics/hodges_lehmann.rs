@@ -0,0 +1,210 @@
+//! Selection of order statistics of pairwise sums and differences, without
+//! materializing all the pairs.
+use num_traits::{Float, FromPrimitive};
+
+/// Returns the Hodges-Lehmann estimate of `xs`: the median of the Walsh
+/// averages `(xᵢ + xⱼ) / 2` for `i ≤ j`. `xs` must not be empty.
+///
+/// NaN values propagate to the result.
+pub(crate) fn hodges_lehmann<A>(mut xs: Vec<A>) -> A
+where
+    A: Float + FromPrimitive,
+{
+    if !sort_without_nan(&mut xs) {
+        return A::nan();
+    }
+    let n = xs.len();
+    let two = A::one() + A::one();
+    let matrix = SortedRows {
+        n_rows: n,
+        n_cols: n,
+        first_col: |i| i,
+        value: |i, j| xs[i] + xs[j],
+    };
+    matrix.median(n * (n + 1) / 2) / two
+}
+
+/// Returns the median of the differences `xᵢ - yⱼ` over all pairs. Neither
+/// `xs` nor `ys` must be empty.
+///
+/// NaN values propagate to the result.
+pub(crate) fn hodges_lehmann_shift<A>(mut xs: Vec<A>, mut ys: Vec<A>) -> A
+where
+    A: Float + FromPrimitive,
+{
+    if !sort_without_nan(&mut xs) || !sort_without_nan(&mut ys) {
+        return A::nan();
+    }
+    // Rows are indexed by `y` in decreasing order, so that the differences
+    // increase along each row.
+    let m = ys.len();
+    let matrix = SortedRows {
+        n_rows: m,
+        n_cols: xs.len(),
+        first_col: |_| 0,
+        value: |i, j| xs[j] - ys[m - 1 - i],
+    };
+    matrix.median(xs.len() * m)
+}
+
+fn sort_without_nan<A: Float>(xs: &mut [A]) -> bool {
+    if xs.iter().any(|x| x.is_nan()) {
+        return false;
+    }
+    xs.sort_unstable_by(|a, b| a.partial_cmp(b).expect("NaN values have been excluded."));
+    true
+}
+
+/// A matrix whose row `i` holds the values `value(i, j)` for `j` in
+/// `first_col(i)..n_cols`, in nondecreasing order.
+struct SortedRows<F, V> {
+    n_rows: usize,
+    n_cols: usize,
+    first_col: F,
+    value: V,
+}
+
+impl<A, F, V> SortedRows<F, V>
+where
+    A: Float + FromPrimitive,
+    F: Fn(usize) -> usize,
+    V: Fn(usize, usize) -> A,
+{
+    /// Returns the median of the `len` elements of the matrix.
+    fn median(&self, len: usize) -> A {
+        if len % 2 == 1 {
+            self.select(len / 2)
+        } else {
+            let two = A::one() + A::one();
+            (self.select(len / 2 - 1) + self.select(len / 2)) / two
+        }
+    }
+
+    /// Returns the `k`th smallest element of the matrix (starting from 0).
+    ///
+    /// Each row keeps a range of candidate columns. At each step, the pivot
+    /// is the weighted median of the medians of the candidate ranges, so at
+    /// least a quarter of the candidates are discarded, and the ranks of
+    /// the pivot are counted with a binary search in each row: this takes
+    /// `O(r log(r c))` time per step and `O(log(r c))` steps for `r` rows and
+    /// `c` columns.
+    fn select(&self, k: usize) -> A {
+        let mut lo: Vec<usize> = (0..self.n_rows).map(|i| (self.first_col)(i)).collect();
+        let mut hi: Vec<usize> = vec![self.n_cols; self.n_rows];
+        // Number of elements known to be smaller than the `k`th one.
+        let mut below = 0;
+        loop {
+            let n_candidates: usize = lo.iter().zip(&hi).map(|(&l, &h)| h - l).sum();
+            debug_assert!(k - below < n_candidates);
+            if n_candidates <= self.n_rows {
+                let mut candidates: Vec<A> = (0..self.n_rows)
+                    .flat_map(|i| (lo[i]..hi[i]).map(move |j| (i, j)))
+                    .map(|(i, j)| (self.value)(i, j))
+                    .collect();
+                candidates
+                    .sort_unstable_by(|a, b| a.partial_cmp(b).expect("Values must not be NaN."));
+                return candidates[k - below];
+            }
+            let pivot = self.pivot(&lo, &hi, n_candidates);
+            // Candidates smaller than the pivot are in `lo[i]..less[i]`, and
+            // candidates equal to it in `less[i]..not_greater[i]`.
+            let less: Vec<usize> = (0..self.n_rows)
+                .map(|i| self.partition_point(i, lo[i], hi[i], |x| x < pivot))
+                .collect();
+            let not_greater: Vec<usize> = (0..self.n_rows)
+                .map(|i| self.partition_point(i, less[i], hi[i], |x| x <= pivot))
+                .collect();
+            let n_less: usize = less.iter().zip(&lo).map(|(&m, &l)| m - l).sum();
+            let n_not_greater: usize = not_greater.iter().zip(&lo).map(|(&m, &l)| m - l).sum();
+            if k < below + n_less {
+                hi = less;
+            } else if k < below + n_not_greater {
+                return pivot;
+            } else {
+                below += n_not_greater;
+                lo = not_greater;
+            }
+        }
+    }
+
+    /// Returns the weighted median of the medians of the candidate ranges,
+    /// weighted by their lengths.
+    fn pivot(&self, lo: &[usize], hi: &[usize], n_candidates: usize) -> A {
+        let mut medians: Vec<(A, usize)> = (0..self.n_rows)
+            .filter(|&i| hi[i] > lo[i])
+            .map(|i| ((self.value)(i, (lo[i] + hi[i]) / 2), hi[i] - lo[i]))
+            .collect();
+        medians.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).expect("Values must not be NaN."));
+        let mut weight = 0;
+        for (median, len) in medians {
+            weight += len;
+            if 2 * weight >= n_candidates {
+                return median;
+            }
+        }
+        unreachable!("The weights sum to the number of candidates.")
+    }
+
+    /// Returns the first column `j` in `lo..hi` of row `i` such that
+    /// `predicate(value(i, j))` is false, assuming that the predicate is true
+    /// and then false along the row.
+    fn partition_point<P>(&self, i: usize, mut lo: usize, mut hi: usize, predicate: P) -> usize
+    where
+        P: Fn(A) -> bool,
+    {
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if predicate((self.value)(i, mid)) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck_macros::quickcheck;
+
+    fn naive_median(mut values: Vec<f64>) -> f64 {
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = values.len();
+        if n % 2 == 1 {
+            values[n / 2]
+        } else {
+            (values[n / 2 - 1] + values[n / 2]) / 2.
+        }
+    }
+
+    #[quickcheck]
+    fn hodges_lehmann_matches_naive(xs: Vec<i16>) -> bool {
+        if xs.is_empty() {
+            return true;
+        }
+        let xs: Vec<f64> = xs.into_iter().map(f64::from).collect();
+        let mut walsh_averages = Vec::new();
+        for i in 0..xs.len() {
+            for j in i..xs.len() {
+                walsh_averages.push((xs[i] + xs[j]) / 2.);
+            }
+        }
+        hodges_lehmann(xs) == naive_median(walsh_averages)
+    }
+
+    #[quickcheck]
+    fn shift_matches_naive(xs: Vec<i8>, ys: Vec<i8>) -> bool {
+        if xs.is_empty() || ys.is_empty() {
+            return true;
+        }
+        let xs: Vec<f64> = xs.into_iter().map(f64::from).collect();
+        let ys: Vec<f64> = ys.into_iter().map(f64::from).collect();
+        let differences = xs
+            .iter()
+            .flat_map(|x| ys.iter().map(move |y| x - y))
+            .collect();
+        hodges_lehmann_shift(xs, ys) == naive_median(differences)
+    }
+}
@@ -1,9 +1,26 @@
-use super::SummaryStatisticsExt;
-use crate::errors::EmptyInput;
-use ndarray::{ArrayBase, Data, Dimension};
+use super::counts;
+use super::describe;
+use super::hodges_lehmann::{hodges_lehmann, hodges_lehmann_shift};
+use super::robust;
+use super::{SummaryReport, SummaryStatisticsExt, VarianceAlgorithm};
+use crate::errors::{
+    EmptyInput, MultiInputError, NanPolicyError, ShapeMismatch, SkipNanError, StatsError,
+    WeightedStatsError,
+};
+use crate::kernels::{lanes_are_contiguous, sum_by};
+#[cfg(feature = "rayon")]
+use crate::parallel::par_map_lanes;
+use crate::stream::{ExactSum, MeanVar};
+use crate::{MaybeNan, MaybeNanExt, NanPolicy};
+use core::hash::Hash;
+use core::ops::{Add, Div};
+use indexmap::IndexMap;
+use ndarray::{
+    Array, Array1, ArrayBase, ArrayView, ArrayViewD, Axis, Data, DataMut, Dimension, RemoveAxis,
+    Zip,
+};
 use num_integer::IterBinomial;
 use num_traits::{Float, FromPrimitive, Zero};
-use std::ops::{Add, Div};
 
 impl<A, S, D> SummaryStatisticsExt<A, S, D> for ArrayBase<S, D>
 where
@@ -38,6 +55,31 @@ where
         self.map(|x| x.ln()).mean().map(|x| x.exp())
     }
 
+    fn hodges_lehmann(&self) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        if self.is_empty() {
+            return Err(EmptyInput);
+        }
+        Ok(hodges_lehmann(self.iter().cloned().collect()))
+    }
+
+    fn hodges_lehmann_shift<S2, E>(&self, other: &ArrayBase<S2, E>) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = A>,
+        E: Dimension,
+    {
+        if self.is_empty() || other.is_empty() {
+            return Err(EmptyInput);
+        }
+        Ok(hodges_lehmann_shift(
+            self.iter().cloned().collect(),
+            other.iter().cloned().collect(),
+        ))
+    }
+
     fn kurtosis(&self) -> Result<A, EmptyInput>
     where
         A: Float + FromPrimitive,
@@ -46,82 +88,934 @@ where
         Ok(central_moments[4] / central_moments[2].powi(2))
     }
 
-    fn skewness(&self) -> Result<A, EmptyInput>
-    where
-        A: Float + FromPrimitive,
-    {
-        let central_moments = self.central_moments(3)?;
-        Ok(central_moments[3] / central_moments[2].sqrt().powi(3))
+    fn skewness(&self) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        let central_moments = self.central_moments(3)?;
+        Ok(central_moments[3] / central_moments[2].sqrt().powi(3))
+    }
+
+    fn central_moment(&self, order: u16) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        if self.is_empty() {
+            return Err(EmptyInput);
+        }
+        match order {
+            0 => Ok(A::one()),
+            1 => Ok(A::zero()),
+            n => {
+                let mean = self.mean().unwrap();
+                let shifted_moments = shifted_moments(self, n, mean);
+                let correction_term = -shifted_moments[1];
+
+                let coefficients = central_moment_coefficients(&shifted_moments);
+                Ok(horner_method(coefficients, correction_term))
+            }
+        }
+    }
+
+    fn central_moments(&self, order: u16) -> Result<Vec<A>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        if self.is_empty() {
+            return Err(EmptyInput);
+        }
+        match order {
+            0 => Ok(vec![A::one()]),
+            1 => Ok(vec![A::one(), A::zero()]),
+            n => {
+                // We only perform these operations once, and then reuse their
+                // result to compute all the required moments
+                let mean = self.mean().unwrap();
+                let shifted_moments = shifted_moments(self, n, mean);
+                let correction_term = -shifted_moments[1];
+
+                let mut central_moments = vec![A::one(), A::zero()];
+                for k in 2..=n {
+                    let coefficients =
+                        central_moment_coefficients(&shifted_moments[..=(k as usize)]);
+                    let central_moment = horner_method(coefficients, correction_term);
+                    central_moments.push(central_moment)
+                }
+                Ok(central_moments)
+            }
+        }
+    }
+
+    fn kurtosis_axis(
+        &self,
+        axis: Axis,
+        bias_correction: bool,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        if self.len_of(axis) == 0 {
+            return Err(EmptyInput);
+        }
+        Ok(map_lane_moments(self, axis, false, 4, |n, m| {
+            kurtosis_from_moments(n, m, bias_correction)
+        }))
+    }
+
+    fn kurtosis_axis_skipnan(&self, axis: Axis, bias_correction: bool) -> Array<A, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        map_lane_moments(self, axis, true, 4, |n, m| {
+            kurtosis_from_moments(n, m, bias_correction)
+        })
+    }
+
+    fn skewness_axis(
+        &self,
+        axis: Axis,
+        bias_correction: bool,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        if self.len_of(axis) == 0 {
+            return Err(EmptyInput);
+        }
+        Ok(map_lane_moments(self, axis, false, 3, |n, m| {
+            skewness_from_moments(n, m, bias_correction)
+        }))
+    }
+
+    fn skewness_axis_skipnan(&self, axis: Axis, bias_correction: bool) -> Array<A, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        map_lane_moments(self, axis, true, 3, |n, m| {
+            skewness_from_moments(n, m, bias_correction)
+        })
+    }
+
+    fn central_moment_axis(
+        &self,
+        order: u16,
+        axis: Axis,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        if self.len_of(axis) == 0 {
+            return Err(EmptyInput);
+        }
+        Ok(map_lane_moments(self, axis, false, order, |_, m| {
+            m[order as usize]
+        }))
+    }
+
+    fn central_moment_axis_skipnan(&self, order: u16, axis: Axis) -> Array<A, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        map_lane_moments(self, axis, true, order, |_, m| m[order as usize])
+    }
+
+    fn mean_with_policy(&self, policy: NanPolicy) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Clone + FromPrimitive + Add<Output = A> + Div<Output = A> + Zero,
+        A::NotNan: Clone,
+    {
+        with_policy(self, policy, |a| a.mean())
+    }
+
+    fn harmonic_mean_with_policy(&self, policy: NanPolicy) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Float + FromPrimitive,
+        A::NotNan: Clone,
+    {
+        with_policy(self, policy, |a| a.harmonic_mean())
+    }
+
+    fn geometric_mean_with_policy(&self, policy: NanPolicy) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Float + FromPrimitive,
+        A::NotNan: Clone,
+    {
+        with_policy(self, policy, |a| a.geometric_mean())
+    }
+
+    fn kurtosis_with_policy(&self, policy: NanPolicy) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Float + FromPrimitive,
+        A::NotNan: Clone,
+    {
+        with_policy(self, policy, |a| a.kurtosis())
+    }
+
+    fn skewness_with_policy(&self, policy: NanPolicy) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Float + FromPrimitive,
+        A::NotNan: Clone,
+    {
+        with_policy(self, policy, |a| a.skewness())
+    }
+
+    fn central_moment_with_policy(&self, order: u16, policy: NanPolicy) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Float + FromPrimitive,
+        A::NotNan: Clone,
+    {
+        with_policy(self, policy, |a| a.central_moment(order))
+    }
+
+    fn mean_axis_into<S2>(
+        &self,
+        axis: Axis,
+        out: &mut ArrayBase<S2, D::Smaller>,
+    ) -> Result<(), MultiInputError>
+    where
+        A: Clone + FromPrimitive + Add<Output = A> + Div<Output = A> + Zero,
+        D: RemoveAxis,
+        S2: DataMut<Elem = A>,
+    {
+        let axis_len = check_axis_output(self, axis, out)?;
+        let n = A::from_usize(axis_len).expect("Converting axis length to `A` must not fail.");
+        if lanes_are_contiguous(self, axis) {
+            Zip::from(out)
+                .and(self.lanes(axis))
+                .apply(|out, lane| *out = lane.sum() / n.clone());
+        } else {
+            // Summing strided lanes one at a time jumps around in memory:
+            // adding whole subviews instead reads the data in memory order.
+            out.fill(A::zero());
+            for subview in self.axis_iter(axis) {
+                Zip::from(&mut *out)
+                    .and(&subview)
+                    .apply(|out, x| *out = out.clone() + x.clone());
+            }
+            out.mapv_inplace(|sum| sum / n.clone());
+        }
+        Ok(())
+    }
+
+    fn var_axis_into<S2>(
+        &self,
+        axis: Axis,
+        ddof: A,
+        out: &mut ArrayBase<S2, D::Smaller>,
+    ) -> Result<(), MultiInputError>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+        S2: DataMut<Elem = A>,
+    {
+        let axis_len = check_axis_output(self, axis, out)?;
+        let n = A::from_usize(axis_len).expect("Converting axis length to `A` must not fail.");
+        assert!(
+            !(ddof < A::zero() || ddof >= n),
+            "`ddof` must be non-negative and less than the length of the axis."
+        );
+        let dof = n - ddof;
+        Zip::from(out).and(self.lanes(axis)).apply(|out, lane| {
+            let mean = sum_by(&lane, |&x| x) / n;
+            let sum_sq_dev = sum_by(&lane, |&x| {
+                let dev = x - mean;
+                dev * dev
+            });
+            *out = sum_sq_dev / dof;
+        });
+        Ok(())
+    }
+
+    fn try_mean_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, StatsError>
+    where
+        A: Clone + FromPrimitive + Add<Output = A> + Div<Output = A> + Zero,
+        D: RemoveAxis,
+    {
+        let mut out = Array::zeros(self.raw_dim().remove_axis(axis));
+        self.mean_axis_into(axis, &mut out)?;
+        Ok(out)
+    }
+
+    fn try_var_axis(&self, axis: Axis, ddof: A) -> Result<Array<A, D::Smaller>, StatsError>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        let mut out = Array::zeros(self.raw_dim().remove_axis(axis));
+        self.try_var_axis_into(axis, ddof, &mut out)?;
+        Ok(out)
+    }
+
+    fn try_std_axis(&self, axis: Axis, ddof: A) -> Result<Array<A, D::Smaller>, StatsError>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        Ok(self.try_var_axis(axis, ddof)?.mapv_into(A::sqrt))
+    }
+
+    fn try_var_axis_into<S2>(
+        &self,
+        axis: Axis,
+        ddof: A,
+        out: &mut ArrayBase<S2, D::Smaller>,
+    ) -> Result<(), StatsError>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+        S2: DataMut<Elem = A>,
+    {
+        let axis_len = check_axis_output(self, axis, out)?;
+        check_ddof(ddof, axis_len)?;
+        self.var_axis_into(axis, ddof, out)?;
+        Ok(())
+    }
+
+    fn mean_in<B>(&self) -> Result<B, EmptyInput>
+    where
+        A: Copy + Into<B>,
+        B: Float + FromPrimitive,
+    {
+        if self.is_empty() {
+            return Err(EmptyInput);
+        }
+        let n =
+            B::from_usize(self.len()).expect("Converting number of elements to `B` must not fail.");
+        Ok(sum_by(self, |&x| x.into()) / n)
+    }
+
+    fn mean_axis_in<B>(&self, axis: Axis) -> Result<Array<B, D::Smaller>, EmptyInput>
+    where
+        A: Copy + Into<B>,
+        B: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        let axis_len = self.len_of(axis);
+        if axis_len == 0 {
+            return Err(EmptyInput);
+        }
+        let n = B::from_usize(axis_len).expect("Converting axis length to `B` must not fail.");
+        Ok(self.map_axis(axis, |lane| sum_by(&lane, |&x| x.into()) / n))
+    }
+
+    fn var_axis_in<B>(&self, axis: Axis, ddof: B) -> Result<Array<B, D::Smaller>, EmptyInput>
+    where
+        A: Copy + Into<B>,
+        B: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        let axis_len = self.len_of(axis);
+        if axis_len == 0 {
+            return Err(EmptyInput);
+        }
+        let n = B::from_usize(axis_len).expect("Converting axis length to `B` must not fail.");
+        assert!(
+            !(ddof < B::zero() || ddof >= n),
+            "`ddof` must be non-negative and less than the length of the axis."
+        );
+        let dof = n - ddof;
+        Ok(self.map_axis(axis, |lane| {
+            let mean = sum_by(&lane, |&x| x.into()) / n;
+            let sum_sq_dev = sum_by(&lane, |&x| {
+                let dev = x.into() - mean;
+                dev * dev
+            });
+            sum_sq_dev / dof
+        }))
+    }
+
+    fn try_var_axis_in<B>(&self, axis: Axis, ddof: B) -> Result<Array<B, D::Smaller>, StatsError>
+    where
+        A: Copy + Into<B>,
+        B: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        check_ddof(ddof, non_empty_axis_len(self, axis)?)?;
+        Ok(self.var_axis_in(axis, ddof)?)
+    }
+
+    fn var_axis_with(
+        &self,
+        axis: Axis,
+        ddof: A,
+        algorithm: VarianceAlgorithm,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        let axis_len = self.len_of(axis);
+        if axis_len == 0 {
+            return Err(EmptyInput);
+        }
+        let n = A::from_usize(axis_len).expect("Converting axis length to `A` must not fail.");
+        assert!(
+            !(ddof < A::zero() || ddof >= n),
+            "`ddof` must be non-negative and less than the length of the axis."
+        );
+        let dof = n - ddof;
+        Ok(self.map_axis(axis, |lane| sum_sq_dev(&lane, algorithm) / dof))
+    }
+
+    fn try_var_axis_with(
+        &self,
+        axis: Axis,
+        ddof: A,
+        algorithm: VarianceAlgorithm,
+    ) -> Result<Array<A, D::Smaller>, StatsError>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        check_ddof(ddof, non_empty_axis_len(self, axis)?)?;
+        Ok(self.var_axis_with(axis, ddof, algorithm)?)
+    }
+
+    fn mean_deterministic(&self) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        if self.is_empty() {
+            return Err(EmptyInput);
+        }
+        let n =
+            A::from_usize(self.len()).expect("Converting number of elements to `A` must not fail.");
+        let mut sum = ExactSum::new();
+        sum.extend(self);
+        Ok(sum.sum() / n)
+    }
+
+    fn var_deterministic(&self, ddof: A) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        let mean = self.mean_deterministic()?;
+        let n =
+            A::from_usize(self.len()).expect("Converting number of elements to `A` must not fail.");
+        assert!(
+            !(ddof < A::zero() || ddof >= n),
+            "`ddof` must be non-negative and less than the number of elements."
+        );
+        let mut sum_sq_dev = ExactSum::new();
+        sum_sq_dev.extend(self.iter().map(|&x| (x - mean) * (x - mean)));
+        Ok(sum_sq_dev.sum() / (n - ddof))
+    }
+
+    fn try_var_deterministic(&self, ddof: A) -> Result<A, StatsError>
+    where
+        A: Float + FromPrimitive,
+    {
+        if self.is_empty() {
+            return Err(StatsError::EmptyInput);
+        }
+        check_ddof(ddof, self.len())?;
+        Ok(self.var_deterministic(ddof)?)
+    }
+
+    fn weighted_mean<S2, E>(&self, weights: &ArrayBase<S2, E>) -> Result<A, WeightedStatsError>
+    where
+        A: Float,
+        S2: Data<Elem = A>,
+        E: Dimension,
+    {
+        let weights = broadcast_weights(self, weights)?;
+        weighted_mean(self, &weights).map(|(mean, _)| mean)
+    }
+
+    fn weighted_var<S2, E>(
+        &self,
+        weights: &ArrayBase<S2, E>,
+        ddof: A,
+    ) -> Result<A, WeightedStatsError>
+    where
+        A: Float,
+        S2: Data<Elem = A>,
+        E: Dimension,
+    {
+        let weights = broadcast_weights(self, weights)?;
+        let (mean, sum_weights) = weighted_mean(self, &weights)?;
+        if !(ddof >= A::zero() && ddof < sum_weights) {
+            return Err(WeightedStatsError::InvalidDdof);
+        }
+        let mut sum_sq_dev = A::zero();
+        Zip::from(self).and(&weights).apply(|&x, &w| {
+            if !x.is_nan() {
+                sum_sq_dev = sum_sq_dev + w * (x - mean) * (x - mean);
+            }
+        });
+        Ok(sum_sq_dev / (sum_weights - ddof))
+    }
+
+    fn weighted_std<S2, E>(
+        &self,
+        weights: &ArrayBase<S2, E>,
+        ddof: A,
+    ) -> Result<A, WeightedStatsError>
+    where
+        A: Float,
+        S2: Data<Elem = A>,
+        E: Dimension,
+    {
+        self.weighted_var(weights, ddof).map(|var| var.sqrt())
+    }
+
+    fn weighted_sum_axis<S2, E>(
+        &self,
+        axis: Axis,
+        weights: &ArrayBase<S2, E>,
+    ) -> Result<Array<A, D::Smaller>, WeightedStatsError>
+    where
+        A: Float,
+        S2: Data<Elem = A>,
+        E: Dimension,
+        D: RemoveAxis,
+    {
+        let weights = broadcast_weights(self, weights)?;
+        let mut weighted = Array::zeros(self.raw_dim());
+        Zip::from(&mut weighted)
+            .and(self)
+            .and(&weights)
+            .apply(|out, &x, &w| {
+                if !x.is_nan() {
+                    *out = w * x;
+                }
+            });
+        Ok(weighted.sum_axis(axis))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_mean_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Clone + FromPrimitive + Add<Output = A> + Div<Output = A> + Zero + Send + Sync,
+        D: RemoveAxis,
+    {
+        if self.len_of(axis) == 0 {
+            return Err(EmptyInput);
+        }
+        Ok(par_map_lanes(self, axis, |lane| lane.mean().unwrap()))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_var_axis(&self, axis: Axis, ddof: A) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive + Send + Sync,
+        D: RemoveAxis,
+    {
+        let axis_len = self.len_of(axis);
+        if axis_len == 0 {
+            return Err(EmptyInput);
+        }
+        let n = A::from_usize(axis_len).expect("Converting axis length to `A` must not fail.");
+        assert!(
+            !(ddof < A::zero() || ddof >= n),
+            "`ddof` must be non-negative and less than the length of the axis."
+        );
+        let dof = n - ddof;
+        Ok(par_map_lanes(self, axis, |lane| {
+            let mean = lane.mean().unwrap();
+            let sum_sq_dev = sum_by(&lane, |&x| {
+                let dev = x - mean;
+                dev * dev
+            });
+            sum_sq_dev / dof
+        }))
+    }
+
+    #[cfg(feature = "rayon")]
+    fn try_par_var_axis(&self, axis: Axis, ddof: A) -> Result<Array<A, D::Smaller>, StatsError>
+    where
+        A: Float + FromPrimitive + Send + Sync,
+        D: RemoveAxis,
+    {
+        check_ddof(ddof, non_empty_axis_len(self, axis)?)?;
+        Ok(self.par_var_axis(axis, ddof)?)
+    }
+
+    fn median_abs_deviation(&self, scale: A) -> Result<A, EmptyInput>
+    where
+        A: Float,
+    {
+        if self.is_empty() {
+            return Err(EmptyInput);
+        }
+        Ok(robust::median_abs_deviation(
+            self.iter().cloned().collect(),
+            scale,
+        ))
+    }
+
+    fn median_abs_deviation_skipnan(&self, scale: A) -> Result<A, SkipNanError>
+    where
+        A: Float,
+    {
+        without_nan(self).map(|xs| robust::median_abs_deviation(xs, scale))
+    }
+
+    fn median_abs_deviation_axis(
+        &self,
+        axis: Axis,
+        scale: A,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float,
+        D: RemoveAxis,
+    {
+        if self.len_of(axis) == 0 {
+            return Err(EmptyInput);
+        }
+        Ok(robust::map_lanes(self, axis, false, |xs| {
+            robust::median_abs_deviation(xs, scale)
+        }))
+    }
+
+    fn median_abs_deviation_axis_skipnan(&self, axis: Axis, scale: A) -> Array<A, D::Smaller>
+    where
+        A: Float,
+        D: RemoveAxis,
+    {
+        robust::map_lanes(self, axis, true, |xs| {
+            robust::median_abs_deviation(xs, scale)
+        })
+    }
+
+    fn trimmed_mean(&self, fraction: A) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        if self.is_empty() {
+            return Err(EmptyInput);
+        }
+        Ok(robust::trimmed_mean(
+            self.iter().cloned().collect(),
+            fraction,
+        ))
+    }
+
+    fn trimmed_mean_skipnan(&self, fraction: A) -> Result<A, SkipNanError>
+    where
+        A: Float + FromPrimitive,
+    {
+        without_nan(self).map(|xs| robust::trimmed_mean(xs, fraction))
+    }
+
+    fn trimmed_mean_axis(&self, axis: Axis, fraction: A) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        if self.len_of(axis) == 0 {
+            return Err(EmptyInput);
+        }
+        Ok(robust::map_lanes(self, axis, false, |xs| {
+            robust::trimmed_mean(xs, fraction)
+        }))
+    }
+
+    fn trimmed_mean_axis_skipnan(&self, axis: Axis, fraction: A) -> Array<A, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        robust::map_lanes(self, axis, true, |xs| robust::trimmed_mean(xs, fraction))
+    }
+
+    fn winsorized_mean(&self, fraction: A) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+    {
+        if self.is_empty() {
+            return Err(EmptyInput);
+        }
+        Ok(robust::winsorized_mean(
+            self.iter().cloned().collect(),
+            fraction,
+        ))
+    }
+
+    fn winsorized_mean_skipnan(&self, fraction: A) -> Result<A, SkipNanError>
+    where
+        A: Float + FromPrimitive,
+    {
+        without_nan(self).map(|xs| robust::winsorized_mean(xs, fraction))
+    }
+
+    fn winsorized_mean_axis(
+        &self,
+        axis: Axis,
+        fraction: A,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        if self.len_of(axis) == 0 {
+            return Err(EmptyInput);
+        }
+        Ok(robust::map_lanes(self, axis, false, |xs| {
+            robust::winsorized_mean(xs, fraction)
+        }))
+    }
+
+    fn winsorized_mean_axis_skipnan(&self, axis: Axis, fraction: A) -> Array<A, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        robust::map_lanes(self, axis, true, |xs| robust::winsorized_mean(xs, fraction))
+    }
+
+    fn mode(&self) -> Result<A, EmptyInput>
+    where
+        A: Clone + Hash + Ord,
+    {
+        counts::mode(self).ok_or(EmptyInput)
+    }
+
+    fn mode_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Clone + Hash + Ord,
+        D: RemoveAxis,
+    {
+        if self.len_of(axis) == 0 {
+            return Err(EmptyInput);
+        }
+        Ok(self.map_axis(axis, |lane| {
+            counts::mode(lane).expect("Lanes along a non-empty axis are not empty.")
+        }))
+    }
+
+    fn value_counts(&self) -> IndexMap<A, usize>
+    where
+        A: Clone + Hash + Ord,
+    {
+        counts::value_counts(self)
+    }
+
+    fn describe(&self) -> SummaryReport<A>
+    where
+        A: Float + FromPrimitive,
+    {
+        describe::describe(self)
+    }
+
+    fn describe_axis(&self, axis: Axis) -> Array<SummaryReport<A>, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+    {
+        describe::describe_axis(self, axis)
+    }
+
+    private_impl! {}
+}
+
+/// Returns the length of `axis`, or `Err(StatsError::EmptyInput)` if it is
+/// zero.
+///
+/// **Panics** if `axis` is out of bounds.
+fn non_empty_axis_len<S, D>(a: &ArrayBase<S, D>, axis: Axis) -> Result<usize, StatsError>
+where
+    S: Data,
+    D: Dimension,
+{
+    match a.len_of(axis) {
+        0 => Err(StatsError::EmptyInput),
+        len => Ok(len),
+    }
+}
+
+/// Checks that `ddof` is non-negative and smaller than the number `n` of
+/// observations, returning `Err(StatsError::InvalidDdof)` otherwise (or if
+/// `ddof` is NaN).
+///
+/// **Panics** if `A::from_usize()` fails to convert `n`.
+fn check_ddof<A>(ddof: A, n: usize) -> Result<(), StatsError>
+where
+    A: Float + FromPrimitive,
+{
+    let n = A::from_usize(n).expect("Converting the number of observations to `A` must not fail.");
+    if ddof >= A::zero() && ddof < n {
+        Ok(())
+    } else {
+        Err(StatsError::InvalidDdof)
+    }
+}
+
+/// Returns the elements of `a` that aren't NaN, `Err(SkipNanError::EmptyInput)`
+/// if `a` is empty, or `Err(SkipNanError::AllNan)` if all its elements are NaN.
+fn without_nan<A, S, D>(a: &ArrayBase<S, D>) -> Result<Vec<A>, SkipNanError>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    if a.is_empty() {
+        return Err(SkipNanError::EmptyInput);
     }
+    let xs: Vec<A> = a.iter().cloned().filter(|x| !x.is_nan()).collect();
+    if xs.is_empty() {
+        Err(SkipNanError::AllNan)
+    } else {
+        Ok(xs)
+    }
+}
 
-    fn central_moment(&self, order: u16) -> Result<A, EmptyInput>
-    where
-        A: Float + FromPrimitive,
-    {
-        if self.is_empty() {
-            return Err(EmptyInput);
+/// Computes `statistic` on the elements of `array`, handling NaN values
+/// according to `policy`.
+///
+/// With `NanPolicy::Omit`, the non-NaN elements are copied into a new array
+/// before calling `statistic`.
+fn with_policy<A, S, D, F>(
+    array: &ArrayBase<S, D>,
+    policy: NanPolicy,
+    statistic: F,
+) -> Result<A, NanPolicyError>
+where
+    A: MaybeNan + Clone,
+    A::NotNan: Clone,
+    S: Data<Elem = A>,
+    D: Dimension,
+    F: FnOnce(ArrayViewD<'_, A>) -> Result<A, EmptyInput>,
+{
+    match policy {
+        NanPolicy::Propagate => {
+            if array.has_nan() {
+                Ok(A::from_not_nan_opt(None))
+            } else {
+                Ok(statistic(array.view().into_dyn())?)
+            }
         }
-        match order {
-            0 => Ok(A::one()),
-            1 => Ok(A::zero()),
-            n => {
-                let mean = self.mean().unwrap();
-                let shifted_array = self.mapv(|x| x - mean);
-                let shifted_moments = moments(shifted_array, n);
-                let correction_term = -shifted_moments[1];
-
-                let coefficients = central_moment_coefficients(&shifted_moments);
-                Ok(horner_method(coefficients, correction_term))
+        NanPolicy::Omit => {
+            let not_nan = array.fold_skipnan(Vec::new(), |mut not_nan, elem| {
+                not_nan.push(A::from_not_nan(elem.clone()));
+                not_nan
+            });
+            if not_nan.is_empty() && !array.is_empty() {
+                return Err(NanPolicyError::AllNan);
+            }
+            Ok(statistic(Array1::from(not_nan).into_dyn().view())?)
+        }
+        NanPolicy::Error => {
+            if array.has_nan() {
+                Err(NanPolicyError::ContainsNan)
+            } else {
+                Ok(statistic(array.view().into_dyn())?)
             }
         }
     }
+}
 
-    fn central_moments(&self, order: u16) -> Result<Vec<A>, EmptyInput>
-    where
-        A: Float + FromPrimitive,
-    {
-        if self.is_empty() {
-            return Err(EmptyInput);
+/// Broadcasts `weights` to the shape of `array`, checking that they are
+/// non-negative and not NaN.
+fn broadcast_weights<'a, A, S, D, S2, E>(
+    array: &ArrayBase<S, D>,
+    weights: &'a ArrayBase<S2, E>,
+) -> Result<ArrayView<'a, A, D>, WeightedStatsError>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: Dimension,
+    S2: Data<Elem = A>,
+    E: Dimension,
+{
+    if weights.iter().any(|&w| w < A::zero() || w.is_nan()) {
+        return Err(WeightedStatsError::InvalidWeights);
+    }
+    weights.broadcast(array.raw_dim()).ok_or_else(|| {
+        ShapeMismatch {
+            first_shape: array.shape().to_vec(),
+            second_shape: weights.shape().to_vec(),
         }
-        match order {
-            0 => Ok(vec![A::one()]),
-            1 => Ok(vec![A::one(), A::zero()]),
-            n => {
-                // We only perform these operations once, and then reuse their
-                // result to compute all the required moments
-                let mean = self.mean().unwrap();
-                let shifted_array = self.mapv(|x| x - mean);
-                let shifted_moments = moments(shifted_array, n);
-                let correction_term = -shifted_moments[1];
+        .into()
+    })
+}
 
-                let mut central_moments = vec![A::one(), A::zero()];
-                for k in 2..=n {
-                    let coefficients =
-                        central_moment_coefficients(&shifted_moments[..=(k as usize)]);
-                    let central_moment = horner_method(coefficients, correction_term);
-                    central_moments.push(central_moment)
-                }
-                Ok(central_moments)
-            }
+/// Returns the weighted mean of the non-NaN elements of `array` and the sum
+/// of their weights.
+fn weighted_mean<A, S, D>(
+    array: &ArrayBase<S, D>,
+    weights: &ArrayView<'_, A, D>,
+) -> Result<(A, A), WeightedStatsError>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    let mut sum = A::zero();
+    let mut sum_weights = A::zero();
+    Zip::from(array).and(weights).apply(|&x, &w| {
+        if !x.is_nan() {
+            sum = sum + w * x;
+            sum_weights = sum_weights + w;
         }
+    });
+    if sum_weights == A::zero() {
+        Err(WeightedStatsError::ZeroWeights)
+    } else {
+        Ok((sum / sum_weights, sum_weights))
     }
+}
 
-    private_impl! {}
+/// Checks that `out` can hold a reduction of `array` along `axis`, returning
+/// the length of `axis`.
+///
+/// Returns `Err(EmptyInput)` if the length of `axis` is zero, and
+/// `Err(ShapeMismatch)` if the shape of `out` is not the shape of `array`
+/// with `axis` removed.
+fn check_axis_output<S, S2, D>(
+    array: &ArrayBase<S, D>,
+    axis: Axis,
+    out: &ArrayBase<S2, D::Smaller>,
+) -> Result<usize, MultiInputError>
+where
+    S: Data,
+    S2: Data,
+    D: RemoveAxis,
+{
+    let axis_len = array.len_of(axis);
+    if axis_len == 0 {
+        return Err(MultiInputError::EmptyInput);
+    }
+    let expected_shape = array
+        .shape()
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != axis.index())
+        .map(|(_, &len)| len);
+    if !expected_shape.eq(out.shape().iter().cloned()) {
+        let mut first_shape = array.shape().to_vec();
+        first_shape.remove(axis.index());
+        return Err(ShapeMismatch {
+            first_shape,
+            second_shape: out.shape().to_vec(),
+        }
+        .into());
+    }
+    Ok(axis_len)
 }
 
-/// Returns a vector containing all moments of the array elements up to
-/// *order*, where the *p*-th moment is defined as:
+/// Returns a vector containing all moments of the array elements, shifted by
+/// `shift`, up to *order*, where the *p*-th moment is defined as:
 ///
 /// ```text
 /// 1  n
-/// ―  ∑ xᵢᵖ
+/// ―  ∑ (xᵢ - shift)ᵖ
 /// n i=1
 /// ```
 ///
 /// The returned moments are ordered by power magnitude: 0th moment, 1st moment, etc.
 ///
+/// The shifted elements are never materialized: each moment is a single pass
+/// over `a` with the unrolled summation kernel.
+///
 /// **Panics** if `A::from_usize()` fails to convert the number of elements in the array.
-fn moments<A, S, D>(a: ArrayBase<S, D>, order: u16) -> Vec<A>
+fn shifted_moments<A, S, D>(a: &ArrayBase<S, D>, order: u16, shift: A) -> Vec<A>
 where
     A: Float + FromPrimitive,
     S: Data<Elem = A>,
@@ -137,25 +1031,126 @@ where
 
     if order >= 1 {
         // When k=1, we don't need to raise elements to the 1th power (identity)
-        moments.push(a.sum() / n_elements)
+        moments.push(sum_by(a, |&x| x - shift) / n_elements)
     }
 
     for k in 2..=order {
-        moments.push(a.map(|x| x.powi(k)).sum() / n_elements)
+        moments.push(sum_by(a, |&x| (x - shift).powi(k)) / n_elements)
     }
     moments
 }
 
+/// Applies `statistic` to the number of elements and the central moments up
+/// to `order` of each lane of `a` along `axis`, without the NaN elements if
+/// `skipnan` is `true`.
+///
+/// The shifted moments of a lane are accumulated in a single pass, using its
+/// first element as the shift to limit cancellation. Lanes without elements
+/// are mapped to NaN.
+fn map_lane_moments<A, S, D, F>(
+    a: &ArrayBase<S, D>,
+    axis: Axis,
+    skipnan: bool,
+    order: u16,
+    statistic: F,
+) -> Array<A, D::Smaller>
+where
+    A: Float + FromPrimitive,
+    S: Data<Elem = A>,
+    D: RemoveAxis,
+    F: Fn(A, &[A]) -> A,
+{
+    let order = order as usize;
+    let dim = a.raw_dim().remove_axis(axis);
+    if a.len_of(axis) == 0 {
+        return Array::from_elem(dim, A::nan());
+    }
+    let mut sums = vec![A::zero(); order + 1];
+    let lanes = a.lanes(axis).into_iter().map(|lane| {
+        for sum in sums.iter_mut() {
+            *sum = A::zero();
+        }
+        let mut elements = lane.iter().filter(|x| !(skipnan && x.is_nan())).peekable();
+        let shift = match elements.peek() {
+            Some(&&shift) => shift,
+            None => return A::nan(),
+        };
+        let mut n_elements = 0;
+        for &x in elements {
+            let deviation = x - shift;
+            let mut power = A::one();
+            for sum in sums.iter_mut() {
+                *sum = *sum + power;
+                power = power * deviation;
+            }
+            n_elements += 1;
+        }
+        let n =
+            A::from_usize(n_elements).expect("Converting number of elements to `A` must not fail");
+        let shifted_moments: Vec<A> = sums.iter().map(|&sum| sum / n).collect();
+        let mut central_moments = vec![A::one()];
+        if order >= 1 {
+            central_moments.push(A::zero());
+        }
+        for k in 2..=order {
+            let coefficients = central_moment_coefficients(&shifted_moments[..=k]);
+            central_moments.push(horner_method(coefficients, -shifted_moments[1]));
+        }
+        statistic(n, &central_moments)
+    });
+    Array::from_shape_vec(dim, lanes.collect())
+        .expect("The lanes of `a` match the shape without `axis`.")
+}
+
+/// Returns the kurtosis from the number of elements and the central moments
+/// up to order 4, optionally adjusted for the bias of the sample moments.
+fn kurtosis_from_moments<A>(n: A, moments: &[A], bias_correction: bool) -> A
+where
+    A: Float + FromPrimitive,
+{
+    let kurtosis = moments[4] / moments[2].powi(2);
+    if !bias_correction {
+        return kurtosis;
+    }
+    let three = A::from_u8(3).unwrap();
+    if n < A::from_u8(4).unwrap() {
+        return A::nan();
+    }
+    let one = A::one();
+    let two = one + one;
+    three
+        + (n - one) / ((n - two) * (n - three))
+            * ((n + one) * (kurtosis - three) + A::from_u8(6).unwrap())
+}
+
+/// Returns the skewness from the number of elements and the central moments
+/// up to order 3, optionally adjusted for the bias of the sample moments.
+fn skewness_from_moments<A>(n: A, moments: &[A], bias_correction: bool) -> A
+where
+    A: Float + FromPrimitive,
+{
+    let skewness = moments[3] / moments[2].sqrt().powi(3);
+    if !bias_correction {
+        return skewness;
+    }
+    let one = A::one();
+    let two = one + one;
+    if n < two + one {
+        return A::nan();
+    }
+    (n * (n - one)).sqrt() / (n - two) * skewness
+}
+
 /// Returns the coefficients in the polynomial expression to compute the *p*th
 /// central moment as a function of the sample mean.
 ///
 /// It takes as input all moments up to order *p*, ordered by power magnitude - *p* is
-/// inferred to be the length of the *moments* array.
+/// inferred to be the length of the *moments* array minus one.
 fn central_moment_coefficients<A>(moments: &[A]) -> Vec<A>
 where
     A: Float + FromPrimitive,
 {
-    let order = moments.len();
+    let order = moments.len() - 1;
     IterBinomial::new(order)
         .zip(moments.iter().rev())
         .map(|(binom, &moment)| A::from_usize(binom).unwrap() * moment)
@@ -185,14 +1180,52 @@ where
     result
 }
 
+/// Returns the sum of the squared deviations of the elements of `a` from
+/// their mean, computed with `algorithm`.
+///
+/// **Panics** if `A::from_usize()` fails to convert the number of elements.
+pub(crate) fn sum_sq_dev<A, S, D>(a: &ArrayBase<S, D>, algorithm: VarianceAlgorithm) -> A
+where
+    A: Float + FromPrimitive,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    if a.is_empty() {
+        return A::zero();
+    }
+    let n = A::from_usize(a.len()).expect("Converting number of elements to `A` must not fail.");
+    match algorithm {
+        VarianceAlgorithm::OnePass => {
+            let (sum, sum_sq) = a.fold((A::zero(), A::zero()), |(sum, sum_sq), &x| {
+                (sum + x, sum_sq + x * x)
+            });
+            sum_sq - sum * sum / n
+        }
+        VarianceAlgorithm::TwoPass => {
+            let mean = sum_by(a, |&x| x) / n;
+            sum_by(a, |&x| (x - mean) * (x - mean))
+        }
+        VarianceAlgorithm::Welford => {
+            let mut mean_var = MeanVar::new();
+            mean_var.extend(a);
+            mean_var.sum_sq_dev()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::SummaryStatisticsExt;
-    use crate::errors::EmptyInput;
+    use super::{SummaryStatisticsExt, VarianceAlgorithm};
+    use crate::errors::{
+        EmptyInput, MultiInputError, NanPolicyError, ShapeMismatch, SkipNanError, StatsError,
+        WeightedStatsError,
+    };
+    use crate::NanPolicy;
     use approx::assert_abs_diff_eq;
-    use ndarray::{array, Array, Array1};
+    use ndarray::{array, s, Array, Array1, Axis, RemoveAxis};
     use ndarray_rand::RandomExt;
     use noisy_float::types::N64;
+    use quickcheck_macros::quickcheck;
     use rand::distributions::Uniform;
     use std::f64;
 
@@ -204,6 +1237,60 @@ mod tests {
         assert!(a.geometric_mean().unwrap().is_nan());
     }
 
+    #[test]
+    fn test_var_axis_with_algorithms() {
+        let a = Array::random((20, 30), Uniform::new(-1., 1.));
+        for &axis in &[Axis(0), Axis(1)] {
+            let expected = a.var_axis(axis, 1.);
+            for &algorithm in &[
+                VarianceAlgorithm::OnePass,
+                VarianceAlgorithm::TwoPass,
+                VarianceAlgorithm::Welford,
+            ] {
+                let var = a.var_axis_with(axis, 1., algorithm).unwrap();
+                assert!(var.all_close(&expected, 1e-12));
+                let var = (&a + 1e9).var_axis_with(axis, 1., algorithm).unwrap();
+                if algorithm == VarianceAlgorithm::OnePass {
+                    assert!(!var.all_close(&expected, 1e-2));
+                } else {
+                    assert!(var.all_close(&expected, 1e-6));
+                }
+            }
+        }
+        let empty = Array::<f64, _>::zeros((0, 2));
+        assert_eq!(
+            empty.var_axis_with(Axis(0), 0., VarianceAlgorithm::Welford),
+            Err(EmptyInput)
+        );
+    }
+
+    #[test]
+    fn test_deterministic_mean_var_are_independent_of_order_and_layout() {
+        let a = Array::random((40, 30), Uniform::new(-1e6, 1e6));
+        let mut shuffled: Vec<f64> = a.iter().cloned().collect();
+        shuffled.reverse();
+        shuffled.rotate_left(17);
+        let shuffled = Array1::from(shuffled);
+        let transposed = a.t().to_owned();
+        for other in &[
+            shuffled.into_dyn(),
+            transposed.into_dyn(),
+            a.t().into_dyn().to_owned(),
+        ] {
+            assert_eq!(a.mean_deterministic(), other.mean_deterministic());
+            assert_eq!(a.var_deterministic(1.), other.var_deterministic(1.));
+        }
+        assert_abs_diff_eq!(
+            a.mean_deterministic().unwrap(),
+            a.mean().unwrap(),
+            epsilon = 1e-6
+        );
+        assert_eq!(
+            Array1::<f64>::zeros(0).var_deterministic(0.),
+            Err(EmptyInput)
+        );
+    }
+
     #[test]
     fn test_means_with_empty_array_of_floats() {
         let a: Array1<f64> = array![];
@@ -290,7 +1377,7 @@ mod tests {
             0.1809703,
         ];
         // Computed using scipy.stats.moment
-        let expected_moments = vec![
+        let expected_moments = [
             1.,
             0.,
             0.09339920262960291,
@@ -351,4 +1438,409 @@ mod tests {
         assert_abs_diff_eq!(kurtosis, expected_kurtosis, epsilon = 1e-12);
         assert_abs_diff_eq!(skewness, expected_skewness, epsilon = 1e-8);
     }
+
+    #[test]
+    fn test_mean_with_policy() {
+        let a = array![1., f64::NAN, 2., 6.];
+        assert!(a.mean_with_policy(NanPolicy::Propagate).unwrap().is_nan());
+        assert_eq!(a.mean_with_policy(NanPolicy::Omit), Ok(3.));
+        assert_eq!(
+            a.mean_with_policy(NanPolicy::Error),
+            Err(NanPolicyError::ContainsNan)
+        );
+
+        let a = array![1., 2., 6.];
+        for &policy in &[NanPolicy::Propagate, NanPolicy::Omit, NanPolicy::Error] {
+            assert_eq!(a.mean_with_policy(policy), Ok(3.));
+        }
+    }
+
+    #[test]
+    fn test_with_policy_empty_input() {
+        let a: Array1<f64> = array![];
+        for &policy in &[NanPolicy::Propagate, NanPolicy::Omit, NanPolicy::Error] {
+            assert_eq!(a.mean_with_policy(policy), Err(NanPolicyError::EmptyInput));
+            assert_eq!(
+                a.skewness_with_policy(policy),
+                Err(NanPolicyError::EmptyInput)
+            );
+        }
+        let a = array![f64::NAN, f64::NAN];
+        assert_eq!(
+            a.mean_with_policy(NanPolicy::Omit),
+            Err(NanPolicyError::AllNan)
+        );
+        assert_eq!(
+            a.median_abs_deviation_skipnan(1.).map_err(StatsError::from),
+            Err(StatsError::AllNan)
+        );
+    }
+
+    #[test]
+    fn test_moments_with_policy_omit_matches_filtered_array() {
+        let a = array![0.5, f64::NAN, 0.1, 0.9, f64::NAN, 0.3, 0.35];
+        let filtered = array![0.5, 0.1, 0.9, 0.3, 0.35];
+        let policy = NanPolicy::Omit;
+        assert_eq!(
+            a.harmonic_mean_with_policy(policy).unwrap(),
+            filtered.harmonic_mean().unwrap()
+        );
+        assert_eq!(
+            a.geometric_mean_with_policy(policy).unwrap(),
+            filtered.geometric_mean().unwrap()
+        );
+        assert_eq!(
+            a.kurtosis_with_policy(policy).unwrap(),
+            filtered.kurtosis().unwrap()
+        );
+        assert_eq!(
+            a.skewness_with_policy(policy).unwrap(),
+            filtered.skewness().unwrap()
+        );
+        assert_eq!(
+            a.central_moment_with_policy(3, policy).unwrap(),
+            filtered.central_moment(3).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_axis_into_matches_allocating_versions() {
+        let a = Array::random((4, 7, 3), Uniform::new(-1., 1.));
+        for axis in 0..a.ndim() {
+            let axis = Axis(axis);
+            let mut out = Array::zeros(a.raw_dim().remove_axis(axis));
+            a.mean_axis_into(axis, &mut out).unwrap();
+            assert!(out.all_close(&a.mean_axis(axis), 1e-12));
+            a.var_axis_into(axis, 1., &mut out).unwrap();
+            assert!(out.all_close(&a.var_axis(axis, 1.), 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_accumulate_in_f64() {
+        let a = Array1::<f32>::from_elem(20_000_000, 0.1);
+        let exact = f64::from(0.1f32);
+        assert!((a.mean_in::<f64>().unwrap() - exact).abs() < 1e-12);
+        assert!((a.mean_axis_in::<f64>(Axis(0)).unwrap()[()] - exact).abs() < 1e-12);
+
+        let b = Array::from_shape_fn((3, 4), |(i, j)| (i * j) as f32 + 0.5);
+        let expected = b.mapv(f64::from).var_axis(Axis(1), 1.);
+        assert!(b
+            .var_axis_in::<f64>(Axis(1), 1.)
+            .unwrap()
+            .all_close(&expected, 1e-12));
+        assert_eq!(Array1::<f32>::zeros(0).mean_in::<f64>(), Err(EmptyInput));
+        assert_eq!(
+            b.slice(s![.., ..0]).mean_axis_in::<f64>(Axis(1)),
+            Err(EmptyInput)
+        );
+    }
+
+    #[test]
+    fn test_axis_into_errors() {
+        let a = Array::<f64, _>::zeros((2, 3));
+        let mut out = Array1::from_elem(2, 7.);
+        assert_eq!(
+            a.mean_axis_into(Axis(0), &mut out),
+            Err(MultiInputError::ShapeMismatch(ShapeMismatch {
+                first_shape: vec![3],
+                second_shape: vec![2],
+            }))
+        );
+        assert_eq!(out, array![7., 7.]);
+        let empty = Array::<f64, _>::zeros((0, 2));
+        assert_eq!(
+            empty.var_axis_into(Axis(0), 0., &mut out),
+            Err(MultiInputError::EmptyInput)
+        );
+        assert_eq!(out, array![7., 7.]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_var_axis_into_ddof_too_large() {
+        let a = Array::<f64, _>::zeros((2, 3));
+        let mut out = Array1::zeros(3);
+        let _ = a.var_axis_into(Axis(0), 2., &mut out);
+    }
+
+    #[test]
+    fn test_try_axis_statistics() {
+        let a = array![[1., 2., 4.], [3., 6., 5.]];
+        assert_eq!(a.try_mean_axis(Axis(1)), Ok(a.mean_axis(Axis(1))));
+        assert_eq!(a.try_var_axis(Axis(0), 1.), Ok(a.var_axis(Axis(0), 1.)));
+        assert_eq!(a.try_std_axis(Axis(1), 0.), Ok(a.std_axis(Axis(1), 0.)));
+        for &ddof in &[-1., 2., f64::NAN] {
+            assert_eq!(a.try_var_axis(Axis(0), ddof), Err(StatsError::InvalidDdof));
+            assert_eq!(a.try_std_axis(Axis(0), ddof), Err(StatsError::InvalidDdof));
+        }
+        let empty = Array::<f64, _>::zeros((0, 2));
+        assert_eq!(empty.try_mean_axis(Axis(0)), Err(StatsError::EmptyInput));
+        assert_eq!(empty.try_var_axis(Axis(0), 0.), Err(StatsError::EmptyInput));
+
+        let mut out = Array1::from_elem(2, 7.);
+        assert_eq!(
+            a.try_var_axis_into(Axis(0), 0., &mut out),
+            Err(StatsError::ShapeMismatch(ShapeMismatch {
+                first_shape: vec![3],
+                second_shape: vec![2],
+            }))
+        );
+        assert_eq!(
+            a.try_var_axis_into(Axis(1), 3., &mut out),
+            Err(StatsError::InvalidDdof)
+        );
+        assert_eq!(out, array![7., 7.]);
+    }
+
+    #[test]
+    fn test_try_variances() {
+        let a = array![[1., 2., 4.], [3., 6., 5.]];
+        let algorithm = VarianceAlgorithm::Welford;
+        assert_eq!(
+            a.try_var_axis_in::<f64>(Axis(1), 1.),
+            a.var_axis_in::<f64>(Axis(1), 1.).map_err(StatsError::from)
+        );
+        assert_eq!(
+            a.try_var_axis_with(Axis(0), 0., algorithm),
+            a.var_axis_with(Axis(0), 0., algorithm)
+                .map_err(StatsError::from)
+        );
+        assert_eq!(
+            a.try_var_deterministic(1.),
+            a.var_deterministic(1.).map_err(StatsError::from)
+        );
+        for &ddof in &[-1., 6., f64::NAN] {
+            assert_eq!(
+                a.try_var_axis_in::<f64>(Axis(0), ddof),
+                Err(StatsError::InvalidDdof)
+            );
+            assert_eq!(
+                a.try_var_axis_with(Axis(1), ddof, algorithm),
+                Err(StatsError::InvalidDdof)
+            );
+            assert_eq!(a.try_var_deterministic(ddof), Err(StatsError::InvalidDdof));
+        }
+        let empty = Array::<f64, _>::zeros((0, 2));
+        assert_eq!(
+            empty.try_var_axis_in::<f64>(Axis(0), 0.),
+            Err(StatsError::EmptyInput)
+        );
+        assert_eq!(
+            empty.try_var_axis_with(Axis(0), 0., algorithm),
+            Err(StatsError::EmptyInput)
+        );
+        assert_eq!(empty.try_var_deterministic(0.), Err(StatsError::EmptyInput));
+    }
+
+    #[quickcheck]
+    fn weighted_matches_repeated_elements(xs: Vec<(i16, u8)>) -> bool {
+        let values = Array1::from_iter(xs.iter().map(|&(x, _)| f64::from(x)));
+        let weights = Array1::from_iter(xs.iter().map(|&(_, w)| f64::from(w % 4)));
+        let repeated = Array1::from_iter(
+            xs.iter()
+                .flat_map(|&(x, w)| std::iter::repeat(f64::from(x)).take(usize::from(w % 4))),
+        );
+        if repeated.is_empty() {
+            return values.weighted_mean(&weights) == Err(WeightedStatsError::ZeroWeights);
+        }
+        if repeated.len() == 1 {
+            // The sample variance is undefined.
+            return values.weighted_mean(&weights).ok() == repeated.mean().ok();
+        }
+        let close = |x: f64, y: f64| (x - y).abs() <= 1e-9 * y.abs().max(1.);
+        close(
+            values.weighted_mean(&weights).unwrap(),
+            repeated.mean().unwrap(),
+        ) && close(
+            values.weighted_var(&weights, 1.).unwrap(),
+            repeated.var_axis(Axis(0), 1.)[()],
+        )
+    }
+
+    #[test]
+    fn test_weighted_statistics_skip_nan() {
+        let a = array![[1., f64::NAN, 3.], [f64::NAN, 5., 7.]];
+        let weights = array![3., 1., 1.];
+        assert_eq!(a.weighted_mean(&weights), Ok(3.));
+        assert_eq!(a.weighted_var(&weights, 2.), Ok(8.));
+        assert_eq!(a.weighted_sum_axis(Axis(1), &weights), Ok(array![6., 12.]));
+        assert_eq!(
+            a.weighted_sum_axis(Axis(0), &array![[1.], [2.]]),
+            Ok(array![1., 10., 17.])
+        );
+    }
+
+    #[test]
+    fn test_weighted_statistics_errors() {
+        let a = array![[1., 2., 3.], [4., 5., 6.]];
+        assert_eq!(
+            a.weighted_mean(&array![1., 1.]),
+            Err(WeightedStatsError::ShapeMismatch(ShapeMismatch {
+                first_shape: vec![2, 3],
+                second_shape: vec![2],
+            }))
+        );
+        assert_eq!(
+            a.weighted_std(&array![1., -1., 1.], 0.),
+            Err(WeightedStatsError::InvalidWeights)
+        );
+        assert_eq!(
+            a.weighted_sum_axis(Axis(0), &array![1., f64::NAN, 1.]),
+            Err(WeightedStatsError::InvalidWeights)
+        );
+        assert_eq!(
+            a.weighted_var(&array![0., 0., 0.], 0.),
+            Err(WeightedStatsError::ZeroWeights)
+        );
+        let empty = Array1::<f64>::zeros(0);
+        assert_eq!(
+            empty.weighted_mean(&empty),
+            Err(WeightedStatsError::ZeroWeights)
+        );
+        // The weights sum to 6 over the array.
+        for &ddof in &[-1., 6., f64::NAN] {
+            assert_eq!(
+                a.weighted_var(&array![1., 1., 1.], ddof),
+                Err(WeightedStatsError::InvalidDdof)
+            );
+        }
+        assert_eq!(
+            a.weighted_std(&array![1., 1., 1.], 7.)
+                .map_err(StatsError::from),
+            Err(StatsError::InvalidDdof)
+        );
+    }
+
+    #[test]
+    fn test_robust_statistics_axis_and_skipnan() {
+        let a = array![
+            [1., 2., f64::NAN],
+            [2., 4., f64::NAN],
+            [3., 6., f64::NAN],
+            [100., f64::NAN, f64::NAN]
+        ];
+        let mad = a.median_abs_deviation_axis(Axis(0), 1.).unwrap();
+        assert_eq!(mad[0], 1.);
+        assert!(mad[1].is_nan() && mad[2].is_nan());
+        assert_eq!(
+            a.median_abs_deviation_axis_skipnan(Axis(0), 2.)
+                .slice(s![..2]),
+            array![2., 4.]
+        );
+        assert!(a.trimmed_mean_axis_skipnan(Axis(0), 0.25)[2].is_nan());
+        assert_eq!(
+            a.trimmed_mean_axis_skipnan(Axis(0), 0.25).slice(s![..2]),
+            array![2.5, 4.]
+        );
+        assert_eq!(
+            a.winsorized_mean_axis_skipnan(Axis(1), 0.).slice(s![..3]),
+            array![1.5, 3., 4.5]
+        );
+        // The elements other than NaN are 1, 2, 2, 3, 4, 6 and 100.
+        assert_abs_diff_eq!(
+            a.trimmed_mean_skipnan(0.2).unwrap(),
+            17. / 5.,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            a.winsorized_mean_skipnan(0.2).unwrap(),
+            25. / 7.,
+            epsilon = 1e-12
+        );
+        assert_eq!(a.median_abs_deviation_skipnan(1.), Ok(1.));
+
+        let empty = Array::<f64, _>::zeros((0, 2));
+        assert_eq!(empty.trimmed_mean(0.1), Err(EmptyInput));
+        assert_eq!(empty.winsorized_mean_axis(Axis(0), 0.1), Err(EmptyInput));
+        assert_eq!(
+            empty.median_abs_deviation_axis(Axis(1), 1.),
+            Ok(Array1::zeros(0))
+        );
+        assert_eq!(
+            array![f64::NAN].median_abs_deviation_skipnan(1.),
+            Err(SkipNanError::AllNan)
+        );
+        assert_eq!(
+            empty.trimmed_mean_skipnan(0.1),
+            Err(SkipNanError::EmptyInput)
+        );
+        assert_eq!(
+            array![f64::NAN, f64::NAN].winsorized_mean_skipnan(0.1),
+            Err(SkipNanError::AllNan)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_trimmed_mean_fraction_too_large() {
+        let _ = array![1., 2.].trimmed_mean(0.5);
+    }
+
+    #[quickcheck]
+    fn moments_along_axis_match_lane_moments(xs: Vec<i8>) -> bool {
+        let n_rows = xs.len() / 3;
+        if n_rows == 0 {
+            return true;
+        }
+        let a = Array::from_shape_vec((n_rows, 3), xs[..3 * n_rows].to_vec())
+            .unwrap()
+            .mapv(f64::from);
+        let close =
+            |x: f64, y: f64| (x.is_nan() && y.is_nan()) || (x - y).abs() <= 1e-9 * y.abs().max(1.);
+        let kurtosis = a.kurtosis_axis(Axis(0), false).unwrap();
+        let skewness = a.skewness_axis(Axis(0), false).unwrap();
+        let moments = a.central_moment_axis(3, Axis(0)).unwrap();
+        a.axis_iter(Axis(1)).enumerate().all(|(i, column)| {
+            close(kurtosis[i], column.kurtosis().unwrap())
+                && close(skewness[i], column.skewness().unwrap())
+                && close(moments[i], column.central_moment(3).unwrap())
+        })
+    }
+
+    #[test]
+    fn test_moments_along_axis_bias_correction_and_skipnan() {
+        let a = array![
+            [1., 1., f64::NAN],
+            [2., 2., f64::NAN],
+            [3., f64::NAN, f64::NAN],
+            [10., 3., f64::NAN],
+            [4.5, f64::NAN, f64::NAN]
+        ];
+        let skewness = a.skewness_axis_skipnan(Axis(0), true);
+        assert_abs_diff_eq!(skewness[0], 1.546_909_869_537_289_8, epsilon = 1e-12);
+        assert_abs_diff_eq!(skewness[1], 0., epsilon = 1e-12);
+        assert!(skewness[2].is_nan());
+        let kurtosis = a.kurtosis_axis_skipnan(Axis(0), true);
+        assert_abs_diff_eq!(kurtosis[0], 5.515_912_445_834_193, epsilon = 1e-12);
+        // The bias correction is undefined for fewer than four elements.
+        assert!(kurtosis[1].is_nan() && kurtosis[2].is_nan());
+        assert_eq!(
+            a.central_moment_axis_skipnan(0, Axis(0)).slice(s![..2]),
+            array![1., 1.]
+        );
+        assert_abs_diff_eq!(
+            a.central_moment_axis_skipnan(2, Axis(0))[1],
+            2. / 3.,
+            epsilon = 1e-12
+        );
+        assert!(a.central_moment_axis(2, Axis(0)).unwrap()[1].is_nan());
+
+        let empty = Array::<f64, _>::zeros((0, 2));
+        assert_eq!(empty.skewness_axis(Axis(0), false), Err(EmptyInput));
+        assert_eq!(empty.kurtosis_axis(Axis(1), true), Ok(Array1::zeros(0)));
+        assert!(empty
+            .central_moment_axis_skipnan(2, Axis(0))
+            .iter()
+            .all(|x| x.is_nan()));
+    }
+
+    #[test]
+    fn test_mode_of_empty_input() {
+        let empty = Array::<u8, _>::zeros((2, 0));
+        assert_eq!(empty.mode(), Err(EmptyInput));
+        assert_eq!(empty.mode_axis(Axis(1)), Err(EmptyInput));
+        assert_eq!(empty.mode_axis(Axis(0)), Ok(Array1::zeros(0)));
+        assert!(empty.value_counts().is_empty());
+    }
 }
@@ -1,8 +1,57 @@
 //! Summary statistics (e.g. mean, variance, etc.).
-use crate::errors::EmptyInput;
-use ndarray::{Data, Dimension};
+use crate::errors::{
+    EmptyInput, MultiInputError, NanPolicyError, SkipNanError, StatsError, WeightedStatsError,
+};
+use crate::{MaybeNan, NanPolicy};
+use core::hash::Hash;
+use core::ops::{Add, Div};
+use indexmap::IndexMap;
+use ndarray::{Array, ArrayBase, Axis, Data, DataMut, Dimension, RemoveAxis};
 use num_traits::{Float, FromPrimitive, Zero};
-use std::ops::{Add, Div};
+
+pub use self::describe::SummaryReport;
+
+/// Algorithm used to compute a variance or covariance (e.g. by
+/// [`var_axis_with`] and [`CorrelationExt::cov_with`]).
+///
+/// In the error bounds below, `n` is the number of values, `ε` the machine
+/// epsilon of the element type, and `κ = sqrt(1 + x̅² / var)` the condition
+/// number of the data (large when the mean is large compared to the
+/// standard deviation).
+///
+/// [`var_axis_with`]: trait.SummaryStatisticsExt.html#tymethod.var_axis_with
+/// [`CorrelationExt::cov_with`]: trait.CorrelationExt.html#tymethod.cov_with
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VarianceAlgorithm {
+    /// Reads the data once, accumulating `∑ xᵢ` and `∑ xᵢ²`, and returns
+    /// `(∑ xᵢ² - (∑ xᵢ)² / n) / (n - ddof)`.
+    ///
+    /// This is the fastest algorithm, but the subtraction cancels
+    /// catastrophically: the relative error grows like `n ε κ²`, so the
+    /// result can be meaningless (even negative) for data with a large
+    /// offset, e.g. timestamps or temperatures in kelvin. Suitable for
+    /// well-centered data or when speed matters more than accuracy.
+    OnePass,
+    /// Reads the data twice: once to compute the mean, and once to sum the
+    /// squared deviations from it.
+    ///
+    /// The relative error is about `n ε + n² ε² κ²`, which is essentially
+    /// independent of the offset of the data. This is the algorithm used by
+    /// the other methods of this crate, and the right choice for data that
+    /// is already in memory.
+    TwoPass,
+    /// Reads the data once, updating the mean and the sum of squared
+    /// deviations with every value (Welford's algorithm).
+    ///
+    /// The relative error grows like `n ε κ`, much better than
+    /// [`OnePass`](#variant.OnePass) and only slightly worse than
+    /// [`TwoPass`](#variant.TwoPass), but it needs a division per value,
+    /// which makes it the slowest. Suitable for data that can only be read
+    /// once, e.g. streaming telemetry (see also [`MeanVar`]).
+    ///
+    /// [`MeanVar`]: stream/struct.MeanVar.html
+    Welford,
+}
 
 /// Extension trait for `ArrayBase` providing methods
 /// to compute several summary statistics (e.g. mean, variance, etc.).
@@ -62,6 +111,66 @@ where
     where
         A: Float + FromPrimitive;
 
+    /// Returns the [Hodges-Lehmann estimator] of the location of the
+    /// elements of the array: the median of the `n (n + 1) / 2` Walsh
+    /// averages `(xᵢ + xⱼ) / 2` with `i ≤ j`.
+    ///
+    /// This robust alternative to the mean tolerates up to 29% of outliers,
+    /// while being nearly as efficient as the mean for normally distributed
+    /// data (95%, compared to 64% for the median). The median is selected
+    /// without materializing the Walsh averages, in `O(n log² n)` time and
+    /// `O(n)` memory.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned. NaN elements
+    /// propagate to the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![1., 2., 3., 4., 100.];
+    /// // Walsh averages: 1, 1.5, 2, 2, 2.5, 2.5, 3, 3, 3.5, 4, 50.5, 51, 51.5, 52, 100.
+    /// assert_eq!(a.hodges_lehmann(), Ok(3.));
+    /// ```
+    ///
+    /// [Hodges-Lehmann estimator]: https://en.wikipedia.org/wiki/Hodges%E2%80%93Lehmann_estimator
+    fn hodges_lehmann(&self) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the two-sample [Hodges-Lehmann estimator] of the shift in
+    /// location between the elements of `self` and those of `other`: the
+    /// median of the differences `xᵢ - yⱼ` over all the pairs of an element
+    /// `xᵢ` of `self` and an element `yⱼ` of `other`.
+    ///
+    /// The estimate is positive if the elements of `self` tend to be larger,
+    /// and it is the shift associated with the Mann-Whitney U test. It is
+    /// computed in `O((n + m) log² (n m))` time without materializing the
+    /// `n m` differences.
+    ///
+    /// If either array is empty, `Err(EmptyInput)` is returned. NaN elements
+    /// propagate to the result.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let treated = array![12., 15., 14., 11.];
+    /// let control = array![10., 9., 12.];
+    /// assert_eq!(treated.hodges_lehmann_shift(&control), Ok(2.5));
+    /// ```
+    ///
+    /// [Hodges-Lehmann estimator]: https://en.wikipedia.org/wiki/Hodges%E2%80%93Lehmann_estimator
+    fn hodges_lehmann_shift<S2, E>(&self, other: &ArrayBase<S2, E>) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        S2: Data<Elem = A>,
+        E: Dimension;
+
     /// Returns the [kurtosis] `Kurt[X]` of all elements in the array:
     ///
     /// ```text
@@ -141,7 +250,1143 @@ where
     where
         A: Float + FromPrimitive;
 
+    /// Returns the [kurtosis] of each lane along `axis`, see
+    /// [`kurtosis`](#tymethod.kurtosis).
+    ///
+    /// If `bias_correction` is `true`, the kurtosis is adjusted for the bias
+    /// of the sample moments, as in `scipy.stats.kurtosis(fisher=False,
+    /// bias=False)`:
+    ///
+    /// ```text
+    ///                 n - 1
+    /// K = 3 + ――――――――――――――――― ((n + 1) (μ₄ / σ⁴ - 3) + 6)
+    ///         (n - 2) (n - 3)
+    /// ```
+    ///
+    /// which is NaN for lanes of fewer than four elements.
+    ///
+    /// The moments of each lane are computed in a single pass, by shifting
+    /// the elements by the first one of the lane. NaN elements propagate to
+    /// the result.
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![[1., 1.], [2., 1.], [3., 1.], [10., 9.]];
+    /// let kurtosis = a.kurtosis_axis(Axis(0), false).unwrap();
+    /// let expected = array![a.column(0).kurtosis().unwrap(), 7. / 3.];
+    /// assert!(kurtosis.all_close(&expected, 1e-12));
+    /// ```
+    ///
+    /// [kurtosis]: #tymethod.kurtosis
+    fn kurtosis_axis(
+        &self,
+        axis: Axis,
+        bias_correction: bool,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Like [`kurtosis_axis`](#tymethod.kurtosis_axis), but ignores NaN
+    /// elements. Lanes without elements other than NaN are mapped to NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    fn kurtosis_axis_skipnan(&self, axis: Axis, bias_correction: bool) -> Array<A, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the [skewness] of each lane along `axis`, see
+    /// [`skewness`](#tymethod.skewness).
+    ///
+    /// If `bias_correction` is `true`, the skewness is adjusted for the bias
+    /// of the sample moments, as in `scipy.stats.skew(bias=False)`:
+    ///
+    /// ```text
+    ///     √(n (n - 1))
+    /// G = ―――――――――――― μ₃ / σ³
+    ///        n - 2
+    /// ```
+    ///
+    /// which is NaN for lanes of fewer than three elements.
+    ///
+    /// The moments of each lane are computed in a single pass, by shifting
+    /// the elements by the first one of the lane. NaN elements propagate to
+    /// the result.
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![[1., 3.], [2., 2.], [3., 1.], [10., 0.]];
+    /// let skewness = a.skewness_axis(Axis(0), false).unwrap();
+    /// let expected = array![a.column(0).skewness().unwrap(), 0.];
+    /// assert!(skewness.all_close(&expected, 1e-12));
+    /// ```
+    ///
+    /// [skewness]: #tymethod.skewness
+    fn skewness_axis(
+        &self,
+        axis: Axis,
+        bias_correction: bool,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Like [`skewness_axis`](#tymethod.skewness_axis), but ignores NaN
+    /// elements. Lanes without elements other than NaN are mapped to NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    fn skewness_axis_skipnan(&self, axis: Axis, bias_correction: bool) -> Array<A, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the *p*-th [central moment] of each lane along `axis`, see
+    /// [`central_moment`](#tymethod.central_moment).
+    ///
+    /// The shifted moments of each lane are computed in a single pass, by
+    /// shifting the elements by the first one of the lane, and then
+    /// converted to the central moment. NaN elements propagate to the
+    /// result.
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![[1., 2., 3.], [1., 3., 5.]];
+    /// let variances = a.central_moment_axis(2, Axis(1)).unwrap();
+    /// assert!(variances.all_close(&array![2. / 3., 8. / 3.], 1e-12));
+    /// ```
+    ///
+    /// [central moment]: #tymethod.central_moment
+    fn central_moment_axis(
+        &self,
+        order: u16,
+        axis: Axis,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Like [`central_moment_axis`](#tymethod.central_moment_axis), but
+    /// ignores NaN elements. Lanes without elements other than NaN are mapped
+    /// to NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    fn central_moment_axis_skipnan(&self, order: u16, axis: Axis) -> Array<A, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the [arithmetic mean] of all elements in the array, handling
+    /// NaN values according to `policy`:
+    ///
+    /// - `NanPolicy::Propagate`: a NaN value is returned if any element is NaN;
+    /// - `NanPolicy::Omit`: the mean of the non-NaN elements is returned, or
+    ///   `Err(NanPolicyError::AllNan)` if all the elements are NaN;
+    /// - `NanPolicy::Error`: `Err(NanPolicyError::ContainsNan)` is returned if
+    ///   any element is NaN.
+    ///
+    /// If the array is empty, `Err(NanPolicyError::EmptyInput)` is returned.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of elements in the array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{NanPolicy, SummaryStatisticsExt};
+    /// use ndarray_stats::errors::NanPolicyError;
+    ///
+    /// let a = array![1., ::std::f64::NAN, 3.];
+    /// assert!(a.mean_with_policy(NanPolicy::Propagate).unwrap().is_nan());
+    /// assert_eq!(a.mean_with_policy(NanPolicy::Omit), Ok(2.));
+    /// assert_eq!(a.mean_with_policy(NanPolicy::Error), Err(NanPolicyError::ContainsNan));
+    /// ```
+    ///
+    /// [arithmetic mean]: #tymethod.mean
+    fn mean_with_policy(&self, policy: NanPolicy) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Clone + FromPrimitive + Add<Output = A> + Div<Output = A> + Zero,
+        A::NotNan: Clone;
+
+    /// Returns the [harmonic mean] of all elements in the array, handling NaN
+    /// values according to `policy`.
+    ///
+    /// See [`mean_with_policy`] for details on the policies and the errors.
+    ///
+    /// [harmonic mean]: #tymethod.harmonic_mean
+    /// [`mean_with_policy`]: #tymethod.mean_with_policy
+    fn harmonic_mean_with_policy(&self, policy: NanPolicy) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Float + FromPrimitive,
+        A::NotNan: Clone;
+
+    /// Returns the [geometric mean] of all elements in the array, handling NaN
+    /// values according to `policy`.
+    ///
+    /// See [`mean_with_policy`] for details on the policies and the errors.
+    ///
+    /// [geometric mean]: #tymethod.geometric_mean
+    /// [`mean_with_policy`]: #tymethod.mean_with_policy
+    fn geometric_mean_with_policy(&self, policy: NanPolicy) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Float + FromPrimitive,
+        A::NotNan: Clone;
+
+    /// Returns the [kurtosis] of all elements in the array, handling NaN
+    /// values according to `policy`.
+    ///
+    /// See [`mean_with_policy`] for details on the policies and the errors.
+    ///
+    /// [kurtosis]: #tymethod.kurtosis
+    /// [`mean_with_policy`]: #tymethod.mean_with_policy
+    fn kurtosis_with_policy(&self, policy: NanPolicy) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Float + FromPrimitive,
+        A::NotNan: Clone;
+
+    /// Returns the [skewness] of all elements in the array, handling NaN
+    /// values according to `policy`.
+    ///
+    /// See [`mean_with_policy`] for details on the policies and the errors.
+    ///
+    /// [skewness]: #tymethod.skewness
+    /// [`mean_with_policy`]: #tymethod.mean_with_policy
+    fn skewness_with_policy(&self, policy: NanPolicy) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Float + FromPrimitive,
+        A::NotNan: Clone;
+
+    /// Returns the *p*-th [central moment] of all elements in the array,
+    /// handling NaN values according to `policy`.
+    ///
+    /// See [`mean_with_policy`] for details on the policies and the errors.
+    ///
+    /// **Panics** if `order` overflows `i32`.
+    ///
+    /// [central moment]: #tymethod.central_moment
+    /// [`mean_with_policy`]: #tymethod.mean_with_policy
+    fn central_moment_with_policy(
+        &self,
+        order: u16,
+        policy: NanPolicy,
+    ) -> Result<A, NanPolicyError>
+    where
+        A: MaybeNan + Float + FromPrimitive,
+        A::NotNan: Clone;
+
+    /// Computes the [arithmetic mean] along `axis`, writing the result into
+    /// `out` instead of allocating a new array.
+    ///
+    /// If the length of `axis` is zero, `Err(MultiInputError::EmptyInput)`
+    /// is returned. If the shape of `out` is not the shape of the array with
+    /// `axis` removed, `Err(MultiInputError::ShapeMismatch)` is returned.
+    /// `out` is left untouched in both cases.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array1, Axis};
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let mut out = Array1::zeros(2);
+    /// for a in &[array![[1., 2.], [3., 4.]], array![[0., 1.], [2., 5.]]] {
+    ///     a.mean_axis_into(Axis(0), &mut out).unwrap();
+    ///     assert_eq!(out, a.mean_axis(Axis(0)));
+    /// }
+    /// ```
+    ///
+    /// [arithmetic mean]: #tymethod.mean
+    fn mean_axis_into<S2>(
+        &self,
+        axis: Axis,
+        out: &mut ArrayBase<S2, D::Smaller>,
+    ) -> Result<(), MultiInputError>
+    where
+        A: Clone + FromPrimitive + Add<Output = A> + Div<Output = A> + Zero,
+        D: RemoveAxis,
+        S2: DataMut<Elem = A>;
+
+    /// Computes the variance along `axis`, writing the result into `out`
+    /// instead of allocating a new array.
+    ///
+    /// The parameter `ddof` has the same meaning as for `ArrayBase::var_axis`:
+    /// use `ddof = 0` for the population variance and `ddof = 1` for the
+    /// unbiased sample variance.
+    ///
+    /// The errors are the same as for [`mean_axis_into`](#tymethod.mean_axis_into).
+    ///
+    /// **Panics** if `axis` is out of bounds, if `ddof` is less than zero or
+    /// greater than or equal to the length of `axis`, or if `A::from_usize()`
+    /// fails to convert the length of `axis`.
+    fn var_axis_into<S2>(
+        &self,
+        axis: Axis,
+        ddof: A,
+        out: &mut ArrayBase<S2, D::Smaller>,
+    ) -> Result<(), MultiInputError>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+        S2: DataMut<Elem = A>;
+
+    /// Returns the [arithmetic mean] along `axis`, or an error instead of
+    /// panicking:
+    ///
+    /// - `Err(StatsError::EmptyInput)` if the length of `axis` is zero.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    ///
+    /// [arithmetic mean]: #tymethod.mean
+    fn try_mean_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, StatsError>
+    where
+        A: Clone + FromPrimitive + Add<Output = A> + Div<Output = A> + Zero,
+        D: RemoveAxis;
+
+    /// Returns the variance along `axis`, or an error instead of panicking:
+    ///
+    /// - `Err(StatsError::EmptyInput)` if the length of `axis` is zero;
+    /// - `Err(StatsError::InvalidDdof)` if `ddof` is negative, NaN, or not
+    ///   smaller than the length of `axis`.
+    ///
+    /// The parameter `ddof` has the same meaning as for `ArrayBase::var_axis`.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Array2, Axis};
+    /// use ndarray_stats::{errors::StatsError, SummaryStatisticsExt};
+    ///
+    /// let a = array![[1., 2.], [3., 6.]];
+    /// assert_eq!(a.try_var_axis(Axis(0), 1.), Ok(array![2., 8.]));
+    /// assert_eq!(a.try_var_axis(Axis(0), 2.), Err(StatsError::InvalidDdof));
+    /// assert_eq!(
+    ///     Array2::<f64>::zeros((0, 2)).try_var_axis(Axis(0), 0.),
+    ///     Err(StatsError::EmptyInput)
+    /// );
+    /// ```
+    fn try_var_axis(&self, axis: Axis, ddof: A) -> Result<Array<A, D::Smaller>, StatsError>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the standard deviation along `axis`, the square root of
+    /// [`try_var_axis`](#tymethod.try_var_axis), with the same errors.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    fn try_std_axis(&self, axis: Axis, ddof: A) -> Result<Array<A, D::Smaller>, StatsError>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Like [`var_axis_into`](#tymethod.var_axis_into), but returns
+    /// `Err(StatsError::InvalidDdof)` instead of panicking if `ddof` is
+    /// negative, NaN, or not smaller than the length of `axis`. A shape
+    /// mismatch of `out` is reported as `Err(StatsError::ShapeMismatch)`.
+    ///
+    /// `out` is left untouched if an error is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    fn try_var_axis_into<S2>(
+        &self,
+        axis: Axis,
+        ddof: A,
+        out: &mut ArrayBase<S2, D::Smaller>,
+    ) -> Result<(), StatsError>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis,
+        S2: DataMut<Elem = A>;
+
+    /// Returns the [arithmetic mean] of all elements in the array, accumulated
+    /// in the wider type `B`.
+    ///
+    /// Summing many `f32` values in `f32` loses several significant digits;
+    /// `mean_in::<f64>()` converts each element to `f64` before adding it.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `B::from_usize()` fails to convert the number of elements in the array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::Array1;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = Array1::<f32>::from_elem(10_000_000, 0.1);
+    /// let mean: f64 = a.mean_in::<f64>().unwrap();
+    /// assert!((mean - 0.1).abs() < 1e-8);
+    /// ```
+    ///
+    /// [arithmetic mean]: #tymethod.mean
+    fn mean_in<B>(&self) -> Result<B, EmptyInput>
+    where
+        A: Copy + Into<B>,
+        B: Float + FromPrimitive;
+
+    /// Returns the [arithmetic mean] along `axis`, accumulated in the wider
+    /// type `B` (see [`mean_in`](#tymethod.mean_in)).
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `B::from_usize()` fails to
+    /// convert the length of `axis`.
+    ///
+    /// [arithmetic mean]: #tymethod.mean
+    fn mean_axis_in<B>(&self, axis: Axis) -> Result<Array<B, D::Smaller>, EmptyInput>
+    where
+        A: Copy + Into<B>,
+        B: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the variance along `axis`, accumulated in the wider type `B`
+    /// (see [`mean_in`](#tymethod.mean_in)).
+    ///
+    /// The parameter `ddof` has the same meaning as for `ArrayBase::var_axis`:
+    /// use `ddof = 0` for the population variance and `ddof = 1` for the
+    /// unbiased sample variance.
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `ddof` is less than zero or
+    /// greater than or equal to the length of `axis`, or if `B::from_usize()`
+    /// fails to convert the length of `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![[1f32, 2.], [3., 6.]];
+    /// assert_eq!(a.var_axis_in::<f64>(Axis(0), 1.).unwrap(), array![2., 8.]);
+    /// ```
+    fn var_axis_in<B>(&self, axis: Axis, ddof: B) -> Result<Array<B, D::Smaller>, EmptyInput>
+    where
+        A: Copy + Into<B>,
+        B: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Like [`var_axis_in`](#tymethod.var_axis_in), but returns
+    /// `Err(StatsError::InvalidDdof)` instead of panicking if `ddof` is
+    /// negative, NaN, or not smaller than the length of `axis`.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `B::from_usize()` fails to
+    /// convert the length of `axis`.
+    fn try_var_axis_in<B>(&self, axis: Axis, ddof: B) -> Result<Array<B, D::Smaller>, StatsError>
+    where
+        A: Copy + Into<B>,
+        B: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the variance along `axis`, computed with the given
+    /// [`VarianceAlgorithm`].
+    ///
+    /// The parameter `ddof` specifies the "delta degrees of freedom": use
+    /// `ddof = 0` for the population variance and `ddof = 1` for the unbiased
+    /// sample variance.
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `ddof` is less than zero or
+    /// greater than or equal to the length of `axis`, or if `A::from_usize()`
+    /// fails to convert the length of `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::{SummaryStatisticsExt, VarianceAlgorithm};
+    ///
+    /// let a = array![[1e9 + 4., 1e9 + 7.], [1e9 + 13., 1e9 + 16.]];
+    /// let two_pass = a.var_axis_with(Axis(1), 1., VarianceAlgorithm::TwoPass).unwrap();
+    /// let welford = a.var_axis_with(Axis(1), 1., VarianceAlgorithm::Welford).unwrap();
+    /// assert_eq!(two_pass, array![4.5, 4.5]);
+    /// assert_eq!(welford, array![4.5, 4.5]);
+    /// ```
+    ///
+    /// [`VarianceAlgorithm`]: enum.VarianceAlgorithm.html
+    fn var_axis_with(
+        &self,
+        axis: Axis,
+        ddof: A,
+        algorithm: VarianceAlgorithm,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Like [`var_axis_with`](#tymethod.var_axis_with), but returns
+    /// `Err(StatsError::InvalidDdof)` instead of panicking if `ddof` is
+    /// negative, NaN, or not smaller than the length of `axis`.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    fn try_var_axis_with(
+        &self,
+        axis: Axis,
+        ddof: A,
+        algorithm: VarianceAlgorithm,
+    ) -> Result<Array<A, D::Smaller>, StatsError>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the [arithmetic mean] of all elements in the array, with a
+    /// result that doesn't depend on the summation order.
+    ///
+    /// The elements are summed exactly with [`ExactSum`] and the sum is
+    /// rounded once, so the mean is the same bit for bit whatever the memory
+    /// layout of the array, the platform, or the way the data is chunked or
+    /// split between threads (as long as the same elements are summarized).
+    /// This is slower than [`mean`](#tymethod.mean).
+    ///
+    /// Histograms don't need a deterministic variant: they count elements
+    /// with integers, which is exact in any order.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of elements
+    /// in the array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![1e20, 1., -1e20, 3.];
+    /// assert_eq!(a.mean_deterministic(), Ok(1.));
+    /// ```
+    ///
+    /// [arithmetic mean]: #tymethod.mean
+    /// [`ExactSum`]: stream/struct.ExactSum.html
+    fn mean_deterministic(&self) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the variance of all elements in the array, with a result that
+    /// doesn't depend on the summation order (see
+    /// [`mean_deterministic`](#tymethod.mean_deterministic)).
+    ///
+    /// The mean and the sum of the squared deviations from it are both
+    /// computed with exact summation.
+    ///
+    /// The parameter `ddof` specifies the "delta degrees of freedom": use
+    /// `ddof = 0` for the population variance and `ddof = 1` for the unbiased
+    /// sample variance.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `ddof` is less than zero or greater than or equal to the
+    /// number of elements, or if `A::from_usize()` fails to convert the number
+    /// of elements in the array.
+    fn var_deterministic(&self, ddof: A) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Like [`var_deterministic`](#tymethod.var_deterministic), but returns
+    /// `Err(StatsError::InvalidDdof)` instead of panicking if `ddof` is
+    /// negative, NaN, or not smaller than the number of elements.
+    ///
+    /// **Panics** if `A::from_usize()` fails to convert the number of
+    /// elements in the array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::{errors::StatsError, SummaryStatisticsExt};
+    ///
+    /// let a = array![1., 2., 3.];
+    /// assert_eq!(a.try_var_deterministic(1.), Ok(1.));
+    /// assert_eq!(a.try_var_deterministic(3.), Err(StatsError::InvalidDdof));
+    /// ```
+    fn try_var_deterministic(&self, ddof: A) -> Result<A, StatsError>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the weighted [arithmetic mean] of all elements in the array:
+    ///
+    /// ```text
+    ///      n        ⎛ n   ⎞
+    /// x̅ = ( ∑ wᵢ xᵢ ) / ⎜ ∑ wᵢ⎟
+    ///     i=1       ⎝i=1  ⎠
+    /// ```
+    ///
+    /// `weights` must be broadcastable to the shape of the array (e.g. a
+    /// one-dimensional array of weights applies to the last axis). NaN
+    /// elements are skipped, together with their weights.
+    ///
+    /// Returns `Err(WeightedStatsError::ShapeMismatch)` if `weights` can't be
+    /// broadcast to the shape of the array,
+    /// `Err(WeightedStatsError::InvalidWeights)` if a weight is negative or
+    /// NaN, and `Err(WeightedStatsError::ZeroWeights)` if the weights of the
+    /// non-NaN elements sum to zero (in particular if the array is empty).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![1., 2., ::std::f64::NAN, 4.];
+    /// let weights = array![1., 1., 5., 2.];
+    /// assert_eq!(a.weighted_mean(&weights), Ok(2.75));
+    /// ```
+    ///
+    /// [arithmetic mean]: #tymethod.mean
+    fn weighted_mean<S2, E>(&self, weights: &ArrayBase<S2, E>) -> Result<A, WeightedStatsError>
+    where
+        A: Float,
+        S2: Data<Elem = A>,
+        E: Dimension;
+
+    /// Returns the weighted variance of all elements in the array:
+    ///
+    /// ```text
+    ///        n              ⎛ n         ⎞
+    /// σ² = ( ∑ wᵢ (xᵢ - x̅)² ) / ⎜ ∑ wᵢ - ddof⎟
+    ///       i=1             ⎝i=1        ⎠
+    /// ```
+    ///
+    /// where x̅ is the [weighted mean](#tymethod.weighted_mean). The weights
+    /// are interpreted as frequencies: with integer weights, `ddof = 1` gives
+    /// the unbiased sample variance of the data with each element repeated
+    /// `wᵢ` times, and `ddof = 0` gives the population variance.
+    ///
+    /// The broadcasting of `weights`, the handling of NaN elements and the
+    /// errors are the same as for [`weighted_mean`](#tymethod.weighted_mean);
+    /// in addition, `Err(WeightedStatsError::InvalidDdof)` is returned if
+    /// `ddof` is negative, NaN, or not smaller than the sum of the weights.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![1., 2., 4.];
+    /// let weights = array![1., 1., 2.];
+    /// assert_eq!(a.weighted_var(&weights, 0.), Ok(1.6875));
+    /// assert_eq!(a.weighted_var(&weights, 1.), Ok(2.25));
+    /// ```
+    fn weighted_var<S2, E>(
+        &self,
+        weights: &ArrayBase<S2, E>,
+        ddof: A,
+    ) -> Result<A, WeightedStatsError>
+    where
+        A: Float,
+        S2: Data<Elem = A>,
+        E: Dimension;
+
+    /// Returns the weighted standard deviation of all elements in the array,
+    /// the square root of [`weighted_var`](#tymethod.weighted_var), with the
+    /// same errors.
+    fn weighted_std<S2, E>(
+        &self,
+        weights: &ArrayBase<S2, E>,
+        ddof: A,
+    ) -> Result<A, WeightedStatsError>
+    where
+        A: Float,
+        S2: Data<Elem = A>,
+        E: Dimension;
+
+    /// Returns the sums of the elements weighted by `weights` along `axis`.
+    ///
+    /// `weights` must be broadcastable to the shape of the array. NaN elements
+    /// are skipped (they contribute zero to the sums), so the sum of an empty
+    /// lane or of a lane with zero weights is zero.
+    ///
+    /// Returns `Err(WeightedStatsError::ShapeMismatch)` if `weights` can't be
+    /// broadcast to the shape of the array, and
+    /// `Err(WeightedStatsError::InvalidWeights)` if a weight is negative or
+    /// NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![[1., 2.], [3., 4.]];
+    /// let weights = array![0.5, 2.];
+    /// assert_eq!(a.weighted_sum_axis(Axis(1), &weights), Ok(array![4.5, 9.5]));
+    /// ```
+    fn weighted_sum_axis<S2, E>(
+        &self,
+        axis: Axis,
+        weights: &ArrayBase<S2, E>,
+    ) -> Result<Array<A, D::Smaller>, WeightedStatsError>
+    where
+        A: Float,
+        S2: Data<Elem = A>,
+        E: Dimension,
+        D: RemoveAxis;
+
+    /// Returns the [arithmetic mean] along `axis`, computing the mean of the
+    /// lanes in parallel.
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![[1., 2.], [3., 4.]];
+    /// assert_eq!(a.par_mean_axis(Axis(0)).unwrap(), array![2., 3.]);
+    /// ```
+    ///
+    /// [arithmetic mean]: #tymethod.mean
+    #[cfg(feature = "rayon")]
+    fn par_mean_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Clone + FromPrimitive + Add<Output = A> + Div<Output = A> + Zero + Send + Sync,
+        D: RemoveAxis;
+
+    /// Returns the variance along `axis`, computing the variance of the lanes
+    /// in parallel.
+    ///
+    /// The parameter `ddof` has the same meaning as for `ArrayBase::var_axis`:
+    /// use `ddof = 0` for the population variance and `ddof = 1` for the
+    /// unbiased sample variance.
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `ddof` is less than zero or
+    /// greater than or equal to the length of `axis`, or if `A::from_usize()`
+    /// fails to convert the length of `axis`.
+    #[cfg(feature = "rayon")]
+    fn par_var_axis(&self, axis: Axis, ddof: A) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive + Send + Sync,
+        D: RemoveAxis;
+
+    /// Like [`par_var_axis`](#tymethod.par_var_axis), but returns
+    /// `Err(StatsError::InvalidDdof)` instead of panicking if `ddof` is
+    /// negative, NaN, or not smaller than the length of `axis`.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `A::from_usize()` fails to
+    /// convert the length of `axis`.
+    #[cfg(feature = "rayon")]
+    fn try_par_var_axis(&self, axis: Axis, ddof: A) -> Result<Array<A, D::Smaller>, StatsError>
+    where
+        A: Float + FromPrimitive + Send + Sync,
+        D: RemoveAxis;
+
+    /// Returns `scale` times the [median absolute deviation] (MAD) of all
+    /// elements in the array: the median of the absolute deviations of the
+    /// elements from their median.
+    ///
+    /// The MAD is a robust estimator of scale: up to half of the elements
+    /// can be arbitrarily large without making it arbitrarily large. With
+    /// `scale = 1.`, it is the raw MAD; with `scale = 1.4826` (the inverse of
+    /// the third quartile of the standard normal distribution), it is a
+    /// consistent estimator of the standard deviation of normally
+    /// distributed data.
+    ///
+    /// The medians are computed by selection in `O(n)` time. NaN elements
+    /// propagate to the result.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![1., 2., 3., 4., 100.];
+    /// // The absolute deviations from the median 3 are 2, 1, 0, 1 and 97.
+    /// assert_eq!(a.median_abs_deviation(1.), Ok(1.));
+    /// ```
+    ///
+    /// [median absolute deviation]: https://en.wikipedia.org/wiki/Median_absolute_deviation
+    fn median_abs_deviation(&self, scale: A) -> Result<A, EmptyInput>
+    where
+        A: Float;
+
+    /// Like [`median_abs_deviation`](#tymethod.median_abs_deviation), but
+    /// ignores NaN elements.
+    ///
+    /// If the array is empty, `Err(SkipNanError::EmptyInput)` is returned,
+    /// and if all its elements are NaN, `Err(SkipNanError::AllNan)` is
+    /// returned.
+    fn median_abs_deviation_skipnan(&self, scale: A) -> Result<A, SkipNanError>
+    where
+        A: Float;
+
+    /// Returns `scale` times the [median absolute deviation] of each lane
+    /// along `axis`, see
+    /// [`median_abs_deviation`](#tymethod.median_abs_deviation).
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![[1., 10.], [2., 20.], [3., 30.], [100., 40.]];
+    /// assert_eq!(a.median_abs_deviation_axis(Axis(0), 1.), Ok(array![1., 10.]));
+    /// ```
+    ///
+    /// [median absolute deviation]: https://en.wikipedia.org/wiki/Median_absolute_deviation
+    fn median_abs_deviation_axis(
+        &self,
+        axis: Axis,
+        scale: A,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float,
+        D: RemoveAxis;
+
+    /// Like [`median_abs_deviation_axis`](#tymethod.median_abs_deviation_axis),
+    /// but ignores NaN elements. Lanes without elements other than NaN are
+    /// mapped to NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    fn median_abs_deviation_axis_skipnan(&self, axis: Axis, scale: A) -> Array<A, D::Smaller>
+    where
+        A: Float,
+        D: RemoveAxis;
+
+    /// Returns the [trimmed mean] of all elements in the array: the mean of
+    /// the elements left after removing the `⌊fraction n⌋` smallest and the
+    /// `⌊fraction n⌋` largest ones.
+    ///
+    /// This matches `scipy.stats.trim_mean`. A `fraction` of `0.` gives the
+    /// arithmetic mean, and a `fraction` close to `0.5` the median (or a
+    /// mean of its two middle elements). The elements to remove are found
+    /// by selection in `O(n)` time. NaN elements propagate to the result.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `fraction` is not in `[0., 0.5)`, or if
+    /// `A::from_usize()` fails to convert the number of elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![4., 1., 100., 3., 2.];
+    /// // 1 and 100 are removed.
+    /// assert_eq!(a.trimmed_mean(0.2), Ok(3.));
+    /// ```
+    ///
+    /// [trimmed mean]: https://en.wikipedia.org/wiki/Truncated_mean
+    fn trimmed_mean(&self, fraction: A) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Like [`trimmed_mean`](#tymethod.trimmed_mean), but ignores NaN
+    /// elements: the fraction applies to the number of other elements.
+    ///
+    /// If the array is empty, `Err(SkipNanError::EmptyInput)` is returned,
+    /// and if all its elements are NaN, `Err(SkipNanError::AllNan)` is
+    /// returned.
+    ///
+    /// **Panics** if `fraction` is not in `[0., 0.5)`, or if
+    /// `A::from_usize()` fails to convert the number of elements.
+    fn trimmed_mean_skipnan(&self, fraction: A) -> Result<A, SkipNanError>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the [trimmed mean] of each lane along `axis`, see
+    /// [`trimmed_mean`](#tymethod.trimmed_mean).
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `fraction` is not in
+    /// `[0., 0.5)`, or if `A::from_usize()` fails to convert the length of
+    /// `axis`.
+    ///
+    /// [trimmed mean]: https://en.wikipedia.org/wiki/Truncated_mean
+    fn trimmed_mean_axis(
+        &self,
+        axis: Axis,
+        fraction: A,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Like [`trimmed_mean_axis`](#tymethod.trimmed_mean_axis), but ignores
+    /// NaN elements. Lanes without elements other than NaN are mapped to NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `fraction` is not in
+    /// `[0., 0.5)`, or if `A::from_usize()` fails to convert the length of
+    /// `axis`.
+    fn trimmed_mean_axis_skipnan(&self, axis: Axis, fraction: A) -> Array<A, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the [winsorized mean] of all elements in the array: the mean
+    /// of the elements after replacing the `⌊fraction n⌋` smallest ones by
+    /// the smallest remaining one, and the `⌊fraction n⌋` largest ones by the
+    /// largest remaining one.
+    ///
+    /// Like the trimmed mean, it limits the influence of outliers, but it
+    /// keeps their weight in the mean. The elements to replace are found by
+    /// selection in `O(n)` time. NaN elements propagate to the result.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `fraction` is not in `[0., 0.5)`, or if
+    /// `A::from_usize()` fails to convert the number of elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![4., 1., 100., 3., 2.];
+    /// // The mean of 2, 2, 3, 4 and 4.
+    /// assert_eq!(a.winsorized_mean(0.2), Ok(3.));
+    /// ```
+    ///
+    /// [winsorized mean]: https://en.wikipedia.org/wiki/Winsorized_mean
+    fn winsorized_mean(&self, fraction: A) -> Result<A, EmptyInput>
+    where
+        A: Float + FromPrimitive;
+
+    /// Like [`winsorized_mean`](#tymethod.winsorized_mean), but ignores NaN
+    /// elements: the fraction applies to the number of other elements.
+    ///
+    /// If the array is empty, `Err(SkipNanError::EmptyInput)` is returned,
+    /// and if all its elements are NaN, `Err(SkipNanError::AllNan)` is
+    /// returned.
+    ///
+    /// **Panics** if `fraction` is not in `[0., 0.5)`, or if
+    /// `A::from_usize()` fails to convert the number of elements.
+    fn winsorized_mean_skipnan(&self, fraction: A) -> Result<A, SkipNanError>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the [winsorized mean] of each lane along `axis`, see
+    /// [`winsorized_mean`](#tymethod.winsorized_mean).
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `fraction` is not in
+    /// `[0., 0.5)`, or if `A::from_usize()` fails to convert the length of
+    /// `axis`.
+    ///
+    /// [winsorized mean]: https://en.wikipedia.org/wiki/Winsorized_mean
+    fn winsorized_mean_axis(
+        &self,
+        axis: Axis,
+        fraction: A,
+    ) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Like [`winsorized_mean_axis`](#tymethod.winsorized_mean_axis), but
+    /// ignores NaN elements. Lanes without elements other than NaN are mapped
+    /// to NaN.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `fraction` is not in
+    /// `[0., 0.5)`, or if `A::from_usize()` fails to convert the length of
+    /// `axis`.
+    fn winsorized_mean_axis_skipnan(&self, axis: Axis, fraction: A) -> Array<A, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
+    /// Returns the [mode] of all elements in the array: the value that
+    /// occurs most often.
+    ///
+    /// If several values occur most often, the smallest one is returned, as
+    /// in `scipy.stats.mode`. Unlike the other statistics of this trait, it
+    /// is meant for discrete data (e.g. integers or categories), so floating
+    /// point elements must be wrapped in an `Ord` type like `noisy_float`'s
+    /// `N64` first.
+    ///
+    /// If the array is empty, `Err(EmptyInput)` is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![3, 1, 2, 3, 1];
+    /// // 1 and 3 both occur twice.
+    /// assert_eq!(a.mode(), Ok(1));
+    /// ```
+    ///
+    /// [mode]: https://en.wikipedia.org/wiki/Mode_(statistics)
+    fn mode(&self) -> Result<A, EmptyInput>
+    where
+        A: Clone + Hash + Ord;
+
+    /// Returns the [mode] of each lane along `axis`, with the same
+    /// tie-breaking as [`mode`](#tymethod.mode).
+    ///
+    /// If the length of `axis` is zero, `Err(EmptyInput)` is returned.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![[1, 2, 2], [3, 3, 1]];
+    /// assert_eq!(a.mode_axis(Axis(1)), Ok(array![2, 3]));
+    /// assert_eq!(a.mode_axis(Axis(0)), Ok(array![1, 2, 1]));
+    /// ```
+    ///
+    /// [mode]: https://en.wikipedia.org/wiki/Mode_(statistics)
+    fn mode_axis(&self, axis: Axis) -> Result<Array<A, D::Smaller>, EmptyInput>
+    where
+        A: Clone + Hash + Ord,
+        D: RemoveAxis;
+
+    /// Returns the distinct elements of the array with the number of times
+    /// each one occurs.
+    ///
+    /// The map is ordered by decreasing count and, among values with the same
+    /// count, by increasing value, so that its first key is the
+    /// [`mode`](#tymethod.mode). It is empty if the array is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array!["b", "a", "c", "a", "b", "a"];
+    /// let counts: Vec<_> = a.value_counts().into_iter().collect();
+    /// assert_eq!(counts, vec![("a", 3), ("b", 2), ("c", 1)]);
+    /// ```
+    fn value_counts(&self) -> IndexMap<A, usize>
+    where
+        A: Clone + Hash + Ord;
+
+    /// Returns the count, the number of NaN elements, the mean, the standard
+    /// deviation, the minimum, the quartiles and the maximum of all elements
+    /// in the array, like pandas' `describe`.
+    ///
+    /// NaN elements are skipped by all the statistics but `nan_count`; see
+    /// [`SummaryReport`] for the statistics of arrays with fewer than two
+    /// other elements. The standard deviation is the sample one (`ddof = 1`)
+    /// and the quartiles are linearly interpolated, as in pandas.
+    ///
+    /// The elements are copied once, while accumulating the mean and the
+    /// variance; the quartiles, the minimum and the maximum are then
+    /// selected together in a single partition of the copy rather than with
+    /// a partial sort for each of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::array;
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![3., 1., ::std::f64::NAN, 4., 2., 5.];
+    /// let report = a.describe();
+    /// assert_eq!((report.count, report.nan_count), (5, 1));
+    /// assert_eq!(report.mean, 3.);
+    /// assert_eq!(report.std, 2.5f64.sqrt());
+    /// assert_eq!((report.min, report.max), (1., 5.));
+    /// assert_eq!(
+    ///     (report.lower_quartile, report.median, report.upper_quartile),
+    ///     (2., 3., 4.)
+    /// );
+    /// ```
+    ///
+    /// [`SummaryReport`]: struct.SummaryReport.html
+    fn describe(&self) -> SummaryReport<A>
+    where
+        A: Float + FromPrimitive;
+
+    /// Returns the [summary report](#tymethod.describe) of each lane along
+    /// `axis`.
+    ///
+    /// If the length of `axis` is zero, every report has a count of zero.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::SummaryStatisticsExt;
+    ///
+    /// let a = array![[1., 10.], [2., ::std::f64::NAN], [3., 30.]];
+    /// let reports = a.describe_axis(Axis(0));
+    /// assert_eq!(reports[0].median, 2.);
+    /// assert_eq!(reports[1].median, 20.);
+    /// assert_eq!(reports[1].nan_count, 1);
+    /// ```
+    fn describe_axis(&self, axis: Axis) -> Array<SummaryReport<A>, D::Smaller>
+    where
+        A: Float + FromPrimitive,
+        D: RemoveAxis;
+
     private_decl! {}
 }
 
+mod counts;
+mod describe;
+mod hodges_lehmann;
 mod means;
+mod robust;
@@ -0,0 +1,187 @@
+//! Robust estimators of location and scale, computed with selection instead
+//! of a full sort.
+use crate::sort::select_nth_by;
+use core::cmp::Ordering;
+use ndarray::{Array, ArrayBase, Axis, Data, RemoveAxis};
+use num_traits::{Float, FromPrimitive};
+
+fn compare<A: Float>(a: &A, b: &A) -> Ordering {
+    a.partial_cmp(b).expect("NaN values have been excluded.")
+}
+
+fn to_a<A: FromPrimitive>(n: usize) -> A {
+    A::from_usize(n).expect("Converting the number of elements to `A` must not fail.")
+}
+
+/// Returns the median of `xs`, which must not be empty nor contain NaN,
+/// reordering it.
+fn median_mut<A: Float>(xs: &mut [A]) -> A {
+    let n = xs.len();
+    let (lower, &mut upper_median, _) = select_nth_by(xs, n / 2, compare);
+    if n % 2 == 1 {
+        upper_median
+    } else {
+        let lower_median = lower.iter().cloned().fold(A::neg_infinity(), A::max);
+        (lower_median + upper_median) / (A::one() + A::one())
+    }
+}
+
+/// Returns `scale` times the median absolute deviation of `xs` from its
+/// median; `xs` must not be empty. NaN values propagate to the result.
+pub(super) fn median_abs_deviation<A: Float>(mut xs: Vec<A>, scale: A) -> A {
+    if xs.iter().any(|x| x.is_nan()) {
+        return A::nan();
+    }
+    let median = median_mut(&mut xs);
+    for x in xs.iter_mut() {
+        *x = (*x - median).abs();
+    }
+    scale * median_mut(&mut xs)
+}
+
+/// Returns the number of elements cut from each end of a sample of `n`
+/// elements for a trimmed or winsorized mean.
+///
+/// **Panics** if `fraction` is not in `[0, 0.5)`.
+fn n_cut<A: Float>(n: usize, fraction: A) -> usize {
+    assert!(
+        fraction >= A::zero() && fraction < A::from(0.5).unwrap(),
+        "The fraction to cut must be in [0, 0.5)."
+    );
+    (to_f64(fraction) * n as f64).floor() as usize
+}
+
+fn to_f64<A: Float>(x: A) -> f64 {
+    x.to_f64()
+        .expect("Converting the fraction to `f64` must not fail.")
+}
+
+/// Partitions `xs` into the `g` smallest elements, the middle ones and the
+/// `g` largest ones, and returns the sum of the middle elements together with
+/// the smallest and the largest of them.
+fn partition_ends<A: Float>(xs: &mut [A], g: usize) -> (A, A, A) {
+    let n = xs.len();
+    let (_, &mut low, rest) = select_nth_by(xs, g, compare);
+    // `rest` holds the `n - g - 1` elements after `low`, of which the last
+    // `g` are the largest.
+    let high = if n - 2 * g == 1 {
+        low
+    } else {
+        let (_, &mut high, _) = select_nth_by(rest, n - 2 * g - 2, compare);
+        high
+    };
+    let sum = xs[g..n - g].iter().fold(A::zero(), |acc, &x| acc + x);
+    (sum, low, high)
+}
+
+/// Returns the mean of `xs` without the `fraction` smallest and the
+/// `fraction` largest elements; `xs` must not be empty. NaN values propagate
+/// to the result.
+pub(super) fn trimmed_mean<A: Float + FromPrimitive>(mut xs: Vec<A>, fraction: A) -> A {
+    let n = xs.len();
+    let g = n_cut(n, fraction);
+    if xs.iter().any(|x| x.is_nan()) {
+        return A::nan();
+    }
+    let (sum, _, _) = partition_ends(&mut xs, g);
+    sum / to_a(n - 2 * g)
+}
+
+/// Returns the mean of `xs` after replacing the `fraction` smallest elements
+/// by the smallest remaining one, and the `fraction` largest by the largest
+/// remaining one; `xs` must not be empty. NaN values propagate to the result.
+pub(super) fn winsorized_mean<A: Float + FromPrimitive>(mut xs: Vec<A>, fraction: A) -> A {
+    let n = xs.len();
+    let g = n_cut(n, fraction);
+    if xs.iter().any(|x| x.is_nan()) {
+        return A::nan();
+    }
+    let (sum, low, high) = partition_ends(&mut xs, g);
+    (sum + to_a::<A>(g) * (low + high)) / to_a(n)
+}
+
+/// Applies `statistic` to the elements of each lane of `a` along `axis`,
+/// without the NaN ones if `skipnan` is `true`.
+///
+/// Lanes without elements are mapped to NaN.
+pub(super) fn map_lanes<A, S, D, F>(
+    a: &ArrayBase<S, D>,
+    axis: Axis,
+    skipnan: bool,
+    statistic: F,
+) -> Array<A, D::Smaller>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: RemoveAxis,
+    F: Fn(Vec<A>) -> A,
+{
+    if a.len_of(axis) == 0 {
+        return Array::from_elem(a.raw_dim().remove_axis(axis), A::nan());
+    }
+    a.map_axis(axis, |lane| {
+        let xs: Vec<A> = lane
+            .iter()
+            .cloned()
+            .filter(|x| !(skipnan && x.is_nan()))
+            .collect();
+        if xs.is_empty() {
+            A::nan()
+        } else {
+            statistic(xs)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck_macros::quickcheck;
+
+    fn sorted(xs: &[i16]) -> Vec<f64> {
+        let mut xs: Vec<f64> = xs.iter().map(|&x| f64::from(x)).collect();
+        xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        xs
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-9 * b.abs().max(1.)
+    }
+
+    #[quickcheck]
+    fn trimmed_and_winsorized_means_match_sorting(xs: Vec<i16>, percent: u8) -> bool {
+        if xs.is_empty() {
+            return true;
+        }
+        let fraction = f64::from(percent % 50) / 100.;
+        let s = sorted(&xs);
+        let n = s.len();
+        let g = (fraction * n as f64).floor() as usize;
+        let trimmed = s[g..n - g].iter().sum::<f64>() / (n - 2 * g) as f64;
+        let winsorized = s
+            .iter()
+            .map(|&x| x.max(s[g]).min(s[n - g - 1]))
+            .sum::<f64>()
+            / n as f64;
+        let xs: Vec<f64> = xs.into_iter().map(f64::from).collect();
+        close(trimmed_mean(xs.clone(), fraction), trimmed)
+            && close(winsorized_mean(xs, fraction), winsorized)
+    }
+
+    #[quickcheck]
+    fn median_abs_deviation_matches_sorting(xs: Vec<i16>) -> bool {
+        if xs.is_empty() {
+            return true;
+        }
+        let median_of = |s: &[f64]| {
+            let n = s.len();
+            (s[(n - 1) / 2] + s[n / 2]) / 2.
+        };
+        let s = sorted(&xs);
+        let median = median_of(&s);
+        let mut deviations: Vec<f64> = s.iter().map(|x| (x - median).abs()).collect();
+        deviations.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let xs: Vec<f64> = xs.into_iter().map(f64::from).collect();
+        median_abs_deviation(xs, 1.) == median_of(&deviations)
+    }
+}
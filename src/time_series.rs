@@ -0,0 +1,600 @@
+//! Transforms of time series stored along an axis of an array.
+use crate::MaybeNan;
+use core::ops::Sub;
+use ndarray::{s, Array, ArrayBase, ArrayView1, Axis, Data, Dimension, Slice, Zip};
+use num_traits::Float;
+
+/// Extension trait for `ArrayBase` providing transforms of time series.
+///
+/// Each lane along the chosen `axis` is treated as a time series whose
+/// elements are equally spaced observations, oldest first; all the lanes are
+/// transformed independently.
+pub trait TimeSeriesExt<A, S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    /// Returns the `n`th order difference along `axis`.
+    ///
+    /// The first order difference is `out[i] = a[i + 1] - a[i]`, and higher
+    /// order differences are computed by applying it recursively. Differencing
+    /// removes polynomial trends of degree less than `n`, which is usually
+    /// needed to make a series stationary before analysing its
+    /// autocorrelation.
+    ///
+    /// The result is shorter than the input by `n` along `axis` (it is empty
+    /// if `n` is greater than or equal to the length of `axis`); `n = 0`
+    /// returns a copy of the input. Use
+    /// [`diff_axis_lagged`](#tymethod.diff_axis_lagged) for seasonal
+    /// differences.
+    ///
+    /// This is equivalent to NumPy's `diff(a, n, axis)`.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::TimeSeriesExt;
+    ///
+    /// let a = array![1, 4, 9, 16, 25];
+    /// assert_eq!(a.diff_axis(Axis(0), 1), array![3, 5, 7, 9]);
+    /// assert_eq!(a.diff_axis(Axis(0), 2), array![2, 2, 2]);
+    /// ```
+    fn diff_axis(&self, axis: Axis, n: usize) -> Array<A, D>
+    where
+        A: Clone + Sub<Output = A>;
+
+    /// Returns the difference between each element and the element `lag`
+    /// positions before it along `axis`: `out[i] = a[i + lag] - a[i]`.
+    ///
+    /// With `lag` equal to the length of a season (e.g. 12 for monthly data
+    /// with a yearly cycle) this is the seasonal difference, which removes a
+    /// periodic component of that length.
+    ///
+    /// The result is shorter than the input by `lag` along `axis` (it is
+    /// empty if `lag` is greater than or equal to the length of `axis`). For
+    /// a result with the same shape as the input, padded with NaN at the
+    /// start (like pandas' `diff`), compute
+    /// `&a - &a.shift_axis(axis, lag as isize)` instead.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::TimeSeriesExt;
+    ///
+    /// // Two quarterly series with a seasonal pattern and a linear trend.
+    /// let a = array![[1., 5., 2., 8., 2., 6., 3., 9.], [0., 0., 1., 1., 2., 2., 3., 3.]];
+    /// assert_eq!(
+    ///     a.diff_axis_lagged(Axis(1), 4),
+    ///     array![[1., 1., 1., 1.], [2., 2., 2., 2.]]
+    /// );
+    /// ```
+    fn diff_axis_lagged(&self, axis: Axis, lag: usize) -> Array<A, D>
+    where
+        A: Clone + Sub<Output = A>;
+
+    /// Shifts the elements by `periods` positions along `axis`, filling the
+    /// vacated positions with NaN.
+    ///
+    /// A positive `periods` moves the elements forward (towards higher
+    /// indices), so that `out[i] = a[i - periods]`: this aligns each element
+    /// with the value observed `periods` steps earlier (a lagged feature). A
+    /// negative `periods` moves them backward. Elements shifted past the end
+    /// of the axis are dropped, and the result has the same shape as the
+    /// input. See [`shift_axis_with`](#tymethod.shift_axis_with) for a
+    /// different fill value.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, s, Axis};
+    /// use ndarray_stats::TimeSeriesExt;
+    ///
+    /// let a = array![1f64, 2., 3., 4.];
+    /// let lagged = a.shift_axis(Axis(0), 1);
+    /// assert!(lagged[0].is_nan());
+    /// assert_eq!(lagged.slice(s![1..]), array![1., 2., 3.]);
+    /// let ahead = a.shift_axis(Axis(0), -3);
+    /// assert_eq!(ahead[0], 4.);
+    /// assert!(ahead.iter().skip(1).all(|x| x.is_nan()));
+    /// ```
+    fn shift_axis(&self, axis: Axis, periods: isize) -> Array<A, D>
+    where
+        A: MaybeNan + Clone;
+
+    /// Shifts the elements by `periods` positions along `axis`, filling the
+    /// vacated positions with `fill`.
+    ///
+    /// See [`shift_axis`](#tymethod.shift_axis) for the meaning of
+    /// `periods`.
+    ///
+    /// **Panics** if `axis` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::TimeSeriesExt;
+    ///
+    /// let a = array![[1, 2, 3], [4, 5, 6]];
+    /// assert_eq!(a.shift_axis_with(Axis(1), 2, 0), array![[0, 0, 1], [0, 0, 4]]);
+    /// ```
+    fn shift_axis_with(&self, axis: Axis, periods: isize, fill: A) -> Array<A, D>
+    where
+        A: Clone;
+
+    /// Applies `f` to the sliding windows of length `window` along `axis`.
+    ///
+    /// The element `i` of the result is computed from the window ending at
+    /// element `i` (included), i.e. the (at most) `window` elements
+    /// `max(0, i + 1 - window)..=i`; the windows at the start of each lane are
+    /// shorter. `f` is only called if the window contains at least
+    /// `min_periods` elements that aren't NaN, and the result is NaN
+    /// otherwise. `f` receives the whole window, including its NaN elements.
+    ///
+    /// The result has the same shape as the input.
+    ///
+    /// **Panics** if `axis` is out of bounds, if `window` is zero or if
+    /// `min_periods` is greater than `window`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, s, Axis};
+    /// use ndarray_stats::{QuantileExt, TimeSeriesExt};
+    ///
+    /// let a = array![1., 5., ::std::f64::NAN, 2., 4., 3.];
+    /// // Rolling maximum over 3 elements, skipping NaN values and requiring
+    /// // at least 2 valid elements.
+    /// let max = a.rolling_apply_axis(Axis(0), 3, 2, |w| *w.max_skipnan());
+    /// assert!(max[0].is_nan());
+    /// assert_eq!(max.slice(s![1..]), array![5., 5., 5., 4., 4.]);
+    /// ```
+    fn rolling_apply_axis<B, F>(
+        &self,
+        axis: Axis,
+        window: usize,
+        min_periods: usize,
+        f: F,
+    ) -> Array<B, D>
+    where
+        A: MaybeNan,
+        B: MaybeNan,
+        F: FnMut(ArrayView1<'_, A>) -> B;
+
+    /// Returns the exponentially weighted moving average along `axis`.
+    ///
+    /// Element `i` of the result is the weighted mean of the elements up to
+    /// `i` (included), where the element `j` positions before `i` has weight
+    /// `(1 - alpha)ʲ`:
+    ///
+    /// ```text
+    ///        ∑ⱼ (1 - α)ʲ xᵢ₋ⱼ
+    /// ewmᵢ = ――――――――――――――――
+    ///          ∑ⱼ (1 - α)ʲ
+    /// ```
+    ///
+    /// The mean is updated recursively, so each lane is read once. NaN
+    /// elements are skipped, but still count when computing the age of the
+    /// other elements; the result at a NaN position is the previous average.
+    /// The result is NaN before the first element that isn't NaN.
+    ///
+    /// This is equivalent to `pandas.Series.ewm(alpha=alpha).mean()`.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `alpha` is not in `(0, 1]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::TimeSeriesExt;
+    ///
+    /// let a = array![1., 2., 3.];
+    /// let ewm = a.ewm_mean_axis(Axis(0), 0.5);
+    /// assert!(ewm.all_close(&array![1., 5. / 3., 17. / 7.], 1e-12));
+    /// ```
+    fn ewm_mean_axis(&self, axis: Axis, alpha: A) -> Array<A, D>
+    where
+        A: Float;
+
+    /// Returns the exponentially weighted moving variance along `axis`.
+    ///
+    /// The weights are the ones of [`ewm_mean_axis`], and the variance is the
+    /// weighted mean of the squared deviations from the exponentially
+    /// weighted mean, updated recursively with West's algorithm (the weighted
+    /// version of Welford's algorithm). If `bias` is `false`, the variance is
+    /// multiplied by the correction factor `(∑ wⱼ)² / ((∑ wⱼ)² - ∑ wⱼ²)`,
+    /// which makes it an unbiased estimate for independent observations; the
+    /// unbiased variance is NaN while there is only one observation.
+    ///
+    /// NaN elements are handled as in [`ewm_mean_axis`].
+    ///
+    /// This is equivalent to `pandas.Series.ewm(alpha=alpha).var(bias=bias)`.
+    ///
+    /// **Panics** if `axis` is out of bounds or if `alpha` is not in `(0, 1]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ndarray::{array, Axis};
+    /// use ndarray_stats::TimeSeriesExt;
+    ///
+    /// let a = array![1f64, 3., 1.];
+    /// let var = a.ewm_var_axis(Axis(0), 0.5, true);
+    /// assert!(var.all_close(&array![0., 8. / 9., 40. / 49.], 1e-12));
+    /// let var = a.ewm_var_axis(Axis(0), 0.5, false);
+    /// assert!(var[0].is_nan());
+    /// assert!((var[1] - 2.).abs() < 1e-12);
+    /// ```
+    ///
+    /// [`ewm_mean_axis`]: #tymethod.ewm_mean_axis
+    fn ewm_var_axis(&self, axis: Axis, alpha: A, bias: bool) -> Array<A, D>
+    where
+        A: Float;
+
+    /// Returns the exponentially weighted moving standard deviation along
+    /// `axis`, the square root of [`ewm_var_axis`](#tymethod.ewm_var_axis).
+    ///
+    /// **Panics** if `axis` is out of bounds or if `alpha` is not in `(0, 1]`.
+    fn ewm_std_axis(&self, axis: Axis, alpha: A, bias: bool) -> Array<A, D>
+    where
+        A: Float;
+
+    private_decl! {}
+}
+
+impl<A, S, D> TimeSeriesExt<A, S, D> for ArrayBase<S, D>
+where
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    fn diff_axis(&self, axis: Axis, n: usize) -> Array<A, D>
+    where
+        A: Clone + Sub<Output = A>,
+    {
+        let mut diff = self.to_owned();
+        for _ in 0..n.min(self.len_of(axis)) {
+            diff = diff.diff_axis_lagged(axis, 1);
+        }
+        diff
+    }
+
+    fn diff_axis_lagged(&self, axis: Axis, lag: usize) -> Array<A, D>
+    where
+        A: Clone + Sub<Output = A>,
+    {
+        let lag = lag.min(self.len_of(axis));
+        let later = self.slice_axis(axis, Slice::from(lag..));
+        let earlier = self.slice_axis(axis, Slice::from(..self.len_of(axis) - lag));
+        // Collecting by hand instead of subtracting the views: ndarray
+        // trips a debug assertion when copying empty views with strides.
+        let diff = later
+            .iter()
+            .zip(&earlier)
+            .map(|(x, y)| x.clone() - y.clone())
+            .collect();
+        Array::from_shape_vec(later.raw_dim(), diff).unwrap()
+    }
+
+    fn shift_axis(&self, axis: Axis, periods: isize) -> Array<A, D>
+    where
+        A: MaybeNan + Clone,
+    {
+        self.shift_axis_with(axis, periods, A::from_not_nan_opt(None))
+    }
+
+    fn shift_axis_with(&self, axis: Axis, periods: isize, fill: A) -> Array<A, D>
+    where
+        A: Clone,
+    {
+        let len = self.len_of(axis);
+        // The absolute value of `periods`, without overflowing on `isize::MIN`.
+        let magnitude = if periods < 0 {
+            (periods as usize).wrapping_neg()
+        } else {
+            periods as usize
+        };
+        let offset = magnitude.min(len);
+        let mut out = Array::from_elem(self.raw_dim(), fill);
+        let (src, dst) = if periods >= 0 {
+            (Slice::from(..len - offset), Slice::from(offset..))
+        } else {
+            (Slice::from(offset..), Slice::from(..len - offset))
+        };
+        out.slice_axis_mut(axis, dst)
+            .assign(&self.slice_axis(axis, src));
+        out
+    }
+
+    fn rolling_apply_axis<B, F>(
+        &self,
+        axis: Axis,
+        window: usize,
+        min_periods: usize,
+        mut f: F,
+    ) -> Array<B, D>
+    where
+        A: MaybeNan,
+        B: MaybeNan,
+        F: FnMut(ArrayView1<'_, A>) -> B,
+    {
+        assert!(window > 0, "`window` must be positive.");
+        assert!(
+            min_periods <= window,
+            "`min_periods` must be less than or equal to `window`."
+        );
+        let mut out = self.map(|_| B::from_not_nan_opt(None));
+        Zip::from(out.lanes_mut(axis))
+            .and(self.lanes(axis))
+            .apply(|mut out, lane| {
+                let mut n_valid = 0;
+                for i in 0..lane.len() {
+                    if !lane[i].is_nan() {
+                        n_valid += 1;
+                    }
+                    if i >= window && !lane[i - window].is_nan() {
+                        n_valid -= 1;
+                    }
+                    if n_valid >= min_periods {
+                        let start = (i + 1).saturating_sub(window);
+                        out[i] = f(lane.slice(s![start..=i]));
+                    }
+                }
+            });
+        out
+    }
+
+    fn ewm_mean_axis(&self, axis: Axis, alpha: A) -> Array<A, D>
+    where
+        A: Float,
+    {
+        ewm_axis(self, axis, alpha, |state| state.mean)
+    }
+
+    fn ewm_var_axis(&self, axis: Axis, alpha: A, bias: bool) -> Array<A, D>
+    where
+        A: Float,
+    {
+        ewm_axis(self, axis, alpha, |state| state.var(bias))
+    }
+
+    fn ewm_std_axis(&self, axis: Axis, alpha: A, bias: bool) -> Array<A, D>
+    where
+        A: Float,
+    {
+        ewm_axis(self, axis, alpha, |state| state.var(bias).sqrt())
+    }
+
+    private_impl! {}
+}
+
+/// State of the recursive computation of exponentially weighted moments.
+struct EwmState<A> {
+    /// Sum of the weights of the observations.
+    sum_weights: A,
+    /// Sum of the squared weights of the observations.
+    sum_sq_weights: A,
+    mean: A,
+    /// Weighted sum of the squared deviations from the mean.
+    m2: A,
+}
+
+impl<A: Float> EwmState<A> {
+    /// Ages the observations by one step, and adds `x` (unless it is NaN)
+    /// with weight one.
+    fn update(&mut self, x: A, decay: A) {
+        self.sum_weights = self.sum_weights * decay;
+        self.sum_sq_weights = self.sum_sq_weights * decay * decay;
+        self.m2 = self.m2 * decay;
+        if x.is_nan() {
+            return;
+        }
+        self.sum_weights = self.sum_weights + A::one();
+        self.sum_sq_weights = self.sum_sq_weights + A::one();
+        let delta = x - self.mean;
+        self.mean = self.mean + delta / self.sum_weights;
+        self.m2 = self.m2 + delta * (x - self.mean);
+    }
+
+    fn var(&self, bias: bool) -> A {
+        if bias {
+            self.m2 / self.sum_weights
+        } else {
+            let sq_sum_weights = self.sum_weights * self.sum_weights;
+            self.m2 * self.sum_weights / (sq_sum_weights - self.sum_sq_weights)
+        }
+    }
+}
+
+/// Computes `value` of the exponentially weighted moments of each prefix of
+/// the lanes of `a` along `axis`.
+fn ewm_axis<A, S, D, F>(a: &ArrayBase<S, D>, axis: Axis, alpha: A, value: F) -> Array<A, D>
+where
+    A: Float,
+    S: Data<Elem = A>,
+    D: Dimension,
+    F: Fn(&EwmState<A>) -> A,
+{
+    assert!(
+        alpha > A::zero() && alpha <= A::one(),
+        "`alpha` must be in the interval (0, 1]."
+    );
+    let decay = A::one() - alpha;
+    let mut out = a.map(|_| A::nan());
+    Zip::from(out.lanes_mut(axis))
+        .and(a.lanes(axis))
+        .apply(|mut out, lane| {
+            let mut state = EwmState {
+                sum_weights: A::zero(),
+                sum_sq_weights: A::zero(),
+                mean: A::zero(),
+                m2: A::zero(),
+            };
+            for (out, &x) in out.iter_mut().zip(&lane) {
+                state.update(x, decay);
+                if state.sum_weights > A::zero() {
+                    *out = value(&state);
+                }
+            }
+        });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::{array, Array1, Array2};
+    use quickcheck_macros::quickcheck;
+    use std::f64;
+
+    #[quickcheck]
+    fn diff_of_cumsum_recovers_the_series(xs: Vec<i32>) -> bool {
+        let xs: Array1<i64> = xs.into_iter().map(i64::from).collect();
+        let mut cumsum = Array1::zeros(xs.len() + 1);
+        for (i, &x) in xs.iter().enumerate() {
+            cumsum[i + 1] = cumsum[i] + x;
+        }
+        cumsum.diff_axis(Axis(0), 1) == xs
+    }
+
+    #[quickcheck]
+    fn rolling_apply_matches_naive_windows(
+        xs: Vec<Option<i32>>,
+        window: u8,
+        min_periods: u8,
+    ) -> bool {
+        let window = usize::from(window) % 8 + 1;
+        let min_periods = usize::from(min_periods) % (window + 1);
+        let a: Array1<f64> = xs.iter().map(|x| x.map_or(f64::NAN, f64::from)).collect();
+        let sums = a.rolling_apply_axis(Axis(0), window, min_periods, |w| {
+            w.iter().filter(|x| !x.is_nan()).sum::<f64>()
+        });
+        (0..a.len()).all(|i| {
+            let w = &xs[(i + 1).saturating_sub(window)..=i];
+            let valid: Vec<f64> = w.iter().filter_map(|x| x.map(f64::from)).collect();
+            if valid.len() >= min_periods {
+                sums[i] == valid.iter().sum::<f64>()
+            } else {
+                sums[i].is_nan()
+            }
+        })
+    }
+
+    #[test]
+    fn test_rolling_apply_axis_along_rows() {
+        let a = array![[1., 2., 3., 4.], [10., 20., 30., 40.]];
+        let ranges = a.rolling_apply_axis(Axis(1), 2, 2, |w| w[w.len() - 1] - w[0]);
+        assert!(ranges.column(0).iter().all(|x| x.is_nan()));
+        assert_eq!(
+            ranges.slice(s![.., 1..]),
+            array![[1., 1., 1.], [10., 10., 10.]]
+        );
+        let counts = a.rolling_apply_axis(Axis(0), 5, 0, |w| Some(w.len() as f64));
+        assert_eq!(
+            counts,
+            Array2::from_shape_fn((2, 4), |(i, _)| Some((i + 1) as f64))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rolling_apply_axis_min_periods_too_large() {
+        array![1., 2.].rolling_apply_axis(Axis(0), 2, 3, |w| w[0]);
+    }
+
+    #[test]
+    fn test_ewm_matches_definition() {
+        let nan = f64::NAN;
+        let a = array![[1., 4., nan, 2., 8., 5.], [nan, 3., 3., 3., 3., 3.]];
+        let close = |x: &Array2<f64>, y: Array2<f64>| {
+            x.iter()
+                .zip(&y)
+                .all(|(&x, &y)| (x.is_nan() && y.is_nan()) || (x - y).abs() < 1e-12)
+        };
+        // Computed from the definitions, with explicit weights.
+        let expected_mean = array![
+            [
+                1.,
+                2.764705882352941,
+                2.764705882352941,
+                2.347517730496454,
+                4.823310411282906,
+                4.891315810743716
+            ],
+            [nan, 3., 3., 3., 3., 3.]
+        ];
+        assert!(close(&a.ewm_mean_axis(Axis(1), 0.3), expected_mean));
+        let expected_var = array![
+            [
+                nan,
+                4.5,
+                4.5,
+                1.9057758198727368,
+                12.494911887483726,
+                7.165332337300743
+            ],
+            [nan, nan, 0., 0., 0., 0.]
+        ];
+        assert!(close(&a.ewm_var_axis(Axis(1), 0.3, false), expected_var));
+        let std = a.ewm_std_axis(Axis(1), 0.3, true);
+        assert_eq!(std[[0, 0]], 0.);
+        assert!(std[[1, 0]].is_nan());
+    }
+
+    #[quickcheck]
+    fn shift_axis_moves_elements(xs: Vec<i32>, periods: i8) -> bool {
+        let a = Array1::from(xs.clone());
+        let shifted = a.shift_axis_with(Axis(0), isize::from(periods), -1);
+        (0..xs.len() as isize).all(|i| {
+            let src = i - isize::from(periods);
+            let expected = if src >= 0 && src < xs.len() as isize {
+                xs[src as usize]
+            } else {
+                -1
+            };
+            shifted[i as usize] == expected
+        })
+    }
+
+    #[test]
+    fn test_shift_axis_2d() {
+        let a = array![[1., 2.], [3., 4.], [5., 6.]];
+        let shifted = a.shift_axis(Axis(0), -1);
+        assert_eq!(shifted.slice(s![..2, ..]), array![[3., 4.], [5., 6.]]);
+        assert!(shifted.row(2).iter().all(|x| x.is_nan()));
+        assert_eq!(
+            a.shift_axis(Axis(1), 5).mapv(|x| x.is_nan()),
+            Array2::from_elem((3, 2), true)
+        );
+        assert_eq!(a.shift_axis(Axis(1), 0), a);
+        assert_eq!(
+            array![Some(1.), None].shift_axis(Axis(0), 1),
+            array![None, Some(1.)]
+        );
+    }
+
+    #[test]
+    fn test_diff_axis_shapes() {
+        let a = Array2::<f64>::zeros((3, 5));
+        assert_eq!(a.diff_axis(Axis(0), 0).shape(), &[3, 5]);
+        assert_eq!(a.diff_axis(Axis(0), 2).shape(), &[1, 5]);
+        assert_eq!(a.diff_axis(Axis(0), 4).shape(), &[0, 5]);
+        assert_eq!(a.diff_axis(Axis(1), 3).shape(), &[3, 2]);
+        assert_eq!(a.diff_axis_lagged(Axis(1), 7).shape(), &[3, 0]);
+    }
+
+    #[test]
+    fn test_diff_axis_matches_numpy() {
+        let a = array![[1., 2., 4., 7., 0.], [3., 1., 4., 1., 5.]];
+        // numpy.diff(a, n=3, axis=1)
+        assert_eq!(a.diff_axis(Axis(1), 3), array![[0., -11.], [-11., 13.]]);
+        assert_eq!(a.diff_axis(Axis(0), 1), array![[2., -1., 0., -6., 5.]]);
+    }
+}
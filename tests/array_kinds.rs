@@ -0,0 +1,123 @@
+//! The extension traits are implemented for all `ArrayBase<S, D>`: these
+//! tests check that they give the same results for owned arrays, shared
+//! (`ArcArray`) arrays and views with non-standard strides.
+use approx::assert_abs_diff_eq;
+use ndarray::prelude::*;
+use ndarray::{array, s, ArcArray};
+use ndarray_stats::{
+    histogram::{Bins, Edges, Grid},
+    interpolate::Linear,
+    CorrelationExt, EntropyExt, HistogramExt, MaskedExt, Quantile1dExt, QuantileExt, Sort1dExt,
+    SummaryStatisticsExt,
+};
+use noisy_float::types::n64;
+
+fn data() -> Array2<f64> {
+    Array::from_shape_fn((4, 6), |(i, j)| ((7 * i + 3 * j) % 11) as f64 + 0.5)
+}
+
+#[test]
+fn test_summary_statistics() {
+    let owned = data();
+    let shared: ArcArray<f64, Ix2> = owned.clone().into_shared();
+    let strided = owned.slice(s![..;-1, ..;2]).to_owned();
+    let view = owned.slice(s![..;-1, ..;2]);
+    assert_eq!(shared.mean(), owned.mean());
+    assert_eq!(shared.kurtosis(), owned.kurtosis());
+    assert_eq!(view.mean(), strided.mean());
+    // The summation order depends on the memory layout.
+    let moments = view.central_moments(3).unwrap();
+    for (m, expected) in moments.iter().zip(strided.central_moments(3).unwrap()) {
+        assert_abs_diff_eq!(*m, expected, epsilon = 1e-12);
+    }
+    assert_abs_diff_eq!(
+        view.t().mean_in::<f64>().unwrap(),
+        strided.mean_in::<f64>().unwrap(),
+        epsilon = 1e-12
+    );
+
+    let mut out = ArcArray::zeros(6);
+    let mut expected = Array1::zeros(6);
+    shared.var_axis_into(Axis(0), 1., &mut out).unwrap();
+    owned.var_axis_into(Axis(0), 1., &mut expected).unwrap();
+    assert_eq!(out, expected);
+    let mut out = Array1::zeros(8);
+    let mut out_view = out.slice_mut(s![..;-2]);
+    view.mean_axis_into(Axis(1), &mut out_view).unwrap();
+    assert_eq!(out_view, strided.mean_axis(Axis(1)));
+}
+
+#[test]
+fn test_quantiles_and_sorting() {
+    let owned = data().mapv(n64);
+    let mut shared = owned.clone().into_shared();
+    let mut strided = owned.clone();
+    let mut expected = owned.clone();
+    assert_eq!(
+        shared.quantile_axis_mut(Axis(1), 0.3, &Linear),
+        expected.quantile_axis_mut(Axis(1), 0.3, &Linear)
+    );
+    assert_eq!(shared.max(), owned.max());
+
+    let mut view = strided.slice_mut(s![.., ..;-1]);
+    let mut contiguous = view.to_owned();
+    assert_eq!(
+        view.quantile_axis_mut(Axis(0), 0.5, &Linear),
+        contiguous.quantile_axis_mut(Axis(0), 0.5, &Linear)
+    );
+
+    let mut column = strided.column_mut(2);
+    let mut contiguous = column.to_owned();
+    assert_eq!(
+        column.quantile_mut(0.75, &Linear),
+        contiguous.quantile_mut(0.75, &Linear)
+    );
+    let mut shared_row = owned.row(1).to_owned().into_shared();
+    assert_eq!(
+        shared_row.get_from_sorted_mut(2),
+        owned.row(1).to_owned().get_from_sorted_mut(2)
+    );
+}
+
+#[test]
+fn test_correlation_histogram_entropy_and_masks() {
+    let owned = data();
+    let shared = owned.clone().into_shared();
+    let transposed = owned.t();
+    assert_eq!(shared.cov(1.), owned.cov(1.));
+    assert!(transposed
+        .cov(1.)
+        .all_close(&transposed.to_owned().cov(1.), 1e-12));
+
+    let points = owned.mapv(|x| x as i64);
+    let edges = array![12, 8, 4, 0];
+    let grid = Grid::from(vec![Bins::new(Edges::from(edges.slice(s![..;-1]))); 6]);
+    assert_eq!(
+        grid.projections()[0],
+        Bins::new(Edges::from(vec![0, 4, 8, 12]))
+    );
+    assert_eq!(
+        points.clone().into_shared().histogram(grid.clone()),
+        points.histogram(grid.clone())
+    );
+    let reversed = points.slice(s![..;-1, ..]);
+    assert_eq!(
+        reversed.histogram(grid.clone()),
+        reversed.to_owned().histogram(grid)
+    );
+
+    let p = owned.mapv(|x| x / owned.sum());
+    let p_view = p.slice(s![.., ..;-1]);
+    assert_eq!(p.clone().into_shared().entropy(), p.entropy());
+    assert_eq!(p_view.entropy(), p_view.to_owned().entropy());
+
+    let mask = owned.mapv(|x| x > 3.).into_shared();
+    assert_eq!(
+        owned.view().mean_masked(&mask),
+        owned.mean_masked(&mask.to_owned())
+    );
+    assert_eq!(
+        owned.t().sum_masked(&mask.t()),
+        owned.t().to_owned().sum_masked(&mask.t().to_owned())
+    );
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "rayon")]
+
+use ndarray::array;
+use ndarray::prelude::*;
+use ndarray_rand::RandomExt;
+use ndarray_stats::{
+    errors::{EmptyInput, QuantileError, StatsError},
+    histogram::{strategies::Sqrt, GridBuilder},
+    interpolate::Linear,
+    CorrelationExt, HistogramExt, MaybeNanExt, QuantileExt, SummaryStatisticsExt,
+};
+use noisy_float::types::{n64, N64};
+use rand::distributions::Uniform;
+
+#[test]
+fn test_par_mean_axis_matches_mean_axis() {
+    let a = Array::random((7, 11, 5), Uniform::new(-10., 10.));
+    for axis in 0..a.ndim() {
+        let axis = Axis(axis);
+        assert!(a
+            .par_mean_axis(axis)
+            .unwrap()
+            .all_close(&a.mean_axis(axis), 1e-12));
+    }
+}
+
+#[test]
+fn test_par_var_axis_matches_var_axis() {
+    let a = Array::random((7, 11, 5), Uniform::new(-10., 10.));
+    for axis in 0..a.ndim() {
+        let axis = Axis(axis);
+        for &ddof in &[0., 1.] {
+            assert!(a
+                .par_var_axis(axis, ddof)
+                .unwrap()
+                .all_close(&a.var_axis(axis, ddof), 1e-10));
+        }
+    }
+}
+
+#[test]
+fn test_par_axis_statistics_empty_axis() {
+    let a = Array2::<f64>::zeros((0, 3));
+    assert_eq!(a.par_mean_axis(Axis(0)), Err(EmptyInput));
+    assert_eq!(a.par_var_axis(Axis(0), 0.), Err(EmptyInput));
+    assert_eq!(a.par_mean_axis(Axis(1)).unwrap(), Array1::zeros(0));
+}
+
+#[test]
+fn test_try_par_var_axis() {
+    let a = array![[1., 2., 4.], [3., 6., 5.]];
+    assert_eq!(a.try_par_var_axis(Axis(1), 1.), Ok(a.var_axis(Axis(1), 1.)));
+    for &ddof in &[-1., 2., ::std::f64::NAN] {
+        assert_eq!(
+            a.try_par_var_axis(Axis(0), ddof),
+            Err(StatsError::InvalidDdof)
+        );
+    }
+    assert_eq!(
+        Array2::<f64>::zeros((0, 3)).try_par_var_axis(Axis(0), 0.),
+        Err(StatsError::EmptyInput)
+    );
+}
+
+#[test]
+fn test_par_quantile_axis_mut_matches_quantile_axis_mut() {
+    let a = Array::random((9, 13), Uniform::new(-10., 10.)).mapv(n64);
+    for axis in 0..a.ndim() {
+        let axis = Axis(axis);
+        let expected = a
+            .clone()
+            .quantile_axis_mut(axis, n64(0.3), &Linear)
+            .unwrap();
+        let result = a
+            .clone()
+            .par_quantile_axis_mut(axis, n64(0.3), &Linear)
+            .unwrap();
+        assert_eq!(result, expected);
+    }
+}
+
+#[test]
+fn test_par_quantile_axis_mut_errors() {
+    let mut a = array![[n64(1.), n64(2.)]];
+    assert_eq!(
+        a.par_quantile_axis_mut(Axis(1), n64(1.5), &Linear),
+        Err(QuantileError::InvalidQuantile(1.5))
+    );
+    match a.par_quantile_axis_mut(Axis(1), ::std::f64::NAN, &Linear) {
+        Err(QuantileError::InvalidQuantile(q)) => assert!(q.is_nan()),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    let mut empty = Array2::<N64>::zeros((2, 0));
+    assert_eq!(
+        empty.par_quantile_axis_mut(Axis(1), n64(0.5), &Linear),
+        Err(QuantileError::EmptyInput)
+    );
+}
+
+#[test]
+fn test_par_cov_matches_cov() {
+    let a = Array::random((6, 40), Uniform::new(-10., 10.));
+    assert!(a.par_cov(1.).all_close(&a.cov(1.), 1e-10));
+    assert!(a
+        .par_pearson_correlation()
+        .all_close(&a.pearson_correlation(), 1e-12));
+}
+
+#[test]
+fn test_par_histogram_matches_histogram() {
+    let observations = Array::random((500, 2), Uniform::new(-10., 10.)).mapv(n64);
+    let grid = GridBuilder::<Sqrt<N64>>::from_array(&observations)
+        .unwrap()
+        .build();
+    let expected = observations.histogram(grid.clone());
+    let result = observations.par_histogram(grid);
+    assert_eq!(result.counts(), expected.counts());
+}
+
+fn with_nans(shape: (usize, usize)) -> Array2<f64> {
+    let mut a = Array::random(shape, Uniform::new(-10., 10.));
+    a.mapv_inplace(|x| if x > 5. { ::std::f64::NAN } else { x });
+    // A lane without any value.
+    a.row_mut(0).fill(::std::f64::NAN);
+    a
+}
+
+fn assert_same_or_nan(a: &Array1<f64>, b: &Array1<f64>, tol: f64) {
+    assert_eq!(a.shape(), b.shape());
+    for (x, y) in a.iter().zip(b) {
+        assert!((x.is_nan() && y.is_nan()) || (x - y).abs() <= tol);
+    }
+}
+
+#[test]
+fn test_par_skipnan_axis_statistics_match_sequential() {
+    let a = with_nans((9, 13));
+    for axis in 0..a.ndim() {
+        let axis = Axis(axis);
+        assert_same_or_nan(
+            &a.par_mean_axis_skipnan(axis),
+            &a.mean_axis_skipnan(axis),
+            1e-12,
+        );
+        assert_same_or_nan(
+            &a.par_var_axis_skipnan(axis, n64(1.)),
+            &a.var_axis_skipnan(axis, n64(1.)),
+            1e-12,
+        );
+        assert_same_or_nan(
+            &a.clone()
+                .par_quantile_axis_skipnan_mut(axis, n64(0.3), &Linear)
+                .unwrap(),
+            &a.clone()
+                .quantile_axis_skipnan_mut(axis, n64(0.3), &Linear)
+                .unwrap(),
+            0.,
+        );
+    }
+}
+
+#[test]
+fn test_par_skipnan_axis_statistics_empty_axis() {
+    let mut a = Array2::<f64>::zeros((0, 3));
+    assert!(a.par_mean_axis_skipnan(Axis(0)).iter().all(|x| x.is_nan()));
+    assert_eq!(a.par_var_axis_skipnan(Axis(1), n64(0.)).len(), 0);
+    assert_eq!(
+        a.par_quantile_axis_skipnan_mut(Axis(0), n64(0.5), &Linear),
+        Err(QuantileError::EmptyInput)
+    );
+}
@@ -1,10 +1,13 @@
 use itertools::izip;
-use ndarray::array;
 use ndarray::prelude::*;
+use ndarray::{array, s};
 use ndarray_stats::{
-    errors::{EmptyInput, MinMaxError, QuantileError},
-    interpolate::{Higher, Interpolate, Linear, Lower, Midpoint, Nearest},
-    Quantile1dExt, QuantileExt,
+    errors::{
+        MinMaxError, NanPolicyQuantileError, QuantileError, ShapeMismatch, SkipNanError,
+        WeightedQuantileError,
+    },
+    interpolate::{Higher, HyndmanFan, Interpolate, Linear, Lower, Midpoint, Nearest},
+    NanPolicy, Quantile1dExt, QuantileExt,
 };
 use noisy_float::types::{n64, N64};
 use quickcheck_macros::quickcheck;
@@ -24,6 +27,33 @@ fn test_argmin() {
     assert_eq!(a.argmin(), Err(MinMaxError::EmptyInput));
 }
 
+#[test]
+fn test_argmin_argmax_layouts() {
+    let a = Array::from_shape_fn((3, 4, 5), |(i, j, k)| {
+        ((7 * i + 11 * j + 13 * k) % 17) as i32
+    });
+    for view in &[a.view(), a.t(), a.slice(s![.., ..;-1, 1..])] {
+        let naive_min = view
+            .indexed_iter()
+            .fold(None, |acc: Option<(_, &i32)>, (index, x)| match acc {
+                Some((_, min)) if min <= x => acc,
+                _ => Some((index, x)),
+            })
+            .unwrap()
+            .0;
+        let naive_max = view
+            .indexed_iter()
+            .fold(None, |acc: Option<(_, &i32)>, (index, x)| match acc {
+                Some((_, max)) if max >= x => acc,
+                _ => Some((index, x)),
+            })
+            .unwrap()
+            .0;
+        assert_eq!(view.argmin(), Ok(naive_min));
+        assert_eq!(view.argmax(), Ok(naive_max));
+    }
+}
+
 #[quickcheck]
 fn argmin_matches_min(data: Vec<f32>) -> bool {
     let a = Array1::from(data);
@@ -42,10 +72,10 @@ fn test_argmin_skipnan() {
     assert_eq!(a.argmin_skipnan(), Ok((1, 0)));
 
     let a: Array2<f64> = array![[], []];
-    assert_eq!(a.argmin_skipnan(), Err(EmptyInput));
+    assert_eq!(a.argmin_skipnan(), Err(SkipNanError::EmptyInput));
 
     let a = arr2(&[[::std::f64::NAN; 2]; 2]);
-    assert_eq!(a.argmin_skipnan(), Err(EmptyInput));
+    assert_eq!(a.argmin_skipnan(), Err(SkipNanError::AllNan));
 }
 
 #[quickcheck]
@@ -54,7 +84,12 @@ fn argmin_skipnan_matches_min_skipnan(data: Vec<Option<i32>>) -> bool {
     let min = a.min_skipnan();
     let argmin = a.argmin_skipnan();
     if min.is_none() {
-        argmin == Err(EmptyInput)
+        let expected = if a.is_empty() {
+            SkipNanError::EmptyInput
+        } else {
+            SkipNanError::AllNan
+        };
+        argmin == Err(expected)
     } else {
         a[argmin.unwrap()] == *min
     }
@@ -87,6 +122,17 @@ fn test_min_skipnan_all_nan() {
     assert!(a.min_skipnan().is_nan());
 }
 
+#[test]
+fn test_min_max_skipnan_opt_f64() {
+    let a = array![[Some(1.), None], [Some(::std::f64::NAN), Some(-3.)]];
+    assert_eq!(a.min_skipnan(), &Some(-3.));
+    assert_eq!(a.max_skipnan(), &Some(1.));
+    assert_eq!(a.argmin_skipnan(), Ok((1, 1)));
+
+    let a = array![None, Some(::std::f64::NAN)];
+    assert_eq!(a.min_skipnan(), &None);
+}
+
 #[test]
 fn test_argmax() {
     let a = array![[1, 5, 3], [2, 0, 6]];
@@ -123,10 +169,10 @@ fn test_argmax_skipnan() {
     assert_eq!(a.argmax_skipnan(), Ok((1, 2)));
 
     let a: Array2<f64> = array![[], []];
-    assert_eq!(a.argmax_skipnan(), Err(EmptyInput));
+    assert_eq!(a.argmax_skipnan(), Err(SkipNanError::EmptyInput));
 
     let a = arr2(&[[::std::f64::NAN; 2]; 2]);
-    assert_eq!(a.argmax_skipnan(), Err(EmptyInput));
+    assert_eq!(a.argmax_skipnan(), Err(SkipNanError::AllNan));
 }
 
 #[quickcheck]
@@ -135,7 +181,12 @@ fn argmax_skipnan_matches_max_skipnan(data: Vec<Option<i32>>) -> bool {
     let max = a.max_skipnan();
     let argmax = a.argmax_skipnan();
     if max.is_none() {
-        argmax == Err(EmptyInput)
+        let expected = if a.is_empty() {
+            SkipNanError::EmptyInput
+        } else {
+            SkipNanError::AllNan
+        };
+        argmax == Err(expected)
     } else {
         a[argmax.unwrap()] == *max
     }
@@ -234,6 +285,62 @@ fn test_quantile_axis_skipnan_mut_nearest_opt_i32() {
     assert!(q[1].is_none());
 }
 
+#[test]
+fn test_quantile_axis_skipnan_mut_linear_opt_f64() {
+    let mut a = arr2(&[
+        [
+            Some(4.),
+            Some(2.),
+            None,
+            Some(1.),
+            Some(::std::f64::NAN),
+            Some(5.),
+        ],
+        [None, Some(::std::f64::NAN), None, None, None, None],
+    ]);
+    let q = a
+        .quantile_axis_skipnan_mut(Axis(1), n64(0.75), &Linear)
+        .unwrap();
+    assert_eq!(q.shape(), &[2]);
+    assert_eq!(q[0], Some(4.25));
+    assert!(q[1].is_none());
+}
+
+#[test]
+fn test_quantile_axis_skipnan_mut_f32_with_plain_float_quantiles() {
+    let mut a = arr2(&[[4f32, 2., ::std::f32::NAN, 1., 5.], [3., 6., 9., 0., 12.]]);
+    let q64 = a.quantile_axis_skipnan_mut(Axis(1), 0.75, &Linear).unwrap();
+    let q32 = a
+        .quantile_axis_skipnan_mut(Axis(1), 0.75f32, &Linear)
+        .unwrap();
+    let qn64 = a
+        .quantile_axis_skipnan_mut(Axis(1), n64(0.75), &Linear)
+        .unwrap();
+    assert_eq!(q64, array![4.25, 9.]);
+    assert_eq!(q32, q64);
+    assert_eq!(qn64, q64);
+}
+
+#[test]
+fn test_quantiles_mut_with_f32_quantiles() {
+    let mut a = array![n64(1.), n64(3.), n64(2.)];
+    let qs = a.quantiles_mut(&array![0.5f32, 1.], &Lower).unwrap();
+    assert_eq!(qs, array![n64(2.), n64(3.)]);
+    assert_eq!(
+        a.quantiles_mut(&array![0.5f32, 2.], &Lower),
+        Err(QuantileError::InvalidQuantile(2.))
+    );
+}
+
+#[test]
+fn test_quantile_mut_nan_quantile() {
+    let mut a = array![1, 2, 3];
+    match a.quantile_mut(::std::f64::NAN, &Lower) {
+        Err(QuantileError::InvalidQuantile(q)) => assert!(q.is_nan()),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
+
 #[test]
 fn test_quantile_axis_skipnan_mut_midpoint_opt_i32() {
     let mut a = arr2(&[[Some(4), Some(2), None, Some(1), Some(5)], [None; 5]]);
@@ -267,6 +374,45 @@ fn test_quantile_axis_skipnan_mut_linear_opt_i32() {
     assert!(q[1].is_none());
 }
 
+#[test]
+fn test_quantile_axis_with_policy_mut() {
+    let a = arr2(&[
+        [Some(2), Some(4), None, Some(1)],
+        [Some(5), Some(3), Some(1), Some(7)],
+    ]);
+
+    let q = a
+        .clone()
+        .quantile_axis_with_policy_mut(Axis(1), n64(0.5), &Lower, NanPolicy::Propagate)
+        .unwrap();
+    assert_eq!(q, arr1(&[None, Some(3)]));
+
+    let q = a
+        .clone()
+        .quantile_axis_with_policy_mut(Axis(1), n64(0.5), &Lower, NanPolicy::Omit)
+        .unwrap();
+    assert_eq!(q, arr1(&[Some(2), Some(3)]));
+
+    assert_eq!(
+        a.clone()
+            .quantile_axis_with_policy_mut(Axis(1), n64(0.5), &Lower, NanPolicy::Error),
+        Err(NanPolicyQuantileError::ContainsNan)
+    );
+    let q = a
+        .slice(s![1.., ..])
+        .to_owned()
+        .quantile_axis_with_policy_mut(Axis(1), n64(0.5), &Lower, NanPolicy::Error)
+        .unwrap();
+    assert_eq!(q, arr1(&[Some(3)]));
+    assert_eq!(
+        a.clone()
+            .quantile_axis_with_policy_mut(Axis(1), n64(1.5), &Lower, NanPolicy::Omit),
+        Err(NanPolicyQuantileError::Quantile(
+            QuantileError::InvalidQuantile(1.5)
+        ))
+    );
+}
+
 #[test]
 fn test_midpoint_overflow() {
     // Regression test
@@ -329,7 +475,7 @@ fn check_one_interpolation_method_for_quantiles_mut(
 ) -> bool {
     let bulk_quantiles = v.clone().quantiles_mut(&quantile_indexes, interpolate);
 
-    if v.len() == 0 {
+    if v.is_empty() {
         bulk_quantiles.is_err()
     } else {
         let bulk_quantiles = bulk_quantiles.unwrap();
@@ -404,7 +550,7 @@ fn check_one_interpolation_method_for_quantiles_axis_mut(
         .clone()
         .quantiles_axis_mut(axis, &quantile_indexes, interpolate);
 
-    if v.len() == 0 {
+    if v.is_empty() {
         bulk_quantiles.is_err()
     } else {
         let bulk_quantiles = bulk_quantiles.unwrap();
@@ -417,3 +563,166 @@ fn check_one_interpolation_method_for_quantiles_axis_mut(
         )
     }
 }
+
+#[quickcheck]
+fn weighted_quantile_with_equal_weights_is_unweighted(xs: Vec<i64>, weight: u8) -> bool {
+    let weight = f64::from(weight) + 1.;
+    let mut a = Array::from(xs);
+    let mut weights = Array1::from_elem(a.len(), weight);
+    [0., 0.1, 0.25, 0.5, 0.75, 0.9, 1.].iter().all(|&q| {
+        let weighted = a.weighted_quantile_mut(&mut weights, q, &Nearest);
+        let unweighted = a.quantile_mut(q, &Nearest);
+        match unweighted {
+            Ok(quantile) => weighted == Ok(quantile),
+            Err(_) => weighted == Err(WeightedQuantileError::ZeroWeights),
+        }
+    })
+}
+
+#[test]
+fn test_weighted_quantile_mut() {
+    let mut a = array![n64(3.), n64(1.), n64(10.), n64(2.)];
+    let mut weights = array![1., 1., 0., 2.];
+    // The positions of 1, 2 and 3 are 0, 2/3 and 1.
+    assert_eq!(
+        a.weighted_quantile_mut(&mut weights, 1., &Linear),
+        Ok(n64(3.))
+    );
+    assert_eq!(
+        a.weighted_quantile_mut(&mut weights, 0.5, &Lower),
+        Ok(n64(1.))
+    );
+    assert_eq!(
+        a.weighted_quantile_mut(&mut weights, 0.5, &Higher),
+        Ok(n64(2.))
+    );
+    assert_eq!(
+        a.weighted_quantile_mut(&mut weights, 0.5, &Midpoint),
+        Ok(n64(1.5))
+    );
+    assert_eq!(
+        a.weighted_quantile_mut(&mut weights, 0.5, &Linear),
+        Ok(n64(1.75))
+    );
+    // Each element kept its weight.
+    let total: f64 = izip!(&a, &weights).map(|(x, w)| x.raw() * w).sum();
+    assert_eq!(total, 8.);
+}
+
+#[test]
+fn test_weighted_quantile_errors() {
+    let mut a = array![[1, 2], [3, 4]];
+    assert_eq!(
+        a.weighted_quantile_mut(&mut array![[1., 1.]], 0.5, &Lower),
+        Err(WeightedQuantileError::ShapeMismatch(ShapeMismatch {
+            first_shape: vec![2, 2],
+            second_shape: vec![1, 2],
+        }))
+    );
+    assert_eq!(
+        a.weighted_quantile_mut(&mut array![[1., 1.], [1., 1.]], 1.5, &Lower),
+        Err(WeightedQuantileError::InvalidQuantile(1.5))
+    );
+    match a.weighted_quantile_axis_mut(
+        Axis(0),
+        &mut array![[1., 1.], [1., 1.]],
+        ::std::f64::NAN,
+        &Lower,
+    ) {
+        Err(WeightedQuantileError::InvalidQuantile(q)) => assert!(q.is_nan()),
+        other => panic!("Unexpected result: {:?}", other),
+    }
+    assert_eq!(
+        a.weighted_quantile_mut(&mut array![[1., -1.], [1., 1.]], 0.5, &Lower),
+        Err(WeightedQuantileError::InvalidWeights)
+    );
+    assert_eq!(
+        a.weighted_quantile_axis_mut(Axis(1), &mut array![[1., 1.], [0., 0.]], 0.5, &Lower),
+        Err(WeightedQuantileError::ZeroWeights)
+    );
+    let mut empty = Array2::<i32>::zeros((0, 3));
+    let mut weights = Array2::<f64>::zeros((0, 3));
+    assert_eq!(
+        empty.weighted_quantile_axis_mut(Axis(0), &mut weights, 0.5, &Lower),
+        Err(WeightedQuantileError::ZeroWeights)
+    );
+    assert_eq!(
+        empty.weighted_quantile_axis_mut(Axis(1), &mut weights, 0.5, &Lower),
+        Ok(Array1::zeros(0))
+    );
+}
+
+#[quickcheck]
+fn weighted_quantile_axis_matches_lanes(xs: Vec<(u8, u8)>) -> bool {
+    let n = xs.len() / 3;
+    let mut a = Array::from_shape_fn((3, n), |(i, j)| xs[i * n + j].0);
+    let mut weights = Array::from_shape_fn((3, n), |(i, j)| f64::from(xs[i * n + j].1) + 1.);
+    let quantiles = a.weighted_quantile_axis_mut(Axis(1), &mut weights, 0.3, &Higher);
+    if n == 0 {
+        return quantiles == Err(WeightedQuantileError::ZeroWeights);
+    }
+    izip!(
+        quantiles.unwrap().iter(),
+        a.outer_iter_mut(),
+        weights.outer_iter_mut()
+    )
+    .all(|(&quantile, mut lane, mut lane_weights)| {
+        lane.weighted_quantile_mut(&mut lane_weights, 0.3, &Higher) == Ok(quantile)
+    })
+}
+
+#[test]
+fn test_hyndman_fan_definitions_match_r() {
+    // R's `quantile(x, probs, type = t)` for t = 1, ..., 9.
+    let x = array![3., 1., 4., 1., 5., 9., 2., 6., 5., 3., 5.].mapv(n64);
+    let probs = [0., 0.1, 0.25, 0.5, 0.6, 0.9, 1.];
+    let expected = [
+        [1., 1., 2., 4., 5., 6., 9.],
+        [1., 1., 2., 4., 5., 6., 9.],
+        [1., 1., 2., 4., 5., 6., 9.],
+        [1., 1., 1.75, 3.5, 4.6, 5.9, 9.],
+        [1., 1., 2.25, 4., 5., 7.2, 9.],
+        [1., 1., 2., 4., 5., 8.4, 9.],
+        [1., 1., 2.5, 4., 5., 6., 9.],
+        [1., 1., 13. / 6., 4., 5., 7.6, 9.],
+        [1., 1., 2.1875, 4., 5., 7.5, 9.],
+    ];
+    let y = array![40., 10., 30., 20.].mapv(n64);
+    let y_probs = [0., 0.25, 0.3, 0.5, 0.75, 1.];
+    let y_expected = [
+        [10., 10., 20., 20., 30., 40.],
+        [10., 15., 20., 25., 35., 40.],
+        [10., 10., 10., 20., 30., 40.],
+        [10., 10., 12., 20., 30., 40.],
+        [10., 15., 17., 25., 35., 40.],
+        [10., 12.5, 15., 25., 37.5, 40.],
+        [10., 17.5, 19., 25., 32.5, 40.],
+        [10., 85. / 6., 49. / 3., 25., 215. / 6., 40.],
+        [10., 14.375, 16.5, 25., 35.625, 40.],
+    ];
+    for t in 1..=9 {
+        let method = HyndmanFan::from_type(t).unwrap();
+        assert_eq!(method.type_number(), t);
+        for (data, probs, expected) in &[
+            (&x, &probs[..], &expected[t as usize - 1][..]),
+            (&y, &y_probs[..], &y_expected[t as usize - 1][..]),
+        ] {
+            for (&p, &e) in probs.iter().zip(expected.iter()) {
+                let q = method
+                    .quantile_axis_mut(&mut (*data).clone(), Axis(0), p)
+                    .unwrap()
+                    .into_scalar();
+                assert!(
+                    (q.raw() - e).abs() < 1e-12,
+                    "type {}, p = {}: {} != {}",
+                    t,
+                    p,
+                    q,
+                    e
+                );
+            }
+        }
+    }
+    assert_eq!(HyndmanFan::from_type(0), None);
+    assert_eq!(HyndmanFan::from_type(10), None);
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "serde-1")]
+
+use ndarray::array;
+use ndarray_stats::{
+    histogram::{strategies::Sqrt, Bins, Edges, Grid, GridBuilder, Histogram},
+    stream::{MeanVar, MinMax, QuantileSketch},
+    HistogramExt,
+};
+use noisy_float::types::{n64, N64};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+fn roundtrip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+fn assert_roundtrip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) {
+    assert_eq!(&roundtrip(value), value);
+}
+
+#[test]
+fn test_histogram_roundtrip() {
+    let observations = array![
+        [n64(1.), n64(0.5)],
+        [n64(-0.5), n64(1.)],
+        [n64(0.5), n64(-1.)]
+    ];
+    let grid = GridBuilder::<Sqrt<N64>>::from_array(&observations)
+        .unwrap()
+        .build();
+    let histogram: Histogram<N64> = observations.histogram(grid);
+    assert_roundtrip(&histogram);
+}
+
+#[test]
+fn test_histogram_counts_are_checked_against_the_grid() {
+    let grid = Grid::from(vec![Bins::new(Edges::from(vec![0, 5, 10]))]);
+    let histogram = Histogram::from_parts(grid, array![3, 1].into_dyn()).unwrap();
+    let json = serde_json::to_string(&histogram).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Histogram<i32>>(&json).unwrap(),
+        histogram
+    );
+
+    let (grid, _) = histogram.into_parts();
+    let mut value = serde_json::to_value(Histogram::new(grid)).unwrap();
+    value["counts"]["dim"] = serde_json::json!([3]);
+    value["counts"]["data"] = serde_json::json!([3, 1, 4]);
+    let err = serde_json::from_value::<Histogram<i32>>(value).unwrap_err();
+    assert!(err.to_string().contains("shapes do not match"));
+}
+
+#[test]
+fn test_edges_are_serialized_as_a_sequence() {
+    let bins = Bins::new(Edges::from(vec![0, 5, 10]));
+    assert_eq!(serde_json::to_string(&bins).unwrap(), "[0,5,10]");
+    // Deserialized edges are sorted and deduplicated, as in `Edges::from`.
+    let edges: Edges<i32> = serde_json::from_str("[10, 0, 5, 0]").unwrap();
+    assert_eq!(edges, Edges::from(vec![0, 5, 10]));
+}
+
+#[test]
+fn test_stream_accumulators_roundtrip() {
+    let data = array![3., -1., 4., 1., 5., 9., 2., 6.];
+
+    let mut mean_var = MeanVar::new();
+    mean_var.extend(&data);
+    assert_roundtrip(&mean_var);
+
+    let mut min_max = MinMax::<f64>::new();
+    min_max.extend(&data);
+    assert_roundtrip(&min_max);
+
+    let mut sketch = QuantileSketch::new();
+    sketch.extend(&data);
+    let mut restored: QuantileSketch<f64> = roundtrip(&sketch);
+    assert_eq!(restored.count(), sketch.count());
+    assert_eq!(restored.quantile(0.5), sketch.quantile(0.5));
+    // The restored sketch keeps accumulating values.
+    restored.push(100.);
+    assert_eq!(restored.quantile(1.), Ok(100.));
+}
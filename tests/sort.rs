@@ -4,7 +4,7 @@ use quickcheck_macros::quickcheck;
 
 #[test]
 fn test_partition_mut() {
-    let mut l = vec![
+    let mut l = [
         arr1(&[1, 1, 1, 1, 1]),
         arr1(&[1, 3, 2, 10, 10]),
         arr1(&[2, 3, 4, 1]),
@@ -20,7 +20,7 @@ fn test_partition_mut() {
     for a in l.iter_mut() {
         let n = a.len();
         let pivot_index = n - 1;
-        let pivot_value = a[pivot_index].clone();
+        let pivot_value = a[pivot_index];
         let partition_index = a.partition_mut(pivot_index);
         for i in 0..partition_index {
             assert!(a[i] < pivot_value);
@@ -52,7 +52,7 @@ fn test_sorted_get_many_mut(mut xs: Vec<i64>) -> bool {
         let mut v = Array::from_vec(xs.clone());
 
         // Insert each index twice, to get a set of indexes with duplicates, not sorted
-        let mut indexes: Vec<usize> = (0..n).into_iter().collect();
+        let mut indexes: Vec<usize> = (0..n).collect();
         indexes.append(&mut (0..n).collect());
 
         let mut sorted_v = Vec::with_capacity(n);